@@ -0,0 +1,6 @@
+CREATE TABLE
+    IF NOT EXISTS statistics (
+        id integer PRIMARY KEY AUTO_INCREMENT,
+        name VARCHAR(64) NOT NULL UNIQUE,
+        value BIGINT UNSIGNED DEFAULT 0 NOT NULL
+    );
\ No newline at end of file
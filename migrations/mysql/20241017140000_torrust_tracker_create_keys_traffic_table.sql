@@ -0,0 +1,7 @@
+CREATE TABLE
+    IF NOT EXISTS keys_traffic (
+        id integer PRIMARY KEY AUTO_INCREMENT,
+        `key` VARCHAR(32) NOT NULL UNIQUE,
+        uploaded BIGINT UNSIGNED DEFAULT 0 NOT NULL,
+        downloaded BIGINT UNSIGNED DEFAULT 0 NOT NULL
+    );
\ No newline at end of file
@@ -0,0 +1,6 @@
+CREATE TABLE
+    IF NOT EXISTS ip_access_list (
+        id SERIAL PRIMARY KEY,
+        network VARCHAR(43) NOT NULL UNIQUE,
+        policy VARCHAR(5) NOT NULL
+    );
\ No newline at end of file
@@ -0,0 +1,6 @@
+CREATE TABLE
+    IF NOT EXISTS statistics (
+        id SERIAL PRIMARY KEY,
+        name VARCHAR(64) NOT NULL UNIQUE,
+        value BIGINT DEFAULT 0 NOT NULL
+    );
\ No newline at end of file
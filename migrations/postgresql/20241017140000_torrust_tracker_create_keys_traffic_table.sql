@@ -0,0 +1,7 @@
+CREATE TABLE
+    IF NOT EXISTS keys_traffic (
+        id SERIAL PRIMARY KEY,
+        key VARCHAR(32) NOT NULL UNIQUE,
+        uploaded BIGINT DEFAULT 0 NOT NULL,
+        downloaded BIGINT DEFAULT 0 NOT NULL
+    );
\ No newline at end of file
@@ -0,0 +1,6 @@
+CREATE TABLE
+    IF NOT EXISTS ip_access_list (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        network TEXT NOT NULL UNIQUE,
+        policy TEXT NOT NULL
+    );
\ No newline at end of file
@@ -0,0 +1,7 @@
+CREATE TABLE
+    IF NOT EXISTS keys_traffic (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        key TEXT NOT NULL UNIQUE,
+        uploaded INTEGER DEFAULT 0 NOT NULL,
+        downloaded INTEGER DEFAULT 0 NOT NULL
+    );
\ No newline at end of file
@@ -36,16 +36,28 @@ const ENV_VAR_CONFIG_TOML: &str = "TORRUST_TRACKER_CONFIG_TOML";
 pub const ENV_VAR_CONFIG_TOML_PATH: &str = "TORRUST_TRACKER_CONFIG_TOML_PATH";
 
 pub type Configuration = v2_0_0::Configuration;
+pub type ClientSuppliedIp = v2_0_0::network::ClientSuppliedIp;
 pub type Core = v2_0_0::core::Core;
 pub type HealthCheckApi = v2_0_0::health_check_api::HealthCheckApi;
 pub type HttpApi = v2_0_0::tracker_api::HttpApi;
+pub type Scope = v2_0_0::tracker_api::Scope;
 pub type HttpTracker = v2_0_0::http_tracker::HttpTracker;
 pub type UdpTracker = v2_0_0::udp_tracker::UdpTracker;
 pub type Database = v2_0_0::database::Database;
 pub type Driver = v2_0_0::database::Driver;
+pub type FullScrape = v2_0_0::full_scrape::FullScrape;
+pub type MetricsHistory = v2_0_0::metrics_history::MetricsHistory;
+pub type OnEarlyAnnounce = v2_0_0::core::OnEarlyAnnounce;
+pub type OnPeerIdentityMismatch = v2_0_0::core::OnPeerIdentityMismatch;
+pub type PeerSelection = v2_0_0::core::PeerSelection;
+pub type RateLimit = v2_0_0::rate_limit::RateLimit;
+pub type ScrapeCache = v2_0_0::scrape_cache::ScrapeCache;
+pub type Snapshot = v2_0_0::snapshot::Snapshot;
+pub type StatisticsPersistence = v2_0_0::statistics_persistence::StatisticsPersistence;
 pub type Threshold = v2_0_0::logging::Threshold;
 
 pub type AccessTokens = HashMap<String, String>;
+pub type AccessTokenScopes = HashMap<String, Vec<Scope>>;
 
 pub const LATEST_VERSION: &str = "2.0.0";
 
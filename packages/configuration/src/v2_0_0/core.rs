@@ -1,7 +1,13 @@
 use derive_more::{Constructor, Display};
 use serde::{Deserialize, Serialize};
 
-use super::network::Network;
+use super::full_scrape::FullScrape;
+use super::metrics_history::MetricsHistory;
+use super::network::{ClientSuppliedIp, Network};
+use super::rate_limit::RateLimit;
+use super::scrape_cache::ScrapeCache;
+use super::snapshot::Snapshot;
+use super::statistics_persistence::StatisticsPersistence;
 use crate::v2_0_0::database::Database;
 use crate::validator::{SemanticValidationError, Validator};
 use crate::{AnnouncePolicy, TrackerPolicy};
@@ -17,6 +23,11 @@ pub struct Core {
     #[serde(default = "Core::default_database")]
     pub database: Database,
 
+    /// Full scrape configuration. When present, the HTTP trackers return the
+    /// stats of all the torrents for `scrape` requests without infohashes.
+    #[serde(default = "Core::default_full_scrape")]
+    pub full_scrape: Option<FullScrape>,
+
     /// Interval in seconds that the cleanup job will run to remove inactive
     /// peers from the torrent peer list.
     #[serde(default = "Core::default_inactive_peer_cleanup_interval")]
@@ -26,10 +37,40 @@ pub struct Core {
     #[serde(default = "Core::default_listed")]
     pub listed: bool,
 
+    /// Metrics history configuration. When present, the tracker keeps samples
+    /// of its metrics over time, which are served by the tracker API.
+    #[serde(default = "Core::default_metrics_history")]
+    pub metrics_history: Option<MetricsHistory>,
+
     /// Network configuration.
     #[serde(default = "Core::default_network")]
     pub net: Network,
 
+    /// When `true` the `announce` responses to seeders don't include other
+    /// seeders, since two seeders have nothing to exchange. The slots are
+    /// filled with leechers instead.
+    #[serde(default = "Core::default_omit_seeders_for_seeders")]
+    pub omit_seeders_for_seeders: bool,
+
+    /// What to do with the `announce` requests from peers that announce
+    /// again before the minimum announce interval (`interval_min` in the
+    /// announce policy).
+    #[serde(default = "Core::default_on_early_announce")]
+    pub on_early_announce: OnEarlyAnnounce,
+
+    /// What to do with the `announce` requests for a peer already in the
+    /// swarm that can't prove it's the same peer: the `key` is not the one
+    /// the peer sent before or, if it didn't send a key, the IP address has
+    /// changed.
+    #[serde(default = "Core::default_on_peer_identity_mismatch")]
+    pub on_peer_identity_mismatch: OnPeerIdentityMismatch,
+
+    /// How the tracker chooses the peers returned in the `announce`
+    /// responses when there are more peers in the swarm than the client
+    /// wants.
+    #[serde(default = "Core::default_peer_selection")]
+    pub peer_selection: PeerSelection,
+
     /// When `true` clients require a key to connect and use the tracker.
     #[serde(default = "Core::default_private")]
     pub private: bool,
@@ -38,6 +79,34 @@ pub struct Core {
     #[serde(default = "Core::default_private_mode")]
     pub private_mode: Option<PrivateMode>,
 
+    /// Rate limiter configuration. When present, the number of `connect`,
+    /// `announce` and `scrape` requests from the same client is limited.
+    #[serde(default = "Core::default_rate_limit")]
+    pub rate_limit: Option<RateLimit>,
+
+    /// Scrape cache configuration. When present, the swarm metadata returned
+    /// in the `scrape` responses is cached for a short time.
+    #[serde(default = "Core::default_scrape_cache")]
+    pub scrape_cache: Option<ScrapeCache>,
+
+    /// Torrents snapshot configuration. When present, the tracker persists
+    /// the torrents and their peers to a file so that swarms survive a
+    /// restart.
+    #[serde(default = "Core::default_snapshot")]
+    pub snapshot: Option<Snapshot>,
+
+    /// Statistics persistence configuration. When present, the tracker saves
+    /// the usage statistics counters into the database so that they survive a
+    /// restart. It requires `tracker_usage_statistics` to be enabled.
+    #[serde(default = "Core::default_statistics_persistence")]
+    pub statistics_persistence: Option<StatisticsPersistence>,
+
+    /// Whether the tracker should keep activity statistics for each torrent:
+    /// number of `announce` and `scrape` requests, last activity time and
+    /// peak number of seeders and leechers. They are only kept in memory.
+    #[serde(default = "Core::default_torrent_statistics")]
+    pub torrent_statistics: bool,
+
     /// Tracker policy configuration.
     #[serde(default = "Core::default_tracker_policy")]
     pub tracker_policy: TrackerPolicy,
@@ -56,11 +125,22 @@ impl Default for Core {
         Self {
             announce_policy: Self::default_announce_policy(),
             database: Self::default_database(),
+            full_scrape: Self::default_full_scrape(),
             inactive_peer_cleanup_interval: Self::default_inactive_peer_cleanup_interval(),
             listed: Self::default_listed(),
+            metrics_history: Self::default_metrics_history(),
             net: Self::default_network(),
+            omit_seeders_for_seeders: Self::default_omit_seeders_for_seeders(),
+            on_early_announce: Self::default_on_early_announce(),
+            on_peer_identity_mismatch: Self::default_on_peer_identity_mismatch(),
+            peer_selection: Self::default_peer_selection(),
             private: Self::default_private(),
             private_mode: Self::default_private_mode(),
+            rate_limit: Self::default_rate_limit(),
+            scrape_cache: Self::default_scrape_cache(),
+            snapshot: Self::default_snapshot(),
+            statistics_persistence: Self::default_statistics_persistence(),
+            torrent_statistics: Self::default_torrent_statistics(),
             tracker_policy: Self::default_tracker_policy(),
             tracker_usage_statistics: Self::default_tracker_usage_statistics(),
         }
@@ -76,6 +156,10 @@ impl Core {
         Database::default()
     }
 
+    fn default_full_scrape() -> Option<FullScrape> {
+        None
+    }
+
     fn default_inactive_peer_cleanup_interval() -> u64 {
         600
     }
@@ -84,10 +168,30 @@ impl Core {
         false
     }
 
+    fn default_metrics_history() -> Option<MetricsHistory> {
+        None
+    }
+
     fn default_network() -> Network {
         Network::default()
     }
 
+    fn default_omit_seeders_for_seeders() -> bool {
+        false
+    }
+
+    fn default_on_early_announce() -> OnEarlyAnnounce {
+        OnEarlyAnnounce::default()
+    }
+
+    fn default_on_peer_identity_mismatch() -> OnPeerIdentityMismatch {
+        OnPeerIdentityMismatch::default()
+    }
+
+    fn default_peer_selection() -> PeerSelection {
+        PeerSelection::default()
+    }
+
     fn default_private() -> bool {
         false
     }
@@ -100,6 +204,26 @@ impl Core {
         }
     }
 
+    fn default_rate_limit() -> Option<RateLimit> {
+        None
+    }
+
+    fn default_scrape_cache() -> Option<ScrapeCache> {
+        None
+    }
+
+    fn default_snapshot() -> Option<Snapshot> {
+        None
+    }
+
+    fn default_statistics_persistence() -> Option<StatisticsPersistence> {
+        None
+    }
+
+    fn default_torrent_statistics() -> bool {
+        false
+    }
+
     fn default_tracker_policy() -> TrackerPolicy {
         TrackerPolicy::default()
     }
@@ -108,6 +232,73 @@ impl Core {
     }
 }
 
+/// What to do with an `announce` request from a peer that has announced
+/// again before the minimum announce interval.
+///
+/// `stopped` and `completed` announces are never considered early.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OnEarlyAnnounce {
+    /// The announce is handled like any other announce.
+    #[default]
+    Accept,
+    /// The announce is rejected with a failure reason.
+    Reject,
+    /// The peer is not updated. The response contains the swarm data the
+    /// tracker already has.
+    Cached,
+    /// The announce is handled but it's not counted in the tracker
+    /// statistics as an `announce` request handled.
+    SkipCounting,
+}
+
+/// What to do with an `announce` request for a peer that is already in the
+/// swarm when the request doesn't come from the same peer.
+///
+/// A request comes from the same peer when it contains the `key` the peer sent
+/// in its first announce. Peers that didn't send a key must announce from the
+/// same IP address.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OnPeerIdentityMismatch {
+    /// The announce is handled like any other announce. Peers are only
+    /// identified by their `peer_id`.
+    #[default]
+    Ignore,
+    /// The announce is rejected with a failure reason.
+    Reject,
+    /// The announce is answered as if it came from a new peer, but the peer
+    /// is not added to the swarm. The previous peer is kept, so the announce
+    /// can't remove or replace it, and a `completed` announce is not counted
+    /// as a download.
+    NewPeer,
+}
+
+/// Strategy used to choose the peers returned in an `announce` response.
+///
+/// The client peer is never returned. Peers that are equally preferred are
+/// returned in the order they are kept in the swarm.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PeerSelection {
+    /// The first peers in the swarm.
+    #[default]
+    InOrder,
+    /// A random sample of the peers in the swarm.
+    Random,
+    /// Seeders are returned first to leechers.
+    PreferSeeders,
+    /// Leechers are returned first to seeders, because seeders don't need
+    /// other seeders.
+    PreferLeechers,
+    /// The peers that have not been returned in a response for the longest
+    /// time are returned first.
+    LeastRecentlyReturned,
+    /// Peers with an address in the IP family of the client are returned
+    /// first.
+    SameIpFamilyFirst,
+}
+
 /// Configuration specific when the tracker is running in private mode.
 #[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, Constructor, Display)]
 pub struct PrivateMode {
@@ -139,6 +330,87 @@ impl Validator for Core {
             return Err(SemanticValidationError::UselessPrivateModeSection);
         }
 
+        if let Some(rate_limit) = &self.rate_limit {
+            if rate_limit.ipv6_prefix_len > 128 {
+                return Err(SemanticValidationError::InvalidIpv6PrefixLength {
+                    prefix_len: rate_limit.ipv6_prefix_len,
+                });
+            }
+        }
+
+        if let Some(metrics_history) = &self.metrics_history {
+            if metrics_history.interval == 0 {
+                return Err(SemanticValidationError::InvalidMetricsHistoryInterval);
+            }
+        }
+
+        if let Some(full_scrape) = &self.full_scrape {
+            if full_scrape.page_size == 0 {
+                return Err(SemanticValidationError::InvalidFullScrapePageSize);
+            }
+
+            if !full_scrape.public && full_scrape.admin_key.is_none() {
+                return Err(SemanticValidationError::MissingFullScrapeAdminKey);
+            }
+        }
+
+        if self.net.client_supplied_ip == ClientSuppliedIp::TrustedNetworks && self.net.trusted_networks.is_empty() {
+            return Err(SemanticValidationError::MissingTrustedNetworks);
+        }
+
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::v2_0_0::core::Core;
+    use crate::v2_0_0::full_scrape::FullScrape;
+    use crate::validator::{SemanticValidationError, Validator};
+
+    fn core_with_full_scrape(full_scrape: FullScrape) -> Core {
+        Core {
+            full_scrape: Some(full_scrape),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn it_should_reject_a_full_scrape_page_size_of_zero() {
+        let core = core_with_full_scrape(FullScrape {
+            public: true,
+            page_size: 0,
+            ..Default::default()
+        });
+
+        assert!(matches!(
+            core.validate(),
+            Err(SemanticValidationError::InvalidFullScrapePageSize)
+        ));
+    }
+
+    #[test]
+    fn it_should_reject_a_full_scrape_that_is_not_public_without_an_admin_key() {
+        let core = core_with_full_scrape(FullScrape {
+            public: false,
+            admin_key: None,
+            ..Default::default()
+        });
+
+        assert!(matches!(
+            core.validate(),
+            Err(SemanticValidationError::MissingFullScrapeAdminKey)
+        ));
+    }
+
+    #[test]
+    fn it_should_accept_a_full_scrape_that_is_not_public_with_an_admin_key() {
+        let core = core_with_full_scrape(FullScrape {
+            public: false,
+            admin_key: Some("MyAdminKey".to_string()),
+            ..Default::default()
+        });
+
+        assert!(core.validate().is_ok());
+    }
+}
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for the HTTP tracker full scrape.
+///
+/// When enabled, a `scrape` request without any `info_hash` param returns
+/// the stats of all the torrents in the tracker. The response can be big, so
+/// it's streamed, reading the torrents from the repository in pages.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct FullScrape {
+    /// When `true` any client can get the full scrape. Otherwise, only the
+    /// clients that send the `admin_key` can get it.
+    #[serde(default = "FullScrape::default_public")]
+    pub public: bool,
+
+    /// The key clients have to send in the `admin_key` param to get the full
+    /// scrape when it's not public. It's required in that case.
+    #[serde(default = "FullScrape::default_admin_key")]
+    pub admin_key: Option<String>,
+
+    /// Number of torrents read from the repository for each chunk of the
+    /// response. It must be greater than 0.
+    #[serde(default = "FullScrape::default_page_size")]
+    pub page_size: u32,
+}
+
+impl Default for FullScrape {
+    fn default() -> Self {
+        Self {
+            public: Self::default_public(),
+            admin_key: Self::default_admin_key(),
+            page_size: Self::default_page_size(),
+        }
+    }
+}
+
+impl FullScrape {
+    fn default_public() -> bool {
+        false
+    }
+
+    fn default_admin_key() -> Option<String> {
+        None
+    }
+
+    fn default_page_size() -> u32 {
+        1000
+    }
+}
@@ -0,0 +1,51 @@
+use camino::Utf8PathBuf;
+use serde::{Deserialize, Serialize};
+
+/// Configuration for the history of the tracker metrics.
+///
+/// When enabled, the tracker takes a sample of its metrics on intervals and
+/// keeps the latest samples in memory, so that the API can serve the metrics
+/// over time without an external metrics stack.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct MetricsHistory {
+    /// Interval in seconds between samples. It must be greater than `0`.
+    #[serde(default = "MetricsHistory::default_interval")]
+    pub interval: u64,
+
+    /// Maximum number of samples kept in memory. When the history is full,
+    /// the oldest sample is discarded. The default keeps one day of samples
+    /// with the default interval.
+    #[serde(default = "MetricsHistory::default_capacity")]
+    pub capacity: usize,
+
+    /// Optional path to a file where the history is saved on graceful
+    /// shutdown and restored from when the tracker starts. For example:
+    /// `./storage/tracker/lib/metrics/history.json`. The history is only kept
+    /// in memory when it's not set.
+    #[serde(default = "MetricsHistory::default_path")]
+    pub path: Option<Utf8PathBuf>,
+}
+
+impl Default for MetricsHistory {
+    fn default() -> Self {
+        Self {
+            interval: Self::default_interval(),
+            capacity: Self::default_capacity(),
+            path: Self::default_path(),
+        }
+    }
+}
+
+impl MetricsHistory {
+    fn default_interval() -> u64 {
+        60
+    }
+
+    fn default_capacity() -> usize {
+        1440
+    }
+
+    fn default_path() -> Option<Utf8PathBuf> {
+        None
+    }
+}
@@ -215,6 +215,8 @@
 //! [core.net]
 //! external_ip = "0.0.0.0"
 //! on_reverse_proxy = false
+//! client_supplied_ip = "never"
+//! trusted_networks = []
 //!
 //! [core.tracker_policy]
 //! max_peer_timeout = 900
@@ -231,10 +233,16 @@
 //!```
 pub mod core;
 pub mod database;
+pub mod full_scrape;
 pub mod health_check_api;
 pub mod http_tracker;
 pub mod logging;
+pub mod metrics_history;
 pub mod network;
+pub mod rate_limit;
+pub mod scrape_cache;
+pub mod snapshot;
+pub mod statistics_persistence;
 pub mod tracker_api;
 pub mod udp_tracker;
 
@@ -449,7 +457,12 @@ mod tests {
                                 [core]
                                 inactive_peer_cleanup_interval = 600
                                 listed = false
+                                omit_seeders_for_seeders = false
+                                on_early_announce = "accept"
+                                on_peer_identity_mismatch = "ignore"
+                                peer_selection = "in_order"
                                 private = false
+                                torrent_statistics = false
                                 tracker_usage_statistics = true
 
                                 [core.announce_policy]
@@ -463,6 +476,8 @@ mod tests {
                                 [core.net]
                                 external_ip = "0.0.0.0"
                                 on_reverse_proxy = false
+                                client_supplied_ip = "never"
+                                trusted_networks = []
 
                                 [core.tracker_policy]
                                 max_peer_timeout = 900
@@ -1,5 +1,6 @@
 use std::net::{IpAddr, Ipv4Addr};
 
+use ipnet::IpNet;
 use serde::{Deserialize, Serialize};
 
 #[allow(clippy::struct_excessive_bools)]
@@ -18,6 +19,19 @@ pub struct Network {
     /// sent from the proxy will be used to get the client's IP address.
     #[serde(default = "Network::default_on_reverse_proxy")]
     pub on_reverse_proxy: bool,
+
+    /// When the tracker uses the IP address sent by the client in the
+    /// `announce` request (the `ip` param in HTTP and the `ip_address` field
+    /// in UDP) as the peer IP, instead of the IP address the request comes
+    /// from.
+    #[serde(default = "Network::default_client_supplied_ip")]
+    pub client_supplied_ip: ClientSuppliedIp,
+
+    /// The networks, in CIDR notation, whose requests can set the peer IP when
+    /// the `client_supplied_ip` policy is `trusted_networks`. For example:
+    /// `["10.0.0.0/8", "2001:db8::/32"]`.
+    #[serde(default = "Network::default_trusted_networks")]
+    pub trusted_networks: Vec<IpNet>,
 }
 
 impl Default for Network {
@@ -25,6 +39,8 @@ impl Default for Network {
         Self {
             external_ip: Self::default_external_ip(),
             on_reverse_proxy: Self::default_on_reverse_proxy(),
+            client_supplied_ip: Self::default_client_supplied_ip(),
+            trusted_networks: Self::default_trusted_networks(),
         }
     }
 }
@@ -38,4 +54,31 @@ impl Network {
     fn default_on_reverse_proxy() -> bool {
         false
     }
+
+    fn default_client_supplied_ip() -> ClientSuppliedIp {
+        ClientSuppliedIp::default()
+    }
+
+    fn default_trusted_networks() -> Vec<IpNet> {
+        vec![]
+    }
+}
+
+/// Whose `announce` requests can set the peer IP with the IP address sent by
+/// the client.
+///
+/// Seedboxes and clients behind a NAT can ask the tracker to use a different
+/// IP address. But any client could use it to add other hosts to the swarms,
+/// so it's not allowed by default.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientSuppliedIp {
+    /// The IP address sent by the client is always ignored.
+    #[default]
+    Never,
+    /// Only the requests from the `trusted_networks` can set the peer IP.
+    TrustedNetworks,
+    /// Only the requests from loopback or private IP addresses can set the
+    /// peer IP.
+    PrivateNetworks,
 }
@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for the per client rate limiter.
+///
+/// The limiter uses a token bucket for each client and request type. IPv4
+/// clients are identified by their IP address. IPv6 clients are grouped by
+/// network prefix, because a single IPv6 client usually owns a whole prefix.
+///
+/// The limiter is shared by all the UDP and HTTP trackers.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct RateLimit {
+    /// Length of the IPv6 network prefix used to group IPv6 clients. For
+    /// example, with the default value `64` all the addresses in
+    /// `2001:db8::/64` share the same budgets.
+    #[serde(default = "RateLimit::default_ipv6_prefix_len")]
+    pub ipv6_prefix_len: u8,
+
+    /// Budget for UDP `connect` requests.
+    #[serde(default = "RateLimit::default_connect")]
+    pub connect: Budget,
+
+    /// Budget for `announce` requests.
+    #[serde(default = "RateLimit::default_announce")]
+    pub announce: Budget,
+
+    /// Budget for `scrape` requests.
+    #[serde(default = "RateLimit::default_scrape")]
+    pub scrape: Budget,
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        Self {
+            ipv6_prefix_len: Self::default_ipv6_prefix_len(),
+            connect: Self::default_connect(),
+            announce: Self::default_announce(),
+            scrape: Self::default_scrape(),
+        }
+    }
+}
+
+impl RateLimit {
+    fn default_ipv6_prefix_len() -> u8 {
+        64
+    }
+
+    fn default_connect() -> Budget {
+        Budget::new(20, 60)
+    }
+
+    fn default_announce() -> Budget {
+        Budget::new(20, 60)
+    }
+
+    fn default_scrape() -> Budget {
+        Budget::new(10, 30)
+    }
+}
+
+/// A token bucket budget.
+///
+/// A client can make up to `burst` requests in a row. After that, it gets
+/// `per_minute` new requests every minute.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Budget {
+    /// Maximum number of requests in a row (bucket capacity).
+    pub burst: u32,
+
+    /// Number of requests recovered every minute (bucket refill rate).
+    pub per_minute: u32,
+}
+
+impl Budget {
+    #[must_use]
+    pub fn new(burst: u32, per_minute: u32) -> Self {
+        Self { burst, per_minute }
+    }
+}
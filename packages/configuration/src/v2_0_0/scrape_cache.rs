@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for the cache of the swarm metadata returned in the
+/// `scrape` responses.
+///
+/// The cache is shared by all the UDP and HTTP trackers. Cached values are
+/// discarded as soon as the torrent changes, so the TTL only limits how long
+/// an unused value is kept.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct ScrapeCache {
+    /// Number of seconds a value is kept in the cache.
+    #[serde(default = "ScrapeCache::default_ttl")]
+    pub ttl: u64,
+
+    /// Maximum number of torrents in the cache. When the cache is full, new
+    /// torrents are not cached until some values expire.
+    #[serde(default = "ScrapeCache::default_max_entries")]
+    pub max_entries: usize,
+}
+
+impl Default for ScrapeCache {
+    fn default() -> Self {
+        Self {
+            ttl: Self::default_ttl(),
+            max_entries: Self::default_max_entries(),
+        }
+    }
+}
+
+impl ScrapeCache {
+    fn default_ttl() -> u64 {
+        5
+    }
+
+    fn default_max_entries() -> usize {
+        100_000
+    }
+}
@@ -0,0 +1,39 @@
+use camino::Utf8PathBuf;
+use serde::{Deserialize, Serialize};
+
+/// Configuration for the torrents snapshot.
+///
+/// When enabled, the tracker writes the in-memory torrent repository
+/// (including the peer lists) to a file on graceful shutdown and periodically,
+/// and restores it when it starts again. That way swarms survive a restart.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct Snapshot {
+    /// Path to the snapshot file. For example:
+    /// `./storage/tracker/lib/snapshot/torrents.json`.
+    #[serde(default = "Snapshot::default_path")]
+    pub path: Utf8PathBuf,
+
+    /// Interval in seconds between snapshots. Use `0` to only write the
+    /// snapshot on graceful shutdown.
+    #[serde(default = "Snapshot::default_interval")]
+    pub interval: u64,
+}
+
+impl Default for Snapshot {
+    fn default() -> Self {
+        Self {
+            path: Self::default_path(),
+            interval: Self::default_interval(),
+        }
+    }
+}
+
+impl Snapshot {
+    fn default_path() -> Utf8PathBuf {
+        Utf8PathBuf::from("./storage/tracker/lib/snapshot/torrents.json")
+    }
+
+    fn default_interval() -> u64 {
+        300
+    }
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for the persistence of the tracker usage statistics.
+///
+/// When enabled, the tracker saves the statistics counters into the database
+/// periodically and on graceful shutdown, and loads them again when it starts.
+/// That way the lifetime counters survive a restart.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct StatisticsPersistence {
+    /// Interval in seconds between saves. Use `0` to only save the statistics
+    /// on graceful shutdown.
+    #[serde(default = "StatisticsPersistence::default_interval")]
+    pub interval: u64,
+}
+
+impl Default for StatisticsPersistence {
+    fn default() -> Self {
+        Self {
+            interval: Self::default_interval(),
+        }
+    }
+}
+
+impl StatisticsPersistence {
+    fn default_interval() -> u64 {
+        60
+    }
+}
@@ -1,12 +1,68 @@
 use std::collections::HashMap;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
+use derive_more::Display;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
 use crate::TslConfig;
 
 pub type AccessTokens = HashMap<String, String>;
+pub type AccessTokenScopes = HashMap<String, Vec<Scope>>;
+
+/// A permission that can be granted to an API access token. Each API endpoint
+/// requires one scope.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Display, Clone, Copy)]
+pub enum Scope {
+    /// List and get the traffic of the authentication keys.
+    #[serde(rename = "accounting:read")]
+    #[display("accounting:read")]
+    AccountingRead,
+    /// Reload the tracker configuration.
+    #[serde(rename = "config:write")]
+    #[display("config:write")]
+    ConfigWrite,
+    /// Subscribe to the real-time tracker events.
+    #[serde(rename = "events:read")]
+    #[display("events:read")]
+    EventsRead,
+    /// List the IP access rules.
+    #[serde(rename = "ip_access_list:read")]
+    #[display("ip_access_list:read")]
+    IpAccessListRead,
+    /// Add, remove and reload the IP access rules.
+    #[serde(rename = "ip_access_list:write")]
+    #[display("ip_access_list:write")]
+    IpAccessListWrite,
+    /// List and get the authentication keys, and receive their events.
+    #[serde(rename = "keys:read")]
+    #[display("keys:read")]
+    KeysRead,
+    /// Generate, add, delete and reload the authentication keys.
+    #[serde(rename = "keys:write")]
+    #[display("keys:write")]
+    KeysWrite,
+    /// Get the tracker statistics and their history.
+    #[serde(rename = "stats:read")]
+    #[display("stats:read")]
+    StatsRead,
+    /// Reset the tracker statistics.
+    #[serde(rename = "stats:write")]
+    #[display("stats:write")]
+    StatsWrite,
+    /// Get the torrents and their peers.
+    #[serde(rename = "torrents:read")]
+    #[display("torrents:read")]
+    TorrentsRead,
+    /// List and get the whitelisted torrents.
+    #[serde(rename = "whitelist:read")]
+    #[display("whitelist:read")]
+    WhitelistRead,
+    /// Add, remove and reload the whitelisted torrents.
+    #[serde(rename = "whitelist:write")]
+    #[display("whitelist:write")]
+    WhitelistWrite,
+}
 
 /// Configuration for the HTTP API.
 #[serde_as]
@@ -25,10 +81,23 @@ pub struct HttpApi {
 
     /// Access tokens for the HTTP API. The key is a label identifying the
     /// token and the value is the token itself. The token is used to
-    /// authenticate the user. All tokens are valid for all endpoints and have
-    /// all permissions.
+    /// authenticate the user. Tokens have all the permissions unless their
+    /// scopes are limited in `access_token_scopes`.
     #[serde(default = "HttpApi::default_access_tokens")]
     pub access_tokens: AccessTokens,
+
+    /// The scopes granted to the access tokens. The key is the label of the
+    /// token in `access_tokens` and the value is the list of granted
+    /// [`Scope`]s, for example `["stats:read", "torrents:read"]` for a
+    /// read-only token. Tokens without an entry have all the scopes.
+    #[serde(default = "HttpApi::default_access_token_scopes")]
+    pub access_token_scopes: AccessTokenScopes,
+
+    /// Whether the access token can be sent in the `token` query param. When
+    /// it's `false`, the token can only be sent in the `Authorization: Bearer`
+    /// header, so it does not end up in the access logs of the proxies.
+    #[serde(default = "HttpApi::default_query_token_enabled")]
+    pub query_token_enabled: bool,
 }
 
 impl Default for HttpApi {
@@ -37,6 +106,8 @@ impl Default for HttpApi {
             bind_address: Self::default_bind_address(),
             tsl_config: Self::default_tsl_config(),
             access_tokens: Self::default_access_tokens(),
+            access_token_scopes: Self::default_access_token_scopes(),
+            query_token_enabled: Self::default_query_token_enabled(),
         }
     }
 }
@@ -55,10 +126,24 @@ impl HttpApi {
         [].iter().cloned().collect()
     }
 
+    fn default_access_token_scopes() -> AccessTokenScopes {
+        AccessTokenScopes::new()
+    }
+
+    fn default_query_token_enabled() -> bool {
+        true
+    }
+
     pub fn add_token(&mut self, key: &str, token: &str) {
         self.access_tokens.insert(key.to_string(), token.to_string());
     }
 
+    /// It limits the permissions of the token with the label `key` to the
+    /// given `scopes`.
+    pub fn limit_token_scopes(&mut self, key: &str, scopes: &[Scope]) {
+        self.access_token_scopes.insert(key.to_string(), scopes.to_vec());
+    }
+
     pub fn mask_secrets(&mut self) {
         for token in self.access_tokens.values_mut() {
             *token = "***".to_string();
@@ -68,7 +153,7 @@ impl HttpApi {
 
 #[cfg(test)]
 mod tests {
-    use crate::v2_0_0::tracker_api::HttpApi;
+    use crate::v2_0_0::tracker_api::{HttpApi, Scope};
 
     #[test]
     fn default_http_api_configuration_should_not_contains_any_token() {
@@ -85,4 +170,36 @@ mod tests {
 
         assert!(configuration.access_tokens.values().any(|t| t == "MyAccessToken"));
     }
+
+    #[test]
+    fn http_api_configuration_should_allow_limiting_the_scopes_of_a_token() {
+        let mut configuration = HttpApi::default();
+
+        configuration.add_token("monitoring", "MyMonitoringToken");
+        configuration.limit_token_scopes("monitoring", &[Scope::StatsRead]);
+
+        assert_eq!(
+            configuration.access_token_scopes.get("monitoring"),
+            Some(&vec![Scope::StatsRead])
+        );
+    }
+
+    #[test]
+    fn access_token_scopes_should_be_deserialized_from_their_names() {
+        let configuration: HttpApi = toml::from_str(
+            r#"
+            [access_tokens]
+            monitoring = "MyMonitoringToken"
+
+            [access_token_scopes]
+            monitoring = ["stats:read", "torrents:read"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            configuration.access_token_scopes.get("monitoring"),
+            Some(&vec![Scope::StatsRead, Scope::TorrentsRead])
+        );
+    }
 }
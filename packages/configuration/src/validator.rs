@@ -9,6 +9,21 @@ use thiserror::Error;
 pub enum SemanticValidationError {
     #[error("Private mode section in configuration can only be included when the tracker is running in private mode.")]
     UselessPrivateModeSection,
+
+    #[error("The IPv6 prefix length for the rate limiter must be between 0 and 128, got {prefix_len}.")]
+    InvalidIpv6PrefixLength { prefix_len: u8 },
+
+    #[error("The `trusted_networks` client supplied IP policy requires at least one trusted network.")]
+    MissingTrustedNetworks,
+
+    #[error("The interval of the metrics history must be greater than 0 seconds.")]
+    InvalidMetricsHistoryInterval,
+
+    #[error("The page size of the full scrape must be greater than 0 torrents.")]
+    InvalidFullScrapePageSize,
+
+    #[error("The full scrape requires an `admin_key` when it's not public.")]
+    MissingFullScrapeAdminKey,
 }
 
 pub trait Validator {
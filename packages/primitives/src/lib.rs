@@ -14,6 +14,7 @@ pub mod pagination;
 pub mod peer;
 pub mod swarm_metadata;
 pub mod torrent_metrics;
+pub mod torrent_stats;
 
 /// Duration since the Unix Epoch.
 pub type DurationSinceUnixEpoch = Duration;
@@ -14,6 +14,9 @@
 //! peer::Peer {
 //!     peer_id: PeerId(*b"-qB00000000000000000"),
 //!     peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080),
+//!     alt_peer_addr: None,
+//!     key: None,
+//!     crypto: peer::Crypto::NotSupported,
 //!     updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
 //!     uploaded: NumberOfBytes::new(0),
 //!     downloaded: NumberOfBytes::new(0),
@@ -26,8 +29,8 @@ use std::net::{IpAddr, SocketAddr};
 use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
 
-use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes, PeerId};
-use serde::Serialize;
+use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes, PeerId, PeerKey};
+use serde::{Deserialize, Serialize};
 use zerocopy::FromBytes as _;
 
 use crate::DurationSinceUnixEpoch;
@@ -48,6 +51,9 @@ use crate::DurationSinceUnixEpoch;
 /// peer::Peer {
 ///     peer_id: PeerId(*b"-qB00000000000000000"),
 ///     peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080),
+///     alt_peer_addr: None,
+///     key: None,
+///     crypto: peer::Crypto::NotSupported,
 ///     updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
 ///     uploaded: NumberOfBytes::new(0),
 ///     downloaded: NumberOfBytes::new(0),
@@ -62,6 +68,20 @@ pub struct Peer {
     pub peer_id: PeerId,
     /// The IP and port this peer is listening on
     pub peer_addr: SocketAddr,
+    /// The IP and port this peer is listening on in the other IP family, when
+    /// the peer is reachable on both IPv4 and IPv6. See
+    /// [BEP 07. IPv6 Tracker Extension](https://www.bittorrent.org/beps/bep_0007.html).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alt_peer_addr: Option<SocketAddr>,
+    /// The key the peer sent in its first announce. Peers can use it to prove
+    /// their identity when their IP address changes. It's never serialized
+    /// because anyone knowing it could impersonate the peer.
+    #[serde(skip)]
+    pub key: Option<PeerKey>,
+    /// Whether the peer supports or requires encrypted connections. See
+    /// [`Crypto`].
+    #[serde(skip)]
+    pub crypto: Crypto,
     /// The last time the the tracker receive an announce request from this peer (timestamp)
     #[serde(serialize_with = "ser_unix_time_value")]
     pub updated: DurationSinceUnixEpoch,
@@ -79,6 +99,34 @@ pub struct Peer {
     pub event: AnnounceEvent,
 }
 
+/// The support of the peer for encrypted connections, the
+/// [Message Stream Encryption](https://wiki.vuze.com/w/Message_Stream_Encryption)
+/// protocol.
+///
+/// HTTP clients report it with the `supportcrypto` and `requirecrypto`
+/// announce params. It's unknown for other clients, so they are considered
+/// not to support it.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Crypto {
+    /// The peer only accepts plaintext connections, or it has not said it
+    /// supports encryption.
+    #[default]
+    NotSupported,
+    /// The peer accepts both plaintext and encrypted connections.
+    Supported,
+    /// The peer only accepts encrypted connections.
+    Required,
+}
+
+impl Crypto {
+    /// Whether the peer accepts encrypted connections.
+    #[must_use]
+    pub fn is_supported(self) -> bool {
+        self != Crypto::NotSupported
+    }
+}
+
 /// Serializes a `DurationSinceUnixEpoch` as a Unix timestamp in milliseconds.
 /// # Errors
 ///
@@ -205,8 +253,30 @@ impl Peer {
         self.peer_addr.ip()
     }
 
+    /// It changes the IP of the peer address. The alternative address is
+    /// removed if it's in the same IP family as the new IP.
     pub fn change_ip(&mut self, new_ip: &IpAddr) {
         self.peer_addr = SocketAddr::new(*new_ip, self.peer_addr.port());
+        self.change_alt_addr(self.alt_peer_addr);
+    }
+
+    /// It sets the address of the peer in the other IP family. The address is
+    /// ignored if it's in the same IP family as the peer address.
+    pub fn change_alt_addr(&mut self, alt_addr: Option<SocketAddr>) {
+        self.alt_peer_addr = alt_addr.filter(|alt_addr| alt_addr.is_ipv4() != self.peer_addr.is_ipv4());
+    }
+
+    /// The peer address in the same IP family as the given IP, if the peer is
+    /// reachable in that family.
+    #[must_use]
+    pub fn addr_in_family_of(&self, ip: &IpAddr) -> Option<SocketAddr> {
+        self.addresses().find(|addr| addr.is_ipv4() == ip.is_ipv4())
+    }
+
+    /// All the addresses of the peer: the peer address and the alternative
+    /// one, if any.
+    pub fn addresses(&self) -> impl Iterator<Item = SocketAddr> {
+        std::iter::once(self.peer_addr).chain(self.alt_peer_addr)
     }
 }
 
@@ -390,9 +460,9 @@ impl<P: Encoding> FromIterator<Peer> for Vec<P> {
 pub mod fixture {
     use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
-    use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes};
+    use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes, PeerKey};
 
-    use super::{Id, Peer, PeerId};
+    use super::{Crypto, Id, Peer, PeerId};
     use crate::DurationSinceUnixEpoch;
 
     #[derive(PartialEq, Debug)]
@@ -415,6 +485,9 @@ pub mod fixture {
             let peer = Peer {
                 peer_id: PeerId(*b"-qB00000000000000001"),
                 peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+                alt_peer_addr: None,
+                key: None,
+                crypto: Crypto::NotSupported,
                 updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
                 uploaded: NumberOfBytes::new(0),
                 downloaded: NumberOfBytes::new(0),
@@ -431,6 +504,9 @@ pub mod fixture {
             let peer = Peer {
                 peer_id: PeerId(*b"-qB00000000000000002"),
                 peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 2)), 8080),
+                alt_peer_addr: None,
+                key: None,
+                crypto: Crypto::NotSupported,
                 updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
                 uploaded: NumberOfBytes::new(0),
                 downloaded: NumberOfBytes::new(0),
@@ -455,6 +531,27 @@ pub mod fixture {
             self
         }
 
+        #[allow(dead_code)]
+        #[must_use]
+        pub fn with_alt_peer_addr(mut self, alt_peer_addr: &SocketAddr) -> Self {
+            self.peer.change_alt_addr(Some(*alt_peer_addr));
+            self
+        }
+
+        #[allow(dead_code)]
+        #[must_use]
+        pub fn with_key(mut self, key: &PeerKey) -> Self {
+            self.peer.key = Some(*key);
+            self
+        }
+
+        #[allow(dead_code)]
+        #[must_use]
+        pub fn with_crypto(mut self, crypto: Crypto) -> Self {
+            self.peer.crypto = crypto;
+            self
+        }
+
         #[allow(dead_code)]
         #[must_use]
         pub fn with_bytes_pending_to_download(mut self, left: i64) -> Self {
@@ -494,6 +591,9 @@ pub mod fixture {
             Self {
                 peer_id: PeerId(*b"-qB00000000000000000"),
                 peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+                alt_peer_addr: None,
+                key: None,
+                crypto: Crypto::NotSupported,
                 updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
                 uploaded: NumberOfBytes::new(0),
                 downloaded: NumberOfBytes::new(0),
@@ -560,4 +660,58 @@ pub mod test {
             assert_eq!(id.to_string(), "0x009f9296009f9296009f9296009f9296009f9296");
         }
     }
+
+    mod torrent_peer_addresses {
+        use std::net::{IpAddr, SocketAddr};
+
+        use crate::peer::fixture::PeerBuilder;
+
+        fn ipv4_addr() -> SocketAddr {
+            "126.0.0.1:8080".parse().unwrap()
+        }
+
+        fn ipv6_addr() -> SocketAddr {
+            "[2001:db8::1]:8080".parse().unwrap()
+        }
+
+        #[test]
+        fn should_ignore_an_alternative_address_in_the_same_ip_family() {
+            let peer = PeerBuilder::default()
+                .with_peer_addr(&ipv4_addr())
+                .with_alt_peer_addr(&"126.0.0.2:8080".parse().unwrap())
+                .build();
+
+            assert_eq!(peer.alt_peer_addr, None);
+        }
+
+        #[test]
+        fn should_return_the_address_in_the_requested_ip_family() {
+            let peer = PeerBuilder::default()
+                .with_peer_addr(&ipv4_addr())
+                .with_alt_peer_addr(&ipv6_addr())
+                .build();
+
+            assert_eq!(peer.addr_in_family_of(&"126.0.0.2".parse().unwrap()), Some(ipv4_addr()));
+            assert_eq!(peer.addr_in_family_of(&"2001:db8::2".parse().unwrap()), Some(ipv6_addr()));
+        }
+
+        #[test]
+        fn should_not_return_any_address_when_the_peer_is_not_reachable_in_the_requested_ip_family() {
+            let peer = PeerBuilder::default().with_peer_addr(&ipv4_addr()).build();
+
+            assert_eq!(peer.addr_in_family_of(&"2001:db8::2".parse().unwrap()), None);
+        }
+
+        #[test]
+        fn should_remove_the_alternative_address_when_the_ip_changes_to_its_ip_family() {
+            let mut peer = PeerBuilder::default()
+                .with_peer_addr(&ipv4_addr())
+                .with_alt_peer_addr(&ipv6_addr())
+                .build();
+
+            peer.change_ip(&IpAddr::V6("2001:db8::2".parse().unwrap()));
+
+            assert_eq!(peer.alt_peer_addr, None);
+        }
+    }
 }
@@ -0,0 +1,20 @@
+use crate::DurationSinceUnixEpoch;
+
+/// Activity statistics for one torrent.
+///
+/// Unlike the [`SwarmMetadata`](crate::swarm_metadata::SwarmMetadata), they
+/// are not part of the `BitTorrent` protocol. They are only kept in memory to
+/// know which torrents are more active.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TorrentStats {
+    /// Number of `announce` requests received for the torrent.
+    pub announces: u64,
+    /// Number of times the torrent has been included in a `scrape` request.
+    pub scrapes: u64,
+    /// Time of the last `announce` or `scrape` request for the torrent.
+    pub last_activity: Option<DurationSinceUnixEpoch>,
+    /// Maximum number of seeders the swarm has ever had at the same time.
+    pub peak_seeders: u32,
+    /// Maximum number of leechers the swarm has ever had at the same time.
+    pub peak_leechers: u32,
+}
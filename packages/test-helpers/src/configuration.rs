@@ -2,7 +2,7 @@
 use std::env;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 
-use torrust_tracker_configuration::{Configuration, HttpApi, HttpTracker, Threshold, UdpTracker};
+use torrust_tracker_configuration::{Configuration, HttpApi, HttpTracker, RateLimit, Threshold, UdpTracker};
 
 use crate::random;
 
@@ -136,6 +136,16 @@ pub fn ephemeral_with_external_ip(ip: IpAddr) -> Configuration {
     cfg
 }
 
+/// Ephemeral configuration with the rate limiter enabled.
+#[must_use]
+pub fn ephemeral_with_rate_limit(rate_limit: RateLimit) -> Configuration {
+    let mut cfg = ephemeral();
+
+    cfg.core.rate_limit = Some(rate_limit);
+
+    cfg
+}
+
 /// Ephemeral configuration using a wildcard IPv6 for the UDP, HTTP and API
 /// services.
 #[must_use]
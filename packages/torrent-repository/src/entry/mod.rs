@@ -2,11 +2,14 @@ use std::fmt::Debug;
 use std::net::SocketAddr;
 use std::sync::Arc;
 
+use aquatic_udp_protocol::PeerId;
 use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
+use torrust_tracker_primitives::torrent_stats::TorrentStats;
 use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
 
 use self::peer_list::PeerList;
+use crate::peer_selection::PeerSelector;
 
 pub mod mutex_parking_lot;
 pub mod mutex_std;
@@ -33,6 +36,9 @@ pub trait Entry {
     /// Get all swarm peers, optionally limiting the result.
     fn get_peers(&self, limit: Option<usize>) -> Vec<Arc<peer::Peer>>;
 
+    /// Get a swarm peer by its peer id.
+    fn get_peer(&self, peer_id: &PeerId) -> Option<Arc<peer::Peer>>;
+
     /// It returns the list of peers for a given peer client, optionally limiting the
     /// result.
     ///
@@ -40,6 +46,14 @@ pub trait Entry {
     /// list of peers to that client peer.
     fn get_peers_for_client(&self, client: &SocketAddr, limit: Option<usize>) -> Vec<Arc<peer::Peer>>;
 
+    /// It returns up to `limit` peers for a given peer client, chosen with
+    /// the peer `selector` strategy. The client peer is never included.
+    ///
+    /// It needs a mutable entry because some strategies keep track of the
+    /// peers they have returned. See [`PeerSelector::needs_mut`].
+    fn select_peers_for_client(&mut self, client: &peer::Peer, limit: usize, selector: &dyn PeerSelector)
+        -> Vec<Arc<peer::Peer>>;
+
     /// It updates a peer and returns true if the number of complete downloads have increased.
     ///
     /// The number of peers that have complete downloading is synchronously updated when peers are updated.
@@ -48,6 +62,17 @@ pub trait Entry {
 
     /// It removes peer from the swarm that have not been updated for more than `current_cutoff` seconds
     fn remove_inactive_peers(&mut self, current_cutoff: DurationSinceUnixEpoch);
+
+    /// It returns the torrent activity statistics.
+    fn get_torrent_stats(&self) -> TorrentStats;
+
+    /// It counts an `announce` request for the torrent. It should be called
+    /// after updating the peer, so that the peak number of seeders and
+    /// leechers includes it.
+    fn record_announce(&mut self, now: DurationSinceUnixEpoch);
+
+    /// It counts a `scrape` request for the torrent.
+    fn record_scrape(&mut self, now: DurationSinceUnixEpoch);
 }
 
 #[allow(clippy::module_name_repetitions)]
@@ -57,9 +82,14 @@ pub trait EntrySync {
     fn peers_is_empty(&self) -> bool;
     fn get_peers_len(&self) -> usize;
     fn get_peers(&self, limit: Option<usize>) -> Vec<Arc<peer::Peer>>;
+    fn get_peer(&self, peer_id: &PeerId) -> Option<Arc<peer::Peer>>;
     fn get_peers_for_client(&self, client: &SocketAddr, limit: Option<usize>) -> Vec<Arc<peer::Peer>>;
+    fn select_peers_for_client(&self, client: &peer::Peer, limit: usize, selector: &dyn PeerSelector) -> Vec<Arc<peer::Peer>>;
     fn upsert_peer(&self, peer: &peer::Peer) -> bool;
     fn remove_inactive_peers(&self, current_cutoff: DurationSinceUnixEpoch);
+    fn get_torrent_stats(&self) -> TorrentStats;
+    fn record_announce(&self, now: DurationSinceUnixEpoch);
+    fn record_scrape(&self, now: DurationSinceUnixEpoch);
 }
 
 #[allow(clippy::module_name_repetitions)]
@@ -69,13 +99,23 @@ pub trait EntryAsync {
     fn peers_is_empty(&self) -> impl std::future::Future<Output = bool> + Send;
     fn get_peers_len(&self) -> impl std::future::Future<Output = usize> + Send;
     fn get_peers(&self, limit: Option<usize>) -> impl std::future::Future<Output = Vec<Arc<peer::Peer>>> + Send;
+    fn get_peer(&self, peer_id: &PeerId) -> impl std::future::Future<Output = Option<Arc<peer::Peer>>> + Send;
     fn get_peers_for_client(
         &self,
         client: &SocketAddr,
         limit: Option<usize>,
     ) -> impl std::future::Future<Output = Vec<Arc<peer::Peer>>> + Send;
+    fn select_peers_for_client(
+        self,
+        client: &peer::Peer,
+        limit: usize,
+        selector: &dyn PeerSelector,
+    ) -> impl std::future::Future<Output = Vec<Arc<peer::Peer>>> + Send;
     fn upsert_peer(self, peer: &peer::Peer) -> impl std::future::Future<Output = bool> + Send;
     fn remove_inactive_peers(self, current_cutoff: DurationSinceUnixEpoch) -> impl std::future::Future<Output = ()> + Send;
+    fn get_torrent_stats(&self) -> impl std::future::Future<Output = TorrentStats> + Send;
+    fn record_announce(self, now: DurationSinceUnixEpoch) -> impl std::future::Future<Output = ()> + Send;
+    fn record_scrape(self, now: DurationSinceUnixEpoch) -> impl std::future::Future<Output = ()> + Send;
 }
 
 /// A data structure containing all the information about a torrent in the tracker.
@@ -89,4 +129,6 @@ pub struct Torrent {
     pub(crate) swarm: PeerList,
     /// The number of peers that have ever completed downloading the torrent associated to this entry
     pub(crate) downloaded: u32,
+    /// The activity statistics of the torrent associated to this entry
+    pub(crate) stats: TorrentStats,
 }
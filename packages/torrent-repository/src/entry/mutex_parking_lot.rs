@@ -1,11 +1,14 @@
 use std::net::SocketAddr;
 use std::sync::Arc;
 
+use aquatic_udp_protocol::PeerId;
 use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
+use torrust_tracker_primitives::torrent_stats::TorrentStats;
 use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
 
 use super::{Entry, EntrySync};
+use crate::peer_selection::PeerSelector;
 use crate::{EntryMutexParkingLot, EntrySingle};
 
 impl EntrySync for EntryMutexParkingLot {
@@ -29,10 +32,18 @@ impl EntrySync for EntryMutexParkingLot {
         self.lock().get_peers(limit)
     }
 
+    fn get_peer(&self, peer_id: &PeerId) -> Option<Arc<peer::Peer>> {
+        self.lock().get_peer(peer_id)
+    }
+
     fn get_peers_for_client(&self, client: &SocketAddr, limit: Option<usize>) -> Vec<Arc<peer::Peer>> {
         self.lock().get_peers_for_client(client, limit)
     }
 
+    fn select_peers_for_client(&self, client: &peer::Peer, limit: usize, selector: &dyn PeerSelector) -> Vec<Arc<peer::Peer>> {
+        self.lock().select_peers_for_client(client, limit, selector)
+    }
+
     fn upsert_peer(&self, peer: &peer::Peer) -> bool {
         self.lock().upsert_peer(peer)
     }
@@ -40,6 +51,18 @@ impl EntrySync for EntryMutexParkingLot {
     fn remove_inactive_peers(&self, current_cutoff: DurationSinceUnixEpoch) {
         self.lock().remove_inactive_peers(current_cutoff);
     }
+
+    fn get_torrent_stats(&self) -> TorrentStats {
+        self.lock().get_torrent_stats()
+    }
+
+    fn record_announce(&self, now: DurationSinceUnixEpoch) {
+        self.lock().record_announce(now);
+    }
+
+    fn record_scrape(&self, now: DurationSinceUnixEpoch) {
+        self.lock().record_scrape(now);
+    }
 }
 
 impl From<EntrySingle> for EntryMutexParkingLot {
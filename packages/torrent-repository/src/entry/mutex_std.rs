@@ -1,11 +1,14 @@
 use std::net::SocketAddr;
 use std::sync::Arc;
 
+use aquatic_udp_protocol::PeerId;
 use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
+use torrust_tracker_primitives::torrent_stats::TorrentStats;
 use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
 
 use super::{Entry, EntrySync};
+use crate::peer_selection::PeerSelector;
 use crate::{EntryMutexStd, EntrySingle};
 
 impl EntrySync for EntryMutexStd {
@@ -29,10 +32,20 @@ impl EntrySync for EntryMutexStd {
         self.lock().expect("it should get lock").get_peers(limit)
     }
 
+    fn get_peer(&self, peer_id: &PeerId) -> Option<Arc<peer::Peer>> {
+        self.lock().expect("it should get lock").get_peer(peer_id)
+    }
+
     fn get_peers_for_client(&self, client: &SocketAddr, limit: Option<usize>) -> Vec<Arc<peer::Peer>> {
         self.lock().expect("it should get lock").get_peers_for_client(client, limit)
     }
 
+    fn select_peers_for_client(&self, client: &peer::Peer, limit: usize, selector: &dyn PeerSelector) -> Vec<Arc<peer::Peer>> {
+        self.lock()
+            .expect("it should lock the entry")
+            .select_peers_for_client(client, limit, selector)
+    }
+
     fn upsert_peer(&self, peer: &peer::Peer) -> bool {
         self.lock().expect("it should lock the entry").upsert_peer(peer)
     }
@@ -42,6 +55,18 @@ impl EntrySync for EntryMutexStd {
             .expect("it should lock the entry")
             .remove_inactive_peers(current_cutoff);
     }
+
+    fn get_torrent_stats(&self) -> TorrentStats {
+        self.lock().expect("it should get a lock").get_torrent_stats()
+    }
+
+    fn record_announce(&self, now: DurationSinceUnixEpoch) {
+        self.lock().expect("it should lock the entry").record_announce(now);
+    }
+
+    fn record_scrape(&self, now: DurationSinceUnixEpoch) {
+        self.lock().expect("it should lock the entry").record_scrape(now);
+    }
 }
 
 impl From<EntrySingle> for EntryMutexStd {
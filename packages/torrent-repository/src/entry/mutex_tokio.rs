@@ -1,11 +1,14 @@
 use std::net::SocketAddr;
 use std::sync::Arc;
 
+use aquatic_udp_protocol::PeerId;
 use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
+use torrust_tracker_primitives::torrent_stats::TorrentStats;
 use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
 
 use super::{Entry, EntryAsync};
+use crate::peer_selection::PeerSelector;
 use crate::{EntryMutexTokio, EntrySingle};
 
 impl EntryAsync for EntryMutexTokio {
@@ -29,10 +32,23 @@ impl EntryAsync for EntryMutexTokio {
         self.lock().await.get_peers(limit)
     }
 
+    async fn get_peer(&self, peer_id: &PeerId) -> Option<Arc<peer::Peer>> {
+        self.lock().await.get_peer(peer_id)
+    }
+
     async fn get_peers_for_client(&self, client: &SocketAddr, limit: Option<usize>) -> Vec<Arc<peer::Peer>> {
         self.lock().await.get_peers_for_client(client, limit)
     }
 
+    async fn select_peers_for_client(
+        self,
+        client: &peer::Peer,
+        limit: usize,
+        selector: &dyn PeerSelector,
+    ) -> Vec<Arc<peer::Peer>> {
+        self.lock().await.select_peers_for_client(client, limit, selector)
+    }
+
     async fn upsert_peer(self, peer: &peer::Peer) -> bool {
         self.lock().await.upsert_peer(peer)
     }
@@ -40,6 +56,18 @@ impl EntryAsync for EntryMutexTokio {
     async fn remove_inactive_peers(self, current_cutoff: DurationSinceUnixEpoch) {
         self.lock().await.remove_inactive_peers(current_cutoff);
     }
+
+    async fn get_torrent_stats(&self) -> TorrentStats {
+        self.lock().await.get_torrent_stats()
+    }
+
+    async fn record_announce(self, now: DurationSinceUnixEpoch) {
+        self.lock().await.record_announce(now);
+    }
+
+    async fn record_scrape(self, now: DurationSinceUnixEpoch) {
+        self.lock().await.record_scrape(now);
+    }
 }
 
 impl From<EntrySingle> for EntryMutexTokio {
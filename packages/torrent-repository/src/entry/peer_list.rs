@@ -10,9 +10,42 @@ use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
 // For example, two peers with the same socket address but a different peer Id
 // would be allowed. That would lead to duplicated peers in the tracker responses.
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Two lists are equal when they have the same peers. The bookkeeping of the
+/// returned peers is not compared, ordered or hashed.
+#[derive(Clone, Debug, Default)]
 pub struct PeerList {
     peers: std::collections::BTreeMap<PeerId, Arc<peer::Peer>>,
+    /// The last time each peer was returned to a client, as a sequence
+    /// number. It's only used by the least-recently-returned peer selection.
+    returned: std::collections::BTreeMap<PeerId, u64>,
+    /// The sequence number of the last time peers were returned.
+    last_return: u64,
+}
+
+impl PartialEq for PeerList {
+    fn eq(&self, other: &Self) -> bool {
+        self.peers == other.peers
+    }
+}
+
+impl Eq for PeerList {}
+
+impl PartialOrd for PeerList {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PeerList {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.peers.cmp(&other.peers)
+    }
+}
+
+impl std::hash::Hash for PeerList {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.peers.hash(state);
+    }
 }
 
 impl PeerList {
@@ -31,12 +64,16 @@ impl PeerList {
     }
 
     pub fn remove(&mut self, key: &PeerId) -> Option<Arc<peer::Peer>> {
+        self.returned.remove(key);
         self.peers.remove(key)
     }
 
     pub fn remove_inactive_peers(&mut self, current_cutoff: DurationSinceUnixEpoch) {
         self.peers
             .retain(|_, peer| peer::ReadInfo::get_updated(peer) > current_cutoff);
+
+        let peers = &self.peers;
+        self.returned.retain(|peer_id, _| peers.contains_key(peer_id));
     }
 
     #[must_use]
@@ -52,6 +89,11 @@ impl PeerList {
         }
     }
 
+    /// It iterates over the peers in the list order.
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<peer::Peer>> {
+        self.peers.values()
+    }
+
     #[must_use]
     pub fn seeders_and_leechers(&self) -> (usize, usize) {
         let seeders = self.peers.values().filter(|peer| peer.is_seeder()).count();
@@ -60,6 +102,22 @@ impl PeerList {
         (seeders, leechers)
     }
 
+    /// The sequence number of the last time the peer was returned to a
+    /// client. It's `0` if the peer has never been returned.
+    #[must_use]
+    pub fn last_returned(&self, peer_id: &PeerId) -> u64 {
+        self.returned.get(peer_id).copied().unwrap_or_default()
+    }
+
+    /// It records that the peers have been returned to a client.
+    pub fn mark_as_returned(&mut self, peers: &[Arc<peer::Peer>]) {
+        self.last_return += 1;
+
+        for peer in peers {
+            self.returned.insert(peer.peer_id, self.last_return);
+        }
+    }
+
     #[must_use]
     pub fn get_peers_excluding_addr(&self, peer_addr: &SocketAddr, limit: Option<usize>) -> Vec<Arc<peer::Peer>> {
         match limit {
@@ -282,5 +340,19 @@ mod tests {
 
             assert_eq!(peer_list.len(), 2);
         }
+
+        #[test]
+        fn be_equal_to_a_list_with_the_same_peers_regardless_of_the_returned_peers() {
+            let peer = Arc::new(PeerBuilder::default().build());
+
+            let mut peer_list = PeerList::default();
+            peer_list.upsert(peer.clone());
+
+            let mut returned_peer_list = peer_list.clone();
+            returned_peer_list.mark_as_returned(&[peer]);
+
+            assert_eq!(peer_list, returned_peer_list);
+            assert_eq!(peer_list.cmp(&returned_peer_list), std::cmp::Ordering::Equal);
+        }
     }
 }
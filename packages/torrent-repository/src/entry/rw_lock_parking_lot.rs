@@ -1,11 +1,14 @@
 use std::net::SocketAddr;
 use std::sync::Arc;
 
+use aquatic_udp_protocol::PeerId;
 use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
+use torrust_tracker_primitives::torrent_stats::TorrentStats;
 use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
 
 use super::{Entry, EntrySync};
+use crate::peer_selection::PeerSelector;
 use crate::{EntryRwLockParkingLot, EntrySingle};
 
 impl EntrySync for EntryRwLockParkingLot {
@@ -29,10 +32,22 @@ impl EntrySync for EntryRwLockParkingLot {
         self.read().get_peers(limit)
     }
 
+    fn get_peer(&self, peer_id: &PeerId) -> Option<Arc<peer::Peer>> {
+        self.read().get_peer(peer_id)
+    }
+
     fn get_peers_for_client(&self, client: &SocketAddr, limit: Option<usize>) -> Vec<Arc<peer::Peer>> {
         self.read().get_peers_for_client(client, limit)
     }
 
+    fn select_peers_for_client(&self, client: &peer::Peer, limit: usize, selector: &dyn PeerSelector) -> Vec<Arc<peer::Peer>> {
+        if selector.needs_mut() {
+            return self.write().select_peers_for_client(client, limit, selector);
+        }
+
+        selector.select(&self.read().swarm, client, limit)
+    }
+
     fn upsert_peer(&self, peer: &peer::Peer) -> bool {
         self.write().upsert_peer(peer)
     }
@@ -40,6 +55,18 @@ impl EntrySync for EntryRwLockParkingLot {
     fn remove_inactive_peers(&self, current_cutoff: DurationSinceUnixEpoch) {
         self.write().remove_inactive_peers(current_cutoff);
     }
+
+    fn get_torrent_stats(&self) -> TorrentStats {
+        self.read().get_torrent_stats()
+    }
+
+    fn record_announce(&self, now: DurationSinceUnixEpoch) {
+        self.write().record_announce(now);
+    }
+
+    fn record_scrape(&self, now: DurationSinceUnixEpoch) {
+        self.write().record_scrape(now);
+    }
 }
 
 impl From<EntrySingle> for EntryRwLockParkingLot {
@@ -1,13 +1,15 @@
 use std::net::SocketAddr;
 use std::sync::Arc;
 
-use aquatic_udp_protocol::AnnounceEvent;
+use aquatic_udp_protocol::{AnnounceEvent, PeerId};
 use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_primitives::peer::{self};
 use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
+use torrust_tracker_primitives::torrent_stats::TorrentStats;
 use torrust_tracker_primitives::DurationSinceUnixEpoch;
 
 use super::Entry;
+use crate::peer_selection::PeerSelector;
 use crate::EntrySingle;
 
 impl Entry for EntrySingle {
@@ -46,10 +48,27 @@ impl Entry for EntrySingle {
         self.swarm.get_all(limit)
     }
 
+    fn get_peer(&self, peer_id: &PeerId) -> Option<Arc<peer::Peer>> {
+        self.swarm.get(peer_id).cloned()
+    }
+
     fn get_peers_for_client(&self, client: &SocketAddr, limit: Option<usize>) -> Vec<Arc<peer::Peer>> {
         self.swarm.get_peers_excluding_addr(client, limit)
     }
 
+    fn select_peers_for_client(
+        &mut self,
+        client: &peer::Peer,
+        limit: usize,
+        selector: &dyn PeerSelector,
+    ) -> Vec<Arc<peer::Peer>> {
+        let peers = selector.select(&self.swarm, client, limit);
+
+        selector.record_returned(&mut self.swarm, &peers);
+
+        peers
+    }
+
     fn upsert_peer(&mut self, peer: &peer::Peer) -> bool {
         let mut downloaded_stats_updated: bool = false;
 
@@ -76,4 +95,23 @@ impl Entry for EntrySingle {
     fn remove_inactive_peers(&mut self, current_cutoff: DurationSinceUnixEpoch) {
         self.swarm.remove_inactive_peers(current_cutoff);
     }
+
+    fn get_torrent_stats(&self) -> TorrentStats {
+        self.stats
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn record_announce(&mut self, now: DurationSinceUnixEpoch) {
+        let (seeders, leechers) = self.swarm.seeders_and_leechers();
+
+        self.stats.announces += 1;
+        self.stats.last_activity = Some(now);
+        self.stats.peak_seeders = self.stats.peak_seeders.max(seeders as u32);
+        self.stats.peak_leechers = self.stats.peak_leechers.max(leechers as u32);
+    }
+
+    fn record_scrape(&mut self, now: DurationSinceUnixEpoch) {
+        self.stats.scrapes += 1;
+        self.stats.last_activity = Some(now);
+    }
 }
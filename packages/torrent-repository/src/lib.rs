@@ -7,6 +7,7 @@ use repository::skip_map_mutex_std::CrossbeamSkipList;
 use torrust_tracker_clock::clock;
 
 pub mod entry;
+pub mod peer_selection;
 pub mod repository;
 
 // Repo Entries
@@ -0,0 +1,426 @@
+//! Strategies to choose the peers returned to a client in the `announce`
+//! response.
+//!
+//! Every strategy implements the [`PeerSelector`] trait. The entries of all
+//! the repositories use them to choose the peers in the swarm, so they don't
+//! depend on the repository implementation.
+//!
+//! The strategy is chosen with the [`PeerSelection`] option in the tracker
+//! configuration. Use [`selector`] to get the strategy for the option,
+//! [`OmitSeeders`] to leave the seeders out of the responses to seeders and
+//! [`CryptoSupported`] to only return peers that accept encrypted connections.
+use std::cmp::Reverse;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use rand::seq::SliceRandom as _;
+use torrust_tracker_configuration::PeerSelection;
+use torrust_tracker_primitives::peer;
+
+use crate::entry::peer_list::PeerList;
+
+/// A strategy to choose the peers returned to a client.
+pub trait PeerSelector: Debug + Sync {
+    /// It returns up to `limit` peers from the `swarm` for the `client` peer,
+    /// only among the peers for which `include` returns `true`.
+    ///
+    /// The client peer is never included. It's identified by its socket
+    /// address.
+    fn select_where(
+        &self,
+        swarm: &PeerList,
+        client: &peer::Peer,
+        limit: usize,
+        include: &dyn Fn(&peer::Peer) -> bool,
+    ) -> Vec<Arc<peer::Peer>>;
+
+    /// It returns up to `limit` peers from the `swarm` for the `client` peer.
+    fn select(&self, swarm: &PeerList, client: &peer::Peer, limit: usize) -> Vec<Arc<peer::Peer>> {
+        self.select_where(swarm, client, limit, &|_| true)
+    }
+
+    /// It returns `true` if the strategy keeps track of the peers it returns,
+    /// so the entries need exclusive access to the swarm to call
+    /// [`record_returned`](PeerSelector::record_returned).
+    fn needs_mut(&self) -> bool {
+        false
+    }
+
+    /// It records in the `swarm` that the selected `peers` have been returned
+    /// to a client. It does nothing unless the strategy
+    /// [`needs_mut`](PeerSelector::needs_mut).
+    fn record_returned(&self, _swarm: &mut PeerList, _peers: &[Arc<peer::Peer>]) {}
+}
+
+/// It returns the strategy for the [`PeerSelection`] configuration option.
+#[must_use]
+pub fn selector(peer_selection: PeerSelection) -> &'static dyn PeerSelector {
+    match peer_selection {
+        PeerSelection::InOrder => &InOrder,
+        PeerSelection::Random => &Random,
+        PeerSelection::PreferSeeders => &PreferSeeders,
+        PeerSelection::PreferLeechers => &PreferLeechers,
+        PeerSelection::LeastRecentlyReturned => &LeastRecentlyReturned,
+        PeerSelection::SameIpFamilyFirst => &SameIpFamilyFirst,
+    }
+}
+
+/// The first peers in the swarm.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InOrder;
+
+impl PeerSelector for InOrder {
+    fn select_where(
+        &self,
+        swarm: &PeerList,
+        client: &peer::Peer,
+        limit: usize,
+        include: &dyn Fn(&peer::Peer) -> bool,
+    ) -> Vec<Arc<peer::Peer>> {
+        candidates(swarm, client, include).take(limit).collect()
+    }
+}
+
+/// A random sample of the peers in the swarm.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Random;
+
+impl PeerSelector for Random {
+    fn select_where(
+        &self,
+        swarm: &PeerList,
+        client: &peer::Peer,
+        limit: usize,
+        include: &dyn Fn(&peer::Peer) -> bool,
+    ) -> Vec<Arc<peer::Peer>> {
+        candidates(swarm, client, include)
+            .collect::<Vec<_>>()
+            .choose_multiple(&mut rand::thread_rng(), limit)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Seeders first for leechers. Seeders get the peers in order.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PreferSeeders;
+
+impl PeerSelector for PreferSeeders {
+    fn select_where(
+        &self,
+        swarm: &PeerList,
+        client: &peer::Peer,
+        limit: usize,
+        include: &dyn Fn(&peer::Peer) -> bool,
+    ) -> Vec<Arc<peer::Peer>> {
+        if client.is_seeder() {
+            return InOrder.select_where(swarm, client, limit, include);
+        }
+
+        first_by_key(swarm, client, limit, include, |peer| !peer.is_seeder())
+    }
+}
+
+/// Leechers first for seeders. Leechers get the peers in order.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PreferLeechers;
+
+impl PeerSelector for PreferLeechers {
+    fn select_where(
+        &self,
+        swarm: &PeerList,
+        client: &peer::Peer,
+        limit: usize,
+        include: &dyn Fn(&peer::Peer) -> bool,
+    ) -> Vec<Arc<peer::Peer>> {
+        if !client.is_seeder() {
+            return InOrder.select_where(swarm, client, limit, include);
+        }
+
+        first_by_key(swarm, client, limit, include, peer::Peer::is_seeder)
+    }
+}
+
+/// The peers that have not been returned for the longest time first, so that
+/// all the peers in the swarm are handed out in turns.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LeastRecentlyReturned;
+
+impl PeerSelector for LeastRecentlyReturned {
+    fn select_where(
+        &self,
+        swarm: &PeerList,
+        client: &peer::Peer,
+        limit: usize,
+        include: &dyn Fn(&peer::Peer) -> bool,
+    ) -> Vec<Arc<peer::Peer>> {
+        first_by_key(swarm, client, limit, include, |peer| swarm.last_returned(&peer.peer_id))
+    }
+
+    fn needs_mut(&self) -> bool {
+        true
+    }
+
+    fn record_returned(&self, swarm: &mut PeerList, peers: &[Arc<peer::Peer>]) {
+        swarm.mark_as_returned(peers);
+    }
+}
+
+/// Peers that have an address in the IP family of the client first. It
+/// includes the dual-stack peers that have announced an address in that
+/// family.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SameIpFamilyFirst;
+
+impl PeerSelector for SameIpFamilyFirst {
+    fn select_where(
+        &self,
+        swarm: &PeerList,
+        client: &peer::Peer,
+        limit: usize,
+        include: &dyn Fn(&peer::Peer) -> bool,
+    ) -> Vec<Arc<peer::Peer>> {
+        let client_ip = client.peer_addr.ip();
+
+        first_by_key(swarm, client, limit, include, |peer| {
+            Reverse(peer.addr_in_family_of(&client_ip).is_some())
+        })
+    }
+}
+
+/// It only returns leechers, chosen with the wrapped strategy.
+///
+/// It's used for the responses to seeders when the tracker is configured to
+/// omit the seeders, since two seeders have nothing to exchange.
+#[derive(Debug, Clone, Copy)]
+pub struct OmitSeeders<'a>(pub &'a dyn PeerSelector);
+
+impl PeerSelector for OmitSeeders<'_> {
+    fn select_where(
+        &self,
+        swarm: &PeerList,
+        client: &peer::Peer,
+        limit: usize,
+        include: &dyn Fn(&peer::Peer) -> bool,
+    ) -> Vec<Arc<peer::Peer>> {
+        self.0
+            .select_where(swarm, client, limit, &|peer| !peer.is_seeder() && include(peer))
+    }
+
+    fn needs_mut(&self) -> bool {
+        self.0.needs_mut()
+    }
+
+    fn record_returned(&self, swarm: &mut PeerList, peers: &[Arc<peer::Peer>]) {
+        self.0.record_returned(swarm, peers);
+    }
+}
+
+/// It only returns peers that support encrypted connections, chosen with the
+/// wrapped strategy.
+///
+/// It's used for the responses to peers that require encryption, since they
+/// can't connect to the others.
+#[derive(Debug, Clone, Copy)]
+pub struct CryptoSupported<'a>(pub &'a dyn PeerSelector);
+
+impl PeerSelector for CryptoSupported<'_> {
+    fn select_where(
+        &self,
+        swarm: &PeerList,
+        client: &peer::Peer,
+        limit: usize,
+        include: &dyn Fn(&peer::Peer) -> bool,
+    ) -> Vec<Arc<peer::Peer>> {
+        self.0
+            .select_where(swarm, client, limit, &|peer| peer.crypto.is_supported() && include(peer))
+    }
+
+    fn needs_mut(&self) -> bool {
+        self.0.needs_mut()
+    }
+
+    fn record_returned(&self, swarm: &mut PeerList, peers: &[Arc<peer::Peer>]) {
+        self.0.record_returned(swarm, peers);
+    }
+}
+
+/// The peers in the swarm that can be returned to the client, in the swarm
+/// order.
+fn candidates<'a>(
+    swarm: &'a PeerList,
+    client: &'a peer::Peer,
+    include: &'a dyn Fn(&peer::Peer) -> bool,
+) -> impl Iterator<Item = Arc<peer::Peer>> + 'a {
+    swarm
+        .iter()
+        .filter(move |peer| peer.peer_addr != client.peer_addr && include(peer))
+        .cloned()
+}
+
+/// It returns the first `limit` peers ordered by the `key`. Peers with the
+/// same key keep the swarm order.
+fn first_by_key<K, F>(
+    swarm: &PeerList,
+    client: &peer::Peer,
+    limit: usize,
+    include: &dyn Fn(&peer::Peer) -> bool,
+    mut key: F,
+) -> Vec<Arc<peer::Peer>>
+where
+    K: Ord,
+    F: FnMut(&peer::Peer) -> K,
+{
+    let mut peers: Vec<_> = candidates(swarm, client, include).collect();
+
+    peers.sort_by_key(|peer| key(peer));
+    peers.truncate(limit);
+
+    peers
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod the_peer_selection {
+        use std::net::SocketAddr;
+        use std::sync::Arc;
+
+        use aquatic_udp_protocol::PeerId;
+        use torrust_tracker_primitives::peer::fixture::PeerBuilder;
+        use torrust_tracker_primitives::peer::{self};
+
+        use crate::entry::peer_list::PeerList;
+        use crate::peer_selection::{
+            CryptoSupported, InOrder, LeastRecentlyReturned, OmitSeeders, PeerSelector, PreferLeechers, PreferSeeders, Random,
+            SameIpFamilyFirst,
+        };
+
+        fn seeder(id: u8) -> peer::Peer {
+            PeerBuilder::seeder()
+                .with_peer_id(&PeerId([id; 20]))
+                .with_peer_addr(&SocketAddr::new([126, 0, 0, id].into(), 8080))
+                .build()
+        }
+
+        fn leecher(id: u8) -> peer::Peer {
+            PeerBuilder::leecher()
+                .with_peer_id(&PeerId([id; 20]))
+                .with_peer_addr(&SocketAddr::new([126, 0, 0, id].into(), 8080))
+                .build()
+        }
+
+        fn swarm_with(peers: &[peer::Peer]) -> PeerList {
+            let mut swarm = PeerList::default();
+            for peer in peers {
+                swarm.upsert(Arc::new(*peer));
+            }
+            swarm
+        }
+
+        fn ids(peers: &[Arc<peer::Peer>]) -> Vec<u8> {
+            peers.iter().map(|peer| peer.peer_id.0[0]).collect()
+        }
+
+        #[test]
+        fn it_should_return_the_first_peers_in_the_swarm_excluding_the_client() {
+            let swarm = swarm_with(&[leecher(1), leecher(2), leecher(3)]);
+
+            assert_eq!(ids(&InOrder.select(&swarm, &leecher(1), 1)), vec![2]);
+        }
+
+        #[test]
+        fn it_should_return_a_random_sample_of_the_peers_excluding_the_client() {
+            let swarm = swarm_with(&[leecher(1), leecher(2), leecher(3), leecher(4)]);
+
+            let peers = Random.select(&swarm, &leecher(1), 2);
+
+            assert_eq!(peers.len(), 2);
+            assert!(!ids(&peers).contains(&1));
+        }
+
+        #[test]
+        fn it_should_return_the_seeders_first_to_a_leecher_when_it_prefers_seeders() {
+            let swarm = swarm_with(&[leecher(1), leecher(2), seeder(3)]);
+
+            assert_eq!(ids(&PreferSeeders.select(&swarm, &leecher(9), 2)), vec![3, 1]);
+            assert_eq!(ids(&PreferSeeders.select(&swarm, &seeder(9), 2)), vec![1, 2]);
+        }
+
+        #[test]
+        fn it_should_return_the_leechers_first_to_a_seeder_when_it_prefers_leechers() {
+            let swarm = swarm_with(&[seeder(1), seeder(2), leecher(3)]);
+
+            assert_eq!(ids(&PreferLeechers.select(&swarm, &seeder(9), 2)), vec![3, 1]);
+            assert_eq!(ids(&PreferLeechers.select(&swarm, &leecher(9), 2)), vec![1, 2]);
+        }
+
+        #[test]
+        fn it_should_return_the_peers_that_have_not_been_returned_for_the_longest_time_first() {
+            let mut swarm = swarm_with(&[leecher(1), leecher(2), leecher(3)]);
+
+            for expected in [vec![1, 2], vec![3, 1], vec![2, 1]] {
+                let peers = LeastRecentlyReturned.select(&swarm, &leecher(9), 2);
+                LeastRecentlyReturned.record_returned(&mut swarm, &peers);
+
+                assert_eq!(ids(&peers), expected);
+            }
+        }
+
+        #[test]
+        fn it_should_only_need_a_mutable_swarm_for_the_strategies_that_record_the_returned_peers() {
+            assert!(!InOrder.needs_mut());
+            assert!(!Random.needs_mut());
+            assert!(LeastRecentlyReturned.needs_mut());
+            assert!(OmitSeeders(&LeastRecentlyReturned).needs_mut());
+            assert!(CryptoSupported(&LeastRecentlyReturned).needs_mut());
+            assert!(!CryptoSupported(&InOrder).needs_mut());
+        }
+
+        #[test]
+        fn it_should_fill_the_response_with_leechers_when_it_omits_the_seeders() {
+            let swarm = swarm_with(&[seeder(1), leecher(2), seeder(3), leecher(4)]);
+
+            assert_eq!(ids(&OmitSeeders(&InOrder).select(&swarm, &seeder(9), 2)), vec![2, 4]);
+        }
+
+        #[test]
+        fn it_should_only_return_the_peers_that_support_encryption_when_it_requires_it() {
+            let swarm = swarm_with(&[
+                leecher(1),
+                PeerBuilder::leecher()
+                    .with_peer_id(&PeerId([2; 20]))
+                    .with_peer_addr(&SocketAddr::new([126, 0, 0, 2].into(), 8080))
+                    .with_crypto(peer::Crypto::Supported)
+                    .build(),
+                PeerBuilder::leecher()
+                    .with_peer_id(&PeerId([3; 20]))
+                    .with_peer_addr(&SocketAddr::new([126, 0, 0, 3].into(), 8080))
+                    .with_crypto(peer::Crypto::Required)
+                    .build(),
+            ]);
+
+            assert_eq!(ids(&CryptoSupported(&InOrder).select(&swarm, &leecher(9), 3)), vec![2, 3]);
+        }
+
+        #[test]
+        fn it_should_return_the_peers_in_the_ip_family_of_the_client_first() {
+            let ipv6_leecher = PeerBuilder::leecher()
+                .with_peer_id(&PeerId([2; 20]))
+                .with_peer_addr(&"[2001:db8::2]:8080".parse().unwrap())
+                .build();
+            let dual_stack_leecher = PeerBuilder::leecher()
+                .with_peer_id(&PeerId([3; 20]))
+                .with_peer_addr(&"126.0.0.3:8080".parse().unwrap())
+                .with_alt_peer_addr(&"[2001:db8::3]:8080".parse().unwrap())
+                .build();
+            let swarm = swarm_with(&[leecher(1), ipv6_leecher, dual_stack_leecher]);
+
+            let ipv6_client = PeerBuilder::leecher()
+                .with_peer_addr(&"[2001:db8::9]:8080".parse().unwrap())
+                .build();
+
+            assert_eq!(ids(&SameIpFamilyFirst.select(&swarm, &ipv6_client, 3)), vec![2, 3, 1]);
+        }
+    }
+}
@@ -3,6 +3,7 @@ use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_primitives::pagination::Pagination;
 use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
 use torrust_tracker_primitives::torrent_metrics::TorrentsMetrics;
+use torrust_tracker_primitives::torrent_stats::TorrentStats;
 use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch, PersistentTorrents};
 
 use super::Repository;
@@ -103,6 +104,7 @@ where
             let entry = EntrySingle {
                 swarm: PeerList::default(),
                 downloaded: *downloaded,
+                stats: TorrentStats::default(),
             };
 
             torrents.insert(*info_hash, entry);
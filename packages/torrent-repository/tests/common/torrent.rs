@@ -1,10 +1,13 @@
 use std::net::SocketAddr;
 use std::sync::Arc;
 
+use aquatic_udp_protocol::PeerId;
 use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
+use torrust_tracker_primitives::torrent_stats::TorrentStats;
 use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
 use torrust_tracker_torrent_repository::entry::{Entry as _, EntryAsync as _, EntrySync as _};
+use torrust_tracker_torrent_repository::peer_selection::PeerSelector;
 use torrust_tracker_torrent_repository::{
     EntryMutexParkingLot, EntryMutexStd, EntryMutexTokio, EntryRwLockParkingLot, EntrySingle,
 };
@@ -69,6 +72,16 @@ impl Torrent {
         }
     }
 
+    pub(crate) async fn get_peer(&self, peer_id: &PeerId) -> Option<Arc<peer::Peer>> {
+        match self {
+            Torrent::Single(entry) => entry.get_peer(peer_id),
+            Torrent::MutexStd(entry) => entry.get_peer(peer_id),
+            Torrent::MutexTokio(entry) => entry.clone().get_peer(peer_id).await,
+            Torrent::MutexParkingLot(entry) => entry.get_peer(peer_id),
+            Torrent::RwLockParkingLot(entry) => entry.get_peer(peer_id),
+        }
+    }
+
     pub(crate) async fn get_peers_for_client(&self, client: &SocketAddr, limit: Option<usize>) -> Vec<Arc<peer::Peer>> {
         match self {
             Torrent::Single(entry) => entry.get_peers_for_client(client, limit),
@@ -79,6 +92,21 @@ impl Torrent {
         }
     }
 
+    pub(crate) async fn select_peers_for_client(
+        &mut self,
+        client: &peer::Peer,
+        limit: usize,
+        selector: &dyn PeerSelector,
+    ) -> Vec<Arc<peer::Peer>> {
+        match self {
+            Torrent::Single(entry) => entry.select_peers_for_client(client, limit, selector),
+            Torrent::MutexStd(entry) => entry.select_peers_for_client(client, limit, selector),
+            Torrent::MutexTokio(entry) => entry.clone().select_peers_for_client(client, limit, selector).await,
+            Torrent::MutexParkingLot(entry) => entry.select_peers_for_client(client, limit, selector),
+            Torrent::RwLockParkingLot(entry) => entry.select_peers_for_client(client, limit, selector),
+        }
+    }
+
     pub(crate) async fn upsert_peer(&mut self, peer: &peer::Peer) -> bool {
         match self {
             Torrent::Single(entry) => entry.upsert_peer(peer),
@@ -98,4 +126,34 @@ impl Torrent {
             Torrent::RwLockParkingLot(entry) => entry.remove_inactive_peers(current_cutoff),
         }
     }
+
+    pub(crate) async fn get_torrent_stats(&self) -> TorrentStats {
+        match self {
+            Torrent::Single(entry) => entry.get_torrent_stats(),
+            Torrent::MutexStd(entry) => entry.get_torrent_stats(),
+            Torrent::MutexTokio(entry) => entry.get_torrent_stats().await,
+            Torrent::MutexParkingLot(entry) => entry.get_torrent_stats(),
+            Torrent::RwLockParkingLot(entry) => entry.get_torrent_stats(),
+        }
+    }
+
+    pub(crate) async fn record_announce(&mut self, now: DurationSinceUnixEpoch) {
+        match self {
+            Torrent::Single(entry) => entry.record_announce(now),
+            Torrent::MutexStd(entry) => entry.record_announce(now),
+            Torrent::MutexTokio(entry) => entry.clone().record_announce(now).await,
+            Torrent::MutexParkingLot(entry) => entry.record_announce(now),
+            Torrent::RwLockParkingLot(entry) => entry.record_announce(now),
+        }
+    }
+
+    pub(crate) async fn record_scrape(&mut self, now: DurationSinceUnixEpoch) {
+        match self {
+            Torrent::Single(entry) => entry.record_scrape(now),
+            Torrent::MutexStd(entry) => entry.record_scrape(now),
+            Torrent::MutexTokio(entry) => entry.clone().record_scrape(now).await,
+            Torrent::MutexParkingLot(entry) => entry.record_scrape(now),
+            Torrent::RwLockParkingLot(entry) => entry.record_scrape(now),
+        }
+    }
 }
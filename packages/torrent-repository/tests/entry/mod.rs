@@ -1,16 +1,18 @@
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::ops::Sub;
+use std::sync::Arc;
 use std::time::Duration;
 
-use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes};
+use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes, PeerId};
 use rstest::{fixture, rstest};
 use torrust_tracker_clock::clock::stopped::Stopped as _;
 use torrust_tracker_clock::clock::{self, Time as _};
-use torrust_tracker_configuration::{TrackerPolicy, TORRENT_PEERS_LIMIT};
-use torrust_tracker_primitives::peer;
+use torrust_tracker_configuration::{PeerSelection, TrackerPolicy, TORRENT_PEERS_LIMIT};
 use torrust_tracker_primitives::peer::Peer;
+use torrust_tracker_primitives::torrent_stats::TorrentStats;
+use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
 use torrust_tracker_torrent_repository::{
-    EntryMutexParkingLot, EntryMutexStd, EntryMutexTokio, EntryRwLockParkingLot, EntrySingle,
+    peer_selection, EntryMutexParkingLot, EntryMutexStd, EntryMutexTokio, EntryRwLockParkingLot, EntrySingle,
 };
 
 use crate::common::torrent::Torrent;
@@ -179,6 +181,26 @@ async fn it_should_get_peers_for_torrent_entry(
     }
 }
 
+#[rstest]
+#[case::empty(&Makes::Empty)]
+#[case::started(&Makes::Started)]
+#[case::completed(&Makes::Completed)]
+#[case::downloaded(&Makes::Downloaded)]
+#[case::three(&Makes::Three)]
+#[tokio::test]
+async fn it_should_get_a_peer_by_its_id(
+    #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
+    #[case] makes: &Makes,
+) {
+    let peers = make(&mut torrent, makes).await;
+
+    for peer in peers {
+        assert_eq!(torrent.get_peer(&peer.peer_id).await, Some(Arc::new(peer)));
+    }
+
+    assert_eq!(torrent.get_peer(&PeerId(*b"-qB00000000000000099")).await, None);
+}
+
 #[rstest]
 #[case::empty(&Makes::Empty)]
 #[case::started(&Makes::Started)]
@@ -409,6 +431,35 @@ async fn it_should_limit_the_number_of_peers_returned(
     assert_eq!(peers.len(), 74);
 }
 
+#[rstest]
+#[case::empty(&Makes::Empty)]
+#[case::started(&Makes::Started)]
+#[case::completed(&Makes::Completed)]
+#[case::downloaded(&Makes::Downloaded)]
+#[case::three(&Makes::Three)]
+#[tokio::test]
+async fn it_should_select_the_peers_for_a_client_with_the_least_recently_returned_peers_first(
+    #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
+    #[case] makes: &Makes,
+) {
+    let peers = make(&mut torrent, makes).await;
+
+    let mut client = a_started_peer(-1);
+    client.peer_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080);
+    let selector = peer_selection::selector(PeerSelection::LeastRecentlyReturned);
+
+    let mut returned = vec![];
+    for _ in 0..peers.len() {
+        returned.extend(torrent.select_peers_for_client(&client, 1, selector).await);
+    }
+
+    // Every peer is returned once before any peer is returned again.
+    assert_eq!(returned.len(), peers.len());
+    for peer in peers {
+        assert!(returned.contains(&peer.into()));
+    }
+}
+
 #[rstest]
 #[case::empty(&Makes::Empty)]
 #[case::started(&Makes::Started)]
@@ -441,3 +492,37 @@ async fn it_should_remove_inactive_peers_beyond_cutoff(
 
     assert_eq!(torrent.get_peers_len().await, peers.len());
 }
+
+#[rstest]
+#[case::empty(&Makes::Empty)]
+#[case::started(&Makes::Started)]
+#[case::completed(&Makes::Completed)]
+#[case::downloaded(&Makes::Downloaded)]
+#[case::three(&Makes::Three)]
+#[tokio::test]
+async fn it_should_record_the_torrent_activity(
+    #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
+    #[case] makes: &Makes,
+) {
+    make(&mut torrent, makes).await;
+
+    let swarm_metadata = torrent.get_stats().await;
+
+    torrent.record_announce(DurationSinceUnixEpoch::from_secs(1)).await;
+    torrent.record_scrape(DurationSinceUnixEpoch::from_secs(2)).await;
+
+    // The peaks are kept after the peers leave the swarm.
+    torrent.remove_inactive_peers(DurationSinceUnixEpoch::MAX).await;
+    torrent.record_announce(DurationSinceUnixEpoch::from_secs(3)).await;
+
+    assert_eq!(
+        torrent.get_torrent_stats().await,
+        TorrentStats {
+            announces: 2,
+            scrapes: 1,
+            last_activity: Some(DurationSinceUnixEpoch::from_secs(3)),
+            peak_seeders: swarm_metadata.complete,
+            peak_leechers: swarm_metadata.incomplete,
+        }
+    );
+}
@@ -2,9 +2,7 @@
 //!
 //! The tracker application has a global configuration for multiple jobs.
 //! It's basically a container for other services.
-//! It also check constraint and dependencies between services. For example:
-//! It's not safe to run a UDP tracker on top of a core public tracker, as UDP trackers
-//! do not allow private access to the tracker data.
+//! It also check constraint and dependencies between services.
 //!
 //! The application is responsible for:
 //!
@@ -21,15 +19,25 @@
 //! - UDP trackers: the user can enable multiple UDP tracker on several ports.
 //! - HTTP trackers: the user can enable multiple HTTP tracker on several ports.
 //! - Tracker REST API: the tracker API can be enabled/disabled.
+//!
+//! The configuration can be reloaded while the application is running (on
+//! `SIGHUP` or with the tracker API). Refer to the [`reload`](crate::bootstrap::reload)
+//! module for more info.
 use std::sync::Arc;
 
 use tokio::task::JoinHandle;
 use torrust_tracker_configuration::Configuration;
 use tracing::instrument;
 
-use crate::bootstrap::jobs::{health_check_api, http_tracker, torrent_cleanup, tracker_apis, udp_tracker};
+#[cfg(unix)]
+use crate::bootstrap::jobs::config_reload;
+use crate::bootstrap::jobs::{
+    health_check_api, http_tracker, metrics_history, statistics_persistence, torrent_cleanup, torrents_snapshot, tracker_apis,
+    udp_tracker,
+};
+use crate::bootstrap::reload::Reloader;
 use crate::servers::registar::Registar;
-use crate::{core, servers};
+use crate::{bootstrap, core, servers};
 
 /// # Panics
 ///
@@ -37,6 +45,8 @@ use crate::{core, servers};
 ///
 /// - Can't retrieve tracker keys from database.
 /// - Can't load whitelist from database.
+/// - Can't restore the torrents snapshot.
+/// - Can't restore the metrics history.
 #[instrument(skip(config, tracker))]
 pub async fn start(config: &Configuration, tracker: Arc<core::Tracker>) -> Vec<JoinHandle<()>> {
     if config.http_api.is_none()
@@ -50,12 +60,22 @@ pub async fn start(config: &Configuration, tracker: Arc<core::Tracker>) -> Vec<J
 
     let registar = Registar::default();
 
-    // Load peer keys
+    let reloader = Arc::new(Reloader::new(
+        config,
+        tracker.clone(),
+        Box::new(bootstrap::config::load_configuration),
+    ));
+
+    // Load peer keys and their traffic
     if tracker.is_private() {
         tracker
             .load_keys_from_database()
             .await
             .expect("Could not retrieve keys from database.");
+
+        tracker
+            .load_keys_traffic_from_database()
+            .expect("Could not retrieve the keys traffic from database.");
     }
 
     // Load whitelisted torrents
@@ -66,17 +86,26 @@ pub async fn start(config: &Configuration, tracker: Arc<core::Tracker>) -> Vec<J
             .expect("Could not load whitelist from database.");
     }
 
+    // Load the IP access list
+    tracker
+        .load_ip_access_list_from_database()
+        .await
+        .expect("Could not load the IP access list from database.");
+
+    // Restore torrents (and peers) from the last snapshot
+    tracker
+        .load_torrents_snapshot()
+        .expect("Could not restore torrents from the snapshot.");
+
+    // Restore the metrics history from the last saved history file
+    tracker
+        .load_metrics_history()
+        .expect("Could not restore the metrics history.");
+
     // Start the UDP blocks
     if let Some(udp_trackers) = &config.udp_trackers {
         for udp_tracker_config in udp_trackers {
-            if tracker.is_private() {
-                tracing::warn!(
-                    "Could not start UDP tracker on: {} while in private mode. UDP is not safe for private trackers!",
-                    udp_tracker_config.bind_address
-                );
-            } else {
-                jobs.push(udp_tracker::start_job(udp_tracker_config, tracker.clone(), registar.give_form()).await);
-            }
+            jobs.push(udp_tracker::start_job(udp_tracker_config, tracker.clone(), registar.give_form()).await);
         }
     } else {
         tracing::info!("No UDP blocks in configuration");
@@ -105,6 +134,7 @@ pub async fn start(config: &Configuration, tracker: Arc<core::Tracker>) -> Vec<J
         if let Some(job) = tracker_apis::start_job(
             http_api_config,
             tracker.clone(),
+            reloader.clone(),
             registar.give_form(),
             servers::apis::Version::V1,
         )
@@ -121,6 +151,25 @@ pub async fn start(config: &Configuration, tracker: Arc<core::Tracker>) -> Vec<J
         jobs.push(torrent_cleanup::start_job(&config.core, &tracker));
     }
 
+    // Start runner to save snapshots of the torrents, every interval and on shutdown
+    if let Some(snapshot_config) = &config.core.snapshot {
+        jobs.push(torrents_snapshot::start_job(snapshot_config, &tracker));
+    }
+
+    // Start runner to save the usage statistics, every interval and on shutdown
+    if let Some(statistics_persistence_config) = &config.core.statistics_persistence {
+        jobs.push(statistics_persistence::start_job(statistics_persistence_config, &tracker));
+    }
+
+    // Start runner to take samples of the tracker metrics, every interval
+    if let Some(metrics_history_config) = &config.core.metrics_history {
+        jobs.push(metrics_history::start_job(metrics_history_config, &tracker));
+    }
+
+    // Start runner to reload the configuration on SIGHUP
+    #[cfg(unix)]
+    jobs.push(config_reload::start_job(&reloader));
+
     // Start Health Check API
     jobs.push(health_check_api::start_job(&config.health_check_api, registar.entries()).await);
 
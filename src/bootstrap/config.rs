@@ -2,7 +2,7 @@
 //!
 //! All environment variables are prefixed with `TORRUST_TRACKER_`.
 
-use torrust_tracker_configuration::{Configuration, Info};
+use torrust_tracker_configuration::{Configuration, Error, Info};
 
 pub const DEFAULT_PATH_CONFIG: &str = "./share/default/config/tracker.development.sqlite3.toml";
 
@@ -23,8 +23,22 @@ pub const DEFAULT_PATH_CONFIG: &str = "./share/default/config/tracker.developmen
 /// `./tracker.toml` file or the env var `TORRUST_TRACKER_CONFIG_TOML`.
 #[must_use]
 pub fn initialize_configuration() -> Configuration {
-    let info = Info::new(DEFAULT_PATH_CONFIG.to_string()).expect("info to load configuration is not valid");
-    Configuration::load(&info).expect("error loading configuration from sources")
+    load_configuration().expect("error loading configuration from sources")
+}
+
+/// It loads the application configuration from the environment, the same
+/// way [`initialize_configuration`] does, but it returns an error instead of
+/// panicking.
+///
+/// It's used to reload the configuration while the tracker is running.
+///
+/// # Errors
+///
+/// Will return an error if it can't load the configuration from either the
+/// config file or the env var `TORRUST_TRACKER_CONFIG_TOML`.
+pub fn load_configuration() -> Result<Configuration, Error> {
+    let info = Info::new(DEFAULT_PATH_CONFIG.to_string())?;
+    Configuration::load(&info)
 }
 
 #[cfg(test)]
@@ -0,0 +1,55 @@
+//! Job that reloads the configuration when the process receives the `SIGHUP`
+//! signal.
+//!
+//! For example:
+//!
+//! ```text
+//! kill -HUP $(pidof torrust-tracker)
+//! ```
+//!
+//! The result of the reload is only logged. Refer to the
+//! [`reload`](crate::bootstrap::reload) module for more info about which
+//! settings can be changed without restarting the tracker.
+use std::sync::Arc;
+
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::task::JoinHandle;
+use tracing::instrument;
+
+use crate::bootstrap::reload::Reloader;
+
+/// It starts a job that reloads the configuration on `SIGHUP`.
+///
+/// # Panics
+///
+/// Will panic if it can't listen to the `SIGHUP` signal.
+#[must_use]
+#[instrument(skip(reloader))]
+pub fn start_job(reloader: &Arc<Reloader>) -> JoinHandle<()> {
+    let reloader = reloader.clone();
+
+    let mut hangup = signal(SignalKind::hangup()).expect("it should listen to the SIGHUP signal");
+
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {
+                    tracing::info!("Stopping configuration reload job..");
+                    break;
+                }
+                _ = hangup.recv() => {
+                    tracing::info!("Reloading configuration..");
+                    match reloader.reload() {
+                        Ok(report) => {
+                            tracing::info!("Reloaded configuration. Applied settings: {:?}", report.applied);
+                            if !report.requires_restart.is_empty() {
+                                tracing::warn!("Changed settings that require a restart: {:?}", report.requires_restart);
+                            }
+                        }
+                        Err(err) => tracing::error!("Unable to reload the configuration: {err}"),
+                    }
+                }
+            }
+        }
+    })
+}
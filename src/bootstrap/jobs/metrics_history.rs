@@ -0,0 +1,48 @@
+//! Job that takes samples of the tracker metrics on intervals for the metrics
+//! history.
+//!
+//! The job is only started when the core tracker configuration section
+//! `[core.metrics_history]` is present. When the history file `path` is set,
+//! the history is also saved into the file on graceful shutdown.
+//!
+//! Refer to [`metrics_history`](crate::core::metrics_history) module for more info.
+use std::sync::Arc;
+
+use tokio::task::JoinHandle;
+use torrust_tracker_configuration::MetricsHistory;
+use tracing::instrument;
+
+use crate::core;
+
+/// It starts a job for sampling the tracker metrics.
+#[must_use]
+#[instrument(skip(config, tracker))]
+pub fn start_job(config: &MetricsHistory, tracker: &Arc<core::Tracker>) -> JoinHandle<()> {
+    let weak_tracker = std::sync::Arc::downgrade(tracker);
+    let interval = config.interval;
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(interval));
+
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {
+                    tracing::info!("Stopping metrics history job..");
+                    if let Some(tracker) = weak_tracker.upgrade() {
+                        if let Err(err) = tracker.save_metrics_history() {
+                            tracing::error!("Unable to save the metrics history: {err}");
+                        }
+                    }
+                    break;
+                }
+                _ = interval.tick() => {
+                    if let Some(tracker) = weak_tracker.upgrade() {
+                        tracker.sample_metrics().await;
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
@@ -6,9 +6,15 @@
 //! 2. Launch all the application services as concurrent jobs.
 //!
 //! This modules contains all the functions needed to start those jobs.
+#[cfg(unix)]
+pub mod config_reload;
 pub mod health_check_api;
 pub mod http_tracker;
+pub mod metrics_history;
+pub mod periodic_save;
+pub mod statistics_persistence;
 pub mod torrent_cleanup;
+pub mod torrents_snapshot;
 pub mod tracker_apis;
 pub mod udp_tracker;
 
@@ -0,0 +1,80 @@
+//! Shared launcher for the jobs that save part of the tracker state on
+//! intervals and on graceful shutdown.
+//!
+//! If the `interval` is `0` the state is only saved on graceful shutdown. The
+//! job stops when the tracker is dropped.
+//!
+//! It's used by the [`torrents_snapshot`](crate::bootstrap::jobs::torrents_snapshot)
+//! and the [`statistics_persistence`](crate::bootstrap::jobs::statistics_persistence)
+//! jobs.
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::Arc;
+
+use chrono::Utc;
+use tokio::task::JoinHandle;
+
+use crate::core;
+
+/// It starts a job that calls `save` every `interval` seconds and once more
+/// on graceful shutdown. The `name` of the saved state is used in the logs.
+#[must_use]
+pub fn start_job<F, Fut, E>(name: &'static str, interval: u64, tracker: &Arc<core::Tracker>, save: F) -> JoinHandle<()>
+where
+    F: Fn(Arc<core::Tracker>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), E>> + Send,
+    E: Display,
+{
+    let weak_tracker = std::sync::Arc::downgrade(tracker);
+
+    tokio::spawn(async move {
+        // An interval of `0` means the state is only saved on shutdown.
+        let mut interval = (interval > 0).then(|| tokio::time::interval(std::time::Duration::from_secs(interval)));
+
+        if let Some(interval) = &mut interval {
+            interval.tick().await;
+        }
+
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {
+                    tracing::info!("Stopping {name} job..");
+                    if let Some(tracker) = weak_tracker.upgrade() {
+                        run_save(name, tracker, &save).await;
+                    }
+                    break;
+                }
+                () = tick(&mut interval) => {
+                    if let Some(tracker) = weak_tracker.upgrade() {
+                        run_save(name, tracker, &save).await;
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
+
+async fn tick(interval: &mut Option<tokio::time::Interval>) {
+    match interval {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+async fn run_save<F, Fut, E>(name: &str, tracker: Arc<core::Tracker>, save: &F)
+where
+    F: Fn(Arc<core::Tracker>) -> Fut,
+    Fut: Future<Output = Result<(), E>>,
+    E: Display,
+{
+    let start_time = Utc::now().time();
+    tracing::info!("Saving {name}..");
+    match save(tracker).await {
+        Ok(()) => tracing::info!("Saved {name} in: {}ms", (Utc::now().time() - start_time).num_milliseconds()),
+        Err(err) => tracing::error!("Unable to save {name}: {err}"),
+    }
+}
@@ -0,0 +1,28 @@
+//! Job that saves the tracker usage statistics into the database on intervals
+//! and on graceful shutdown.
+//!
+//! The saved statistics are loaded again when the tracker starts, so the
+//! counters survive a restart.
+//!
+//! The job is only started when the core tracker configuration section
+//! `[core.statistics_persistence]` is present. If the `interval` is `0` the
+//! statistics are only saved on graceful shutdown.
+//!
+//! Refer to [`statistics`](crate::core::statistics) module for more info.
+use std::sync::Arc;
+
+use tokio::task::JoinHandle;
+use torrust_tracker_configuration::StatisticsPersistence;
+use tracing::instrument;
+
+use super::periodic_save;
+use crate::core;
+
+/// It starts a job for saving the tracker usage statistics.
+#[must_use]
+#[instrument(skip(config, tracker))]
+pub fn start_job(config: &StatisticsPersistence, tracker: &Arc<core::Tracker>) -> JoinHandle<()> {
+    periodic_save::start_job("statistics", config.interval, tracker, |tracker| async move {
+        tracker.save_statistics().await
+    })
+}
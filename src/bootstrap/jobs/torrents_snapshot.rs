@@ -0,0 +1,28 @@
+//! Job that saves a snapshot of the torrent repository on intervals and on
+//! graceful shutdown.
+//!
+//! The snapshot includes the peer lists, so swarms can be restored when the
+//! tracker is restarted.
+//!
+//! The job is only started when the core tracker configuration section
+//! `[core.snapshot]` is present. If the snapshot `interval` is `0` the
+//! snapshot is only saved on graceful shutdown.
+//!
+//! Refer to [`snapshot`](crate::core::snapshot) module for more info.
+use std::sync::Arc;
+
+use tokio::task::JoinHandle;
+use torrust_tracker_configuration::Snapshot;
+use tracing::instrument;
+
+use super::periodic_save;
+use crate::core;
+
+/// It starts a job for saving snapshots of the torrent repository.
+#[must_use]
+#[instrument(skip(config, tracker))]
+pub fn start_job(config: &Snapshot, tracker: &Arc<core::Tracker>) -> JoinHandle<()> {
+    periodic_save::start_job("torrents snapshot", config.interval, tracker, |tracker| async move {
+        tracker.save_torrents_snapshot()
+    })
+}
@@ -25,10 +25,11 @@ use std::sync::Arc;
 
 use axum_server::tls_rustls::RustlsConfig;
 use tokio::task::JoinHandle;
-use torrust_tracker_configuration::{AccessTokens, HttpApi};
+use torrust_tracker_configuration::HttpApi;
 use tracing::instrument;
 
 use super::make_rust_tls;
+use crate::bootstrap::reload::Reloader;
 use crate::core;
 use crate::servers::apis::server::{ApiServer, Launcher};
 use crate::servers::apis::Version;
@@ -49,15 +50,19 @@ pub struct ApiServerJobStarted();
 /// This task will send a message to the main application process to notify
 /// that the API server was successfully started.
 ///
+/// The API access tokens are taken from the `reloader`, so they can be changed
+/// without restarting the API server.
+///
 /// # Panics
 ///
 /// It would panic if unable to send the  `ApiServerJobStarted` notice.
 ///
 ///
-#[instrument(skip(config, tracker, form))]
+#[instrument(skip(config, tracker, reloader, form))]
 pub async fn start_job(
     config: &HttpApi,
     tracker: Arc<core::Tracker>,
+    reloader: Arc<Reloader>,
     form: ServiceRegistrationForm,
     version: Version,
 ) -> Option<JoinHandle<()>> {
@@ -67,24 +72,22 @@ pub async fn start_job(
         .await
         .map(|tls| tls.expect("it should have a valid tracker api tls configuration"));
 
-    let access_tokens = Arc::new(config.access_tokens.clone());
-
     match version {
-        Version::V1 => Some(start_v1(bind_to, tls, tracker.clone(), form, access_tokens).await),
+        Version::V1 => Some(start_v1(bind_to, tls, tracker.clone(), reloader, form).await),
     }
 }
 
 #[allow(clippy::async_yields_async)]
-#[instrument(skip(socket, tls, tracker, form, access_tokens))]
+#[instrument(skip(socket, tls, tracker, reloader, form))]
 async fn start_v1(
     socket: SocketAddr,
     tls: Option<RustlsConfig>,
     tracker: Arc<core::Tracker>,
+    reloader: Arc<Reloader>,
     form: ServiceRegistrationForm,
-    access_tokens: Arc<AccessTokens>,
 ) -> JoinHandle<()> {
     let server = ApiServer::new(Launcher::new(socket, tls))
-        .start(tracker, form, access_tokens)
+        .start(tracker, form, reloader)
         .await
         .expect("it should be able to start to the tracker api");
 
@@ -102,6 +105,7 @@ mod tests {
 
     use crate::bootstrap::app::initialize_with_configuration;
     use crate::bootstrap::jobs::tracker_apis::start_job;
+    use crate::bootstrap::reload::Reloader;
     use crate::servers::apis::Version;
     use crate::servers::registar::Registar;
 
@@ -112,7 +116,14 @@ mod tests {
         let tracker = initialize_with_configuration(&cfg);
         let version = Version::V1;
 
-        start_job(config, tracker, Registar::default().give_form(), version)
+        let loaded_cfg = cfg.clone();
+        let reloader = Arc::new(Reloader::new(
+            &cfg,
+            tracker.clone(),
+            Box::new(move || Ok((*loaded_cfg).clone())),
+        ));
+
+        start_job(config, tracker, reloader, Registar::default().give_form(), version)
             .await
             .expect("it should be able to join to the tracker api start-job");
     }
@@ -10,14 +10,24 @@
 //! - `Debug`
 //! - `Trace`
 //!
+//! The threshold can be changed while the tracker is running (see
+//! [`set_threshold`]), but only if logging was enabled when the tracker
+//! started.
+//!
 //! Refer to the [configuration crate documentation](https://docs.rs/torrust-tracker-configuration) to know how to change log settings.
-use std::sync::Once;
+use std::sync::{Once, OnceLock};
 
 use torrust_tracker_configuration::{Configuration, Threshold};
 use tracing::level_filters::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, Registry};
 
 static INIT: Once = Once::new();
 
+/// Handle to change the log threshold after logging has been initialized.
+static THRESHOLD: OnceLock<reload::Handle<LevelFilter, Registry>> = OnceLock::new();
+
 /// It redirects the log info to the standard output with the log threshold
 /// defined in the configuration.
 pub fn setup(cfg: &Configuration) {
@@ -32,6 +42,18 @@ pub fn setup(cfg: &Configuration) {
     });
 }
 
+/// It changes the log threshold of the running application.
+///
+/// It returns `false` if the threshold can't be changed because logging was
+/// not initialized (the threshold was `off` when the tracker started).
+pub fn set_threshold(threshold: &Threshold) -> bool {
+    let Some(handle) = THRESHOLD.get() else {
+        return false;
+    };
+
+    handle.reload(map_to_tracing_level_filter(threshold)).is_ok()
+}
+
 fn map_to_tracing_level_filter(threshold: &Threshold) -> LevelFilter {
     match threshold {
         Threshold::Off => LevelFilter::OFF,
@@ -44,15 +66,20 @@ fn map_to_tracing_level_filter(threshold: &Threshold) -> LevelFilter {
 }
 
 fn tracing_stdout_init(filter: LevelFilter, style: &TraceStyle) {
-    let builder = tracing_subscriber::fmt().with_max_level(filter).with_ansi(true);
+    let (filter, handle) = reload::Layer::new(filter);
+
+    let subscriber = tracing_subscriber::registry().with(filter);
+    let layer = fmt::layer().with_ansi(true);
 
     let () = match style {
-        TraceStyle::Default => builder.init(),
-        TraceStyle::Pretty(display_filename) => builder.pretty().with_file(*display_filename).init(),
-        TraceStyle::Compact => builder.compact().init(),
-        TraceStyle::Json => builder.json().init(),
+        TraceStyle::Default => subscriber.with(layer).init(),
+        TraceStyle::Pretty(display_filename) => subscriber.with(layer.pretty().with_file(*display_filename)).init(),
+        TraceStyle::Compact => subscriber.with(layer.compact()).init(),
+        TraceStyle::Json => subscriber.with(layer.json()).init(),
     };
 
+    drop(THRESHOLD.set(handle));
+
     tracing::info!("Logging initialized");
 }
 
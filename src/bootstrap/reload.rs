@@ -0,0 +1,432 @@
+//! Hot reload of the application configuration.
+//!
+//! The configuration is loaded once when the tracker starts. Some settings can
+//! be changed later without restarting the tracker, which would drop all the
+//! in-memory swarms. The configuration is reloaded:
+//!
+//! - When the process receives the `SIGHUP` signal (only on Unix). See the
+//!   [`config_reload`](crate::bootstrap::jobs::config_reload) job.
+//! - With the API endpoint `POST /api/v1/config/reload`. See the
+//!   [`config`](crate::servers::apis::v1::context::config) API context.
+//!
+//! The new configuration is loaded from the same sources used when the tracker
+//! started (the config file and the env vars), and validated with the
+//! configuration [`Validator`]. Nothing is changed if it can't be loaded or it
+//! is not valid.
+//!
+//! These are the settings that are applied without restarting:
+//!
+//! - `logging.threshold`, only if logging was enabled when the tracker started.
+//! - `core.announce_policy`
+//! - `core.full_scrape`
+//! - `core.net`
+//! - `core.omit_seeders_for_seeders`
+//! - `core.on_early_announce`
+//! - `core.on_peer_identity_mismatch`
+//! - `core.peer_selection`
+//! - `core.private_mode`
+//! - `core.torrent_statistics`
+//! - `core.tracker_policy`
+//! - `http_api.access_tokens`
+//! - `http_api.access_token_scopes`
+//! - `http_api.query_token_enabled`
+//!
+//! Any other change, for example a new `bind_address` for a service, is not
+//! applied. It's reported as a setting that requires a restart instead.
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+use serde::Serialize;
+use serde_json::Value;
+use torrust_tracker_configuration::validator::{SemanticValidationError, Validator};
+use torrust_tracker_configuration::{AccessTokenScopes, AccessTokens, Configuration};
+use torrust_tracker_located_error::{Located, LocatedError};
+
+use crate::bootstrap::logging;
+use crate::core::Tracker;
+
+/// Configuration sections whose settings are compared one by one. The other
+/// sections are compared as a whole.
+const SECTIONS_COMPARED_BY_SETTING: [&str; 3] = ["logging", "core", "http_api"];
+
+/// Function used to load the new configuration.
+pub type Loader = Box<dyn Fn() -> Result<Configuration, torrust_tracker_configuration::Error> + Send + Sync>;
+
+/// The result of a configuration reload.
+///
+/// Settings are identified by their path in the configuration, for example:
+/// `core.announce_policy` or `http_api.bind_address`. Sections that are not
+/// compared setting by setting are identified by the section name, for
+/// example: `udp_trackers`.
+#[derive(Serialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct Report {
+    /// Settings that have changed and are already in use.
+    pub applied: Vec<String>,
+    /// Settings that have changed but are only used after a restart.
+    pub requires_restart: Vec<String>,
+}
+
+/// Errors reloading the configuration.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("unable to load the configuration: {source}")]
+    Load {
+        source: LocatedError<'static, torrust_tracker_configuration::Error>,
+    },
+
+    #[error("invalid configuration: {source}")]
+    Invalid {
+        source: LocatedError<'static, SemanticValidationError>,
+    },
+}
+
+/// Service that reloads the configuration and applies the changes to the
+/// running application.
+pub struct Reloader {
+    loader: Loader,
+    /// The configuration currently in use. It only differs from the last
+    /// loaded configuration in the settings that require a restart.
+    running: Mutex<Configuration>,
+    tracker: Arc<Tracker>,
+    /// The API access tokens, shared with the API authentication middleware.
+    access_tokens: Arc<RwLock<AccessTokens>>,
+    /// The scopes granted to the API access tokens, shared with the API
+    /// authentication middleware.
+    access_token_scopes: Arc<RwLock<AccessTokenScopes>>,
+    /// Whether the API accepts the access token in the query params, shared
+    /// with the API authentication middleware.
+    query_token_enabled: Arc<AtomicBool>,
+}
+
+impl Reloader {
+    /// It creates the service from the configuration the application was
+    /// started with.
+    #[must_use]
+    pub fn new(config: &Configuration, tracker: Arc<Tracker>, loader: Loader) -> Self {
+        let access_tokens = config
+            .http_api
+            .as_ref()
+            .map(|http_api| http_api.access_tokens.clone())
+            .unwrap_or_default();
+
+        let access_token_scopes = config
+            .http_api
+            .as_ref()
+            .map(|http_api| http_api.access_token_scopes.clone())
+            .unwrap_or_default();
+
+        let query_token_enabled = config.http_api.as_ref().map_or(true, |http_api| http_api.query_token_enabled);
+
+        Self {
+            loader,
+            running: Mutex::new(config.clone()),
+            tracker,
+            access_tokens: Arc::new(RwLock::new(access_tokens)),
+            access_token_scopes: Arc::new(RwLock::new(access_token_scopes)),
+            query_token_enabled: Arc::new(AtomicBool::new(query_token_enabled)),
+        }
+    }
+
+    /// The API access tokens currently in use.
+    #[must_use]
+    pub fn access_tokens(&self) -> Arc<RwLock<AccessTokens>> {
+        self.access_tokens.clone()
+    }
+
+    /// The scopes granted to the API access tokens currently in use.
+    #[must_use]
+    pub fn access_token_scopes(&self) -> Arc<RwLock<AccessTokenScopes>> {
+        self.access_token_scopes.clone()
+    }
+
+    /// Whether the API currently accepts the access token in the query params.
+    #[must_use]
+    pub fn query_token_enabled(&self) -> Arc<AtomicBool> {
+        self.query_token_enabled.clone()
+    }
+
+    /// It loads the configuration again and applies the settings that can
+    /// change while the application is running.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the configuration can't be loaded or it's not
+    /// valid. In that case, no setting is changed.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if a previous reload panicked while holding the lock on the
+    /// running configuration.
+    pub fn reload(&self) -> Result<Report, Error> {
+        let config = (self.loader)().map_err(|err| Error::Load {
+            source: Located(err).into(),
+        })?;
+
+        config.validate().map_err(|err| Error::Invalid {
+            source: Located(err).into(),
+        })?;
+
+        let mut running = self.running.lock().expect("it should get the running configuration");
+
+        let mut report = Report::default();
+
+        for setting in changed_settings(&running, &config) {
+            if self.apply(&setting, &config, &mut running) {
+                report.applied.push(setting);
+            } else {
+                report.requires_restart.push(setting);
+            }
+        }
+
+        self.tracker.update_config(&running.core);
+
+        Ok(report)
+    }
+
+    /// It updates the `setting` in the `running` configuration with the value
+    /// in the new `config`.
+    ///
+    /// It returns `false` if the setting can't be changed without restarting.
+    fn apply(&self, setting: &str, config: &Configuration, running: &mut Configuration) -> bool {
+        match setting {
+            "logging.threshold" => {
+                if !logging::set_threshold(&config.logging.threshold) {
+                    return false;
+                }
+                running.logging.threshold = config.logging.threshold.clone();
+            }
+            "core.announce_policy" => running.core.announce_policy = config.core.announce_policy,
+            "core.full_scrape" => running.core.full_scrape.clone_from(&config.core.full_scrape),
+            "core.net" => running.core.net = config.core.net.clone(),
+            "core.omit_seeders_for_seeders" => running.core.omit_seeders_for_seeders = config.core.omit_seeders_for_seeders,
+            "core.on_early_announce" => running.core.on_early_announce = config.core.on_early_announce,
+            "core.on_peer_identity_mismatch" => running.core.on_peer_identity_mismatch = config.core.on_peer_identity_mismatch,
+            "core.peer_selection" => running.core.peer_selection = config.core.peer_selection,
+            "core.private_mode" => running.core.private_mode = config.core.private_mode,
+            "core.torrent_statistics" => running.core.torrent_statistics = config.core.torrent_statistics,
+            "core.tracker_policy" => running.core.tracker_policy = config.core.tracker_policy.clone(),
+            "http_api.access_tokens" => {
+                let (Some(running_http_api), Some(http_api)) = (running.http_api.as_mut(), &config.http_api) else {
+                    return false;
+                };
+                running_http_api.access_tokens.clone_from(&http_api.access_tokens);
+                *self.access_tokens.write().expect("it should get the API access tokens") = http_api.access_tokens.clone();
+            }
+            "http_api.access_token_scopes" => {
+                let (Some(running_http_api), Some(http_api)) = (running.http_api.as_mut(), &config.http_api) else {
+                    return false;
+                };
+                running_http_api.access_token_scopes.clone_from(&http_api.access_token_scopes);
+                *self
+                    .access_token_scopes
+                    .write()
+                    .expect("it should get the API access token scopes") = http_api.access_token_scopes.clone();
+            }
+            "http_api.query_token_enabled" => {
+                let (Some(running_http_api), Some(http_api)) = (running.http_api.as_mut(), &config.http_api) else {
+                    return false;
+                };
+                running_http_api.query_token_enabled = http_api.query_token_enabled;
+                self.query_token_enabled
+                    .store(http_api.query_token_enabled, Ordering::Relaxed);
+            }
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+/// It returns the settings that are different in both configurations.
+///
+/// The configuration metadata is ignored.
+fn changed_settings(current: &Configuration, new: &Configuration) -> Vec<String> {
+    let current = serde_json::to_value(current).expect("the configuration should be serializable");
+    let new = serde_json::to_value(new).expect("the configuration should be serializable");
+
+    let (Value::Object(current), Value::Object(new)) = (current, new) else {
+        panic!("the configuration should be serialized as a JSON object");
+    };
+
+    let mut changed = vec![];
+
+    for (section, new_value) in &new {
+        if section == "metadata" {
+            continue;
+        }
+
+        let current_value = current.get(section).unwrap_or(&Value::Null);
+
+        if current_value == new_value {
+            continue;
+        }
+
+        match (current_value, new_value) {
+            (Value::Object(current_settings), Value::Object(new_settings))
+                if SECTIONS_COMPARED_BY_SETTING.contains(&section.as_str()) =>
+            {
+                for (setting, new_setting_value) in new_settings {
+                    if current_settings.get(setting) != Some(new_setting_value) {
+                        changed.push(format!("{section}.{setting}"));
+                    }
+                }
+            }
+            _ => changed.push(section.clone()),
+        }
+    }
+
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+
+    use torrust_tracker_configuration::{Configuration, Core, Scope};
+    use torrust_tracker_test_helpers::configuration;
+
+    use crate::bootstrap::app::initialize_with_configuration;
+    use crate::bootstrap::reload::{Error, Reloader, Report};
+
+    fn reloader_loading(current: &Configuration, new: Configuration) -> (Reloader, Arc<crate::core::Tracker>) {
+        let tracker = initialize_with_configuration(current);
+
+        let reloader = Reloader::new(current, tracker.clone(), Box::new(move || Ok(new.clone())));
+
+        (reloader, tracker)
+    }
+
+    #[tokio::test]
+    async fn it_should_not_report_any_change_when_the_configuration_is_the_same() {
+        let config = configuration::ephemeral_public();
+
+        let (reloader, _tracker) = reloader_loading(&config, config.clone());
+
+        assert_eq!(reloader.reload().unwrap(), Report::default());
+    }
+
+    #[tokio::test]
+    async fn it_should_apply_the_new_announce_policy() {
+        let config = configuration::ephemeral_public();
+
+        let mut new_config = config.clone();
+        new_config.core.announce_policy.interval = 60;
+
+        let (reloader, tracker) = reloader_loading(&config, new_config);
+
+        let report = reloader.reload().unwrap();
+
+        assert_eq!(report.applied, vec!["core.announce_policy".to_string()]);
+        assert_eq!(tracker.get_announce_policy().interval, 60);
+    }
+
+    #[tokio::test]
+    async fn it_should_apply_the_new_api_access_tokens() {
+        let config = configuration::ephemeral_public();
+
+        let mut new_config = config.clone();
+        new_config.http_api.as_mut().unwrap().add_token("admin", "NewAccessToken");
+
+        let (reloader, _tracker) = reloader_loading(&config, new_config);
+
+        let report = reloader.reload().unwrap();
+
+        assert_eq!(report.applied, vec!["http_api.access_tokens".to_string()]);
+        assert_eq!(
+            reloader.access_tokens().read().unwrap().get("admin"),
+            Some(&"NewAccessToken".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_apply_the_new_api_access_token_scopes() {
+        let config = configuration::ephemeral_public();
+
+        let mut new_config = config.clone();
+        new_config
+            .http_api
+            .as_mut()
+            .unwrap()
+            .limit_token_scopes("admin", &[Scope::StatsRead]);
+
+        let (reloader, _tracker) = reloader_loading(&config, new_config);
+
+        let report = reloader.reload().unwrap();
+
+        assert_eq!(report.applied, vec!["http_api.access_token_scopes".to_string()]);
+        assert_eq!(
+            reloader.access_token_scopes().read().unwrap().get("admin"),
+            Some(&vec![Scope::StatsRead])
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_apply_the_new_api_query_token_option() {
+        let config = configuration::ephemeral_public();
+
+        let mut new_config = config.clone();
+        new_config.http_api.as_mut().unwrap().query_token_enabled = false;
+
+        let (reloader, _tracker) = reloader_loading(&config, new_config);
+
+        let report = reloader.reload().unwrap();
+
+        assert_eq!(report.applied, vec!["http_api.query_token_enabled".to_string()]);
+        assert!(!reloader.query_token_enabled().load(Ordering::Relaxed));
+    }
+
+    #[tokio::test]
+    async fn it_should_report_the_settings_that_require_a_restart_without_applying_them() {
+        let config = configuration::ephemeral_public();
+
+        let mut new_config = config.clone();
+        new_config.core.listed = true;
+        new_config.http_api.as_mut().unwrap().bind_address = "127.0.0.1:1313".parse::<SocketAddr>().unwrap();
+        new_config.udp_trackers = None;
+
+        let (reloader, tracker) = reloader_loading(&config, new_config);
+
+        let report = reloader.reload().unwrap();
+
+        assert_eq!(report.applied, Vec::<String>::new());
+        assert_eq!(
+            report.requires_restart,
+            vec![
+                "core.listed".to_string(),
+                "udp_trackers".to_string(),
+                "http_api.bind_address".to_string()
+            ]
+        );
+        assert!(!tracker.is_listed());
+    }
+
+    #[tokio::test]
+    async fn it_should_keep_reporting_the_settings_that_require_a_restart_until_the_tracker_is_restarted() {
+        let config = configuration::ephemeral_public();
+
+        let mut new_config = config.clone();
+        new_config.core.listed = true;
+
+        let (reloader, _tracker) = reloader_loading(&config, new_config);
+
+        drop(reloader.reload().unwrap());
+
+        assert_eq!(reloader.reload().unwrap().requires_restart, vec!["core.listed".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn it_should_not_change_anything_when_the_new_configuration_is_not_valid() {
+        let config = configuration::ephemeral_public();
+
+        let mut new_config = config.clone();
+        new_config.core.announce_policy.interval = 60;
+        new_config.core.private_mode = Some(torrust_tracker_configuration::v2_0_0::core::PrivateMode::default());
+
+        let (reloader, tracker) = reloader_loading(&config, new_config);
+
+        assert!(matches!(reloader.reload(), Err(Error::Invalid { .. })));
+        assert_eq!(tracker.get_announce_policy(), Core::default().announce_policy);
+    }
+}
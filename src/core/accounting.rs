@@ -0,0 +1,341 @@
+//! Per-user accounting for private trackers.
+//!
+//! In private mode every peer announces with an authentication
+//! [`Key`]. The tracker links the peer to that key and accumulates the bytes
+//! the peer uploaded and downloaded for each key.
+//!
+//! Clients report the total `uploaded` and `downloaded` bytes since they
+//! started the torrent in every `announce` request, so the tracker keeps the
+//! last values reported by each peer (a session) and only adds the difference
+//! to the key totals:
+//!
+//! - A new session is only counted from zero when the peer announces the
+//!   `started` event. Otherwise the first announce the tracker sees (for
+//!   example, after a restart) only sets the initial values, so the traffic
+//!   is not counted twice.
+//! - If the reported values go down, the session is rebased: nothing is
+//!   counted and the new values are kept as the starting point. Otherwise a
+//!   client could add its whole reported traffic again on every announce by
+//!   decreasing its counters.
+//! - The session ends when the peer announces the `stopped` event or when it
+//!   is removed for being inactive.
+//!
+//! The key totals are kept in memory and the [`Tracker`](crate::core::Tracker)
+//! adds the difference to the totals in the database after each `announce`, so
+//! they survive a restart.
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
+
+use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes, PeerId};
+use serde::{Deserialize, Serialize};
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
+
+use super::auth::Key;
+
+/// The bytes uploaded and downloaded by the peers using the same key.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Traffic {
+    /// The total number of bytes uploaded.
+    pub uploaded: u64,
+    /// The total number of bytes downloaded.
+    pub downloaded: u64,
+}
+
+impl Traffic {
+    /// It returns the uploaded/downloaded ratio. It's `None` when nothing has
+    /// been downloaded yet.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn ratio(&self) -> Option<f64> {
+        (self.downloaded > 0).then(|| self.uploaded as f64 / self.downloaded as f64)
+    }
+
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        self.uploaded == 0 && self.downloaded == 0
+    }
+
+    fn add(&mut self, other: &Traffic) {
+        self.uploaded = self.uploaded.saturating_add(other.uploaded);
+        self.downloaded = self.downloaded.saturating_add(other.downloaded);
+    }
+}
+
+/// The last values reported by a peer that announced with a key.
+#[derive(Debug, Clone)]
+struct Session {
+    key: Key,
+    reported: Traffic,
+    updated: DurationSinceUnixEpoch,
+}
+
+/// The traffic of each key and the sessions of the peers using them.
+#[derive(Debug, Default)]
+pub struct Accounting {
+    sessions: Mutex<HashMap<(InfoHash, PeerId), Session>>,
+    totals: RwLock<HashMap<Key, Traffic>>,
+}
+
+impl Accounting {
+    /// It records an `announce` from a peer using the `key`. It returns the
+    /// traffic added to the key totals.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if another thread panicked while holding the lock on the
+    /// sessions or the totals.
+    pub fn record(&self, key: &Key, info_hash: &InfoHash, peer: &peer::Peer) -> Traffic {
+        let reported = Traffic {
+            uploaded: to_bytes(peer.uploaded),
+            downloaded: to_bytes(peer.downloaded),
+        };
+
+        let mut sessions = self.sessions.lock().expect("it should get the accounting sessions");
+
+        let delta = match sessions.get(&(*info_hash, peer.peer_id)) {
+            Some(session) if session.key == *key => Traffic {
+                uploaded: delta(session.reported.uploaded, reported.uploaded),
+                downloaded: delta(session.reported.downloaded, reported.downloaded),
+            },
+            _ if peer.event == AnnounceEvent::Started => reported,
+            _ => Traffic::default(),
+        };
+
+        if peer.event == AnnounceEvent::Stopped {
+            sessions.remove(&(*info_hash, peer.peer_id));
+        } else {
+            sessions.insert(
+                (*info_hash, peer.peer_id),
+                Session {
+                    key: key.clone(),
+                    reported,
+                    updated: peer.updated,
+                },
+            );
+        }
+
+        drop(sessions);
+
+        if !delta.is_zero() {
+            self.totals
+                .write()
+                .expect("it should get the accounting totals")
+                .entry(key.clone())
+                .or_default()
+                .add(&delta);
+        }
+
+        delta
+    }
+
+    /// It ends the sessions of the peers that have not announced since the
+    /// `cutoff` time.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if another thread panicked while holding the lock on the
+    /// sessions.
+    pub fn remove_inactive_sessions(&self, cutoff: DurationSinceUnixEpoch) {
+        self.sessions
+            .lock()
+            .expect("it should get the accounting sessions")
+            .retain(|_, session| session.updated >= cutoff);
+    }
+
+    /// It returns the traffic of the key, if there is any.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if another thread panicked while holding the lock on the
+    /// totals.
+    #[must_use]
+    pub fn get(&self, key: &Key) -> Option<Traffic> {
+        self.totals
+            .read()
+            .expect("it should get the accounting totals")
+            .get(key)
+            .copied()
+    }
+
+    /// It returns the traffic of all the keys ordered by key.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if another thread panicked while holding the lock on the
+    /// totals.
+    #[must_use]
+    pub fn get_all(&self) -> Vec<(Key, Traffic)> {
+        let mut totals: Vec<(Key, Traffic)> = self
+            .totals
+            .read()
+            .expect("it should get the accounting totals")
+            .iter()
+            .map(|(key, traffic)| (key.clone(), *traffic))
+            .collect();
+
+        totals.sort_by_key(|(key, _)| key.to_string());
+
+        totals
+    }
+
+    /// It replaces the traffic of all the keys. The open sessions are kept.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if another thread panicked while holding the lock on the
+    /// totals.
+    pub fn load(&self, totals: Vec<(Key, Traffic)>) {
+        *self.totals.write().expect("it should get the accounting totals") = totals.into_iter().collect();
+    }
+}
+
+fn to_bytes(number_of_bytes: NumberOfBytes) -> u64 {
+    u64::try_from(number_of_bytes.0.get()).unwrap_or_default()
+}
+
+/// The bytes transferred since the previous announce. When the reported
+/// value goes down nothing is counted.
+fn delta(previous: u64, current: u64) -> u64 {
+    current.saturating_sub(previous)
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod the_accounting {
+        use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes};
+        use torrust_tracker_primitives::info_hash::InfoHash;
+        use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
+
+        use crate::core::accounting::{Accounting, Traffic};
+        use crate::core::auth::Key;
+
+        fn key(c: char) -> Key {
+            c.to_string().repeat(32).parse::<Key>().unwrap()
+        }
+
+        fn info_hash() -> InfoHash {
+            InfoHash::from_bytes(&[1; 20])
+        }
+
+        fn announce(uploaded: i64, downloaded: i64, event: AnnounceEvent) -> peer::Peer {
+            let mut peer = peer::fixture::PeerBuilder::leecher().build();
+            peer.uploaded = NumberOfBytes::new(uploaded);
+            peer.downloaded = NumberOfBytes::new(downloaded);
+            peer.event = event;
+            peer
+        }
+
+        fn traffic(uploaded: u64, downloaded: u64) -> Traffic {
+            Traffic { uploaded, downloaded }
+        }
+
+        #[test]
+        fn it_should_count_the_traffic_reported_since_the_peer_started() {
+            let accounting = Accounting::default();
+
+            accounting.record(&key('A'), &info_hash(), &announce(10, 20, AnnounceEvent::Started));
+
+            assert_eq!(accounting.get(&key('A')), Some(traffic(10, 20)));
+        }
+
+        #[test]
+        fn it_should_only_add_the_traffic_since_the_previous_announce() {
+            let accounting = Accounting::default();
+
+            accounting.record(&key('A'), &info_hash(), &announce(10, 20, AnnounceEvent::Started));
+            let delta = accounting.record(&key('A'), &info_hash(), &announce(15, 50, AnnounceEvent::None));
+
+            assert_eq!(delta, traffic(5, 30));
+            assert_eq!(accounting.get(&key('A')), Some(traffic(15, 50)));
+        }
+
+        #[test]
+        fn it_should_not_count_the_first_announce_of_a_peer_that_did_not_announce_the_started_event() {
+            let accounting = Accounting::default();
+
+            accounting.record(&key('A'), &info_hash(), &announce(100, 100, AnnounceEvent::None));
+            accounting.record(&key('A'), &info_hash(), &announce(110, 120, AnnounceEvent::None));
+
+            assert_eq!(accounting.get(&key('A')), Some(traffic(10, 20)));
+        }
+
+        #[test]
+        fn it_should_rebase_the_session_without_counting_anything_when_the_reported_traffic_goes_down() {
+            let accounting = Accounting::default();
+
+            accounting.record(&key('A'), &info_hash(), &announce(100, 100, AnnounceEvent::Started));
+            let delta = accounting.record(&key('A'), &info_hash(), &announce(5, 7, AnnounceEvent::None));
+            accounting.record(&key('A'), &info_hash(), &announce(15, 17, AnnounceEvent::None));
+
+            assert_eq!(delta, traffic(0, 0));
+            assert_eq!(accounting.get(&key('A')), Some(traffic(110, 110)));
+        }
+
+        #[test]
+        fn it_should_not_count_the_reported_traffic_again_when_a_client_decrements_its_counters_by_one() {
+            let accounting = Accounting::default();
+
+            let terabyte = 1_000_000_000_000;
+
+            accounting.record(&key('A'), &info_hash(), &announce(0, 0, AnnounceEvent::Started));
+            accounting.record(&key('A'), &info_hash(), &announce(terabyte, 0, AnnounceEvent::None));
+            accounting.record(&key('A'), &info_hash(), &announce(terabyte - 1, 0, AnnounceEvent::None));
+            accounting.record(&key('A'), &info_hash(), &announce(terabyte - 2, 0, AnnounceEvent::None));
+
+            assert_eq!(accounting.get(&key('A')), Some(traffic(1_000_000_000_000, 0)));
+        }
+
+        #[test]
+        fn it_should_end_the_session_when_the_peer_stops() {
+            let accounting = Accounting::default();
+
+            accounting.record(&key('A'), &info_hash(), &announce(10, 10, AnnounceEvent::Started));
+            accounting.record(&key('A'), &info_hash(), &announce(20, 20, AnnounceEvent::Stopped));
+            accounting.record(&key('A'), &info_hash(), &announce(30, 30, AnnounceEvent::None));
+
+            assert_eq!(accounting.get(&key('A')), Some(traffic(20, 20)));
+        }
+
+        #[test]
+        fn it_should_end_the_sessions_of_inactive_peers() {
+            let accounting = Accounting::default();
+
+            accounting.record(&key('A'), &info_hash(), &announce(10, 10, AnnounceEvent::Started));
+            accounting.remove_inactive_sessions(DurationSinceUnixEpoch::MAX);
+            accounting.record(&key('A'), &info_hash(), &announce(30, 30, AnnounceEvent::None));
+
+            assert_eq!(accounting.get(&key('A')), Some(traffic(10, 10)));
+        }
+
+        #[test]
+        fn it_should_not_attribute_the_session_of_a_peer_to_another_key() {
+            let accounting = Accounting::default();
+
+            accounting.record(&key('A'), &info_hash(), &announce(10, 10, AnnounceEvent::Started));
+            accounting.record(&key('B'), &info_hash(), &announce(30, 30, AnnounceEvent::None));
+
+            assert_eq!(accounting.get(&key('A')), Some(traffic(10, 10)));
+            assert_eq!(accounting.get(&key('B')), None);
+        }
+
+        #[test]
+        fn it_should_return_the_traffic_of_all_the_keys_ordered_by_key() {
+            let accounting = Accounting::default();
+
+            accounting.load(vec![(key('B'), traffic(1, 1)), (key('A'), traffic(2, 2))]);
+
+            assert_eq!(
+                accounting.get_all(),
+                vec![(key('A'), traffic(2, 2)), (key('B'), traffic(1, 1))]
+            );
+        }
+
+        #[test]
+        fn it_should_calculate_the_ratio() {
+            assert_eq!(traffic(30, 20).ratio(), Some(1.5));
+            assert_eq!(traffic(30, 0).ratio(), None);
+        }
+    }
+}
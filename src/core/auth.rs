@@ -6,7 +6,7 @@
 //!
 //! There are services to [`generate_key`]  and [`verify_key_expiration`]  authentication keys.
 //!
-//! Authentication keys are used only by [`HTTP`](crate::servers::http) trackers. All keys have an expiration time, that means
+//! Authentication keys are used by [`HTTP`](crate::servers::http) and [`UDP`](crate::servers::udp) trackers. All keys have an expiration time, that means
 //! they are only valid during a period of time. After that time the expiring key will no longer be valid.
 //!
 //! Keys are stored in this struct:
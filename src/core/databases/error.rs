@@ -47,6 +47,13 @@ pub enum Error {
         driver: Driver,
     },
 
+    /// Unable to parse the connection string
+    #[error("Invalid {driver} connection string: {source}")]
+    InvalidConnectionString {
+        source: LocatedError<'static, dyn std::error::Error + Send + Sync>,
+        driver: Driver,
+    },
+
     /// Unable to create a connection pool
     #[error("Failed to create r2d2 {driver} connection pool: {source}")]
     ConnectionPool {
@@ -82,6 +89,17 @@ impl From<r2d2_mysql::mysql::Error> for Error {
     }
 }
 
+impl From<r2d2_postgres::postgres::Error> for Error {
+    #[track_caller]
+    fn from(err: r2d2_postgres::postgres::Error) -> Self {
+        let e: DynError = Arc::new(err);
+        Error::InvalidQuery {
+            source: e.into(),
+            driver: Driver::PostgreSQL,
+        }
+    }
+}
+
 impl From<UrlError> for Error {
     #[track_caller]
     fn from(err: UrlError) -> Self {
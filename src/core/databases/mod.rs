@@ -2,9 +2,10 @@
 //!
 //! Persistence is currently implemented with one [`Database`] trait.
 //!
-//! There are two implementations of the trait (two drivers):
+//! There are three implementations of the trait (three drivers):
 //!
 //! - [`Mysql`](crate::core::databases::mysql::Mysql)
+//! - [`Postgres`](crate::core::databases::postgres::Postgres)
 //! - [`Sqlite`](crate::core::databases::sqlite::Sqlite)
 //!
 //! > **NOTICE**: There are no database migrations. If there are any changes,
@@ -15,6 +16,9 @@
 //! - [Torrent metrics](#torrent-metrics)
 //! - [Torrent whitelist](torrent-whitelist)
 //! - [Authentication keys](authentication-keys)
+//! - [IP access list](ip-access-list)
+//! - [Statistics](statistics)
+//! - [Keys traffic](keys-traffic)
 //!
 //! # Torrent metrics
 //!
@@ -25,7 +29,8 @@
 //!  `completed`   | 20                                       | The number of peers that have ever completed downloading the torrent associated to this entry. See [`Entry`](torrust_tracker_torrent_repository::entry::Entry) for more information.
 //!
 //! > **NOTICE**: The peer list for a torrent is not persisted. Since peer have to re-announce themselves on intervals, the data is be
-//! > regenerated again after some minutes.
+//! > regenerated again after some minutes. The peer lists can be optionally saved into a file instead. See
+//! > [`snapshot`](crate::core::snapshot).
 //!
 //! # Torrent whitelist
 //!
@@ -43,18 +48,58 @@
 //! `valid_until` | 1672419840                       | Timestamp for the expiring date  
 //!
 //! > **NOTICE**: All keys must have an expiration date.
+//!
+//! # IP access list
+//!
+//! Field         | Sample data       | Description
+//! ---|---|---
+//! `id`          | 1                 | Autoincrement id
+//! `network`     | `192.168.1.0/24`  | Network in CIDR notation
+//! `policy`      | `deny`            | `allow` or `deny`
+//!
+//! Refer to the [`ip_access_list`](crate::core::ip_access_list) module for
+//! more information about how the rules are applied.
+//!
+//! # Statistics
+//!
+//! Field         | Sample data                | Description
+//! ---|---|---
+//! `id`          | 1                          | Autoincrement id
+//! `name`        | `udp4_announces_handled`   | Name of the counter
+//! `value`       | 1024                       | Value of the counter
+//!
+//! The tracker usage statistics are only persisted when the
+//! `core.statistics_persistence` section is present in the configuration.
+//! Refer to the [`statistics`](crate::core::statistics) module for more
+//! information about the counters.
+//!
+//! # Keys traffic
+//!
+//! Field         | Sample data                      | Description
+//! ---|---|---
+//! `id`          | 1                                | Autoincrement id
+//! `key`         | `IrweYtVuQPGbG9Jzx1DihcPmJGGpVy82` | Authentication key
+//! `uploaded`    | 1048576                          | Total bytes uploaded by the peers using the key
+//! `downloaded`  | 524288                           | Total bytes downloaded by the peers using the key
+//!
+//! Only private trackers account the traffic of each key. Refer to the
+//! [`accounting`](crate::core::accounting) module for more information.
 pub mod driver;
 pub mod error;
 pub mod mysql;
+pub mod postgres;
 pub mod sqlite;
 
 use std::marker::PhantomData;
 
+use ipnet::IpNet;
 use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_primitives::PersistentTorrents;
 
 use self::error::Error;
+use crate::core::accounting::Traffic;
 use crate::core::auth::{self, Key};
+use crate::core::ip_access_list::Rule;
 
 struct Builder<T>
 where
@@ -186,6 +231,102 @@ pub trait Database: Sync + Send {
     /// Will return `Err` if unable to save.
     fn remove_info_hash_from_whitelist(&self, info_hash: InfoHash) -> Result<usize, Error>;
 
+    /// It adds many torrents to the whitelist in a single transaction.
+    /// Torrents that are already whitelisted are ignored.
+    ///
+    /// # Context: Whitelist
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save. No torrent is added in that case.
+    fn add_info_hashes_to_whitelist(&self, info_hashes: &[InfoHash]) -> Result<(), Error>;
+
+    /// It removes many torrents from the whitelist in a single transaction.
+    /// Torrents that are not whitelisted are ignored.
+    ///
+    /// # Context: Whitelist
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save. No torrent is removed in that case.
+    fn remove_info_hashes_from_whitelist(&self, info_hashes: &[InfoHash]) -> Result<(), Error>;
+
+    // IP access list
+
+    /// It loads the IP access list rules from the database.
+    ///
+    /// # Context: IP access list
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to load.
+    fn load_ip_access_rules(&self) -> Result<Vec<Rule>, Error>;
+
+    /// It saves an IP access list rule. If there is already a rule for the
+    /// same network, its policy is replaced.
+    ///
+    /// # Context: IP access list
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn save_ip_access_rule(&self, rule: &Rule) -> Result<(), Error>;
+
+    /// It removes the IP access list rule for the network.
+    ///
+    /// It returns the number of removed rules: `0` if there was no rule for
+    /// the network.
+    ///
+    /// # Context: IP access list
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to remove.
+    fn remove_ip_access_rule(&self, network: &IpNet) -> Result<usize, Error>;
+
+    // Statistics
+
+    /// It loads the tracker usage statistics counters from the database.
+    ///
+    /// It returns the name and the value of each counter.
+    ///
+    /// # Context: Statistics
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to load.
+    fn load_statistics(&self) -> Result<Vec<(String, u64)>, Error>;
+
+    /// It saves the tracker usage statistics counters. The values of the
+    /// counters that already exist are replaced.
+    ///
+    /// # Context: Statistics
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn save_statistics(&self, counters: &[(String, u64)]) -> Result<(), Error>;
+
+    // Keys traffic
+
+    /// It loads the traffic of all the authentication keys from the database.
+    ///
+    /// # Context: Keys Traffic
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to load.
+    fn load_keys_traffic(&self) -> Result<Vec<(Key, Traffic)>, Error>;
+
+    /// It adds the `traffic` to the totals of the authentication key.
+    ///
+    /// # Context: Keys Traffic
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn add_key_traffic(&self, key: &Key, traffic: &Traffic) -> Result<(), Error>;
+
     // Authentication keys
 
     /// It loads the expiring authentication keys from the database.
@@ -2,16 +2,19 @@
 use std::str::FromStr;
 use std::time::Duration;
 
+use ipnet::IpNet;
 use r2d2::Pool;
 use r2d2_mysql::mysql::prelude::Queryable;
-use r2d2_mysql::mysql::{params, Opts, OptsBuilder};
+use r2d2_mysql::mysql::{params, Opts, OptsBuilder, TxOpts};
 use r2d2_mysql::MySqlConnectionManager;
 use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_primitives::PersistentTorrents;
 
 use super::driver::Driver;
 use super::{Database, Error};
+use crate::core::accounting::Traffic;
 use crate::core::auth::{self, Key};
+use crate::core::ip_access_list::{parse_network, Policy, Rule};
 use crate::shared::bit_torrent::common::AUTH_KEY_LENGTH;
 
 const DRIVER: Driver = Driver::MySQL;
@@ -66,6 +69,33 @@ impl Database for Mysql {
             i8::try_from(AUTH_KEY_LENGTH).expect("auth::Auth Key Length Should fit within a i8!")
         );
 
+        let create_ip_access_list_table = "
+        CREATE TABLE IF NOT EXISTS ip_access_list (
+            id integer PRIMARY KEY AUTO_INCREMENT,
+            network VARCHAR(43) NOT NULL UNIQUE,
+            policy VARCHAR(5) NOT NULL
+        );"
+        .to_string();
+
+        let create_statistics_table = "
+        CREATE TABLE IF NOT EXISTS statistics (
+            id integer PRIMARY KEY AUTO_INCREMENT,
+            name VARCHAR(64) NOT NULL UNIQUE,
+            value BIGINT UNSIGNED DEFAULT 0 NOT NULL
+        );"
+        .to_string();
+
+        let create_keys_traffic_table = format!(
+            "
+        CREATE TABLE IF NOT EXISTS keys_traffic (
+            id integer PRIMARY KEY AUTO_INCREMENT,
+            `key` VARCHAR({}) NOT NULL UNIQUE,
+            uploaded BIGINT UNSIGNED DEFAULT 0 NOT NULL,
+            downloaded BIGINT UNSIGNED DEFAULT 0 NOT NULL
+        );",
+            i8::try_from(AUTH_KEY_LENGTH).expect("auth::Auth Key Length Should fit within a i8!")
+        );
+
         let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
 
         conn.query_drop(&create_torrents_table)
@@ -73,6 +103,12 @@ impl Database for Mysql {
         conn.query_drop(&create_keys_table).expect("Could not create keys table.");
         conn.query_drop(&create_whitelist_table)
             .expect("Could not create whitelist table.");
+        conn.query_drop(&create_ip_access_list_table)
+            .expect("Could not create ip_access_list table.");
+        conn.query_drop(&create_statistics_table)
+            .expect("Could not create statistics table.");
+        conn.query_drop(&create_keys_traffic_table)
+            .expect("Could not create keys_traffic table.");
 
         Ok(())
     }
@@ -91,6 +127,18 @@ impl Database for Mysql {
             DROP TABLE `keys`;"
             .to_string();
 
+        let drop_ip_access_list_table = "
+        DROP TABLE `ip_access_list`;"
+            .to_string();
+
+        let drop_statistics_table = "
+        DROP TABLE `statistics`;"
+            .to_string();
+
+        let drop_keys_traffic_table = "
+        DROP TABLE `keys_traffic`;"
+            .to_string();
+
         let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
 
         conn.query_drop(&drop_whitelist_table)
@@ -98,6 +146,12 @@ impl Database for Mysql {
         conn.query_drop(&drop_torrents_table)
             .expect("Could not drop `torrents` table.");
         conn.query_drop(&drop_keys_table).expect("Could not drop `keys` table.");
+        conn.query_drop(&drop_ip_access_list_table)
+            .expect("Could not drop `ip_access_list` table.");
+        conn.query_drop(&drop_statistics_table)
+            .expect("Could not drop `statistics` table.");
+        conn.query_drop(&drop_keys_traffic_table)
+            .expect("Could not drop `keys_traffic` table.");
 
         Ok(())
     }
@@ -201,6 +255,128 @@ impl Database for Mysql {
         Ok(1)
     }
 
+    /// Refer to [`databases::Database::add_info_hashes_to_whitelist`](crate::core::databases::Database::add_info_hashes_to_whitelist).
+    fn add_info_hashes_to_whitelist(&self, info_hashes: &[InfoHash]) -> Result<(), Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut tx = conn.start_transaction(TxOpts::default())?;
+
+        tx.exec_batch(
+            "INSERT INTO whitelist (info_hash) VALUES (:info_hash) ON DUPLICATE KEY UPDATE info_hash = info_hash",
+            info_hashes
+                .iter()
+                .map(|info_hash| params! { "info_hash" => info_hash.to_string() }),
+        )?;
+
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// Refer to [`databases::Database::remove_info_hashes_from_whitelist`](crate::core::databases::Database::remove_info_hashes_from_whitelist).
+    fn remove_info_hashes_from_whitelist(&self, info_hashes: &[InfoHash]) -> Result<(), Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut tx = conn.start_transaction(TxOpts::default())?;
+
+        tx.exec_batch(
+            "DELETE FROM whitelist WHERE info_hash = :info_hash",
+            info_hashes
+                .iter()
+                .map(|info_hash| params! { "info_hash" => info_hash.to_string() }),
+        )?;
+
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// Refer to [`databases::Database::load_ip_access_rules`](crate::core::databases::Database::load_ip_access_rules).
+    fn load_ip_access_rules(&self) -> Result<Vec<Rule>, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let rules = conn.query_map(
+            "SELECT network, policy FROM ip_access_list",
+            |(network, policy): (String, String)| Rule::new(parse_network(&network).unwrap(), Policy::from_str(&policy).unwrap()),
+        )?;
+
+        Ok(rules)
+    }
+
+    /// Refer to [`databases::Database::save_ip_access_rule`](crate::core::databases::Database::save_ip_access_rule).
+    fn save_ip_access_rule(&self, rule: &Rule) -> Result<(), Error> {
+        const COMMAND: &str = "INSERT INTO ip_access_list (network, policy) VALUES (:network, :policy) ON DUPLICATE KEY UPDATE policy = VALUES(policy)";
+
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let network = rule.network.to_string();
+        let policy = rule.policy.to_string();
+
+        Ok(conn.exec_drop(COMMAND, params! { network, policy })?)
+    }
+
+    /// Refer to [`databases::Database::remove_ip_access_rule`](crate::core::databases::Database::remove_ip_access_rule).
+    fn remove_ip_access_rule(&self, network: &IpNet) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let network = network.to_string();
+
+        conn.exec_drop("DELETE FROM ip_access_list WHERE network = :network", params! { network })?;
+
+        Ok(usize::try_from(conn.affected_rows()).expect("the number of deleted rows should fit within a usize"))
+    }
+
+    /// Refer to [`databases::Database::load_statistics`](crate::core::databases::Database::load_statistics).
+    fn load_statistics(&self) -> Result<Vec<(String, u64)>, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let counters = conn.query_map("SELECT name, value FROM statistics", |(name, value): (String, u64)| {
+            (name, value)
+        })?;
+
+        Ok(counters)
+    }
+
+    /// Refer to [`databases::Database::save_statistics`](crate::core::databases::Database::save_statistics).
+    fn save_statistics(&self, counters: &[(String, u64)]) -> Result<(), Error> {
+        const COMMAND: &str =
+            "INSERT INTO statistics (name, value) VALUES (:name, :value) ON DUPLICATE KEY UPDATE value = VALUES(value)";
+
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        for (name, value) in counters {
+            conn.exec_drop(COMMAND, params! { name, value })?;
+        }
+
+        Ok(())
+    }
+
+    /// Refer to [`databases::Database::load_keys_traffic`](crate::core::databases::Database::load_keys_traffic).
+    fn load_keys_traffic(&self) -> Result<Vec<(Key, Traffic)>, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let keys_traffic = conn.query_map(
+            "SELECT `key`, uploaded, downloaded FROM keys_traffic",
+            |(key, uploaded, downloaded): (String, u64, u64)| (key.parse::<Key>().unwrap(), Traffic { uploaded, downloaded }),
+        )?;
+
+        Ok(keys_traffic)
+    }
+
+    /// Refer to [`databases::Database::add_key_traffic`](crate::core::databases::Database::add_key_traffic).
+    fn add_key_traffic(&self, key: &Key, traffic: &Traffic) -> Result<(), Error> {
+        const COMMAND: &str = "INSERT INTO keys_traffic (`key`, uploaded, downloaded) VALUES (:key, :uploaded, :downloaded) ON DUPLICATE KEY UPDATE uploaded = uploaded + VALUES(uploaded), downloaded = downloaded + VALUES(downloaded)";
+
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        conn.exec_drop(
+            COMMAND,
+            params! { "key" => key.to_string(), "uploaded" => traffic.uploaded, "downloaded" => traffic.downloaded },
+        )?;
+
+        Ok(())
+    }
+
     /// Refer to [`databases::Database::get_key_from_keys`](crate::core::databases::Database::get_key_from_keys).
     fn get_key_from_keys(&self, key: &Key) -> Result<Option<auth::PeerKey>, Error> {
         let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
@@ -229,10 +405,7 @@ impl Database for Mysql {
         let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
 
         let key = auth_key.key.to_string();
-        let valid_until = match auth_key.valid_until {
-            Some(valid_until) => valid_until.as_secs().to_string(),
-            None => todo!(),
-        };
+        let valid_until = auth_key.valid_until.map(|valid_until| valid_until.as_secs().to_string());
 
         conn.exec_drop(
             "INSERT INTO `keys` (`key`, valid_until) VALUES (:key, :valid_until)",
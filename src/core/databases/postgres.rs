@@ -0,0 +1,548 @@
+//! The `PostgreSQL` database driver.
+//!
+//! It uses the synchronous [`postgres`](r2d2_postgres::postgres) client, which
+//! drives its own `tokio` runtime internally. That runtime can't be started
+//! nor dropped from a thread that is already running the tracker async
+//! runtime, so the connection pool is owned by a dedicated worker thread that
+//! runs all the database operations (and closes the connections).
+use std::panic::{self, AssertUnwindSafe, Location};
+use std::str::FromStr;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use ipnet::IpNet;
+use r2d2::Pool;
+use r2d2_postgres::postgres::{Client, Config, NoTls};
+use r2d2_postgres::PostgresConnectionManager;
+use torrust_tracker_located_error::DynError;
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::PersistentTorrents;
+
+use super::driver::Driver;
+use super::{Database, Error};
+use crate::core::accounting::Traffic;
+use crate::core::auth::{self, Key};
+use crate::core::ip_access_list::{parse_network, Policy, Rule};
+use crate::shared::bit_torrent::common::AUTH_KEY_LENGTH;
+
+const DRIVER: Driver = Driver::PostgreSQL;
+
+/// An operation run in the worker thread with the connection pool.
+type Job = Box<dyn FnOnce(&Pool<PostgresConnectionManager<NoTls>>) + Send>;
+
+pub struct Postgres {
+    /// It sends the operations to the worker thread. It's only `None` while
+    /// the driver is being dropped.
+    jobs: Option<Sender<Job>>,
+    /// The thread that owns the connection pool and runs all the operations.
+    worker: Option<JoinHandle<()>>,
+}
+
+impl Postgres {
+    /// It runs the `operation` with a connection from the pool in the worker
+    /// thread and waits for the result.
+    fn with_connection<T, F>(&self, operation: F) -> Result<T, Error>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Client) -> Result<T, Error> + Send + 'static,
+    {
+        let (result_sender, result_receiver) = mpsc::sync_channel(1);
+
+        let job: Job = Box::new(move |pool| {
+            let result = pool
+                .get()
+                .map_err(|e| Error::from((e, DRIVER)))
+                .and_then(|mut conn| operation(&mut conn));
+
+            drop(result_sender.send(result));
+        });
+
+        self.jobs
+            .as_ref()
+            .expect("the job sender should exist until the driver is dropped")
+            .send(job)
+            .expect("the PostgreSQL worker thread should be running");
+
+        result_receiver
+            .recv()
+            .expect("the PostgreSQL database operation should not panic")
+    }
+
+    /// It runs the operations until the driver is dropped. A panicking
+    /// operation doesn't stop the thread.
+    fn run_jobs(pool: &Pool<PostgresConnectionManager<NoTls>>, jobs: &Receiver<Job>) {
+        for job in jobs {
+            drop(panic::catch_unwind(AssertUnwindSafe(|| job(pool))));
+        }
+    }
+}
+
+impl Drop for Postgres {
+    fn drop(&mut self) {
+        // The worker thread stops when there are no more job senders, and it
+        // closes the idle connections when it drops the pool.
+        drop(self.jobs.take());
+
+        if let Some(worker) = self.worker.take() {
+            drop(worker.join());
+        }
+    }
+}
+
+fn peer_key_from_row(key: &str, valid_until: Option<i64>) -> auth::PeerKey {
+    auth::PeerKey {
+        key: key.parse::<Key>().unwrap(),
+        valid_until: valid_until.map(|valid_until| Duration::from_secs(valid_until.unsigned_abs())),
+    }
+}
+
+impl Database for Postgres {
+    /// It instantiates a new `PostgreSQL` database driver.
+    ///
+    /// Refer to [`databases::Database::new`](crate::core::databases::Database::new).
+    ///
+    /// # Errors
+    ///
+    /// Will return `r2d2::Error` if `db_path` is not able to create `PostgreSQL` database.
+    fn new(db_path: &str) -> Result<Self, Error> {
+        let config = Config::from_str(db_path).map_err(|err| Error::InvalidConnectionString {
+            source: (Arc::new(err) as DynError).into(),
+            driver: DRIVER,
+        })?;
+        let manager = PostgresConnectionManager::new(config, NoTls);
+
+        let (jobs, jobs_receiver) = mpsc::channel::<Job>();
+        let (pool_sender, pool_receiver) = mpsc::sync_channel(1);
+
+        let worker = thread::Builder::new()
+            .name("postgres".to_string())
+            .spawn(move || {
+                // The pool opens the initial connections when it's built.
+                match r2d2::Pool::builder().build(manager) {
+                    Ok(pool) => {
+                        drop(pool_sender.send(Ok(())));
+                        Self::run_jobs(&pool, &jobs_receiver);
+                    }
+                    Err(e) => drop(pool_sender.send(Err(e))),
+                }
+            })
+            .expect("the PostgreSQL worker thread should be spawned");
+
+        match pool_receiver.recv().expect("the PostgreSQL connection pool should be built") {
+            Ok(()) => Ok(Self {
+                jobs: Some(jobs),
+                worker: Some(worker),
+            }),
+            Err(e) => Err((e, DRIVER).into()),
+        }
+    }
+
+    /// Refer to [`databases::Database::create_database_tables`](crate::core::databases::Database::create_database_tables).
+    fn create_database_tables(&self) -> Result<(), Error> {
+        let create_whitelist_table = "
+        CREATE TABLE IF NOT EXISTS whitelist (
+            id SERIAL PRIMARY KEY,
+            info_hash VARCHAR(40) NOT NULL UNIQUE
+        );"
+        .to_string();
+
+        let create_torrents_table = "
+        CREATE TABLE IF NOT EXISTS torrents (
+            id SERIAL PRIMARY KEY,
+            info_hash VARCHAR(40) NOT NULL UNIQUE,
+            completed BIGINT DEFAULT 0 NOT NULL
+        );"
+        .to_string();
+
+        let create_keys_table = format!(
+            "
+        CREATE TABLE IF NOT EXISTS keys (
+            id SERIAL PRIMARY KEY,
+            key VARCHAR({}) NOT NULL UNIQUE,
+            valid_until BIGINT
+        );",
+            i8::try_from(AUTH_KEY_LENGTH).expect("auth::Auth Key Length Should fit within a i8!")
+        );
+
+        let create_ip_access_list_table = "
+        CREATE TABLE IF NOT EXISTS ip_access_list (
+            id SERIAL PRIMARY KEY,
+            network VARCHAR(43) NOT NULL UNIQUE,
+            policy VARCHAR(5) NOT NULL
+        );"
+        .to_string();
+
+        let create_statistics_table = "
+        CREATE TABLE IF NOT EXISTS statistics (
+            id SERIAL PRIMARY KEY,
+            name VARCHAR(64) NOT NULL UNIQUE,
+            value BIGINT DEFAULT 0 NOT NULL
+        );"
+        .to_string();
+
+        let create_keys_traffic_table = format!(
+            "
+        CREATE TABLE IF NOT EXISTS keys_traffic (
+            id SERIAL PRIMARY KEY,
+            key VARCHAR({}) NOT NULL UNIQUE,
+            uploaded BIGINT DEFAULT 0 NOT NULL,
+            downloaded BIGINT DEFAULT 0 NOT NULL
+        );",
+            i8::try_from(AUTH_KEY_LENGTH).expect("auth::Auth Key Length Should fit within a i8!")
+        );
+
+        self.with_connection(move |conn| {
+            conn.batch_execute(&create_whitelist_table)?;
+            conn.batch_execute(&create_keys_table)?;
+            conn.batch_execute(&create_torrents_table)?;
+            conn.batch_execute(&create_ip_access_list_table)?;
+            conn.batch_execute(&create_statistics_table)?;
+            conn.batch_execute(&create_keys_traffic_table)?;
+
+            Ok(())
+        })
+    }
+
+    /// Refer to [`databases::Database::drop_database_tables`](crate::core::databases::Database::drop_database_tables).
+    fn drop_database_tables(&self) -> Result<(), Error> {
+        let drop_whitelist_table = "
+        DROP TABLE whitelist;"
+            .to_string();
+
+        let drop_torrents_table = "
+        DROP TABLE torrents;"
+            .to_string();
+
+        let drop_keys_table = "
+        DROP TABLE keys;"
+            .to_string();
+
+        let drop_ip_access_list_table = "
+        DROP TABLE ip_access_list;"
+            .to_string();
+
+        let drop_statistics_table = "
+        DROP TABLE statistics;"
+            .to_string();
+
+        let drop_keys_traffic_table = "
+        DROP TABLE keys_traffic;"
+            .to_string();
+
+        self.with_connection(move |conn| {
+            conn.batch_execute(&drop_whitelist_table)?;
+            conn.batch_execute(&drop_torrents_table)?;
+            conn.batch_execute(&drop_keys_table)?;
+            conn.batch_execute(&drop_ip_access_list_table)?;
+            conn.batch_execute(&drop_statistics_table)?;
+            conn.batch_execute(&drop_keys_traffic_table)?;
+
+            Ok(())
+        })
+    }
+
+    /// Refer to [`databases::Database::load_persistent_torrents`](crate::core::databases::Database::load_persistent_torrents).
+    fn load_persistent_torrents(&self) -> Result<PersistentTorrents, Error> {
+        self.with_connection(move |conn| {
+            let rows = conn.query("SELECT info_hash, completed FROM torrents", &[])?;
+
+            Ok(rows
+                .iter()
+                .map(|row| {
+                    let info_hash = InfoHash::from_str(row.get(0)).unwrap();
+                    let completed: i64 = row.get(1);
+                    (info_hash, u32::try_from(completed).unwrap_or(u32::MAX))
+                })
+                .collect())
+        })
+    }
+
+    /// Refer to [`databases::Database::load_keys`](crate::core::databases::Database::load_keys).
+    fn load_keys(&self) -> Result<Vec<auth::PeerKey>, Error> {
+        self.with_connection(move |conn| {
+            let rows = conn.query("SELECT key, valid_until FROM keys", &[])?;
+
+            Ok(rows.iter().map(|row| peer_key_from_row(row.get(0), row.get(1))).collect())
+        })
+    }
+
+    /// Refer to [`databases::Database::load_whitelist`](crate::core::databases::Database::load_whitelist).
+    fn load_whitelist(&self) -> Result<Vec<InfoHash>, Error> {
+        self.with_connection(move |conn| {
+            let rows = conn.query("SELECT info_hash FROM whitelist", &[])?;
+
+            Ok(rows.iter().map(|row| InfoHash::from_str(row.get(0)).unwrap()).collect())
+        })
+    }
+
+    /// Refer to [`databases::Database::save_persistent_torrent`](crate::core::databases::Database::save_persistent_torrent).
+    fn save_persistent_torrent(&self, info_hash: &InfoHash, completed: u32) -> Result<(), Error> {
+        const COMMAND: &str = "INSERT INTO torrents (info_hash, completed) VALUES ($1, $2) ON CONFLICT (info_hash) DO UPDATE SET completed = EXCLUDED.completed";
+
+        let info_hash_str = info_hash.to_string();
+        let completed = i64::from(completed);
+
+        let insert = self.with_connection(move |conn| Ok(conn.execute(COMMAND, &[&info_hash_str, &completed])?))?;
+
+        if insert == 0 {
+            Err(Error::InsertFailed {
+                location: Location::caller(),
+                driver: DRIVER,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Refer to [`databases::Database::get_info_hash_from_whitelist`](crate::core::databases::Database::get_info_hash_from_whitelist).
+    fn get_info_hash_from_whitelist(&self, info_hash: InfoHash) -> Result<Option<InfoHash>, Error> {
+        let info_hash_str = info_hash.to_hex_string();
+
+        let row = self.with_connection(move |conn| {
+            Ok(conn.query_opt("SELECT info_hash FROM whitelist WHERE info_hash = $1", &[&info_hash_str])?)
+        })?;
+
+        Ok(row.map(|row| InfoHash::from_str(row.get(0)).expect("Failed to decode InfoHash String from DB!")))
+    }
+
+    /// Refer to [`databases::Database::add_info_hash_to_whitelist`](crate::core::databases::Database::add_info_hash_to_whitelist).
+    fn add_info_hash_to_whitelist(&self, info_hash: InfoHash) -> Result<usize, Error> {
+        let info_hash_str = info_hash.to_string();
+
+        let insert = self
+            .with_connection(move |conn| Ok(conn.execute("INSERT INTO whitelist (info_hash) VALUES ($1)", &[&info_hash_str])?))?;
+
+        if insert == 0 {
+            Err(Error::InsertFailed {
+                location: Location::caller(),
+                driver: DRIVER,
+            })
+        } else {
+            Ok(usize::try_from(insert).expect("the number of inserted rows should fit within a usize"))
+        }
+    }
+
+    /// Refer to [`databases::Database::remove_info_hash_from_whitelist`](crate::core::databases::Database::remove_info_hash_from_whitelist).
+    fn remove_info_hash_from_whitelist(&self, info_hash: InfoHash) -> Result<usize, Error> {
+        let info_hash_str = info_hash.to_string();
+
+        let deleted =
+            self.with_connection(move |conn| Ok(conn.execute("DELETE FROM whitelist WHERE info_hash = $1", &[&info_hash_str])?))?;
+
+        if deleted == 1 {
+            // should only remove a single record.
+            Ok(1)
+        } else {
+            Err(Error::DeleteFailed {
+                location: Location::caller(),
+                error_code: usize::try_from(deleted).unwrap_or(usize::MAX),
+                driver: DRIVER,
+            })
+        }
+    }
+
+    /// Refer to [`databases::Database::add_info_hashes_to_whitelist`](crate::core::databases::Database::add_info_hashes_to_whitelist).
+    fn add_info_hashes_to_whitelist(&self, info_hashes: &[InfoHash]) -> Result<(), Error> {
+        let info_hashes = info_hashes.to_vec();
+
+        self.with_connection(move |conn| {
+            let mut tx = conn.transaction()?;
+
+            for info_hash in &info_hashes {
+                tx.execute(
+                    "INSERT INTO whitelist (info_hash) VALUES ($1) ON CONFLICT (info_hash) DO NOTHING",
+                    &[&info_hash.to_string()],
+                )?;
+            }
+
+            tx.commit()?;
+
+            Ok(())
+        })
+    }
+
+    /// Refer to [`databases::Database::remove_info_hashes_from_whitelist`](crate::core::databases::Database::remove_info_hashes_from_whitelist).
+    fn remove_info_hashes_from_whitelist(&self, info_hashes: &[InfoHash]) -> Result<(), Error> {
+        let info_hashes = info_hashes.to_vec();
+
+        self.with_connection(move |conn| {
+            let mut tx = conn.transaction()?;
+
+            for info_hash in &info_hashes {
+                tx.execute("DELETE FROM whitelist WHERE info_hash = $1", &[&info_hash.to_string()])?;
+            }
+
+            tx.commit()?;
+
+            Ok(())
+        })
+    }
+
+    /// Refer to [`databases::Database::load_ip_access_rules`](crate::core::databases::Database::load_ip_access_rules).
+    fn load_ip_access_rules(&self) -> Result<Vec<Rule>, Error> {
+        self.with_connection(move |conn| {
+            let rows = conn.query("SELECT network, policy FROM ip_access_list", &[])?;
+
+            Ok(rows
+                .iter()
+                .map(|row| Rule::new(parse_network(row.get(0)).unwrap(), Policy::from_str(row.get(1)).unwrap()))
+                .collect())
+        })
+    }
+
+    /// Refer to [`databases::Database::save_ip_access_rule`](crate::core::databases::Database::save_ip_access_rule).
+    fn save_ip_access_rule(&self, rule: &Rule) -> Result<(), Error> {
+        const COMMAND: &str = "INSERT INTO ip_access_list (network, policy) VALUES ($1, $2) ON CONFLICT (network) DO UPDATE SET policy = EXCLUDED.policy";
+
+        let network = rule.network.to_string();
+        let policy = rule.policy.to_string();
+
+        let insert = self.with_connection(move |conn| Ok(conn.execute(COMMAND, &[&network, &policy])?))?;
+
+        if insert == 0 {
+            Err(Error::InsertFailed {
+                location: Location::caller(),
+                driver: DRIVER,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Refer to [`databases::Database::remove_ip_access_rule`](crate::core::databases::Database::remove_ip_access_rule).
+    fn remove_ip_access_rule(&self, network: &IpNet) -> Result<usize, Error> {
+        let network = network.to_string();
+
+        let deleted =
+            self.with_connection(move |conn| Ok(conn.execute("DELETE FROM ip_access_list WHERE network = $1", &[&network])?))?;
+
+        Ok(usize::try_from(deleted).expect("the number of deleted rows should fit within a usize"))
+    }
+
+    /// Refer to [`databases::Database::load_statistics`](crate::core::databases::Database::load_statistics).
+    fn load_statistics(&self) -> Result<Vec<(String, u64)>, Error> {
+        self.with_connection(move |conn| {
+            let rows = conn.query("SELECT name, value FROM statistics", &[])?;
+
+            Ok(rows
+                .iter()
+                .map(|row| {
+                    let value: i64 = row.get(1);
+                    (row.get(0), u64::try_from(value).unwrap_or_default())
+                })
+                .collect())
+        })
+    }
+
+    /// Refer to [`databases::Database::save_statistics`](crate::core::databases::Database::save_statistics).
+    fn save_statistics(&self, counters: &[(String, u64)]) -> Result<(), Error> {
+        const COMMAND: &str =
+            "INSERT INTO statistics (name, value) VALUES ($1, $2) ON CONFLICT (name) DO UPDATE SET value = EXCLUDED.value";
+
+        let counters: Vec<(String, i64)> = counters
+            .iter()
+            .map(|(name, value)| (name.clone(), i64::try_from(*value).unwrap_or(i64::MAX)))
+            .collect();
+
+        self.with_connection(move |conn| {
+            for (name, value) in &counters {
+                conn.execute(COMMAND, &[name, value])?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Refer to [`databases::Database::load_keys_traffic`](crate::core::databases::Database::load_keys_traffic).
+    fn load_keys_traffic(&self) -> Result<Vec<(Key, Traffic)>, Error> {
+        self.with_connection(move |conn| {
+            let rows = conn.query("SELECT key, uploaded, downloaded FROM keys_traffic", &[])?;
+
+            Ok(rows
+                .iter()
+                .map(|row| {
+                    let key: String = row.get(0);
+                    let uploaded: i64 = row.get(1);
+                    let downloaded: i64 = row.get(2);
+
+                    (
+                        key.parse::<Key>().unwrap(),
+                        Traffic {
+                            uploaded: u64::try_from(uploaded).unwrap_or_default(),
+                            downloaded: u64::try_from(downloaded).unwrap_or_default(),
+                        },
+                    )
+                })
+                .collect())
+        })
+    }
+
+    /// Refer to [`databases::Database::add_key_traffic`](crate::core::databases::Database::add_key_traffic).
+    fn add_key_traffic(&self, key: &Key, traffic: &Traffic) -> Result<(), Error> {
+        const COMMAND: &str = "INSERT INTO keys_traffic (key, uploaded, downloaded) VALUES ($1, $2, $3) ON CONFLICT (key) DO UPDATE SET uploaded = keys_traffic.uploaded + EXCLUDED.uploaded, downloaded = keys_traffic.downloaded + EXCLUDED.downloaded";
+
+        let key = key.to_string();
+        let uploaded = i64::try_from(traffic.uploaded).unwrap_or(i64::MAX);
+        let downloaded = i64::try_from(traffic.downloaded).unwrap_or(i64::MAX);
+
+        self.with_connection(move |conn| {
+            conn.execute(COMMAND, &[&key, &uploaded, &downloaded])?;
+
+            Ok(())
+        })
+    }
+
+    /// Refer to [`databases::Database::get_key_from_keys`](crate::core::databases::Database::get_key_from_keys).
+    fn get_key_from_keys(&self, key: &Key) -> Result<Option<auth::PeerKey>, Error> {
+        let key_str = key.to_string();
+
+        let row = self
+            .with_connection(move |conn| Ok(conn.query_opt("SELECT key, valid_until FROM keys WHERE key = $1", &[&key_str])?))?;
+
+        Ok(row.map(|row| peer_key_from_row(row.get(0), row.get(1))))
+    }
+
+    /// Refer to [`databases::Database::add_key_to_keys`](crate::core::databases::Database::add_key_to_keys).
+    fn add_key_to_keys(&self, auth_key: &auth::PeerKey) -> Result<usize, Error> {
+        let key_str = auth_key.key.to_string();
+        let valid_until = auth_key
+            .valid_until
+            .map(|valid_until| i64::try_from(valid_until.as_secs()).expect("the key expiration should fit within a i64"));
+
+        let insert = self.with_connection(move |conn| {
+            Ok(conn.execute(
+                "INSERT INTO keys (key, valid_until) VALUES ($1, $2)",
+                &[&key_str, &valid_until],
+            )?)
+        })?;
+
+        if insert == 0 {
+            Err(Error::InsertFailed {
+                location: Location::caller(),
+                driver: DRIVER,
+            })
+        } else {
+            Ok(usize::try_from(insert).expect("the number of inserted rows should fit within a usize"))
+        }
+    }
+
+    /// Refer to [`databases::Database::remove_key_from_keys`](crate::core::databases::Database::remove_key_from_keys).
+    fn remove_key_from_keys(&self, key: &Key) -> Result<usize, Error> {
+        let key_str = key.to_string();
+
+        let deleted = self.with_connection(move |conn| Ok(conn.execute("DELETE FROM keys WHERE key = $1", &[&key_str])?))?;
+
+        if deleted == 1 {
+            // should only remove a single record.
+            Ok(1)
+        } else {
+            Err(Error::DeleteFailed {
+                location: Location::caller(),
+                error_code: usize::try_from(deleted).unwrap_or(usize::MAX),
+                driver: DRIVER,
+            })
+        }
+    }
+}
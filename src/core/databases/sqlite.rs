@@ -2,6 +2,7 @@
 use std::panic::Location;
 use std::str::FromStr;
 
+use ipnet::IpNet;
 use r2d2::Pool;
 use r2d2_sqlite::rusqlite::params;
 use r2d2_sqlite::rusqlite::types::Null;
@@ -11,7 +12,9 @@ use torrust_tracker_primitives::{DurationSinceUnixEpoch, PersistentTorrents};
 
 use super::driver::Driver;
 use super::{Database, Error};
+use crate::core::accounting::Traffic;
 use crate::core::auth::{self, Key};
+use crate::core::ip_access_list::{parse_network, Policy, Rule};
 
 const DRIVER: Driver = Driver::Sqlite3;
 
@@ -57,11 +60,39 @@ impl Database for Sqlite {
          );"
         .to_string();
 
+        let create_ip_access_list_table = "
+        CREATE TABLE IF NOT EXISTS ip_access_list (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            network TEXT NOT NULL UNIQUE,
+            policy TEXT NOT NULL
+        );"
+        .to_string();
+
+        let create_statistics_table = "
+        CREATE TABLE IF NOT EXISTS statistics (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            value INTEGER DEFAULT 0 NOT NULL
+        );"
+        .to_string();
+
+        let create_keys_traffic_table = "
+        CREATE TABLE IF NOT EXISTS keys_traffic (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            key TEXT NOT NULL UNIQUE,
+            uploaded INTEGER DEFAULT 0 NOT NULL,
+            downloaded INTEGER DEFAULT 0 NOT NULL
+        );"
+        .to_string();
+
         let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
 
         conn.execute(&create_whitelist_table, [])?;
         conn.execute(&create_keys_table, [])?;
         conn.execute(&create_torrents_table, [])?;
+        conn.execute(&create_ip_access_list_table, [])?;
+        conn.execute(&create_statistics_table, [])?;
+        conn.execute(&create_keys_traffic_table, [])?;
 
         Ok(())
     }
@@ -80,11 +111,26 @@ impl Database for Sqlite {
         DROP TABLE keys;"
             .to_string();
 
+        let drop_ip_access_list_table = "
+        DROP TABLE ip_access_list;"
+            .to_string();
+
+        let drop_statistics_table = "
+        DROP TABLE statistics;"
+            .to_string();
+
+        let drop_keys_traffic_table = "
+        DROP TABLE keys_traffic;"
+            .to_string();
+
         let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
 
         conn.execute(&drop_whitelist_table, [])
             .and_then(|_| conn.execute(&drop_torrents_table, []))
-            .and_then(|_| conn.execute(&drop_keys_table, []))?;
+            .and_then(|_| conn.execute(&drop_keys_table, []))
+            .and_then(|_| conn.execute(&drop_ip_access_list_table, []))
+            .and_then(|_| conn.execute(&drop_statistics_table, []))
+            .and_then(|_| conn.execute(&drop_keys_traffic_table, []))?;
 
         Ok(())
     }
@@ -215,6 +261,161 @@ impl Database for Sqlite {
         }
     }
 
+    /// Refer to [`databases::Database::add_info_hashes_to_whitelist`](crate::core::databases::Database::add_info_hashes_to_whitelist).
+    fn add_info_hashes_to_whitelist(&self, info_hashes: &[InfoHash]) -> Result<(), Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let tx = conn.transaction()?;
+
+        for info_hash in info_hashes {
+            tx.execute(
+                "INSERT INTO whitelist (info_hash) VALUES (?) ON CONFLICT(info_hash) DO NOTHING",
+                [info_hash.to_string()],
+            )?;
+        }
+
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// Refer to [`databases::Database::remove_info_hashes_from_whitelist`](crate::core::databases::Database::remove_info_hashes_from_whitelist).
+    fn remove_info_hashes_from_whitelist(&self, info_hashes: &[InfoHash]) -> Result<(), Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let tx = conn.transaction()?;
+
+        for info_hash in info_hashes {
+            tx.execute("DELETE FROM whitelist WHERE info_hash = ?", [info_hash.to_string()])?;
+        }
+
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// Refer to [`databases::Database::load_ip_access_rules`](crate::core::databases::Database::load_ip_access_rules).
+    fn load_ip_access_rules(&self) -> Result<Vec<Rule>, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut stmt = conn.prepare("SELECT network, policy FROM ip_access_list")?;
+
+        let rules_iter = stmt.query_map([], |row| {
+            let network: String = row.get(0)?;
+            let policy: String = row.get(1)?;
+
+            Ok(Rule::new(
+                parse_network(&network).unwrap(),
+                Policy::from_str(&policy).unwrap(),
+            ))
+        })?;
+
+        let rules: Vec<Rule> = rules_iter.filter_map(std::result::Result::ok).collect();
+
+        Ok(rules)
+    }
+
+    /// Refer to [`databases::Database::save_ip_access_rule`](crate::core::databases::Database::save_ip_access_rule).
+    fn save_ip_access_rule(&self, rule: &Rule) -> Result<(), Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let insert = conn.execute(
+            "INSERT INTO ip_access_list (network, policy) VALUES (?1, ?2) ON CONFLICT(network) DO UPDATE SET policy = ?2",
+            [rule.network.to_string(), rule.policy.to_string()],
+        )?;
+
+        if insert == 0 {
+            Err(Error::InsertFailed {
+                location: Location::caller(),
+                driver: DRIVER,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Refer to [`databases::Database::remove_ip_access_rule`](crate::core::databases::Database::remove_ip_access_rule).
+    fn remove_ip_access_rule(&self, network: &IpNet) -> Result<usize, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let deleted = conn.execute("DELETE FROM ip_access_list WHERE network = ?", [network.to_string()])?;
+
+        Ok(deleted)
+    }
+
+    /// Refer to [`databases::Database::load_statistics`](crate::core::databases::Database::load_statistics).
+    fn load_statistics(&self) -> Result<Vec<(String, u64)>, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut stmt = conn.prepare("SELECT name, value FROM statistics")?;
+
+        let counters_iter = stmt.query_map([], |row| {
+            let name: String = row.get(0)?;
+            let value: i64 = row.get(1)?;
+
+            Ok((name, u64::try_from(value).unwrap_or_default()))
+        })?;
+
+        let counters: Vec<(String, u64)> = counters_iter.filter_map(std::result::Result::ok).collect();
+
+        Ok(counters)
+    }
+
+    /// Refer to [`databases::Database::save_statistics`](crate::core::databases::Database::save_statistics).
+    fn save_statistics(&self, counters: &[(String, u64)]) -> Result<(), Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        for (name, value) in counters {
+            conn.execute(
+                "INSERT INTO statistics (name, value) VALUES (?1, ?2) ON CONFLICT(name) DO UPDATE SET value = ?2",
+                params![name, i64::try_from(*value).unwrap_or(i64::MAX)],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Refer to [`databases::Database::load_keys_traffic`](crate::core::databases::Database::load_keys_traffic).
+    fn load_keys_traffic(&self) -> Result<Vec<(Key, Traffic)>, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut stmt = conn.prepare("SELECT key, uploaded, downloaded FROM keys_traffic")?;
+
+        let traffic_iter = stmt.query_map([], |row| {
+            let key: String = row.get(0)?;
+            let uploaded: i64 = row.get(1)?;
+            let downloaded: i64 = row.get(2)?;
+
+            Ok((
+                key.parse::<Key>().unwrap(),
+                Traffic {
+                    uploaded: u64::try_from(uploaded).unwrap_or_default(),
+                    downloaded: u64::try_from(downloaded).unwrap_or_default(),
+                },
+            ))
+        })?;
+
+        let keys_traffic: Vec<(Key, Traffic)> = traffic_iter.filter_map(std::result::Result::ok).collect();
+
+        Ok(keys_traffic)
+    }
+
+    /// Refer to [`databases::Database::add_key_traffic`](crate::core::databases::Database::add_key_traffic).
+    fn add_key_traffic(&self, key: &Key, traffic: &Traffic) -> Result<(), Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        conn.execute(
+            "INSERT INTO keys_traffic (key, uploaded, downloaded) VALUES (?1, ?2, ?3) ON CONFLICT(key) DO UPDATE SET uploaded = uploaded + ?2, downloaded = downloaded + ?3",
+            params![
+                key.to_string(),
+                i64::try_from(traffic.uploaded).unwrap_or(i64::MAX),
+                i64::try_from(traffic.downloaded).unwrap_or(i64::MAX)
+            ],
+        )?;
+
+        Ok(())
+    }
+
     /// Refer to [`databases::Database::get_key_from_keys`](crate::core::databases::Database::get_key_from_keys).
     fn get_key_from_keys(&self, key: &Key) -> Result<Option<auth::PeerKey>, Error> {
         let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
@@ -5,14 +5,20 @@
 //! `PeerKeyNotValid` | Authentication | The supplied key is not valid. It may not be registered or expired.
 //! `PeerNotAuthenticated` | Authentication | The peer did not provide the authentication key.
 //! `TorrentNotWhitelisted` | Authorization | The action cannot be perform on a not-whitelisted torrent (it only applies for trackers running in `listed` or `private_listed` modes).
+//! `IpAddressNotAllowed` | Authorization | The client IP address is not allowed by the [IP access list](crate::core::ip_access_list).
+//! `FullScrapeNotAllowed` | Authorization | The client can't get the full scrape: it's disabled or the client did not send the admin key.
+//! `AnnounceTooEarly` | Announce | The peer announced again before the minimum announce interval. Only when the tracker is configured to reject early announces.
+//! `PeerIdentityMismatch` | Announce | The peer could not prove it's the peer already in the swarm. Only when the tracker is configured to reject those announces.
+//! `RateLimitExceeded` | Rate limiting | The client has made too many requests. See the [rate limiter](crate::core::rate_limiter).
 //!
+use std::net::IpAddr;
 use std::panic::Location;
 
 use torrust_tracker_located_error::LocatedError;
 use torrust_tracker_primitives::info_hash::InfoHash;
 
 use super::auth::ParseKeyError;
-use super::databases;
+use super::{databases, rate_limiter};
 
 /// Authentication or authorization error returned by the core `Tracker`
 #[derive(thiserror::Error, Debug, Clone)]
@@ -33,6 +39,33 @@ pub enum Error {
         info_hash: InfoHash,
         location: &'static Location<'static>,
     },
+
+    #[error("The IP address: {ip}, is not allowed, {location}")]
+    IpAddressNotAllowed {
+        ip: IpAddr,
+        location: &'static Location<'static>,
+    },
+
+    #[error("The full scrape is not allowed, {location}")]
+    FullScrapeNotAllowed { location: &'static Location<'static> },
+
+    // Announce errors
+    #[error("The peer announced again before the minimum announce interval of {interval_min} seconds, {location}")]
+    AnnounceTooEarly {
+        interval_min: u32,
+        location: &'static Location<'static>,
+    },
+
+    #[error("The peer could not prove it is the peer with the same id in the swarm, {location}")]
+    PeerIdentityMismatch { location: &'static Location<'static> },
+
+    // Rate limiting errors
+    #[error("The IP address: {ip}, has exceeded the rate limit for {request} requests, {location}")]
+    RateLimitExceeded {
+        ip: IpAddr,
+        request: rate_limiter::Request,
+        location: &'static Location<'static>,
+    },
 }
 
 /// Errors related to peers keys.
@@ -0,0 +1,113 @@
+//! Real-time events about changes in the tracker.
+//!
+//! The [`Tracker`](crate::core::Tracker) publishes an [`Event`] every time:
+//!
+//! - A peer joins a swarm, leaves it (with the `stopped` event or because it
+//!   was removed by the inactive peers cleanup) or completes the download.
+//! - A torrent is added to the repository or removed by the cleanup.
+//! - A torrent is added to or removed from the whitelist.
+//! - An authentication key is added or removed.
+//!
+//! Events are sent through a [`tokio::sync::broadcast`] channel, so there can
+//! be many subscribers. See [`Tracker::subscribe_to_events`](crate::core::Tracker::subscribe_to_events).
+//!
+//! Events are only published while there is at least one subscriber. Some of
+//! them need extra work (for example, getting the previous state of the peer),
+//! which is skipped when nobody is listening.
+//!
+//! Subscribers that can't keep up with the events lose the oldest ones. The
+//! channel keeps up to [`CHANNEL_CAPACITY`] events.
+use std::net::SocketAddr;
+
+use aquatic_udp_protocol::PeerId;
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
+
+use super::auth::Key;
+
+/// Number of events kept in the channel for slow subscribers.
+pub const CHANNEL_CAPACITY: usize = 1024;
+
+/// An event about a change in the tracker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    PeerJoined {
+        info_hash: InfoHash,
+        peer_id: PeerId,
+        peer_addr: SocketAddr,
+    },
+    PeerLeft {
+        info_hash: InfoHash,
+        peer_id: PeerId,
+        peer_addr: SocketAddr,
+    },
+    PeerCompleted {
+        info_hash: InfoHash,
+        peer_id: PeerId,
+        peer_addr: SocketAddr,
+    },
+    TorrentCreated {
+        info_hash: InfoHash,
+    },
+    TorrentRemoved {
+        info_hash: InfoHash,
+    },
+    TorrentWhitelisted {
+        info_hash: InfoHash,
+    },
+    TorrentRemovedFromWhitelist {
+        info_hash: InfoHash,
+    },
+    KeyAdded {
+        key: Key,
+        valid_until: Option<DurationSinceUnixEpoch>,
+    },
+    KeyRemoved {
+        key: Key,
+    },
+}
+
+impl Event {
+    #[must_use]
+    pub fn peer_joined(info_hash: &InfoHash, peer: &peer::Peer) -> Self {
+        Event::PeerJoined {
+            info_hash: *info_hash,
+            peer_id: peer.peer_id,
+            peer_addr: peer.peer_addr,
+        }
+    }
+
+    #[must_use]
+    pub fn peer_left(info_hash: &InfoHash, peer: &peer::Peer) -> Self {
+        Event::PeerLeft {
+            info_hash: *info_hash,
+            peer_id: peer.peer_id,
+            peer_addr: peer.peer_addr,
+        }
+    }
+
+    #[must_use]
+    pub fn peer_completed(info_hash: &InfoHash, peer: &peer::Peer) -> Self {
+        Event::PeerCompleted {
+            info_hash: *info_hash,
+            peer_id: peer.peer_id,
+            peer_addr: peer.peer_addr,
+        }
+    }
+
+    /// The torrent the event is about. Authentication key events are not
+    /// related to any torrent.
+    #[must_use]
+    pub fn info_hash(&self) -> Option<&InfoHash> {
+        match self {
+            Event::PeerJoined { info_hash, .. }
+            | Event::PeerLeft { info_hash, .. }
+            | Event::PeerCompleted { info_hash, .. }
+            | Event::TorrentCreated { info_hash }
+            | Event::TorrentRemoved { info_hash }
+            | Event::TorrentWhitelisted { info_hash }
+            | Event::TorrentRemovedFromWhitelist { info_hash } => Some(info_hash),
+            Event::KeyAdded { .. } | Event::KeyRemoved { .. } => None,
+        }
+    }
+}
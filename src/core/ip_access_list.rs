@@ -0,0 +1,288 @@
+//! IP access control for `announce` and `scrape` requests.
+//!
+//! The tracker can reject requests depending on the client IP address. The
+//! access list contains rules. Each rule applies a [`Policy`] to a network in
+//! CIDR notation (for example `192.168.1.0/24` or `2001:db8::/32`). A single
+//! IP address is a network with only one host (`/32` for IPv4 and `/128` for
+//! IPv6).
+//!
+//! The rules are evaluated like this:
+//!
+//! 1. If the IP belongs to a network with the `deny` policy, the request is
+//!    rejected.
+//! 2. If there are no `allow` rules, the request is accepted.
+//! 3. If there are `allow` rules, the request is only accepted when the IP
+//!    belongs to one of those networks.
+//!
+//! IPv4-mapped IPv6 addresses (`::ffff:192.168.1.1`) are checked as IPv4
+//! addresses.
+//!
+//! The rules are persisted in the database. Refer to the
+//! [`databases`](crate::core::databases) module for more information.
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use ipnet::IpNet;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// What to do with the requests from a network.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Policy {
+    /// Only the networks with this policy are allowed (when there is at least
+    /// one).
+    Allow,
+    /// The requests from the network are rejected.
+    Deny,
+}
+
+impl fmt::Display for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Policy::Allow => write!(f, "allow"),
+            Policy::Deny => write!(f, "deny"),
+        }
+    }
+}
+
+/// Error returned when a policy can't be parsed.
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+#[error("Invalid IP access policy: {0}, it should be `allow` or `deny`")]
+pub struct ParsePolicyError(String);
+
+impl FromStr for Policy {
+    type Err = ParsePolicyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" => Ok(Policy::Allow),
+            "deny" => Ok(Policy::Deny),
+            _ => Err(ParsePolicyError(s.to_string())),
+        }
+    }
+}
+
+/// Error returned when a network can't be parsed.
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+#[error("Invalid network: {0}, it should be an IP address or a network in CIDR notation")]
+pub struct ParseNetworkError(String);
+
+/// It parses a network in CIDR notation or a single IP address.
+///
+/// The host bits are removed, so `192.168.1.1/24` is parsed as
+/// `192.168.1.0/24`.
+///
+/// ```rust
+/// use torrust_tracker::core::ip_access_list::parse_network;
+///
+/// assert_eq!(parse_network("192.168.1.1/24").unwrap().to_string(), "192.168.1.0/24");
+/// assert_eq!(parse_network("192.168.1.1").unwrap().to_string(), "192.168.1.1/32");
+/// ```
+///
+/// # Errors
+///
+/// Will return an error if the text is neither a network nor an IP address.
+pub fn parse_network(network: &str) -> Result<IpNet, ParseNetworkError> {
+    if let Ok(network) = network.parse::<IpNet>() {
+        return Ok(network.trunc());
+    }
+
+    match network.parse::<IpAddr>() {
+        Ok(ip) => Ok(IpNet::from(ip)),
+        Err(_) => Err(ParseNetworkError(network.to_string())),
+    }
+}
+
+/// A rule in the access list.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule {
+    pub network: IpNet,
+    pub policy: Policy,
+}
+
+impl Rule {
+    #[must_use]
+    pub fn new(network: IpNet, policy: Policy) -> Self {
+        Self {
+            network: network.trunc(),
+            policy,
+        }
+    }
+}
+
+/// The in-memory IP access list.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct IpAccessList {
+    deny: Vec<IpNet>,
+    allow: Vec<IpNet>,
+}
+
+impl IpAccessList {
+    /// It checks if the requests from the IP are allowed.
+    #[must_use]
+    pub fn is_allowed(&self, ip: &IpAddr) -> bool {
+        let ip = canonical(ip);
+
+        if self.deny.iter().any(|network| network.contains(&ip)) {
+            return false;
+        }
+
+        self.allow.is_empty() || self.allow.iter().any(|network| network.contains(&ip))
+    }
+
+    /// It adds a rule to the list. If there is already a rule for the same
+    /// network, its policy is replaced.
+    pub fn add(&mut self, rule: &Rule) {
+        self.remove(&rule.network);
+
+        match rule.policy {
+            Policy::Allow => self.allow.push(rule.network),
+            Policy::Deny => self.deny.push(rule.network),
+        }
+    }
+
+    /// It removes the rule for the network. It returns `false` if there was no
+    /// rule for it.
+    pub fn remove(&mut self, network: &IpNet) -> bool {
+        let rules = self.deny.len() + self.allow.len();
+
+        self.deny.retain(|denied| denied != network);
+        self.allow.retain(|allowed| allowed != network);
+
+        rules != self.deny.len() + self.allow.len()
+    }
+
+    /// It removes all the rules.
+    pub fn clear(&mut self) {
+        self.deny.clear();
+        self.allow.clear();
+    }
+
+    /// All the rules in the list. The `deny` rules go first.
+    #[must_use]
+    pub fn rules(&self) -> Vec<Rule> {
+        self.deny
+            .iter()
+            .map(|network| Rule::new(*network, Policy::Deny))
+            .chain(self.allow.iter().map(|network| Rule::new(*network, Policy::Allow)))
+            .collect()
+    }
+}
+
+/// It converts IPv4-mapped IPv6 addresses into IPv4 addresses.
+fn canonical(ip: &IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(ipv6) => ipv6.to_ipv4_mapped().map_or(*ip, IpAddr::V4),
+        IpAddr::V4(_) => *ip,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod the_ip_access_list {
+        use std::net::IpAddr;
+
+        use crate::core::ip_access_list::{parse_network, IpAccessList, Policy, Rule};
+
+        fn ip(ip: &str) -> IpAddr {
+            ip.parse().unwrap()
+        }
+
+        fn rule(network: &str, policy: Policy) -> Rule {
+            Rule::new(parse_network(network).unwrap(), policy)
+        }
+
+        #[test]
+        fn it_should_allow_all_ips_when_it_is_empty() {
+            let list = IpAccessList::default();
+
+            assert!(list.is_allowed(&ip("126.0.0.1")));
+            assert!(list.is_allowed(&ip("2001:db8::1")));
+        }
+
+        #[test]
+        fn it_should_not_allow_ips_in_a_denied_network() {
+            let mut list = IpAccessList::default();
+
+            list.add(&rule("126.0.0.0/24", Policy::Deny));
+
+            assert!(!list.is_allowed(&ip("126.0.0.1")));
+            assert!(list.is_allowed(&ip("126.0.1.1")));
+        }
+
+        #[test]
+        fn it_should_only_allow_ips_in_the_allowed_networks_when_there_is_at_least_one() {
+            let mut list = IpAccessList::default();
+
+            list.add(&rule("2001:db8::/32", Policy::Allow));
+
+            assert!(list.is_allowed(&ip("2001:db8::1")));
+            assert!(!list.is_allowed(&ip("126.0.0.1")));
+        }
+
+        #[test]
+        fn it_should_give_precedence_to_the_denied_networks() {
+            let mut list = IpAccessList::default();
+
+            list.add(&rule("126.0.0.0/8", Policy::Allow));
+            list.add(&rule("126.0.0.1", Policy::Deny));
+
+            assert!(!list.is_allowed(&ip("126.0.0.1")));
+            assert!(list.is_allowed(&ip("126.0.0.2")));
+        }
+
+        #[test]
+        fn it_should_check_ipv4_mapped_ipv6_addresses_as_ipv4_addresses() {
+            let mut list = IpAccessList::default();
+
+            list.add(&rule("126.0.0.0/24", Policy::Deny));
+
+            assert!(!list.is_allowed(&ip("::ffff:126.0.0.1")));
+        }
+
+        #[test]
+        fn it_should_replace_the_policy_of_a_network_that_is_already_in_the_list() {
+            let mut list = IpAccessList::default();
+
+            list.add(&rule("126.0.0.0/24", Policy::Deny));
+            list.add(&rule("126.0.0.0/24", Policy::Allow));
+
+            assert_eq!(list.rules(), vec![rule("126.0.0.0/24", Policy::Allow)]);
+        }
+
+        #[test]
+        fn it_should_remove_a_rule() {
+            let mut list = IpAccessList::default();
+
+            list.add(&rule("126.0.0.0/24", Policy::Deny));
+
+            assert!(list.remove(&parse_network("126.0.0.0/24").unwrap()));
+            assert!(!list.remove(&parse_network("126.0.0.0/24").unwrap()));
+            assert!(list.is_allowed(&ip("126.0.0.1")));
+        }
+    }
+
+    mod parsing_a_network {
+        use crate::core::ip_access_list::parse_network;
+
+        #[test]
+        fn it_should_parse_a_network_in_cidr_notation_removing_the_host_bits() {
+            assert_eq!(parse_network("126.0.0.1/24").unwrap().to_string(), "126.0.0.0/24");
+        }
+
+        #[test]
+        fn it_should_parse_a_single_ip_address_as_a_network_with_one_host() {
+            assert_eq!(parse_network("126.0.0.1").unwrap().to_string(), "126.0.0.1/32");
+            assert_eq!(parse_network("2001:db8::1").unwrap().to_string(), "2001:db8::1/128");
+        }
+
+        #[test]
+        fn it_should_fail_parsing_an_invalid_network() {
+            assert!(parse_network("126.0.0.1/33").is_err());
+            assert!(parse_network("not a network").is_err());
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! JSON files written atomically.
+//!
+//! Some of the tracker in-memory data (for example, the torrents
+//! [`snapshot`](crate::core::snapshot) and the
+//! [`metrics_history`](crate::core::metrics_history)) is persisted into JSON
+//! files.
+//!
+//! The contents are first written into a temporary file next to the target
+//! file and then renamed, so a crash while writing never leaves a corrupted
+//! file.
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use torrust_tracker_located_error::{Located, LocatedError};
+
+/// Errors writing or reading a JSON file.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("unable to access the file {path}: {source}")]
+    Io {
+        path: Utf8PathBuf,
+        source: LocatedError<'static, std::io::Error>,
+    },
+
+    #[error("invalid JSON file {path}: {source}")]
+    InvalidFormat {
+        path: Utf8PathBuf,
+        source: LocatedError<'static, serde_json::Error>,
+    },
+}
+
+impl Error {
+    #[track_caller]
+    fn io(path: &Utf8Path, err: std::io::Error) -> Self {
+        Self::Io {
+            path: path.to_owned(),
+            source: Located(err).into(),
+        }
+    }
+
+    #[track_caller]
+    fn invalid_format(path: &Utf8Path, err: serde_json::Error) -> Self {
+        Self::InvalidFormat {
+            path: path.to_owned(),
+            source: Located(err).into(),
+        }
+    }
+}
+
+/// It writes the value into the file.
+///
+/// The parent directories are created if they do not exist.
+///
+/// # Errors
+///
+/// Will return an error if the value can't be serialized or written into the
+/// file.
+pub fn save<T: Serialize + ?Sized>(value: &T, path: &Utf8Path) -> Result<(), Error> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_str().is_empty() {
+            std::fs::create_dir_all(parent).map_err(|err| Error::io(parent, err))?;
+        }
+    }
+
+    let contents = serde_json::to_vec(value).map_err(|err| Error::invalid_format(path, err))?;
+
+    let tmp_path = path.with_extension("tmp");
+
+    std::fs::write(&tmp_path, contents).map_err(|err| Error::io(&tmp_path, err))?;
+    std::fs::rename(&tmp_path, path).map_err(|err| Error::io(path, err))?;
+
+    Ok(())
+}
+
+/// It reads the value from the file.
+///
+/// It returns `None` if the file does not exist yet.
+///
+/// # Errors
+///
+/// Will return an error if the file can't be read or it does not contain a
+/// valid value.
+pub fn load<T: DeserializeOwned>(path: &Utf8Path) -> Result<Option<T>, Error> {
+    let contents = match std::fs::read(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(Error::io(path, err)),
+    };
+
+    let value = serde_json::from_slice(&contents).map_err(|err| Error::invalid_format(path, err))?;
+
+    Ok(Some(value))
+}
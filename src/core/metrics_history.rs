@@ -0,0 +1,289 @@
+//! History of the tracker metrics.
+//!
+//! The tracker [`statistics`](crate::core::statistics) only contain the
+//! current values of the metrics. When the history is enabled in the core
+//! configuration:
+//!
+//! ```toml
+//! [core.metrics_history]
+//! interval = 60
+//! capacity = 1440
+//! path = "./storage/tracker/lib/metrics/history.json"
+//! ```
+//!
+//! the tracker takes a [`Sample`] of the metrics every `interval` seconds and
+//! keeps the latest `capacity` samples in memory (one day with the default
+//! values). The oldest sample is discarded when the history is full.
+//!
+//! When the `path` is set, the history is written into a JSON file on
+//! graceful shutdown and restored when the tracker starts. Otherwise, it's
+//! lost on every restart.
+//!
+//! The samples contain the counters as they are, so the rates (for example,
+//! `announce` requests per second) are calculated from two consecutive
+//! samples when the history is requested. Refer to the
+//! [`stats`](crate::servers::apis::v1::context::stats) API context for more
+//! information.
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use camino::Utf8Path;
+use serde::{Deserialize, Serialize};
+use torrust_tracker_primitives::torrent_metrics::TorrentsMetrics;
+
+use super::json_file;
+use super::statistics::Metrics;
+
+/// The tracker metrics at a given time.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Sample {
+    /// Time the sample was taken, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// Total number of torrents.
+    pub torrents: u64,
+    /// Total number of seeders for all torrents.
+    pub seeders: u64,
+    /// Total number of peers that have ever completed downloading for all torrents.
+    pub completed: u64,
+    /// Total number of leechers for all torrents.
+    pub leechers: u64,
+    /// The usage statistics counters.
+    pub metrics: Metrics,
+}
+
+impl Sample {
+    #[must_use]
+    pub fn new(timestamp: u64, torrents_metrics: &TorrentsMetrics, metrics: Metrics) -> Self {
+        Self {
+            timestamp,
+            torrents: torrents_metrics.torrents,
+            seeders: torrents_metrics.complete,
+            completed: torrents_metrics.downloaded,
+            leechers: torrents_metrics.incomplete,
+            metrics,
+        }
+    }
+}
+
+/// A fixed-size history of metrics samples, ordered from the oldest to the
+/// newest.
+#[derive(Debug)]
+pub struct MetricsHistory {
+    capacity: usize,
+    samples: Mutex<VecDeque<Sample>>,
+}
+
+impl MetricsHistory {
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            samples: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    fn samples(&self) -> std::sync::MutexGuard<'_, VecDeque<Sample>> {
+        self.samples.lock().expect("it should get the metrics history samples")
+    }
+
+    /// It adds a new sample to the history. The oldest sample is discarded if
+    /// the history is full.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if another thread panicked while holding the lock on the
+    /// samples.
+    pub fn push(&self, sample: Sample) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut samples = self.samples();
+
+        if samples.len() == self.capacity {
+            samples.pop_front();
+        }
+
+        samples.push_back(sample);
+    }
+
+    /// It replaces the history with the given samples, for example, the ones
+    /// loaded from the history file. Only the newest samples are kept if
+    /// there are more than the capacity.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if another thread panicked while holding the lock on the
+    /// samples.
+    pub fn restore(&self, restored: Vec<Sample>) {
+        let mut samples = self.samples();
+
+        let skip = restored.len().saturating_sub(self.capacity);
+
+        *samples = restored.into_iter().skip(skip).collect();
+    }
+
+    /// It returns all the samples in the history.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if another thread panicked while holding the lock on the
+    /// samples.
+    #[must_use]
+    pub fn get_all(&self) -> Vec<Sample> {
+        self.samples().iter().cloned().collect()
+    }
+
+    /// It returns the samples taken between `from` and `to` (both included).
+    /// When a `step` is given, the samples are taken at least `step` seconds
+    /// apart from each other. All of them are in seconds.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if another thread panicked while holding the lock on the
+    /// samples.
+    #[must_use]
+    pub fn get(&self, from: Option<u64>, to: Option<u64>, step: Option<u64>) -> Vec<Sample> {
+        let samples = self.samples();
+
+        let in_range = samples
+            .iter()
+            .filter(|sample| from.map_or(true, |from| sample.timestamp >= from) && to.map_or(true, |to| sample.timestamp <= to));
+
+        let mut selected: Vec<Sample> = vec![];
+
+        for sample in in_range {
+            let is_far_enough = match (selected.last(), step) {
+                (Some(last), Some(step)) => sample.timestamp >= last.timestamp.saturating_add(step),
+                _ => true,
+            };
+
+            if is_far_enough {
+                selected.push(sample.clone());
+            }
+        }
+
+        selected
+    }
+}
+
+/// Errors writing or reading the metrics history file.
+pub use super::json_file::Error;
+
+/// It writes the history into the file.
+///
+/// The parent directories are created if they do not exist.
+///
+/// # Errors
+///
+/// Will return an error if the history can't be written into the file.
+pub fn save(samples: &[Sample], path: &Utf8Path) -> Result<(), Error> {
+    json_file::save(samples, path)
+}
+
+/// It reads the history from the file.
+///
+/// It returns `None` if the file does not exist yet.
+///
+/// # Errors
+///
+/// Will return an error if the file can't be read or it does not contain
+/// a valid history.
+pub fn load(path: &Utf8Path) -> Result<Option<Vec<Sample>>, Error> {
+    json_file::load(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use camino::Utf8PathBuf;
+
+    use crate::core::metrics_history::{self, MetricsHistory, Sample};
+    use crate::core::statistics::Metrics;
+
+    fn sample(timestamp: u64) -> Sample {
+        Sample {
+            timestamp,
+            torrents: 1,
+            seeders: 2,
+            completed: 3,
+            leechers: 4,
+            metrics: Metrics {
+                udp4_announces_handled: timestamp,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn timestamps(samples: &[Sample]) -> Vec<u64> {
+        samples.iter().map(|sample| sample.timestamp).collect()
+    }
+
+    #[test]
+    fn it_should_discard_the_oldest_sample_when_the_history_is_full() {
+        let history = MetricsHistory::new(2);
+
+        history.push(sample(60));
+        history.push(sample(120));
+        history.push(sample(180));
+
+        assert_eq!(timestamps(&history.get_all()), vec![120, 180]);
+    }
+
+    #[test]
+    fn it_should_return_the_samples_taken_within_the_time_range() {
+        let history = MetricsHistory::new(10);
+
+        for timestamp in [60, 120, 180, 240] {
+            history.push(sample(timestamp));
+        }
+
+        assert_eq!(timestamps(&history.get(Some(120), Some(180), None)), vec![120, 180]);
+    }
+
+    #[test]
+    fn it_should_return_the_samples_at_least_one_step_apart() {
+        let history = MetricsHistory::new(10);
+
+        for timestamp in [60, 120, 180, 240, 300] {
+            history.push(sample(timestamp));
+        }
+
+        assert_eq!(timestamps(&history.get(None, None, Some(120))), vec![60, 180, 300]);
+    }
+
+    #[test]
+    fn it_should_only_return_the_first_sample_when_the_step_is_the_maximum_value() {
+        let history = MetricsHistory::new(10);
+
+        for timestamp in [60, 120, 180] {
+            history.push(sample(timestamp));
+        }
+
+        assert_eq!(timestamps(&history.get(None, None, Some(u64::MAX))), vec![60]);
+    }
+
+    #[test]
+    fn it_should_only_keep_the_newest_samples_when_it_is_restored_with_more_samples_than_the_capacity() {
+        let history = MetricsHistory::new(2);
+
+        history.restore(vec![sample(60), sample(120), sample(180)]);
+
+        assert_eq!(timestamps(&history.get_all()), vec![120, 180]);
+    }
+
+    #[test]
+    fn it_should_load_the_same_samples_it_saved() {
+        let dir = Utf8PathBuf::try_from(env::temp_dir()).unwrap();
+        let path = dir
+            .join(format!("torrust_metrics_history_{}", uuid::Uuid::new_v4()))
+            .join("history.json");
+
+        let samples = vec![sample(60), sample(120)];
+
+        metrics_history::save(&samples, &path).unwrap();
+
+        assert_eq!(metrics_history::load(&path).unwrap(), Some(samples));
+    }
+}
@@ -28,6 +28,7 @@
 //! - [Services](#services)
 //! - [Authentication](#authentication)
 //! - [Statistics](#statistics)
+//! - [Events](#events)
 //! - [Persistence](#persistence)
 //!
 //! # Tracker
@@ -35,9 +36,10 @@
 //! The `Tracker` is the main struct in this module. `The` tracker has some groups of responsibilities:
 //!
 //! - **Core tracker**: it handles the information about torrents and peers.
-//! - **Authentication**: it handles authentication keys which are used by HTTP trackers.
+//! - **Authentication**: it handles authentication keys which are used by HTTP and UDP trackers.
 //! - **Authorization**: it handles the permission to perform requests.
 //! - **Whitelist**: when the tracker runs in `listed` or `private_listed` mode all operations are restricted to whitelisted torrents.
+//! - **IP access list**: `announce` and `scrape` requests can be rejected depending on the client IP address. See [`ip_access_list`].
 //! - **Statistics**: it keeps and serves the tracker statistics.
 //!
 //! Refer to [torrust-tracker-configuration](https://docs.rs/torrust-tracker-configuration) crate docs to get more information about the tracker settings.
@@ -70,6 +72,9 @@
 //! let peer = peer::Peer {
 //!     peer_id: PeerId(*b"-qB00000000000000001"),
 //!     peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8081),
+//!     alt_peer_addr: None,
+//!     key: None,
+//!     crypto: peer::Crypto::NotSupported,
 //!     updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
 //!     uploaded: NumberOfBytes::new(0),
 //!     downloaded: NumberOfBytes::new(0),
@@ -98,12 +103,13 @@
 //!
 //! ```rust,no_run
 //! use torrust_tracker_primitives::peer;
-//! use torrust_tracker_configuration::AnnouncePolicy;
+//! use torrust_tracker_configuration::{AnnouncePolicy, OnEarlyAnnounce};
 //!
 //! pub struct AnnounceData {
 //!     pub peers: Vec<peer::Peer>,
 //!     pub swarm_stats: SwarmMetadata,
 //!     pub policy: AnnouncePolicy, // the tracker announce policy.
+//!     pub early_announce: Option<OnEarlyAnnounce>, // only when the peer announced before `interval_min`
 //! }
 //!
 //! pub struct SwarmMetadata {
@@ -249,6 +255,7 @@
 //! use std::net::SocketAddr;
 
 //! use aquatic_udp_protocol::PeerId;
+//! use aquatic_udp_protocol::PeerKey;
 //! use torrust_tracker_primitives::DurationSinceUnixEpoch;
 //! use aquatic_udp_protocol::NumberOfBytes;
 //! use aquatic_udp_protocol::AnnounceEvent;
@@ -256,6 +263,8 @@
 //! pub struct Peer {
 //!     pub peer_id: PeerId,                     // The peer ID
 //!     pub peer_addr: SocketAddr,           // Peer socket address
+//!     pub alt_peer_addr: Option<SocketAddr>, // Peer socket address in the other IP family (BEP 07)
+//!     pub key: Option<PeerKey>,            // Key sent by the peer to prove its identity
 //!     pub updated: DurationSinceUnixEpoch, // Last time (timestamp) when the peer was updated
 //!     pub uploaded: NumberOfBytes,         // Number of bytes the peer has uploaded so far
 //!     pub downloaded: NumberOfBytes,       // Number of bytes the peer has downloaded so far   
@@ -360,13 +369,16 @@
 //!
 //! # Authentication
 //!
-//! One of the core `Tracker` responsibilities is to create and keep authentication keys. Auth keys are used by HTTP trackers
+//! One of the core `Tracker` responsibilities is to create and keep authentication keys. Auth keys are used by HTTP and UDP trackers
 //! when the tracker is running in `private` or `private_listed` mode.
 //!
 //! HTTP tracker's clients need to obtain an auth key before starting requesting the tracker. Once the get one they have to include
 //! a `PATH` param with the key in all the HTTP requests. For example, when a peer wants to `announce` itself it has to use the
 //! HTTP tracker endpoint `GET /announce/:key`.
 //!
+//! UDP tracker's clients send the same path in the `URLData` option of the
+//! [BEP 41](https://www.bittorrent.org/beps/bep_0041.html) extensions.
+//!
 //! The common way to obtain the keys is by using the tracker API directly or via other applications like the [Torrust Index](https://github.com/torrust/torrust-index).
 //!
 //! To learn more about tracker authentication, refer to the following modules :
@@ -374,6 +386,19 @@
 //! - [`auth`] module.
 //! - [`core`](crate::core) module.
 //! - [`http`](crate::servers::http) module.
+//! - [`udp`](crate::servers::udp) module.
+//!
+//! # Accounting
+//!
+//! In private mode, the `Tracker` links each peer to the key it announced
+//! with and accumulates the bytes uploaded and downloaded for each key. The
+//! totals are kept in memory and in the database.
+//!
+//! ```text
+//! let traffic = tracker.get_key_traffic(&key);
+//! ```
+//!
+//! Refer to [`accounting`] module for more information.
 //!
 //! # Statistics
 //!
@@ -425,8 +450,33 @@
 //! tracker.send_stats_event(statistics::Event::Tcp4Announce).await
 //! ```
 //!
+//! The metrics are kept in memory. When the `core.statistics_persistence`
+//! section is present in the configuration, they are saved into the database
+//! periodically and on graceful shutdown, and loaded again when the tracker
+//! starts.
+//!
+//! Optionally, the `Tracker` also keeps samples of all the metrics taken on
+//! intervals, so they can be queried over time. Refer to [`metrics_history`]
+//! module for more information.
+//!
 //! Refer to [`statistics`] module for more information about statistics.
 //!
+//! # Events
+//!
+//! The `Tracker` also publishes real-time events when the swarms change (a
+//! peer joins or leaves a swarm, a torrent is removed by the cleanup, ...),
+//! and when the whitelist or the authentication keys change.
+//!
+//! ```text
+//! let mut events = tracker.subscribe_to_events();
+//!
+//! while let Ok(event) = events.recv().await {
+//!     println!("{event:?}");
+//! }
+//! ```
+//!
+//! Refer to [`events`] module for more information about events.
+//!
 //! # Persistence
 //!
 //! Right now the `Tracker` is responsible for storing and load data into and
@@ -437,12 +487,28 @@
 //! - Authentication keys (only expiring keys)
 //! - Torrent whitelist
 //! - Torrent metrics
+//! - IP access list
+//! - Usage statistics (only when the statistics persistence is enabled)
+//! - Keys traffic (only for private trackers)
 //!
 //! Refer to [`databases`] module for more information about persistence.
+//!
+//! Peers are not stored in the database. Optionally, the whole torrent
+//! repository (including the peer lists) can be saved into a snapshot file
+//! periodically and on graceful shutdown, and restored when the tracker
+//! starts. Refer to [`snapshot`] module for more information.
+pub mod accounting;
 pub mod auth;
 pub mod databases;
 pub mod error;
+pub mod events;
+pub mod ip_access_list;
+pub mod json_file;
+pub mod metrics_history;
+pub mod rate_limiter;
+pub mod scrape_cache;
 pub mod services;
+pub mod snapshot;
 pub mod statistics;
 pub mod torrent;
 
@@ -450,25 +516,36 @@ pub mod peer_tests;
 
 use std::cmp::max;
 use std::collections::HashMap;
-use std::net::IpAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::panic::Location;
 use std::sync::Arc;
 use std::time::Duration;
 
+use accounting::{Accounting, Traffic};
+use aquatic_udp_protocol::AnnounceEvent;
 use auth::PeerKey;
+use camino::Utf8PathBuf;
 use databases::driver::Driver;
 use derive_more::Constructor;
 use error::PeerKeyError;
+use ip_access_list::IpAccessList;
+use ipnet::IpNet;
+use metrics_history::MetricsHistory;
+use rate_limiter::RateLimiter;
+use scrape_cache::SwarmMetadataCache;
+use tokio::sync::broadcast;
 use tokio::sync::mpsc::error::SendError;
 use torrust_tracker_clock::clock::Time;
-use torrust_tracker_configuration::v2_0_0::database;
-use torrust_tracker_configuration::{AnnouncePolicy, Core, TORRENT_PEERS_LIMIT};
+use torrust_tracker_configuration::{
+    AnnouncePolicy, ClientSuppliedIp, Core, FullScrape, OnEarlyAnnounce, OnPeerIdentityMismatch, TORRENT_PEERS_LIMIT,
+};
 use torrust_tracker_located_error::Located;
 use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
 use torrust_tracker_primitives::torrent_metrics::TorrentsMetrics;
 use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
 use torrust_tracker_torrent_repository::entry::EntrySync;
+use torrust_tracker_torrent_repository::peer_selection::{self, PeerSelector};
 use torrust_tracker_torrent_repository::repository::Repository;
 
 use self::auth::Key;
@@ -487,19 +564,38 @@ use crate::CurrentClock;
 /// > Typically, the `Tracker` is used by a higher application service that handles
 /// > the network layer.
 pub struct Tracker {
-    /// The tracker configuration.
-    config: Core,
+    /// The tracker configuration. Some of the settings can be changed while
+    /// the tracker is running. See [`Tracker::update_config`].
+    config: std::sync::RwLock<Core>,
 
-    /// A database driver implementation: [`Sqlite3`](crate::core::databases::sqlite)
-    /// or [`MySQL`](crate::core::databases::mysql)
+    /// A database driver implementation: [`Sqlite3`](crate::core::databases::sqlite),
+    /// [`MySQL`](crate::core::databases::mysql) or [`PostgreSQL`](crate::core::databases::postgres)
     database: Arc<Box<dyn Database>>,
 
     /// Tracker users' keys. Only for private trackers.
     keys: tokio::sync::RwLock<std::collections::HashMap<Key, auth::PeerKey>>,
 
+    /// The traffic of the tracker users' keys. Only for private trackers.
+    accounting: Accounting,
+
     /// The list of allowed torrents. Only for listed trackers.
     whitelist: tokio::sync::RwLock<std::collections::HashSet<InfoHash>>,
 
+    /// The networks allowed or denied to make `announce` and `scrape` requests.
+    ip_access_list: tokio::sync::RwLock<IpAccessList>,
+
+    /// The per client rate limiter. Only when it's enabled in the
+    /// configuration.
+    rate_limiter: Option<RateLimiter>,
+
+    /// The cache of the swarm metadata returned in the `scrape` responses.
+    /// Only when it's enabled in the configuration.
+    scrape_cache: Option<SwarmMetadataCache>,
+
+    /// The samples of the tracker metrics over time. Only when it's enabled
+    /// in the configuration.
+    metrics_history: Option<MetricsHistory>,
+
     /// The in-memory torrents repository.
     torrents: Arc<Torrents>,
 
@@ -508,6 +604,9 @@ pub struct Tracker {
 
     /// The in-memory stats repo.
     stats_repository: statistics::Repo,
+
+    /// Channel used to publish the real-time [`events`].
+    events: broadcast::Sender<events::Event>,
 }
 
 /// Structure that holds the data returned by the `announce` request.
@@ -519,6 +618,10 @@ pub struct AnnounceData {
     /// Swarm statistics
     pub stats: SwarmMetadata,
     pub policy: AnnouncePolicy,
+    /// What the tracker did with the announce when the peer announced again
+    /// before the minimum announce interval. It's `None` when the announce was
+    /// not early.
+    pub early_announce: Option<OnEarlyAnnounce>,
 }
 
 /// How many peers the peer announcing wants in the announce response.
@@ -626,37 +729,86 @@ impl Tracker {
         stats_event_sender: Option<Box<dyn statistics::EventSender>>,
         stats_repository: statistics::Repo,
     ) -> Result<Tracker, databases::error::Error> {
-        let driver = match config.database.driver {
-            database::Driver::Sqlite3 => Driver::Sqlite3,
-            database::Driver::MySQL => Driver::MySQL,
-        };
-
-        let database = Arc::new(databases::driver::build(&driver, &config.database.path)?);
+        let database = Arc::new(databases::driver::build(
+            &Driver::from(&config.database.driver),
+            &config.database.path,
+        )?);
 
         Ok(Tracker {
-            config: config.clone(),
+            config: std::sync::RwLock::new(config.clone()),
             keys: tokio::sync::RwLock::new(std::collections::HashMap::new()),
+            accounting: Accounting::default(),
             whitelist: tokio::sync::RwLock::new(std::collections::HashSet::new()),
+            ip_access_list: tokio::sync::RwLock::new(IpAccessList::default()),
+            rate_limiter: config.rate_limit.as_ref().map(RateLimiter::new),
+            scrape_cache: config.scrape_cache.as_ref().map(SwarmMetadataCache::new),
+            metrics_history: config
+                .metrics_history
+                .as_ref()
+                .map(|metrics_history| MetricsHistory::new(metrics_history.capacity)),
             torrents: Arc::default(),
             stats_event_sender,
             stats_repository,
+            events: broadcast::channel(events::CHANNEL_CAPACITY).0,
             database,
         })
     }
 
+    fn config(&self) -> std::sync::RwLockReadGuard<'_, Core> {
+        self.config
+            .read()
+            .expect("it should get a read lock for the tracker configuration")
+    }
+
+    /// It returns a copy of the current tracker configuration.
+    pub fn get_config(&self) -> Core {
+        self.config().clone()
+    }
+
+    /// It replaces the tracker configuration with a new one while the tracker
+    /// is running.
+    ///
+    /// Only the settings that can change live are updated. The ones that are
+    /// only read when the tracker starts (`database`, `private`, `listed`,
+    /// `inactive_peer_cleanup_interval`, `metrics_history`, `snapshot`,
+    /// `statistics_persistence` and `tracker_usage_statistics`) keep their
+    /// current values.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the configuration lock is poisoned.
+    pub fn update_config(&self, config: &Core) {
+        let mut current = self
+            .config
+            .write()
+            .expect("it should get a write lock for the tracker configuration");
+
+        *current = Core {
+            database: current.database.clone(),
+            private: current.private,
+            listed: current.listed,
+            inactive_peer_cleanup_interval: current.inactive_peer_cleanup_interval,
+            metrics_history: current.metrics_history.clone(),
+            snapshot: current.snapshot.clone(),
+            statistics_persistence: current.statistics_persistence.clone(),
+            tracker_usage_statistics: current.tracker_usage_statistics,
+            ..config.clone()
+        };
+    }
+
     /// Returns `true` is the tracker is in public mode.
     pub fn is_public(&self) -> bool {
-        !self.config.private
+        !self.config().private
     }
 
     /// Returns `true` is the tracker is in private mode.
     pub fn is_private(&self) -> bool {
-        self.config.private
+        self.config().private
     }
 
     /// Returns `true` is the tracker is in whitelisted mode.
     pub fn is_listed(&self) -> bool {
-        self.config.listed
+        self.config().listed
     }
 
     /// Returns `true` if the tracker requires authentication.
@@ -666,15 +818,57 @@ impl Tracker {
 
     /// Returns `true` is the tracker is in whitelisted mode.
     pub fn is_behind_reverse_proxy(&self) -> bool {
-        self.config.net.on_reverse_proxy
+        self.config().net.on_reverse_proxy
     }
 
     pub fn get_announce_policy(&self) -> AnnouncePolicy {
-        self.config.announce_policy
+        self.config().announce_policy
+    }
+
+    /// Returns the full scrape configuration. It's `None` when the full
+    /// scrape is disabled.
+    pub fn get_full_scrape_config(&self) -> Option<FullScrape> {
+        self.config().full_scrape.clone()
     }
 
     pub fn get_maybe_external_ip(&self) -> Option<IpAddr> {
-        self.config.net.external_ip
+        self.config().net.external_ip
+    }
+
+    /// It returns the IP address the tracker should use for the peer: the IP
+    /// address sent by the client in the `announce` request, when the
+    /// `core.net.client_supplied_ip` policy allows it for the requests from
+    /// the source IP, or the source IP otherwise.
+    #[must_use]
+    pub fn resolve_peer_ip(&self, source_ip: &IpAddr, client_supplied_ip: Option<IpAddr>) -> IpAddr {
+        match client_supplied_ip {
+            Some(client_supplied_ip) if self.trusts_client_supplied_ip(source_ip) => client_supplied_ip,
+            _ => *source_ip,
+        }
+    }
+
+    /// It returns the address of the peer in the other IP family sent by the
+    /// client in the `announce` request, only when the
+    /// `core.net.client_supplied_ip` policy allows it for the requests from
+    /// the source IP. Otherwise, clients could add arbitrary addresses to the
+    /// swarms.
+    #[must_use]
+    pub fn resolve_alt_peer_addr(&self, source_ip: &IpAddr, client_supplied_addr: Option<SocketAddr>) -> Option<SocketAddr> {
+        client_supplied_addr.filter(|_| self.trusts_client_supplied_ip(source_ip))
+    }
+
+    fn trusts_client_supplied_ip(&self, source_ip: &IpAddr) -> bool {
+        let source_ip = match source_ip {
+            IpAddr::V6(ipv6) => ipv6.to_ipv4_mapped().map_or(*source_ip, IpAddr::V4),
+            IpAddr::V4(_) => *source_ip,
+        };
+        let config = self.config();
+
+        match config.net.client_supplied_ip {
+            ClientSuppliedIp::Never => false,
+            ClientSuppliedIp::TrustedNetworks => config.net.trusted_networks.iter().any(|network| network.contains(&source_ip)),
+            ClientSuppliedIp::PrivateNetworks => is_loopback_or_private(&source_ip),
+        }
     }
 
     /// It handles an announce request.
@@ -688,7 +882,7 @@ impl Tracker {
         peer: &mut peer::Peer,
         remote_client_ip: &IpAddr,
         peers_wanted: &PeersWanted,
-    ) -> AnnounceData {
+    ) -> Result<AnnounceData, Error> {
         // code-review: maybe instead of mutating the peer we could just return
         // a tuple with the new peer and the announce data: (Peer, AnnounceData).
         // It could even be a different struct: `StoredPeer` or `PublicPeer`.
@@ -699,24 +893,120 @@ impl Tracker {
         // The `Tracker` has delegated that responsibility to the handlers
         // (because we want to return a friendly error response) but that does not mean we should
         // double-check authorization at this domain level too.
-        // I would propose to return a `Result<AnnounceData, Error>` here.
         // Besides, regarding authentication the `Tracker` is also responsible for authentication but
         // we are actually handling authentication at the handlers level. So I would extract that
         // responsibility into another authentication service.
 
         tracing::debug!("Before: {peer:?}");
-        peer.change_ip(&assign_ip_address_to_peer(remote_client_ip, self.config.net.external_ip));
+        peer.change_ip(&assign_ip_address_to_peer(remote_client_ip, self.get_maybe_external_ip()));
+
+        let identity_mismatch = self
+            .is_peer_identity_mismatch(info_hash, peer)
+            .then(|| self.config().on_peer_identity_mismatch);
+
+        let is_new_peer = match identity_mismatch {
+            Some(OnPeerIdentityMismatch::Reject) => {
+                return Err(Error::PeerIdentityMismatch {
+                    location: Location::caller(),
+                })
+            }
+            Some(OnPeerIdentityMismatch::NewPeer) => true,
+            Some(OnPeerIdentityMismatch::Ignore) | None => false,
+        };
+
+        if !is_new_peer {
+            self.keep_the_previous_alt_peer_addr(info_hash, peer);
+        }
         tracing::debug!("After: {peer:?}");
 
-        let stats = self.upsert_peer_and_get_stats(info_hash, peer);
+        let early_announce = (!is_new_peer && self.is_early_announce(info_hash, peer)).then(|| self.config().on_early_announce);
+
+        let stats = match early_announce {
+            Some(OnEarlyAnnounce::Reject) => {
+                return Err(Error::AnnounceTooEarly {
+                    interval_min: self.get_announce_policy().interval_min,
+                    location: Location::caller(),
+                })
+            }
+            Some(OnEarlyAnnounce::Cached) => self.get_swarm_metadata(info_hash),
+            Some(OnEarlyAnnounce::SkipCounting) => self.upsert_peer_and_get_stats(info_hash, peer),
+            // The peer that is already in the swarm is kept. The new peer only
+            // gets the response.
+            None if is_new_peer => self.get_swarm_metadata(info_hash),
+            Some(OnEarlyAnnounce::Accept) | None => {
+                let stats = self.upsert_peer_and_get_stats(info_hash, peer);
+                self.record_announce(info_hash);
+                stats
+            }
+        };
 
         let peers = self.get_peers_for(info_hash, peer, peers_wanted.limit());
 
-        AnnounceData {
+        Ok(AnnounceData {
             peers,
             stats,
             policy: self.get_announce_policy(),
+            early_announce,
+        })
+    }
+
+    /// Dual-stack clients can announce over IPv4 and over IPv6 instead of
+    /// sending their address in the other IP family with the
+    /// [BEP 07](https://www.bittorrent.org/beps/bep_0007.html) params. When
+    /// the announce does not include the address in the other IP family, the
+    /// one the peer had before is kept.
+    fn keep_the_previous_alt_peer_addr(&self, info_hash: &InfoHash, peer: &mut peer::Peer) {
+        if peer.alt_peer_addr.is_some() {
+            return;
         }
+
+        let Some(previous) = self.torrents.get(info_hash).and_then(|entry| entry.get_peer(&peer.peer_id)) else {
+            return;
+        };
+
+        peer.change_alt_addr(previous.addresses().find(|addr| addr.is_ipv4() != peer.peer_addr.is_ipv4()));
+    }
+
+    /// It checks if the announce comes from a peer that can't prove it's the
+    /// peer with the same `peer_id` in the swarm. The peer must send the same
+    /// `key` it sent before or, if it did not send a key, announce from the
+    /// same IP address.
+    ///
+    /// The identity is not checked when the `on_peer_identity_mismatch`
+    /// option is `ignore`.
+    fn is_peer_identity_mismatch(&self, info_hash: &InfoHash, peer: &peer::Peer) -> bool {
+        if self.config().on_peer_identity_mismatch == OnPeerIdentityMismatch::Ignore {
+            return false;
+        }
+
+        let Some(previous) = self.torrents.get(info_hash).and_then(|entry| entry.get_peer(&peer.peer_id)) else {
+            return false;
+        };
+
+        match previous.key {
+            Some(key) => peer.key != Some(key),
+            None => !previous.addresses().any(|addr| addr.ip() == peer.peer_addr.ip()),
+        }
+    }
+
+    /// It checks if the peer has announced again before the minimum announce
+    /// interval (`interval_min`), using the time of its previous announce in
+    /// the swarm.
+    ///
+    /// `stopped` and `completed` announces are never early, because clients
+    /// send them when the event happens.
+    fn is_early_announce(&self, info_hash: &InfoHash, peer: &peer::Peer) -> bool {
+        if matches!(peer.event, AnnounceEvent::Stopped | AnnounceEvent::Completed) {
+            return false;
+        }
+
+        let Some(previous) = self.torrents.get(info_hash).and_then(|entry| entry.get_peer(&peer.peer_id)) else {
+            return false;
+        };
+
+        let interval_min = Duration::from_secs(u64::from(self.get_announce_policy().interval_min));
+
+        peer.updated < previous.updated + interval_min
     }
 
     /// It handles a scrape request.
@@ -729,7 +1019,10 @@ impl Tracker {
 
         for info_hash in info_hashes {
             let swarm_metadata = match self.authorize(info_hash).await {
-                Ok(()) => self.get_swarm_metadata(info_hash),
+                Ok(()) => {
+                    self.record_scrape(info_hash);
+                    self.get_cached_swarm_metadata(info_hash).await
+                }
                 Err(_) => SwarmMetadata::zeroed(),
             };
             scrape_data.add_file(info_hash, swarm_metadata);
@@ -738,6 +1031,100 @@ impl Tracker {
         scrape_data
     }
 
+    /// It returns the infohashes of all the torrents, in the same order the
+    /// torrents are kept in the repository.
+    ///
+    /// The full scrape takes this list once and then reads the torrents page
+    /// by page, so the pages are not affected by the torrents added or
+    /// removed while the response is streamed.
+    ///
+    /// # Context: Tracker
+    #[must_use]
+    pub fn get_torrent_info_hashes(&self) -> Vec<InfoHash> {
+        self.torrents
+            .get_paginated(None)
+            .into_iter()
+            .map(|(info_hash, _entry)| info_hash)
+            .collect()
+    }
+
+    /// It returns a page of the full scrape: the swarm metadata of the
+    /// torrents in the page.
+    ///
+    /// The torrents that have been removed since the infohashes were listed
+    /// are skipped. When the tracker is running in `listed` mode the torrents
+    /// that are not whitelisted are skipped too, so a page can have fewer
+    /// torrents than infohashes.
+    ///
+    /// # Context: Tracker
+    pub async fn full_scrape_page(&self, info_hashes: &[InfoHash]) -> Vec<(InfoHash, SwarmMetadata)> {
+        let mut files = vec![];
+
+        for info_hash in info_hashes {
+            let Some(entry) = self.torrents.get(info_hash) else {
+                continue;
+            };
+
+            if self.authorize(info_hash).await.is_ok() {
+                files.push((*info_hash, entry.get_swarm_metadata()));
+            }
+        }
+
+        files
+    }
+
+    /// It counts an `announce` request in the torrent activity statistics,
+    /// when they are enabled.
+    fn record_announce(&self, info_hash: &InfoHash) {
+        if !self.config().torrent_statistics {
+            return;
+        }
+
+        if let Some(entry) = self.torrents.get(info_hash) {
+            entry.record_announce(CurrentClock::now());
+        }
+    }
+
+    /// It counts a `scrape` request in the torrent activity statistics, when
+    /// they are enabled.
+    fn record_scrape(&self, info_hash: &InfoHash) {
+        if !self.config().torrent_statistics {
+            return;
+        }
+
+        if let Some(entry) = self.torrents.get(info_hash) {
+            entry.record_scrape(CurrentClock::now());
+        }
+    }
+
+    /// It returns the data for a `scrape` response from the scrape cache, when
+    /// it's enabled.
+    async fn get_cached_swarm_metadata(&self, info_hash: &InfoHash) -> SwarmMetadata {
+        let Some(scrape_cache) = &self.scrape_cache else {
+            return self.get_swarm_metadata(info_hash);
+        };
+
+        if let Some(swarm_metadata) = scrape_cache.get(info_hash) {
+            self.send_stats_event(statistics::Event::ScrapeCacheHit).await;
+            return swarm_metadata;
+        }
+
+        self.send_stats_event(statistics::Event::ScrapeCacheMiss).await;
+
+        let swarm_metadata = self.get_swarm_metadata(info_hash);
+
+        scrape_cache.insert(info_hash, swarm_metadata);
+
+        swarm_metadata
+    }
+
+    /// It discards the cached swarm metadata of all the torrents.
+    fn clear_scrape_cache(&self) {
+        if let Some(scrape_cache) = &self.scrape_cache {
+            scrape_cache.clear();
+        }
+    }
+
     /// It returns the data for a `scrape` response.
     fn get_swarm_metadata(&self, info_hash: &InfoHash) -> SwarmMetadata {
         match self.torrents.get(info_hash) {
@@ -759,6 +1146,96 @@ impl Tracker {
 
         self.torrents.import_persistent(&persistent_torrents);
 
+        self.clear_scrape_cache();
+
+        Ok(())
+    }
+
+    /// It takes a snapshot of the torrent repository, including the peer
+    /// lists.
+    ///
+    /// # Context: Tracker
+    pub fn take_torrents_snapshot(&self) -> snapshot::Snapshot {
+        let torrents = self
+            .torrents
+            .get_paginated(None)
+            .iter()
+            .map(|(info_hash, entry)| snapshot::Torrent {
+                info_hash: *info_hash,
+                downloaded: entry.get_swarm_metadata().downloaded,
+                peers: entry.get_peers(None).iter().map(|peer| peer.as_ref().into()).collect(),
+            })
+            .collect();
+
+        snapshot::Snapshot { torrents }
+    }
+
+    /// It restores the torrents from a snapshot of the torrent repository.
+    ///
+    /// Peers that have not been updated for more than `max_peer_timeout`
+    /// seconds are discarded. Torrents that already exist in the repository
+    /// are not overwritten, but the peers in the snapshot are added to them.
+    ///
+    /// # Context: Tracker
+    pub fn restore_torrents_snapshot(&self, snapshot: snapshot::Snapshot) {
+        let current_cutoff =
+            CurrentClock::now_sub(&Duration::from_secs(u64::from(self.config().tracker_policy.max_peer_timeout)))
+                .unwrap_or_default();
+
+        let persistent_torrents = snapshot
+            .torrents
+            .iter()
+            .map(|torrent| (torrent.info_hash, torrent.downloaded))
+            .collect();
+
+        self.torrents.import_persistent(&persistent_torrents);
+
+        for torrent in snapshot.torrents {
+            for peer in torrent.peers {
+                let peer = peer::Peer::from(peer);
+
+                if peer.updated > current_cutoff {
+                    self.torrents.upsert_peer(&torrent.info_hash, &peer);
+                }
+            }
+        }
+
+        self.clear_scrape_cache();
+    }
+
+    /// It writes a snapshot of the torrent repository into the snapshot file,
+    /// if the snapshot is enabled.
+    ///
+    /// # Context: Tracker
+    ///
+    /// # Errors
+    ///
+    /// Will return a `snapshot::Error` if unable to write the snapshot file.
+    pub fn save_torrents_snapshot(&self) -> Result<(), snapshot::Error> {
+        let Some(snapshot_config) = self.config().snapshot.clone() else {
+            return Ok(());
+        };
+
+        snapshot::save(&self.take_torrents_snapshot(), &snapshot_config.path)
+    }
+
+    /// It restores the torrents from the snapshot file, if the snapshot is
+    /// enabled and the file exists.
+    ///
+    /// # Context: Tracker
+    ///
+    /// # Errors
+    ///
+    /// Will return a `snapshot::Error` if unable to read the snapshot file.
+    pub fn load_torrents_snapshot(&self) -> Result<(), snapshot::Error> {
+        let Some(snapshot_config) = self.config().snapshot.clone() else {
+            return Ok(());
+        };
+
+        if let Some(snapshot) = snapshot::load(&snapshot_config.path)? {
+            self.restore_torrents_snapshot(snapshot);
+        }
+
         Ok(())
     }
 
@@ -766,11 +1243,38 @@ impl Tracker {
     ///
     /// Get torrent peers for a given torrent and client.
     ///
-    /// It filters out the client making the request.
+    /// It filters out the client making the request. The peers are chosen
+    /// with the `core.peer_selection` strategy. Seeders don't get other
+    /// seeders when `core.omit_seeders_for_seeders` is enabled, and peers
+    /// that require encryption only get peers that support it.
     fn get_peers_for(&self, info_hash: &InfoHash, peer: &peer::Peer, limit: usize) -> Vec<Arc<peer::Peer>> {
         match self.torrents.get(info_hash) {
             None => vec![],
-            Some(entry) => entry.get_peers_for_client(&peer.peer_addr, Some(max(limit, TORRENT_PEERS_LIMIT))),
+            Some(entry) => {
+                let (peer_selection, omit_seeders_for_seeders) = {
+                    let config = self.config();
+                    (config.peer_selection, config.omit_seeders_for_seeders)
+                };
+                let limit = max(limit, TORRENT_PEERS_LIMIT);
+
+                let selector = peer_selection::selector(peer_selection);
+
+                let omit_seeders = peer_selection::OmitSeeders(selector);
+                let selector: &dyn PeerSelector = if omit_seeders_for_seeders && peer::ReadInfo::is_seeder(peer) {
+                    &omit_seeders
+                } else {
+                    selector
+                };
+
+                let crypto_supported = peer_selection::CryptoSupported(selector);
+                let selector: &dyn PeerSelector = if peer.crypto == peer::Crypto::Required {
+                    &crypto_supported
+                } else {
+                    selector
+                };
+
+                entry.select_peers_for_client(peer, limit, selector)
+            }
         }
     }
 
@@ -790,6 +1294,11 @@ impl Tracker {
     ///
     /// # Context: Tracker
     pub fn upsert_peer_and_get_stats(&self, info_hash: &InfoHash, peer: &peer::Peer) -> SwarmMetadata {
+        // The previous state of the swarm is only needed to publish the events.
+        let publish_events = self.has_event_subscribers();
+        let entry_before = if publish_events { self.torrents.get(info_hash) } else { None };
+        let peer_before = entry_before.as_ref().and_then(|entry| entry.get_peer(&peer.peer_id));
+
         let swarm_metadata_before = match self.torrents.get_swarm_metadata(info_hash) {
             Some(swarm_metadata) => swarm_metadata,
             None => SwarmMetadata::zeroed(),
@@ -804,6 +1313,28 @@ impl Tracker {
 
         if swarm_metadata_before != swarm_metadata_after {
             self.persist_stats(info_hash, &swarm_metadata_after);
+
+            if let Some(scrape_cache) = &self.scrape_cache {
+                scrape_cache.invalidate(info_hash);
+            }
+        }
+
+        if publish_events {
+            if entry_before.is_none() && self.torrents.get(info_hash).is_some() {
+                self.publish(events::Event::TorrentCreated { info_hash: *info_hash });
+            }
+
+            if peer.event == AnnounceEvent::Stopped {
+                if peer_before.is_some() {
+                    self.publish(events::Event::peer_left(info_hash, peer));
+                }
+            } else if peer_before.is_none() {
+                self.publish(events::Event::peer_joined(info_hash, peer));
+            }
+
+            if swarm_metadata_after.downloaded > swarm_metadata_before.downloaded {
+                self.publish(events::Event::peer_completed(info_hash, peer));
+            }
         }
 
         swarm_metadata_after
@@ -813,7 +1344,7 @@ impl Tracker {
     ///
     /// # Context: Tracker
     fn persist_stats(&self, info_hash: &InfoHash, swarm_metadata: &SwarmMetadata) {
-        if self.config.tracker_policy.persistent_torrent_completed_stat {
+        if self.config().tracker_policy.persistent_torrent_completed_stat {
             let completed = swarm_metadata.downloaded;
             let info_hash = *info_hash;
 
@@ -836,14 +1367,77 @@ impl Tracker {
     ///
     /// # Context: Tracker
     pub fn cleanup_torrents(&self) {
-        let current_cutoff = CurrentClock::now_sub(&Duration::from_secs(u64::from(self.config.tracker_policy.max_peer_timeout)))
-            .unwrap_or_default();
+        let current_cutoff =
+            CurrentClock::now_sub(&Duration::from_secs(u64::from(self.config().tracker_policy.max_peer_timeout)))
+                .unwrap_or_default();
+
+        let publish_events = self.has_event_subscribers();
+
+        // Peers removed because they have not announced for a long time.
+        let inactive_peers = if publish_events {
+            self.torrents
+                .get_paginated(None)
+                .into_iter()
+                .flat_map(|(info_hash, entry)| {
+                    entry
+                        .get_peers(None)
+                        .into_iter()
+                        .filter(|peer| peer.updated < current_cutoff)
+                        .map(move |peer| (info_hash, peer))
+                })
+                .collect()
+        } else {
+            vec![]
+        };
 
         self.torrents.remove_inactive_peers(current_cutoff);
+        self.accounting.remove_inactive_sessions(current_cutoff);
+
+        for (info_hash, peer) in inactive_peers {
+            self.publish(events::Event::peer_left(&info_hash, &peer));
+        }
+
+        let tracker_policy = self.config().tracker_policy.clone();
+
+        if tracker_policy.remove_peerless_torrents {
+            // Torrents that can be removed by the cleanup.
+            let removable_torrents: Vec<InfoHash> = if publish_events {
+                self.torrents
+                    .get_paginated(None)
+                    .into_iter()
+                    .filter(|(_, entry)| !entry.meets_retaining_policy(&tracker_policy))
+                    .map(|(info_hash, _)| info_hash)
+                    .collect()
+            } else {
+                vec![]
+            };
+
+            self.torrents.remove_peerless_torrents(&tracker_policy);
 
-        if self.config.tracker_policy.remove_peerless_torrents {
-            self.torrents.remove_peerless_torrents(&self.config.tracker_policy);
+            for info_hash in removable_torrents {
+                if self.torrents.get(&info_hash).is_none() {
+                    self.publish(events::Event::TorrentRemoved { info_hash });
+                }
+            }
         }
+
+        self.clear_scrape_cache();
+    }
+
+    /// It subscribes to the real-time tracker [`events`].
+    ///
+    /// # Context: Events
+    pub fn subscribe_to_events(&self) -> broadcast::Receiver<events::Event> {
+        self.events.subscribe()
+    }
+
+    fn has_event_subscribers(&self) -> bool {
+        self.events.receiver_count() > 0
+    }
+
+    fn publish(&self, event: events::Event) {
+        // It only fails when there are no subscribers.
+        drop(self.events.send(event));
     }
 
     /// It authenticates the peer `key` against the `Tracker` authentication
@@ -969,6 +1563,7 @@ impl Tracker {
 
         self.database.add_key_to_keys(&auth_key)?;
         self.keys.write().await.insert(auth_key.key.clone(), auth_key.clone());
+        self.publish_key_added(&auth_key);
         Ok(auth_key)
     }
 
@@ -1018,9 +1613,17 @@ impl Tracker {
         // the specif error for each DB driver when a UNIQUE constrain fails.
         self.database.add_key_to_keys(&auth_key)?;
         self.keys.write().await.insert(auth_key.key.clone(), auth_key.clone());
+        self.publish_key_added(&auth_key);
         Ok(auth_key)
     }
 
+    fn publish_key_added(&self, auth_key: &PeerKey) {
+        self.publish(events::Event::KeyAdded {
+            key: auth_key.key.clone(),
+            valid_until: auth_key.valid_until,
+        });
+    }
+
     /// It removes an authentication key.
     ///
     /// # Context: Authentication    
@@ -1030,7 +1633,77 @@ impl Tracker {
     /// Will return a `database::Error` if unable to remove the `key` to the database.
     pub async fn remove_auth_key(&self, key: &Key) -> Result<(), databases::error::Error> {
         self.database.remove_key_from_keys(key)?;
-        self.keys.write().await.remove(key);
+        if self.keys.write().await.remove(key).is_some() {
+            self.publish(events::Event::KeyRemoved { key: key.clone() });
+        }
+        Ok(())
+    }
+
+    /// It returns the authentication key, if the tracker has it.
+    ///
+    /// # Context: Authentication
+    pub async fn get_auth_key(&self, key: &Key) -> Option<auth::PeerKey> {
+        self.keys.read().await.get(key).cloned()
+    }
+
+    /// It returns all the authentication keys ordered by key.
+    ///
+    /// # Context: Authentication
+    pub async fn get_auth_keys(&self) -> Vec<auth::PeerKey> {
+        let mut keys: Vec<auth::PeerKey> = self.keys.read().await.values().cloned().collect();
+
+        keys.sort_by_key(|peer_key| peer_key.key.to_string());
+
+        keys
+    }
+
+    /// It adds the traffic of an `announce` to the totals of the key the peer
+    /// announced with. The traffic is only accounted in private mode.
+    ///
+    /// The difference is also added to the totals in the database. Database
+    /// errors are ignored, like when the torrent metrics are persisted.
+    ///
+    /// # Context: Accounting
+    pub fn record_key_traffic(&self, key: &Key, info_hash: &InfoHash, peer: &peer::Peer) {
+        if !self.is_private() {
+            return;
+        }
+
+        let traffic = self.accounting.record(key, info_hash, peer);
+
+        if !traffic.is_zero() {
+            drop(self.database.add_key_traffic(key, &traffic));
+        }
+    }
+
+    /// It returns the traffic of the key, if any traffic has been accounted
+    /// for it.
+    ///
+    /// # Context: Accounting
+    #[must_use]
+    pub fn get_key_traffic(&self, key: &Key) -> Option<Traffic> {
+        self.accounting.get(key)
+    }
+
+    /// It returns the traffic of all the keys ordered by key.
+    ///
+    /// # Context: Accounting
+    #[must_use]
+    pub fn get_keys_traffic(&self) -> Vec<(Key, Traffic)> {
+        self.accounting.get_all()
+    }
+
+    /// It loads the traffic of the keys from the database.
+    ///
+    /// # Context: Accounting
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to load the keys traffic from
+    /// the database.
+    pub fn load_keys_traffic_from_database(&self) -> Result<(), databases::error::Error> {
+        self.accounting.load(self.database.load_keys_traffic()?);
+
         Ok(())
     }
 
@@ -1042,12 +1715,14 @@ impl Tracker {
     ///
     /// Will return a `key::Error` if unable to get any `auth_key`.
     async fn verify_auth_key(&self, key: &Key) -> Result<(), auth::Error> {
+        let private_mode = self.config().private_mode;
+
         match self.keys.read().await.get(key) {
             None => Err(auth::Error::UnableToReadKey {
                 location: Location::caller(),
                 key: Box::new(key.clone()),
             }),
-            Some(key) => match self.config.private_mode {
+            Some(key) => match private_mode {
                 Some(private_mode) => {
                     if private_mode.check_keys_expiration {
                         return auth::verify_key_expiration(key);
@@ -1107,6 +1782,25 @@ impl Tracker {
         })
     }
 
+    /// It checks if the client can get the full scrape. Any client can get it
+    /// when it's public, otherwise the client has to send the admin key.
+    ///
+    /// # Context: Authorization
+    ///
+    /// # Errors
+    ///
+    /// Will return a `FullScrapeNotAllowed` error if the full scrape is
+    /// disabled, or if it's not public and the admin key is missing or wrong.
+    pub fn authorize_full_scrape(&self, admin_key: Option<&str>) -> Result<(), Error> {
+        match self.get_full_scrape_config() {
+            Some(full_scrape) if full_scrape.public => Ok(()),
+            Some(full_scrape) if full_scrape.admin_key.is_some() && full_scrape.admin_key.as_deref() == admin_key => Ok(()),
+            _ => Err(Error::FullScrapeNotAllowed {
+                location: Location::caller(),
+            }),
+        }
+    }
+
     /// It adds a torrent to the whitelist.
     /// Adding torrents is not relevant to public trackers.
     ///
@@ -1135,10 +1829,51 @@ impl Tracker {
     }
 
     pub async fn add_torrent_to_memory_whitelist(&self, info_hash: &InfoHash) -> bool {
-        self.whitelist.write().await.insert(*info_hash)
+        let added = self.whitelist.write().await.insert(*info_hash);
+
+        if added {
+            self.publish(events::Event::TorrentWhitelisted { info_hash: *info_hash });
+        }
+
+        added
     }
 
-    /// It removes a torrent from the whitelist.
+    /// It adds many torrents to the whitelist. Torrents that are already
+    /// whitelisted are ignored.
+    ///
+    /// The torrents are added to the database in a single transaction, so
+    /// either all of them are whitelisted or none is.
+    ///
+    /// # Context: Whitelist
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to add the `info_hashes`
+    /// into the whitelist database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the database task panics.
+    pub async fn add_torrents_to_whitelist(&self, info_hashes: &[InfoHash]) -> Result<(), databases::error::Error> {
+        let database = self.database.clone();
+        let batch = info_hashes.to_vec();
+
+        tokio::task::spawn_blocking(move || database.add_info_hashes_to_whitelist(&batch))
+            .await
+            .expect("the whitelist database task should not panic")?;
+
+        let mut whitelist = self.whitelist.write().await;
+
+        for info_hash in info_hashes {
+            if whitelist.insert(*info_hash) {
+                self.publish(events::Event::TorrentWhitelisted { info_hash: *info_hash });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// It removes a torrent from the whitelist.
     /// Removing torrents is not relevant to public trackers.
     ///
     /// # Context: Whitelist
@@ -1152,6 +1887,41 @@ impl Tracker {
         Ok(())
     }
 
+    /// It removes many torrents from the whitelist. Torrents that are not
+    /// whitelisted are ignored.
+    ///
+    /// The torrents are removed from the database in a single transaction, so
+    /// either all of them are removed or none is.
+    ///
+    /// # Context: Whitelist
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to remove the `info_hashes`
+    /// from the whitelist database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the database task panics.
+    pub async fn remove_torrents_from_whitelist(&self, info_hashes: &[InfoHash]) -> Result<(), databases::error::Error> {
+        let database = self.database.clone();
+        let batch = info_hashes.to_vec();
+
+        tokio::task::spawn_blocking(move || database.remove_info_hashes_from_whitelist(&batch))
+            .await
+            .expect("the whitelist database task should not panic")?;
+
+        let mut whitelist = self.whitelist.write().await;
+
+        for info_hash in info_hashes {
+            if whitelist.remove(info_hash) {
+                self.publish(events::Event::TorrentRemovedFromWhitelist { info_hash: *info_hash });
+            }
+        }
+
+        Ok(())
+    }
+
     /// It removes a torrent from the whitelist in the database.
     ///
     /// # Context: Whitelist
@@ -1175,7 +1945,13 @@ impl Tracker {
     ///
     /// # Context: Whitelist
     pub async fn remove_torrent_from_memory_whitelist(&self, info_hash: &InfoHash) -> bool {
-        self.whitelist.write().await.remove(info_hash)
+        let removed = self.whitelist.write().await.remove(info_hash);
+
+        if removed {
+            self.publish(events::Event::TorrentRemovedFromWhitelist { info_hash: *info_hash });
+        }
+
+        removed
     }
 
     /// It checks if a torrent is whitelisted.
@@ -1185,6 +1961,17 @@ impl Tracker {
         self.whitelist.read().await.contains(info_hash)
     }
 
+    /// It returns all the whitelisted torrents ordered by infohash.
+    ///
+    /// # Context: Whitelist
+    pub async fn get_whitelist(&self) -> Vec<InfoHash> {
+        let mut whitelist: Vec<InfoHash> = self.whitelist.read().await.iter().copied().collect();
+
+        whitelist.sort();
+
+        whitelist
+    }
+
     /// It loads the whitelist from the database.
     ///
     /// # Context: Whitelist
@@ -1205,6 +1992,100 @@ impl Tracker {
         Ok(())
     }
 
+    /// It checks if the client IP address is allowed to make `announce` and
+    /// `scrape` requests.
+    ///
+    /// # Context: Authorization
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the IP address is not allowed by the
+    /// [`ip_access_list`].
+    pub async fn authorize_client_ip(&self, ip: &IpAddr) -> Result<(), Error> {
+        if self.ip_access_list.read().await.is_allowed(ip) {
+            return Ok(());
+        }
+
+        Err(Error::IpAddressNotAllowed {
+            ip: *ip,
+            location: Location::caller(),
+        })
+    }
+
+    /// It adds a rule to the IP access list. If there is already a rule for
+    /// the same network, its policy is replaced.
+    ///
+    /// # Context: IP access list
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to save the rule into the database.
+    pub async fn add_ip_access_rule(&self, rule: &ip_access_list::Rule) -> Result<(), databases::error::Error> {
+        self.database.save_ip_access_rule(rule)?;
+        self.ip_access_list.write().await.add(rule);
+        Ok(())
+    }
+
+    /// It removes the rule for the network from the IP access list. It returns
+    /// `false` if there was no rule for the network.
+    ///
+    /// # Context: IP access list
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to remove the rule from the database.
+    pub async fn remove_ip_access_rule(&self, network: &IpNet) -> Result<bool, databases::error::Error> {
+        self.database.remove_ip_access_rule(network)?;
+        Ok(self.ip_access_list.write().await.remove(network))
+    }
+
+    /// It returns all the rules in the IP access list.
+    ///
+    /// # Context: IP access list
+    pub async fn get_ip_access_rules(&self) -> Vec<ip_access_list::Rule> {
+        self.ip_access_list.read().await.rules()
+    }
+
+    /// It loads the IP access list from the database.
+    ///
+    /// # Context: IP access list
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to load the rules from the database.
+    pub async fn load_ip_access_list_from_database(&self) -> Result<(), databases::error::Error> {
+        let rules = self.database.load_ip_access_rules()?;
+        let mut ip_access_list = self.ip_access_list.write().await;
+
+        ip_access_list.clear();
+
+        for rule in &rules {
+            ip_access_list.add(rule);
+        }
+
+        Ok(())
+    }
+
+    /// It checks if the client has not exceeded its budget for the type of
+    /// request. Every call consumes one request from the budget.
+    ///
+    /// # Context: Rate limiting
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the [`rate_limiter`] is enabled and the client
+    /// has exceeded its budget.
+    pub fn check_rate_limit(&self, ip: &IpAddr, request: rate_limiter::Request) -> Result<(), Error> {
+        match &self.rate_limiter {
+            Some(rate_limiter) if !rate_limiter.check(ip, request) => Err(Error::RateLimitExceeded {
+                ip: *ip,
+                request,
+                location: Location::caller(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
     /// It return the `Tracker` [`statistics::Metrics`].
     ///
     /// # Context: Statistics
@@ -1212,6 +2093,121 @@ impl Tracker {
         self.stats_repository.get_stats().await
     }
 
+    /// It saves the [`statistics::Metrics`] into the database, if the
+    /// statistics persistence is enabled.
+    ///
+    /// # Context: Statistics
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to save the metrics into the
+    /// database.
+    pub async fn save_statistics(&self) -> Result<(), databases::error::Error> {
+        if self.config().statistics_persistence.is_none() {
+            return Ok(());
+        }
+
+        let counters = self.stats_repository.get_stats().await.counters();
+
+        self.database.save_statistics(&counters)
+    }
+
+    /// It sets all the [`statistics::Metrics`] counters to zero. When the
+    /// statistics persistence is enabled, the counters are also reset in the
+    /// database.
+    ///
+    /// # Context: Statistics
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to save the metrics into the
+    /// database.
+    pub async fn reset_statistics(&self) -> Result<(), databases::error::Error> {
+        self.stats_repository.reset().await;
+
+        self.save_statistics().await
+    }
+
+    /// It adds a sample of the current tracker metrics to the metrics history,
+    /// if the history is enabled.
+    ///
+    /// # Context: Statistics
+    pub async fn sample_metrics(&self) {
+        let Some(metrics_history) = &self.metrics_history else {
+            return;
+        };
+
+        let metrics = self.get_stats().await.clone();
+
+        metrics_history.push(metrics_history::Sample::new(
+            CurrentClock::now().as_secs(),
+            &self.get_torrents_metrics(),
+            metrics,
+        ));
+    }
+
+    /// It returns the samples in the metrics history taken between `from` and
+    /// `to`, at least `step` seconds apart. See [`MetricsHistory::get`].
+    ///
+    /// It returns `None` if the metrics history is disabled.
+    ///
+    /// # Context: Statistics
+    pub fn get_metrics_history(
+        &self,
+        from: Option<u64>,
+        to: Option<u64>,
+        step: Option<u64>,
+    ) -> Option<Vec<metrics_history::Sample>> {
+        self.metrics_history
+            .as_ref()
+            .map(|metrics_history| metrics_history.get(from, to, step))
+    }
+
+    /// It writes the metrics history into the history file, if the history
+    /// is enabled and the file `path` is set.
+    ///
+    /// # Context: Statistics
+    ///
+    /// # Errors
+    ///
+    /// Will return a `metrics_history::Error` if unable to write the history
+    /// file.
+    pub fn save_metrics_history(&self) -> Result<(), metrics_history::Error> {
+        let (Some(metrics_history), Some(path)) = (&self.metrics_history, self.metrics_history_path()) else {
+            return Ok(());
+        };
+
+        metrics_history::save(&metrics_history.get_all(), &path)
+    }
+
+    /// It restores the metrics history from the history file, if the history
+    /// is enabled, the file `path` is set and the file exists.
+    ///
+    /// # Context: Statistics
+    ///
+    /// # Errors
+    ///
+    /// Will return a `metrics_history::Error` if unable to read the history
+    /// file.
+    pub fn load_metrics_history(&self) -> Result<(), metrics_history::Error> {
+        let (Some(metrics_history), Some(path)) = (&self.metrics_history, self.metrics_history_path()) else {
+            return Ok(());
+        };
+
+        if let Some(samples) = metrics_history::load(&path)? {
+            metrics_history.restore(samples);
+        }
+
+        Ok(())
+    }
+
+    fn metrics_history_path(&self) -> Option<Utf8PathBuf> {
+        self.config()
+            .metrics_history
+            .as_ref()
+            .and_then(|metrics_history| metrics_history.path.clone())
+    }
+
     /// It allows to send a statistic events which eventually will be used to update [`statistics::Metrics`].
     ///
     /// # Context: Statistics
@@ -1243,6 +2239,15 @@ fn assign_ip_address_to_peer(remote_client_ip: &IpAddr, tracker_external_ip: Opt
     }
 }
 
+/// Loopback and private IPv4 addresses, and loopback and unique local IPv6
+/// addresses (`fc00::/7`).
+fn is_loopback_or_private(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ipv4) => ipv4.is_loopback() || ipv4.is_private(),
+        IpAddr::V6(ipv6) => ipv6.is_loopback() || (ipv6.segments()[0] & 0xfe00) == 0xfc00,
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -1258,7 +2263,7 @@ mod tests {
         use torrust_tracker_primitives::DurationSinceUnixEpoch;
         use torrust_tracker_test_helpers::configuration;
 
-        use crate::core::peer::Peer;
+        use crate::core::peer::{self, Peer};
         use crate::core::services::tracker_factory;
         use crate::core::{TorrentsMetrics, Tracker};
         use crate::shared::bit_torrent::info_hash::fixture::gen_seeded_infohash;
@@ -1300,6 +2305,9 @@ mod tests {
             Peer {
                 peer_id: PeerId(*b"-qB00000000000000001"),
                 peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8081),
+                alt_peer_addr: None,
+                key: None,
+                crypto: peer::Crypto::NotSupported,
                 updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
                 uploaded: NumberOfBytes::new(0),
                 downloaded: NumberOfBytes::new(0),
@@ -1313,6 +2321,9 @@ mod tests {
             Peer {
                 peer_id: PeerId(*b"-qB00000000000000002"),
                 peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2)), 8082),
+                alt_peer_addr: None,
+                key: None,
+                crypto: peer::Crypto::NotSupported,
                 updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
                 uploaded: NumberOfBytes::new(0),
                 downloaded: NumberOfBytes::new(0),
@@ -1344,6 +2355,9 @@ mod tests {
             Peer {
                 peer_id: PeerId(*b"-qB00000000000000000"),
                 peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080),
+                alt_peer_addr: None,
+                key: None,
+                crypto: peer::Crypto::NotSupported,
                 updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
                 uploaded: NumberOfBytes::new(0),
                 downloaded: NumberOfBytes::new(0),
@@ -1357,6 +2371,9 @@ mod tests {
             Peer {
                 peer_id: PeerId(*b"-qB00000000000000000"),
                 peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080),
+                alt_peer_addr: None,
+                key: None,
+                crypto: peer::Crypto::NotSupported,
                 updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
                 uploaded: NumberOfBytes::new(0),
                 downloaded: NumberOfBytes::new(0),
@@ -1424,6 +2441,9 @@ mod tests {
                 let peer = Peer {
                     peer_id: numeric_peer_id(idx),
                     peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, idx.try_into().unwrap())), 8080),
+                    alt_peer_addr: None,
+                    key: None,
+                    crypto: peer::Crypto::NotSupported,
                     updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
                     uploaded: NumberOfBytes::new(0),
                     downloaded: NumberOfBytes::new(0),
@@ -1468,6 +2488,9 @@ mod tests {
                 let peer = Peer {
                     peer_id: numeric_peer_id(idx),
                     peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, idx.try_into().unwrap())), 8080),
+                    alt_peer_addr: None,
+                    key: None,
+                    crypto: peer::Crypto::NotSupported,
                     updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
                     uploaded: NumberOfBytes::new(0),
                     downloaded: NumberOfBytes::new(0),
@@ -1643,7 +2666,9 @@ mod tests {
 
                     let mut peer = sample_peer();
 
-                    let announce_data = tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+                    let announce_data = tracker
+                        .announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All)
+                        .unwrap();
 
                     assert_eq!(announce_data.peers, vec![]);
                 }
@@ -1653,15 +2678,19 @@ mod tests {
                     let tracker = public_tracker();
 
                     let mut previously_announced_peer = sample_peer_1();
-                    tracker.announce(
-                        &sample_info_hash(),
-                        &mut previously_announced_peer,
-                        &peer_ip(),
-                        &PeersWanted::All,
-                    );
+                    tracker
+                        .announce(
+                            &sample_info_hash(),
+                            &mut previously_announced_peer,
+                            &peer_ip(),
+                            &PeersWanted::All,
+                        )
+                        .unwrap();
 
                     let mut peer = sample_peer_2();
-                    let announce_data = tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+                    let announce_data = tracker
+                        .announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All)
+                        .unwrap();
 
                     assert_eq!(announce_data.peers, vec![Arc::new(previously_announced_peer)]);
                 }
@@ -1679,7 +2708,9 @@ mod tests {
 
                         let mut peer = seeder();
 
-                        let announce_data = tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+                        let announce_data = tracker
+                            .announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All)
+                            .unwrap();
 
                         assert_eq!(announce_data.stats.complete, 1);
                     }
@@ -1690,7 +2721,9 @@ mod tests {
 
                         let mut peer = leecher();
 
-                        let announce_data = tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+                        let announce_data = tracker
+                            .announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All)
+                            .unwrap();
 
                         assert_eq!(announce_data.stats.incomplete, 1);
                     }
@@ -1701,126 +2734,1136 @@ mod tests {
 
                         // We have to announce with "started" event because peer does not count if peer was not previously known
                         let mut started_peer = started_peer();
-                        tracker.announce(&sample_info_hash(), &mut started_peer, &peer_ip(), &PeersWanted::All);
+                        tracker
+                            .announce(&sample_info_hash(), &mut started_peer, &peer_ip(), &PeersWanted::All)
+                            .unwrap();
 
                         let mut completed_peer = completed_peer();
-                        let announce_data =
-                            tracker.announce(&sample_info_hash(), &mut completed_peer, &peer_ip(), &PeersWanted::All);
+                        let announce_data = tracker
+                            .announce(&sample_info_hash(), &mut completed_peer, &peer_ip(), &PeersWanted::All)
+                            .unwrap();
 
                         assert_eq!(announce_data.stats.downloaded, 1);
                     }
                 }
-            }
 
-            mod handling_a_scrape_request {
+                mod handling_a_dual_stack_peer {
 
-                use std::net::{IpAddr, Ipv4Addr};
+                    use std::net::{IpAddr, SocketAddr};
 
-                use torrust_tracker_primitives::info_hash::InfoHash;
+                    use crate::core::tests::the_tracker::{public_tracker, sample_info_hash, sample_peer};
+                    use crate::core::PeersWanted;
 
-                use crate::core::tests::the_tracker::{complete_peer, incomplete_peer, public_tracker};
-                use crate::core::{PeersWanted, ScrapeData, SwarmMetadata};
+                    fn ipv4() -> IpAddr {
+                        "126.0.0.1".parse().unwrap()
+                    }
 
-                #[tokio::test]
-                async fn it_should_return_a_zeroed_swarm_metadata_for_the_requested_file_if_the_tracker_does_not_have_that_torrent(
-                ) {
-                    let tracker = public_tracker();
+                    fn ipv6() -> IpAddr {
+                        "2001:db8::1".parse().unwrap()
+                    }
 
-                    let info_hashes = vec!["3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap()];
+                    #[tokio::test]
+                    async fn it_should_store_the_peer_address_in_the_other_ip_family() {
+                        let tracker = public_tracker();
 
-                    let scrape_data = tracker.scrape(&info_hashes).await;
+                        let mut peer = sample_peer();
+                        peer.change_alt_addr(Some(SocketAddr::new(ipv6(), 8080)));
 
-                    let mut expected_scrape_data = ScrapeData::empty();
+                        tracker
+                            .announce(&sample_info_hash(), &mut peer, &ipv4(), &PeersWanted::All)
+                            .unwrap();
 
-                    expected_scrape_data.add_file_with_zeroed_metadata(&info_hashes[0]);
+                        let peers = tracker.get_torrent_peers(&sample_info_hash());
 
-                    assert_eq!(scrape_data, expected_scrape_data);
-                }
+                        assert_eq!(peers[0].peer_addr, SocketAddr::new(ipv4(), 8080));
+                        assert_eq!(peers[0].alt_peer_addr, Some(SocketAddr::new(ipv6(), 8080)));
+                    }
 
-                #[tokio::test]
-                async fn it_should_return_the_swarm_metadata_for_the_requested_file_if_the_tracker_has_that_torrent() {
-                    let tracker = public_tracker();
+                    #[tokio::test]
+                    async fn it_should_keep_the_previous_address_when_the_peer_announces_using_the_other_ip_family() {
+                        let tracker = public_tracker();
 
-                    let info_hash = "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap();
+                        let mut peer = sample_peer();
+                        tracker
+                            .announce(&sample_info_hash(), &mut peer, &ipv4(), &PeersWanted::All)
+                            .unwrap();
 
-                    // Announce a "complete" peer for the torrent
-                    let mut complete_peer = complete_peer();
-                    tracker.announce(
-                        &info_hash,
-                        &mut complete_peer,
-                        &IpAddr::V4(Ipv4Addr::new(126, 0, 0, 10)),
-                        &PeersWanted::All,
-                    );
+                        let mut peer = sample_peer();
+                        tracker
+                            .announce(&sample_info_hash(), &mut peer, &ipv6(), &PeersWanted::All)
+                            .unwrap();
 
-                    // Announce an "incomplete" peer for the torrent
-                    let mut incomplete_peer = incomplete_peer();
-                    tracker.announce(
-                        &info_hash,
-                        &mut incomplete_peer,
-                        &IpAddr::V4(Ipv4Addr::new(126, 0, 0, 11)),
-                        &PeersWanted::All,
-                    );
+                        let peers = tracker.get_torrent_peers(&sample_info_hash());
 
-                    // Scrape
-                    let scrape_data = tracker.scrape(&vec![info_hash]).await;
+                        assert_eq!(peers[0].peer_addr, SocketAddr::new(ipv6(), 8080));
+                        assert_eq!(peers[0].alt_peer_addr, Some(SocketAddr::new(ipv4(), 8080)));
+                    }
 
-                    // The expected swarm metadata for the file
-                    let mut expected_scrape_data = ScrapeData::empty();
-                    expected_scrape_data.add_file(
-                        &info_hash,
-                        SwarmMetadata {
-                            complete: 0, // the "complete" peer does not count because it was not previously known
-                            downloaded: 0,
-                            incomplete: 1, // the "incomplete" peer we have just announced
-                        },
-                    );
+                    #[tokio::test]
+                    async fn it_should_keep_the_previous_address_in_the_other_ip_family_when_the_announce_does_not_include_it() {
+                        let tracker = public_tracker();
 
-                    assert_eq!(scrape_data, expected_scrape_data);
+                        let mut peer = sample_peer();
+                        peer.change_alt_addr(Some(SocketAddr::new(ipv6(), 8080)));
+                        tracker
+                            .announce(&sample_info_hash(), &mut peer, &ipv4(), &PeersWanted::All)
+                            .unwrap();
+
+                        let mut peer = sample_peer();
+                        tracker
+                            .announce(&sample_info_hash(), &mut peer, &ipv4(), &PeersWanted::All)
+                            .unwrap();
+
+                        let peers = tracker.get_torrent_peers(&sample_info_hash());
+
+                        assert_eq!(peers[0].alt_peer_addr, Some(SocketAddr::new(ipv6(), 8080)));
+                    }
                 }
+            }
 
-                #[tokio::test]
-                async fn it_should_allow_scraping_for_multiple_torrents() {
-                    let tracker = public_tracker();
+            mod resolving_the_peer_ip {
+                use std::net::IpAddr;
 
-                    let info_hashes = vec![
-                        "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap(),
-                        "99c82bb73505a3c0b453f9fa0e881d6e5a32a0c1".parse::<InfoHash>().unwrap(),
-                    ];
+                use torrust_tracker_configuration::ClientSuppliedIp;
+                use torrust_tracker_test_helpers::configuration;
 
-                    let scrape_data = tracker.scrape(&info_hashes).await;
+                use crate::core::services::tracker_factory;
+                use crate::core::Tracker;
 
-                    let mut expected_scrape_data = ScrapeData::empty();
-                    expected_scrape_data.add_file_with_zeroed_metadata(&info_hashes[0]);
-                    expected_scrape_data.add_file_with_zeroed_metadata(&info_hashes[1]);
+                fn tracker_with_client_supplied_ip(client_supplied_ip: ClientSuppliedIp, trusted_networks: &[&str]) -> Tracker {
+                    let mut configuration = configuration::ephemeral_public();
+                    configuration.core.net.client_supplied_ip = client_supplied_ip;
+                    configuration.core.net.trusted_networks =
+                        trusted_networks.iter().map(|network| network.parse().unwrap()).collect();
+                    tracker_factory(&configuration)
+                }
 
-                    assert_eq!(scrape_data, expected_scrape_data);
+                fn ip(ip: &str) -> IpAddr {
+                    ip.parse().unwrap()
                 }
-            }
-        }
 
-        mod configured_as_whitelisted {
+                #[tokio::test]
+                async fn it_should_use_the_source_ip_when_the_client_does_not_send_an_ip() {
+                    let tracker = tracker_with_client_supplied_ip(ClientSuppliedIp::PrivateNetworks, &[]);
 
-            mod handling_authorization {
-                use crate::core::tests::the_tracker::{sample_info_hash, whitelisted_tracker};
+                    assert_eq!(tracker.resolve_peer_ip(&ip("10.0.0.1"), None), ip("10.0.0.1"));
+                }
 
                 #[tokio::test]
-                async fn it_should_authorize_the_announce_and_scrape_actions_on_whitelisted_torrents() {
-                    let tracker = whitelisted_tracker();
+                async fn it_should_ignore_the_ip_sent_by_the_client_by_default() {
+                    let tracker = tracker_with_client_supplied_ip(ClientSuppliedIp::default(), &[]);
 
-                    let info_hash = sample_info_hash();
+                    assert_eq!(
+                        tracker.resolve_peer_ip(&ip("127.0.0.1"), Some(ip("126.0.0.1"))),
+                        ip("127.0.0.1")
+                    );
+                }
 
-                    let result = tracker.add_torrent_to_whitelist(&info_hash).await;
-                    assert!(result.is_ok());
+                #[tokio::test]
+                async fn it_should_use_the_ip_sent_by_a_client_in_a_trusted_network() {
+                    let tracker = tracker_with_client_supplied_ip(ClientSuppliedIp::TrustedNetworks, &["126.0.0.0/24"]);
 
-                    let result = tracker.authorize(&info_hash).await;
-                    assert!(result.is_ok());
+                    assert_eq!(
+                        tracker.resolve_peer_ip(&ip("126.0.0.2"), Some(ip("126.0.1.1"))),
+                        ip("126.0.1.1")
+                    );
+                    assert_eq!(
+                        tracker.resolve_peer_ip(&ip("126.0.1.2"), Some(ip("126.0.1.1"))),
+                        ip("126.0.1.2")
+                    );
                 }
 
                 #[tokio::test]
-                async fn it_should_not_authorize_the_announce_and_scrape_actions_on_not_whitelisted_torrents() {
-                    let tracker = whitelisted_tracker();
+                async fn it_should_ignore_the_address_in_the_other_ip_family_sent_by_the_client_by_default() {
+                    let tracker = tracker_with_client_supplied_ip(ClientSuppliedIp::default(), &[]);
 
-                    let info_hash = sample_info_hash();
+                    assert_eq!(
+                        tracker.resolve_alt_peer_addr(&ip("127.0.0.1"), Some("[2001:db8::1]:8080".parse().unwrap())),
+                        None
+                    );
+                }
+
+                #[tokio::test]
+                async fn it_should_use_the_address_in_the_other_ip_family_sent_by_a_client_in_a_trusted_network() {
+                    let tracker = tracker_with_client_supplied_ip(ClientSuppliedIp::TrustedNetworks, &["126.0.0.0/24"]);
+
+                    let alt_peer_addr = "[2001:db8::1]:8080".parse().unwrap();
+
+                    assert_eq!(
+                        tracker.resolve_alt_peer_addr(&ip("126.0.0.2"), Some(alt_peer_addr)),
+                        Some(alt_peer_addr)
+                    );
+                    assert_eq!(tracker.resolve_alt_peer_addr(&ip("126.0.1.2"), Some(alt_peer_addr)), None);
+                }
+
+                #[tokio::test]
+                async fn it_should_check_ipv4_mapped_ipv6_sources_as_ipv4_addresses() {
+                    let tracker = tracker_with_client_supplied_ip(ClientSuppliedIp::TrustedNetworks, &["126.0.0.0/24"]);
+
+                    assert_eq!(
+                        tracker.resolve_peer_ip(&ip("::ffff:126.0.0.2"), Some(ip("126.0.1.1"))),
+                        ip("126.0.1.1")
+                    );
+                }
+
+                #[tokio::test]
+                async fn it_should_use_the_ip_sent_by_a_client_in_a_loopback_or_private_network() {
+                    let tracker = tracker_with_client_supplied_ip(ClientSuppliedIp::PrivateNetworks, &[]);
+
+                    assert_eq!(
+                        tracker.resolve_peer_ip(&ip("127.0.0.1"), Some(ip("126.0.1.1"))),
+                        ip("126.0.1.1")
+                    );
+                    assert_eq!(
+                        tracker.resolve_peer_ip(&ip("192.168.1.1"), Some(ip("126.0.1.1"))),
+                        ip("126.0.1.1")
+                    );
+                    assert_eq!(
+                        tracker.resolve_peer_ip(&ip("fd00::1"), Some(ip("126.0.1.1"))),
+                        ip("126.0.1.1")
+                    );
+                    assert_eq!(
+                        tracker.resolve_peer_ip(&ip("126.0.0.2"), Some(ip("126.0.1.1"))),
+                        ip("126.0.0.2")
+                    );
+                }
+            }
+
+            mod selecting_the_peers_for_the_announce_response {
+                use std::net::SocketAddr;
+                use std::sync::Arc;
+
+                use aquatic_udp_protocol::PeerId;
+                use torrust_tracker_configuration::PeerSelection;
+                use torrust_tracker_primitives::peer::fixture::PeerBuilder;
+                use torrust_tracker_primitives::peer::Crypto;
+                use torrust_tracker_test_helpers::configuration;
+
+                use crate::core::services::tracker_factory;
+                use crate::core::tests::the_tracker::sample_info_hash;
+                use crate::core::PeersWanted;
+
+                #[tokio::test]
+                async fn it_should_use_the_configured_peer_selection_strategy() {
+                    let mut configuration = configuration::ephemeral_public();
+                    configuration.core.peer_selection = PeerSelection::SameIpFamilyFirst;
+                    let tracker = tracker_factory(&configuration);
+
+                    let ipv4_peer = PeerBuilder::leecher()
+                        .with_peer_id(&PeerId([1; 20]))
+                        .with_peer_addr(&"126.0.0.1:8080".parse().unwrap())
+                        .build();
+                    let ipv6_peer = PeerBuilder::leecher()
+                        .with_peer_id(&PeerId([2; 20]))
+                        .with_peer_addr(&"[2001:db8::2]:8080".parse().unwrap())
+                        .build();
+                    tracker.upsert_peer_and_get_stats(&sample_info_hash(), &ipv4_peer);
+                    tracker.upsert_peer_and_get_stats(&sample_info_hash(), &ipv6_peer);
+
+                    let client_addr: SocketAddr = "[2001:db8::3]:8080".parse().unwrap();
+                    let mut client = PeerBuilder::leecher()
+                        .with_peer_id(&PeerId([3; 20]))
+                        .with_peer_addr(&client_addr)
+                        .build();
+
+                    let announce_data = tracker
+                        .announce(&sample_info_hash(), &mut client, &client_addr.ip(), &PeersWanted::All)
+                        .unwrap();
+
+                    assert_eq!(announce_data.peers[0].peer_id, ipv6_peer.peer_id);
+                }
+
+                #[tokio::test]
+                async fn it_should_omit_the_seeders_in_the_responses_to_seeders_when_it_is_enabled() {
+                    let mut configuration = configuration::ephemeral_public();
+                    configuration.core.omit_seeders_for_seeders = true;
+                    let tracker = tracker_factory(&configuration);
+
+                    let seeder = PeerBuilder::seeder()
+                        .with_peer_id(&PeerId([1; 20]))
+                        .with_peer_addr(&"126.0.0.1:8080".parse().unwrap())
+                        .build();
+                    let leecher = PeerBuilder::leecher()
+                        .with_peer_id(&PeerId([2; 20]))
+                        .with_peer_addr(&"126.0.0.2:8080".parse().unwrap())
+                        .build();
+                    tracker.upsert_peer_and_get_stats(&sample_info_hash(), &seeder);
+                    tracker.upsert_peer_and_get_stats(&sample_info_hash(), &leecher);
+
+                    let client_addr: SocketAddr = "126.0.0.3:8080".parse().unwrap();
+                    let mut client = PeerBuilder::seeder()
+                        .with_peer_id(&PeerId([3; 20]))
+                        .with_peer_addr(&client_addr)
+                        .build();
+
+                    let announce_data = tracker
+                        .announce(&sample_info_hash(), &mut client, &client_addr.ip(), &PeersWanted::All)
+                        .unwrap();
+
+                    assert_eq!(announce_data.peers, vec![Arc::new(leecher)]);
+                }
+
+                #[tokio::test]
+                async fn it_should_only_return_the_peers_that_support_encryption_to_a_peer_that_requires_it() {
+                    let tracker = tracker_factory(&configuration::ephemeral_public());
+
+                    let plaintext_peer = PeerBuilder::leecher()
+                        .with_peer_id(&PeerId([1; 20]))
+                        .with_peer_addr(&"126.0.0.1:8080".parse().unwrap())
+                        .build();
+                    let encrypted_peer = PeerBuilder::leecher()
+                        .with_peer_id(&PeerId([2; 20]))
+                        .with_peer_addr(&"126.0.0.2:8080".parse().unwrap())
+                        .with_crypto(Crypto::Supported)
+                        .build();
+                    tracker.upsert_peer_and_get_stats(&sample_info_hash(), &plaintext_peer);
+                    tracker.upsert_peer_and_get_stats(&sample_info_hash(), &encrypted_peer);
+
+                    let client_addr: SocketAddr = "126.0.0.3:8080".parse().unwrap();
+                    let mut client = PeerBuilder::leecher()
+                        .with_peer_id(&PeerId([3; 20]))
+                        .with_peer_addr(&client_addr)
+                        .with_crypto(Crypto::Required)
+                        .build();
+
+                    let announce_data = tracker
+                        .announce(&sample_info_hash(), &mut client, &client_addr.ip(), &PeersWanted::All)
+                        .unwrap();
+
+                    assert_eq!(announce_data.peers, vec![Arc::new(encrypted_peer)]);
+                }
+            }
+
+            mod handling_a_peer_identity_mismatch {
+                use std::net::IpAddr;
+
+                use aquatic_udp_protocol::{AnnounceEvent, PeerKey};
+                use torrust_tracker_configuration::OnPeerIdentityMismatch;
+                use torrust_tracker_test_helpers::configuration;
+
+                use crate::core::error::Error;
+                use crate::core::services::tracker_factory;
+                use crate::core::tests::the_tracker::{completed_peer, leecher, peer_ip, sample_info_hash};
+                use crate::core::{peer, PeersWanted, Tracker};
+
+                fn tracker_on_peer_identity_mismatch(on_peer_identity_mismatch: OnPeerIdentityMismatch) -> Tracker {
+                    let mut configuration = configuration::ephemeral_public();
+                    configuration.core.on_peer_identity_mismatch = on_peer_identity_mismatch;
+                    tracker_factory(&configuration)
+                }
+
+                fn another_peer_ip() -> IpAddr {
+                    "126.0.0.2".parse().unwrap()
+                }
+
+                fn with_key(mut peer: peer::Peer, key: i32) -> peer::Peer {
+                    peer.key = Some(PeerKey::new(key));
+                    peer
+                }
+
+                fn stopped(mut peer: peer::Peer) -> peer::Peer {
+                    peer.event = AnnounceEvent::Stopped;
+                    peer
+                }
+
+                #[tokio::test]
+                async fn it_should_not_check_the_identity_of_the_peer_by_default() {
+                    let tracker = tracker_on_peer_identity_mismatch(OnPeerIdentityMismatch::default());
+
+                    tracker
+                        .announce(
+                            &sample_info_hash(),
+                            &mut with_key(leecher(), 1),
+                            &peer_ip(),
+                            &PeersWanted::All,
+                        )
+                        .unwrap();
+
+                    tracker
+                        .announce(
+                            &sample_info_hash(),
+                            &mut stopped(with_key(leecher(), 2)),
+                            &another_peer_ip(),
+                            &PeersWanted::All,
+                        )
+                        .unwrap();
+
+                    assert!(tracker.get_torrent_peers(&sample_info_hash()).is_empty());
+                }
+
+                #[tokio::test]
+                async fn it_should_accept_an_announce_with_the_same_key_from_another_ip_address() {
+                    let tracker = tracker_on_peer_identity_mismatch(OnPeerIdentityMismatch::Reject);
+
+                    tracker
+                        .announce(
+                            &sample_info_hash(),
+                            &mut with_key(leecher(), 1),
+                            &peer_ip(),
+                            &PeersWanted::All,
+                        )
+                        .unwrap();
+
+                    tracker
+                        .announce(
+                            &sample_info_hash(),
+                            &mut with_key(leecher(), 1),
+                            &another_peer_ip(),
+                            &PeersWanted::All,
+                        )
+                        .unwrap();
+
+                    assert_eq!(
+                        tracker.get_torrent_peers(&sample_info_hash())[0].peer_addr.ip(),
+                        another_peer_ip()
+                    );
+                }
+
+                #[tokio::test]
+                async fn it_should_reject_an_announce_with_a_different_key() {
+                    let tracker = tracker_on_peer_identity_mismatch(OnPeerIdentityMismatch::Reject);
+
+                    tracker
+                        .announce(
+                            &sample_info_hash(),
+                            &mut with_key(leecher(), 1),
+                            &peer_ip(),
+                            &PeersWanted::All,
+                        )
+                        .unwrap();
+
+                    let result = tracker.announce(
+                        &sample_info_hash(),
+                        &mut stopped(with_key(leecher(), 2)),
+                        &peer_ip(),
+                        &PeersWanted::All,
+                    );
+
+                    assert!(matches!(result, Err(Error::PeerIdentityMismatch { .. })));
+                    assert_eq!(tracker.get_torrent_peers(&sample_info_hash()).len(), 1);
+                }
+
+                #[tokio::test]
+                async fn it_should_reject_an_announce_from_another_ip_address_when_the_peer_did_not_send_a_key() {
+                    let tracker = tracker_on_peer_identity_mismatch(OnPeerIdentityMismatch::Reject);
+
+                    tracker
+                        .announce(&sample_info_hash(), &mut leecher(), &peer_ip(), &PeersWanted::All)
+                        .unwrap();
+
+                    let result = tracker.announce(&sample_info_hash(), &mut leecher(), &another_peer_ip(), &PeersWanted::All);
+
+                    assert!(matches!(result, Err(Error::PeerIdentityMismatch { .. })));
+                }
+
+                #[tokio::test]
+                async fn it_should_not_let_a_new_peer_with_the_same_id_remove_the_previous_peer() {
+                    let tracker = tracker_on_peer_identity_mismatch(OnPeerIdentityMismatch::NewPeer);
+
+                    tracker
+                        .announce(
+                            &sample_info_hash(),
+                            &mut with_key(leecher(), 1),
+                            &peer_ip(),
+                            &PeersWanted::All,
+                        )
+                        .unwrap();
+
+                    tracker
+                        .announce(
+                            &sample_info_hash(),
+                            &mut stopped(with_key(leecher(), 2)),
+                            &another_peer_ip(),
+                            &PeersWanted::All,
+                        )
+                        .unwrap();
+
+                    let peers = tracker.get_torrent_peers(&sample_info_hash());
+
+                    assert_eq!(peers.len(), 1);
+                    assert_eq!(peers[0].key, Some(PeerKey::new(1)));
+                }
+
+                #[tokio::test]
+                async fn it_should_not_let_a_new_peer_with_the_same_id_replace_the_previous_peer() {
+                    let tracker = tracker_on_peer_identity_mismatch(OnPeerIdentityMismatch::NewPeer);
+
+                    tracker
+                        .announce(
+                            &sample_info_hash(),
+                            &mut with_key(leecher(), 1),
+                            &peer_ip(),
+                            &PeersWanted::All,
+                        )
+                        .unwrap();
+
+                    tracker
+                        .announce(
+                            &sample_info_hash(),
+                            &mut with_key(leecher(), 2),
+                            &another_peer_ip(),
+                            &PeersWanted::All,
+                        )
+                        .unwrap();
+
+                    let peers = tracker.get_torrent_peers(&sample_info_hash());
+
+                    assert_eq!(peers.len(), 1);
+                    assert_eq!(peers[0].key, Some(PeerKey::new(1)));
+                    assert_eq!(peers[0].peer_addr.ip(), peer_ip());
+                }
+
+                #[tokio::test]
+                async fn it_should_not_count_the_download_of_a_new_peer_with_the_same_id() {
+                    let tracker = tracker_on_peer_identity_mismatch(OnPeerIdentityMismatch::NewPeer);
+
+                    tracker
+                        .announce(
+                            &sample_info_hash(),
+                            &mut with_key(leecher(), 1),
+                            &peer_ip(),
+                            &PeersWanted::All,
+                        )
+                        .unwrap();
+
+                    let announce_data = tracker
+                        .announce(
+                            &sample_info_hash(),
+                            &mut with_key(completed_peer(), 2),
+                            &peer_ip(),
+                            &PeersWanted::All,
+                        )
+                        .unwrap();
+
+                    assert_eq!(announce_data.stats.downloaded, 0);
+                }
+            }
+
+            mod handling_an_early_announce {
+                use std::time::Duration;
+
+                use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes};
+                use torrust_tracker_configuration::OnEarlyAnnounce;
+                use torrust_tracker_test_helpers::configuration;
+
+                use crate::core::error::Error;
+                use crate::core::services::tracker_factory;
+                use crate::core::tests::the_tracker::{leecher, peer_ip, sample_info_hash};
+                use crate::core::{peer, PeersWanted, Tracker};
+
+                fn tracker_on_early_announce(on_early_announce: OnEarlyAnnounce) -> Tracker {
+                    let mut configuration = configuration::ephemeral_public();
+                    configuration.core.announce_policy.interval_min = 60;
+                    configuration.core.on_early_announce = on_early_announce;
+                    tracker_factory(&configuration)
+                }
+
+                /// A regular announce (without event) from a peer that has
+                /// just become a seeder.
+                fn leecher_that_finished_downloading() -> peer::Peer {
+                    let mut peer = leecher();
+                    peer.left = NumberOfBytes::new(0);
+                    peer.event = AnnounceEvent::None;
+                    peer
+                }
+
+                #[tokio::test]
+                async fn it_should_not_consider_early_the_first_announce_of_a_peer() {
+                    let tracker = tracker_on_early_announce(OnEarlyAnnounce::Reject);
+
+                    let announce_data = tracker
+                        .announce(&sample_info_hash(), &mut leecher(), &peer_ip(), &PeersWanted::All)
+                        .unwrap();
+
+                    assert_eq!(announce_data.early_announce, None);
+                }
+
+                #[tokio::test]
+                async fn it_should_not_consider_early_an_announce_after_the_minimum_interval() {
+                    let tracker = tracker_on_early_announce(OnEarlyAnnounce::Reject);
+
+                    let mut peer = leecher();
+                    tracker
+                        .announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All)
+                        .unwrap();
+
+                    peer.updated += Duration::from_secs(60);
+
+                    let announce_data = tracker
+                        .announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All)
+                        .unwrap();
+
+                    assert_eq!(announce_data.early_announce, None);
+                }
+
+                #[tokio::test]
+                async fn it_should_handle_an_early_announce_like_any_other_announce_by_default() {
+                    let tracker = tracker_on_early_announce(OnEarlyAnnounce::default());
+
+                    let mut peer = leecher();
+                    tracker
+                        .announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All)
+                        .unwrap();
+
+                    let mut peer = leecher_that_finished_downloading();
+                    let announce_data = tracker
+                        .announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All)
+                        .unwrap();
+
+                    assert_eq!(announce_data.early_announce, Some(OnEarlyAnnounce::Accept));
+                    assert_eq!(announce_data.stats.complete, 1);
+                }
+
+                #[tokio::test]
+                async fn it_should_reject_an_early_announce() {
+                    let tracker = tracker_on_early_announce(OnEarlyAnnounce::Reject);
+
+                    let mut peer = leecher();
+                    tracker
+                        .announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All)
+                        .unwrap();
+
+                    let result = tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+
+                    assert!(matches!(result, Err(Error::AnnounceTooEarly { interval_min: 60, .. })));
+                }
+
+                #[tokio::test]
+                async fn it_should_not_update_the_peer_when_it_serves_a_cached_response_to_an_early_announce() {
+                    let tracker = tracker_on_early_announce(OnEarlyAnnounce::Cached);
+
+                    let mut peer = leecher();
+                    tracker
+                        .announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All)
+                        .unwrap();
+
+                    let mut peer = leecher_that_finished_downloading();
+                    let announce_data = tracker
+                        .announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All)
+                        .unwrap();
+
+                    assert_eq!(announce_data.early_announce, Some(OnEarlyAnnounce::Cached));
+                    assert_eq!(announce_data.stats.complete, 0);
+                    assert_eq!(announce_data.stats.incomplete, 1);
+                }
+
+                #[tokio::test]
+                async fn it_should_update_the_peer_when_it_skips_counting_an_early_announce() {
+                    let tracker = tracker_on_early_announce(OnEarlyAnnounce::SkipCounting);
+
+                    let mut peer = leecher();
+                    tracker
+                        .announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All)
+                        .unwrap();
+
+                    let mut peer = leecher_that_finished_downloading();
+                    let announce_data = tracker
+                        .announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All)
+                        .unwrap();
+
+                    assert_eq!(announce_data.early_announce, Some(OnEarlyAnnounce::SkipCounting));
+                    assert_eq!(announce_data.stats.complete, 1);
+                }
+            }
+
+            mod handling_a_scrape_request {
+
+                use std::net::{IpAddr, Ipv4Addr};
+
+                use torrust_tracker_primitives::info_hash::InfoHash;
+
+                use crate::core::tests::the_tracker::{complete_peer, incomplete_peer, public_tracker};
+                use crate::core::{PeersWanted, ScrapeData, SwarmMetadata};
+
+                #[tokio::test]
+                async fn it_should_return_a_zeroed_swarm_metadata_for_the_requested_file_if_the_tracker_does_not_have_that_torrent(
+                ) {
+                    let tracker = public_tracker();
+
+                    let info_hashes = vec!["3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap()];
+
+                    let scrape_data = tracker.scrape(&info_hashes).await;
+
+                    let mut expected_scrape_data = ScrapeData::empty();
+
+                    expected_scrape_data.add_file_with_zeroed_metadata(&info_hashes[0]);
+
+                    assert_eq!(scrape_data, expected_scrape_data);
+                }
+
+                #[tokio::test]
+                async fn it_should_return_the_swarm_metadata_for_the_requested_file_if_the_tracker_has_that_torrent() {
+                    let tracker = public_tracker();
+
+                    let info_hash = "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap();
+
+                    // Announce a "complete" peer for the torrent
+                    let mut complete_peer = complete_peer();
+                    tracker
+                        .announce(
+                            &info_hash,
+                            &mut complete_peer,
+                            &IpAddr::V4(Ipv4Addr::new(126, 0, 0, 10)),
+                            &PeersWanted::All,
+                        )
+                        .unwrap();
+
+                    // Announce an "incomplete" peer for the torrent
+                    let mut incomplete_peer = incomplete_peer();
+                    tracker
+                        .announce(
+                            &info_hash,
+                            &mut incomplete_peer,
+                            &IpAddr::V4(Ipv4Addr::new(126, 0, 0, 11)),
+                            &PeersWanted::All,
+                        )
+                        .unwrap();
+
+                    // Scrape
+                    let scrape_data = tracker.scrape(&vec![info_hash]).await;
+
+                    // The expected swarm metadata for the file
+                    let mut expected_scrape_data = ScrapeData::empty();
+                    expected_scrape_data.add_file(
+                        &info_hash,
+                        SwarmMetadata {
+                            complete: 0, // the "complete" peer does not count because it was not previously known
+                            downloaded: 0,
+                            incomplete: 1, // the "incomplete" peer we have just announced
+                        },
+                    );
+
+                    assert_eq!(scrape_data, expected_scrape_data);
+                }
+
+                #[tokio::test]
+                async fn it_should_allow_scraping_for_multiple_torrents() {
+                    let tracker = public_tracker();
+
+                    let info_hashes = vec![
+                        "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap(),
+                        "99c82bb73505a3c0b453f9fa0e881d6e5a32a0c1".parse::<InfoHash>().unwrap(),
+                    ];
+
+                    let scrape_data = tracker.scrape(&info_hashes).await;
+
+                    let mut expected_scrape_data = ScrapeData::empty();
+                    expected_scrape_data.add_file_with_zeroed_metadata(&info_hashes[0]);
+                    expected_scrape_data.add_file_with_zeroed_metadata(&info_hashes[1]);
+
+                    assert_eq!(scrape_data, expected_scrape_data);
+                }
+            }
+
+            mod handling_a_scrape_request_with_the_scrape_cache {
+                use std::future;
+
+                use aquatic_udp_protocol::PeerId;
+                use mockall::predicate::eq;
+                use torrust_tracker_configuration::ScrapeCache;
+                use torrust_tracker_test_helpers::configuration;
+
+                use crate::core::services::tracker_factory;
+                use crate::core::tests::the_tracker::{leecher, peer_ip, sample_info_hash};
+                use crate::core::{statistics, PeersWanted, Tracker};
+
+                fn tracker_with_scrape_cache() -> Tracker {
+                    let mut configuration = configuration::ephemeral_public();
+                    configuration.core.scrape_cache = Some(ScrapeCache::default());
+                    tracker_factory(&configuration)
+                }
+
+                #[tokio::test]
+                async fn it_should_not_return_the_cached_swarm_metadata_after_the_torrent_changes() {
+                    let tracker = tracker_with_scrape_cache();
+
+                    let info_hash = sample_info_hash();
+
+                    let mut peer = leecher();
+                    tracker
+                        .announce(&info_hash, &mut peer, &peer_ip(), &PeersWanted::All)
+                        .unwrap();
+
+                    let scrape_data = tracker.scrape(&vec![info_hash]).await;
+                    assert_eq!(scrape_data.files[&info_hash].incomplete, 1);
+
+                    let mut peer = leecher();
+                    peer.peer_id = PeerId(*b"-qB00000000000000002");
+                    tracker
+                        .announce(&info_hash, &mut peer, &peer_ip(), &PeersWanted::All)
+                        .unwrap();
+
+                    let scrape_data = tracker.scrape(&vec![info_hash]).await;
+                    assert_eq!(scrape_data.files[&info_hash].incomplete, 2);
+                }
+
+                #[tokio::test]
+                async fn it_should_count_the_scrape_cache_misses_and_hits() {
+                    let mut stats_event_sender_mock = statistics::MockEventSender::new();
+                    stats_event_sender_mock
+                        .expect_send_event()
+                        .with(eq(statistics::Event::ScrapeCacheMiss))
+                        .times(1)
+                        .returning(|_| Box::pin(future::ready(Some(Ok(())))));
+                    stats_event_sender_mock
+                        .expect_send_event()
+                        .with(eq(statistics::Event::ScrapeCacheHit))
+                        .times(1)
+                        .returning(|_| Box::pin(future::ready(Some(Ok(())))));
+
+                    let mut configuration = configuration::ephemeral_public();
+                    configuration.core.scrape_cache = Some(ScrapeCache::default());
+
+                    let tracker = Tracker::new(
+                        &configuration.core,
+                        Some(Box::new(stats_event_sender_mock)),
+                        statistics::Repo::new(),
+                    )
+                    .unwrap();
+
+                    tracker.scrape(&vec![sample_info_hash()]).await;
+                    tracker.scrape(&vec![sample_info_hash()]).await;
+                }
+            }
+
+            mod handling_a_full_scrape_request {
+                use torrust_tracker_configuration::FullScrape;
+                use torrust_tracker_primitives::info_hash::InfoHash;
+                use torrust_tracker_test_helpers::configuration;
+                use torrust_tracker_torrent_repository::repository::Repository;
+
+                use crate::core::services::tracker_factory;
+                use crate::core::tests::the_tracker::{leecher, peer_ip, public_tracker};
+                use crate::core::{PeersWanted, SwarmMetadata, Tracker};
+
+                fn tracker_with_full_scrape(full_scrape: FullScrape) -> Tracker {
+                    let mut configuration = configuration::ephemeral_public();
+                    configuration.core.full_scrape = Some(full_scrape);
+                    tracker_factory(&configuration)
+                }
+
+                fn tracker_with_admin_key(admin_key: &str) -> Tracker {
+                    tracker_with_full_scrape(FullScrape {
+                        admin_key: Some(admin_key.to_owned()),
+                        ..Default::default()
+                    })
+                }
+
+                #[tokio::test]
+                async fn it_should_not_allow_the_full_scrape_when_it_is_disabled() {
+                    let tracker = public_tracker();
+
+                    assert!(tracker.authorize_full_scrape(None).is_err());
+                }
+
+                #[tokio::test]
+                async fn it_should_allow_the_full_scrape_to_any_client_when_it_is_public() {
+                    let tracker = tracker_with_full_scrape(FullScrape {
+                        public: true,
+                        ..Default::default()
+                    });
+
+                    assert!(tracker.authorize_full_scrape(None).is_ok());
+                }
+
+                #[tokio::test]
+                async fn it_should_allow_the_full_scrape_to_the_clients_with_the_admin_key() {
+                    let tracker = tracker_with_admin_key("MyAdminKey");
+
+                    assert!(tracker.authorize_full_scrape(Some("MyAdminKey")).is_ok());
+                    assert!(tracker.authorize_full_scrape(Some("AnotherKey")).is_err());
+                    assert!(tracker.authorize_full_scrape(None).is_err());
+                }
+
+                #[tokio::test]
+                async fn it_should_not_allow_the_full_scrape_without_the_admin_key_when_no_admin_key_is_configured() {
+                    let tracker = tracker_with_full_scrape(FullScrape::default());
+
+                    assert!(tracker.authorize_full_scrape(None).is_err());
+                }
+
+                #[tokio::test]
+                async fn it_should_return_the_swarm_metadata_of_all_the_torrents_in_pages() {
+                    let tracker = tracker_with_full_scrape(FullScrape {
+                        public: true,
+                        ..Default::default()
+                    });
+
+                    let info_hashes = [
+                        "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap(),
+                        "99c82bb73505a3c0b453f9fa0e881d6e5a32a0c1".parse::<InfoHash>().unwrap(),
+                    ];
+
+                    for info_hash in &info_hashes {
+                        let mut peer = leecher();
+                        tracker.announce(info_hash, &mut peer, &peer_ip(), &PeersWanted::All).unwrap();
+                    }
+
+                    let swarm_metadata = SwarmMetadata {
+                        complete: 0,
+                        downloaded: 0,
+                        incomplete: 1,
+                    };
+
+                    assert_eq!(tracker.get_torrent_info_hashes(), info_hashes.to_vec());
+                    assert_eq!(
+                        tracker.full_scrape_page(&info_hashes[..1]).await,
+                        vec![(info_hashes[0], swarm_metadata)]
+                    );
+                    assert_eq!(
+                        tracker.full_scrape_page(&info_hashes[1..]).await,
+                        vec![(info_hashes[1], swarm_metadata)]
+                    );
+                }
+
+                #[tokio::test]
+                async fn it_should_skip_the_torrents_removed_after_listing_the_infohashes_in_the_full_scrape() {
+                    let tracker = tracker_with_full_scrape(FullScrape {
+                        public: true,
+                        ..Default::default()
+                    });
+
+                    let info_hash = "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap();
+
+                    let mut peer = leecher();
+                    tracker
+                        .announce(&info_hash, &mut peer, &peer_ip(), &PeersWanted::All)
+                        .unwrap();
+
+                    let info_hashes = tracker.get_torrent_info_hashes();
+
+                    tracker.torrents.remove(&info_hash);
+
+                    assert_eq!(tracker.full_scrape_page(&info_hashes).await, vec![]);
+                }
+            }
+        }
+
+        mod handling_the_ip_access_list {
+            use std::net::IpAddr;
+
+            use crate::core::ip_access_list::{parse_network, Policy, Rule};
+            use crate::core::tests::the_tracker::public_tracker;
+
+            fn rule(network: &str, policy: Policy) -> Rule {
+                Rule::new(parse_network(network).unwrap(), policy)
+            }
+
+            fn ip(ip: &str) -> IpAddr {
+                ip.parse().unwrap()
+            }
+
+            #[tokio::test]
+            async fn it_should_authorize_any_client_ip_when_the_list_is_empty() {
+                let tracker = public_tracker();
+
+                assert!(tracker.authorize_client_ip(&ip("126.0.0.1")).await.is_ok());
+            }
+
+            #[tokio::test]
+            async fn it_should_not_authorize_a_client_ip_in_a_denied_network() {
+                let tracker = public_tracker();
+
+                tracker.add_ip_access_rule(&rule("126.0.0.0/24", Policy::Deny)).await.unwrap();
+
+                assert!(tracker.authorize_client_ip(&ip("126.0.0.1")).await.is_err());
+            }
+
+            #[tokio::test]
+            async fn it_should_authorize_the_client_ip_again_after_removing_the_rule() {
+                let tracker = public_tracker();
+
+                tracker.add_ip_access_rule(&rule("126.0.0.0/24", Policy::Deny)).await.unwrap();
+                tracker
+                    .remove_ip_access_rule(&parse_network("126.0.0.0/24").unwrap())
+                    .await
+                    .unwrap();
+
+                assert!(tracker.authorize_client_ip(&ip("126.0.0.1")).await.is_ok());
+            }
+
+            mod persistence {
+                use crate::core::ip_access_list::Policy;
+                use crate::core::tests::the_tracker::handling_the_ip_access_list::{ip, rule};
+                use crate::core::tests::the_tracker::public_tracker;
+
+                #[tokio::test]
+                async fn it_should_load_the_ip_access_list_from_the_database() {
+                    let tracker = public_tracker();
+
+                    tracker.add_ip_access_rule(&rule("126.0.0.0/24", Policy::Deny)).await.unwrap();
+
+                    // Remove the rule from the in-memory list
+                    tracker.ip_access_list.write().await.clear();
+                    assert!(tracker.authorize_client_ip(&ip("126.0.0.1")).await.is_ok());
+
+                    tracker.load_ip_access_list_from_database().await.unwrap();
+
+                    assert_eq!(tracker.get_ip_access_rules().await, vec![rule("126.0.0.0/24", Policy::Deny)]);
+                    assert!(tracker.authorize_client_ip(&ip("126.0.0.1")).await.is_err());
+                }
+            }
+        }
+
+        mod handling_rate_limiting {
+            use std::net::IpAddr;
+
+            use torrust_tracker_configuration::v2_0_0::rate_limit::Budget;
+            use torrust_tracker_configuration::RateLimit;
+            use torrust_tracker_test_helpers::configuration;
+
+            use crate::core::rate_limiter::Request;
+            use crate::core::services::tracker_factory;
+            use crate::core::tests::the_tracker::public_tracker;
+
+            fn ip() -> IpAddr {
+                "126.0.0.1".parse().unwrap()
+            }
+
+            #[tokio::test]
+            async fn it_should_not_limit_the_requests_when_the_rate_limiter_is_disabled() {
+                let tracker = public_tracker();
+
+                for _ in 0..100 {
+                    assert!(tracker.check_rate_limit(&ip(), Request::Announce).is_ok());
+                }
+            }
+
+            #[tokio::test]
+            async fn it_should_reject_the_requests_exceeding_the_budget() {
+                let tracker = tracker_factory(&configuration::ephemeral_with_rate_limit(RateLimit {
+                    announce: Budget::new(1, 1),
+                    ..Default::default()
+                }));
+
+                assert!(tracker.check_rate_limit(&ip(), Request::Announce).is_ok());
+                assert!(tracker.check_rate_limit(&ip(), Request::Announce).is_err());
+            }
+        }
+
+        mod handling_the_tracker_events {
+            use std::time::Duration;
+
+            use aquatic_udp_protocol::AnnounceEvent;
+            use torrust_tracker_clock::clock::stopped::Stopped as _;
+            use torrust_tracker_clock::clock::{self};
+            use torrust_tracker_primitives::DurationSinceUnixEpoch;
+
+            use crate::core::events::Event;
+            use crate::core::tests::the_tracker::{completed_peer, leecher, public_tracker, sample_info_hash};
+
+            #[tokio::test]
+            async fn it_should_publish_an_event_when_a_peer_joins_a_new_torrent() {
+                let tracker = public_tracker();
+                let mut events = tracker.subscribe_to_events();
+
+                let peer = leecher();
+                tracker.upsert_peer_and_get_stats(&sample_info_hash(), &peer);
+
+                assert_eq!(
+                    events.try_recv().unwrap(),
+                    Event::TorrentCreated {
+                        info_hash: sample_info_hash()
+                    }
+                );
+                assert_eq!(events.try_recv().unwrap(), Event::peer_joined(&sample_info_hash(), &peer));
+                assert!(events.try_recv().is_err());
+            }
+
+            #[tokio::test]
+            async fn it_should_publish_an_event_when_a_peer_leaves_the_swarm() {
+                let tracker = public_tracker();
+
+                let mut peer = leecher();
+                tracker.upsert_peer_and_get_stats(&sample_info_hash(), &peer);
+
+                let mut events = tracker.subscribe_to_events();
+
+                peer.event = AnnounceEvent::Stopped;
+                tracker.upsert_peer_and_get_stats(&sample_info_hash(), &peer);
+
+                assert_eq!(events.try_recv().unwrap(), Event::peer_left(&sample_info_hash(), &peer));
+            }
+
+            #[tokio::test]
+            async fn it_should_publish_an_event_when_a_peer_completes_the_download() {
+                let tracker = public_tracker();
+
+                tracker.upsert_peer_and_get_stats(&sample_info_hash(), &leecher());
+
+                let mut events = tracker.subscribe_to_events();
+
+                let peer = completed_peer();
+                tracker.upsert_peer_and_get_stats(&sample_info_hash(), &peer);
+
+                assert_eq!(events.try_recv().unwrap(), Event::peer_completed(&sample_info_hash(), &peer));
+            }
+
+            #[tokio::test]
+            async fn it_should_publish_the_events_for_the_peers_and_torrents_removed_by_the_cleanup() {
+                clock::Stopped::local_set(&DurationSinceUnixEpoch::from_secs(10_000));
+
+                let tracker = public_tracker();
+
+                let mut peer = leecher();
+                peer.updated = DurationSinceUnixEpoch::ZERO;
+                tracker.upsert_peer_and_get_stats(&sample_info_hash(), &peer);
+
+                let mut events = tracker.subscribe_to_events();
+
+                tracker.cleanup_torrents();
+
+                assert_eq!(events.try_recv().unwrap(), Event::peer_left(&sample_info_hash(), &peer));
+                assert_eq!(
+                    events.try_recv().unwrap(),
+                    Event::TorrentRemoved {
+                        info_hash: sample_info_hash()
+                    }
+                );
+            }
+
+            #[tokio::test]
+            async fn it_should_publish_an_event_when_an_authentication_key_is_added_or_removed() {
+                let tracker = public_tracker();
+                let mut events = tracker.subscribe_to_events();
+
+                let peer_key = tracker.generate_auth_key(Some(Duration::from_secs(100))).await.unwrap();
+                tracker.remove_auth_key(&peer_key.key).await.unwrap();
+
+                assert_eq!(
+                    events.try_recv().unwrap(),
+                    Event::KeyAdded {
+                        key: peer_key.key.clone(),
+                        valid_until: peer_key.valid_until
+                    }
+                );
+                assert_eq!(events.try_recv().unwrap(), Event::KeyRemoved { key: peer_key.key });
+            }
+        }
+
+        mod configured_as_whitelisted {
+
+            mod handling_authorization {
+                use crate::core::tests::the_tracker::{sample_info_hash, whitelisted_tracker};
+
+                #[tokio::test]
+                async fn it_should_authorize_the_announce_and_scrape_actions_on_whitelisted_torrents() {
+                    let tracker = whitelisted_tracker();
+
+                    let info_hash = sample_info_hash();
+
+                    let result = tracker.add_torrent_to_whitelist(&info_hash).await;
+                    assert!(result.is_ok());
+
+                    let result = tracker.authorize(&info_hash).await;
+                    assert!(result.is_ok());
+                }
+
+                #[tokio::test]
+                async fn it_should_not_authorize_the_announce_and_scrape_actions_on_not_whitelisted_torrents() {
+                    let tracker = whitelisted_tracker();
+
+                    let info_hash = sample_info_hash();
 
                     let result = tracker.authorize(&info_hash).await;
                     assert!(result.is_err());
@@ -1828,6 +3871,8 @@ mod tests {
             }
 
             mod handling_the_torrent_whitelist {
+                use torrust_tracker_primitives::info_hash::InfoHash;
+
                 use crate::core::tests::the_tracker::{sample_info_hash, whitelisted_tracker};
 
                 #[tokio::test]
@@ -1854,6 +3899,30 @@ mod tests {
                     assert!(!tracker.is_info_hash_whitelisted(&info_hash).await);
                 }
 
+                #[tokio::test]
+                async fn it_should_add_many_torrents_to_the_whitelist_at_once() {
+                    let tracker = whitelisted_tracker();
+
+                    let info_hashes = [InfoHash::from_bytes(&[1; 20]), InfoHash::from_bytes(&[2; 20])];
+
+                    tracker.add_torrents_to_whitelist(&info_hashes).await.unwrap();
+
+                    assert_eq!(tracker.get_whitelist().await, info_hashes.to_vec());
+                }
+
+                #[tokio::test]
+                async fn it_should_remove_many_torrents_from_the_whitelist_at_once() {
+                    let tracker = whitelisted_tracker();
+
+                    let info_hashes = [InfoHash::from_bytes(&[1; 20]), InfoHash::from_bytes(&[2; 20])];
+
+                    tracker.add_torrents_to_whitelist(&info_hashes).await.unwrap();
+
+                    tracker.remove_torrents_from_whitelist(&info_hashes).await.unwrap();
+
+                    assert_eq!(tracker.get_whitelist().await, vec![]);
+                }
+
                 mod persistence {
                     use crate::core::tests::the_tracker::{sample_info_hash, whitelisted_tracker};
 
@@ -1907,11 +3976,15 @@ mod tests {
                     let info_hash = "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap();
 
                     let mut peer = incomplete_peer();
-                    tracker.announce(&info_hash, &mut peer, &peer_ip(), &PeersWanted::All);
+                    tracker
+                        .announce(&info_hash, &mut peer, &peer_ip(), &PeersWanted::All)
+                        .unwrap();
 
                     // Announce twice to force non zeroed swarm metadata
                     let mut peer = complete_peer();
-                    tracker.announce(&info_hash, &mut peer, &peer_ip(), &PeersWanted::All);
+                    tracker
+                        .announce(&info_hash, &mut peer, &peer_ip(), &PeersWanted::All)
+                        .unwrap();
 
                     let scrape_data = tracker.scrape(&vec![info_hash]).await;
 
@@ -1921,11 +3994,77 @@ mod tests {
 
                     assert_eq!(scrape_data, expected_scrape_data);
                 }
+
+                #[tokio::test]
+                async fn it_should_skip_the_torrents_that_are_not_whitelisted_in_the_full_scrape() {
+                    let tracker = whitelisted_tracker();
+
+                    let info_hash = "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap();
+
+                    let mut peer = incomplete_peer();
+                    tracker
+                        .announce(&info_hash, &mut peer, &peer_ip(), &PeersWanted::All)
+                        .unwrap();
+
+                    let page = tracker.full_scrape_page(&[info_hash]).await;
+
+                    assert_eq!(page, vec![]);
+                }
             }
         }
 
         mod configured_as_private {
 
+            mod handling_accounting {
+                use std::str::FromStr;
+
+                use aquatic_udp_protocol::NumberOfBytes;
+
+                use crate::core::accounting::Traffic;
+                use crate::core::auth;
+                use crate::core::tests::the_tracker::{private_tracker, public_tracker, sample_info_hash, started_peer};
+
+                #[tokio::test]
+                async fn it_should_account_the_traffic_announced_with_a_key() {
+                    let tracker = private_tracker();
+
+                    let key = auth::Key::from_str("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap();
+
+                    let mut peer = started_peer();
+                    peer.uploaded = NumberOfBytes::new(30);
+                    peer.downloaded = NumberOfBytes::new(20);
+
+                    tracker.record_key_traffic(&key, &sample_info_hash(), &peer);
+
+                    let expected_traffic = Traffic {
+                        uploaded: 30,
+                        downloaded: 20,
+                    };
+
+                    assert_eq!(tracker.get_key_traffic(&key), Some(expected_traffic));
+
+                    // The traffic is persisted and survives reloading it from the database
+                    tracker.accounting.load(vec![]);
+                    tracker.load_keys_traffic_from_database().unwrap();
+
+                    assert_eq!(tracker.get_key_traffic(&key), Some(expected_traffic));
+                }
+
+                #[tokio::test]
+                async fn it_should_not_account_the_traffic_when_the_tracker_is_not_private() {
+                    let tracker = public_tracker();
+
+                    let key = auth::Key::from_str("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap();
+
+                    let mut peer = started_peer();
+                    peer.uploaded = NumberOfBytes::new(30);
+
+                    tracker.record_key_traffic(&key, &sample_info_hash(), &peer);
+
+                    assert_eq!(tracker.get_key_traffic(&key), None);
+                }
+            }
+
             mod handling_authentication {
                 use std::str::FromStr;
                 use std::time::Duration;
@@ -2019,7 +4158,7 @@ mod tests {
                         async fn it_should_accept_an_expired_key_when_checking_expiration_is_disabled_in_configuration() {
                             let mut tracker = private_tracker();
 
-                            tracker.config.private_mode = Some(PrivateMode {
+                            tracker.config.get_mut().unwrap().private_mode = Some(PrivateMode {
                                 check_keys_expiration: false,
                             });
 
@@ -2084,7 +4223,7 @@ mod tests {
                         async fn it_should_accept_an_expired_key_when_checking_expiration_is_disabled_in_configuration() {
                             let mut tracker = private_tracker();
 
-                            tracker.config.private_mode = Some(PrivateMode {
+                            tracker.config.get_mut().unwrap().private_mode = Some(PrivateMode {
                                 check_keys_expiration: false,
                             });
 
@@ -2217,5 +4356,190 @@ mod tests {
                 assert!(torrent_entry.peers_is_empty());
             }
         }
+
+        mod handling_torrent_snapshots {
+
+            use aquatic_udp_protocol::AnnounceEvent;
+            use torrust_tracker_primitives::DurationSinceUnixEpoch;
+            use torrust_tracker_torrent_repository::entry::EntrySync;
+            use torrust_tracker_torrent_repository::repository::Repository;
+
+            use crate::core::tests::the_tracker::{public_tracker, sample_info_hash, sample_peer_1, sample_peer_2};
+
+            #[tokio::test]
+            async fn it_should_restore_the_torrents_and_their_peers_from_a_snapshot() {
+                let tracker = public_tracker();
+
+                let info_hash = sample_info_hash();
+
+                let mut peer = sample_peer_1();
+
+                peer.event = AnnounceEvent::Started;
+                tracker.upsert_peer_and_get_stats(&info_hash, &peer);
+
+                peer.event = AnnounceEvent::Completed;
+                tracker.upsert_peer_and_get_stats(&info_hash, &peer);
+
+                let snapshot = tracker.take_torrents_snapshot();
+
+                let restarted_tracker = public_tracker();
+
+                restarted_tracker.restore_torrents_snapshot(snapshot);
+
+                let torrent_entry = restarted_tracker
+                    .torrents
+                    .get(&info_hash)
+                    .expect("it should be able to get entry");
+
+                assert_eq!(torrent_entry.get_swarm_metadata().downloaded, 1);
+                assert_eq!(*torrent_entry.get_peers(None)[0], peer);
+            }
+
+            #[tokio::test]
+            async fn it_should_discard_the_peers_in_the_snapshot_which_have_not_been_updated_within_the_max_peer_timeout() {
+                let tracker = public_tracker();
+
+                let info_hash = sample_info_hash();
+
+                let active_peer = sample_peer_1();
+
+                let mut inactive_peer = sample_peer_2();
+                inactive_peer.updated = DurationSinceUnixEpoch::ZERO;
+
+                tracker.upsert_peer_and_get_stats(&info_hash, &active_peer);
+                tracker.upsert_peer_and_get_stats(&info_hash, &inactive_peer);
+
+                let snapshot = tracker.take_torrents_snapshot();
+
+                let restarted_tracker = public_tracker();
+
+                restarted_tracker.restore_torrents_snapshot(snapshot);
+
+                assert_eq!(restarted_tracker.get_torrent_peers(&info_hash), vec![active_peer.into()]);
+            }
+        }
+
+        mod handling_the_statistics_persistence {
+
+            use torrust_tracker_configuration::StatisticsPersistence;
+            use torrust_tracker_test_helpers::configuration;
+
+            use crate::core::statistics::{self, Metrics};
+            use crate::core::Tracker;
+
+            fn tracker_with_metrics(statistics_persistence: Option<StatisticsPersistence>, metrics: Metrics) -> Tracker {
+                let mut configuration = configuration::ephemeral_public();
+                configuration.core.statistics_persistence = statistics_persistence;
+
+                Tracker::new(&configuration.core, None, statistics::Repo::with_metrics(metrics)).unwrap()
+            }
+
+            fn sample_metrics() -> Metrics {
+                Metrics {
+                    udp4_announces_handled: 1,
+                    ..Default::default()
+                }
+            }
+
+            #[tokio::test]
+            async fn it_should_save_the_statistics_into_the_database() {
+                let tracker = tracker_with_metrics(Some(StatisticsPersistence::default()), sample_metrics());
+
+                tracker.save_statistics().await.unwrap();
+
+                let counters = tracker.database.load_statistics().unwrap();
+
+                assert_eq!(Metrics::from_counters(&counters), sample_metrics());
+            }
+
+            #[tokio::test]
+            async fn it_should_not_save_the_statistics_when_the_persistence_is_disabled() {
+                let tracker = tracker_with_metrics(None, sample_metrics());
+
+                tracker.save_statistics().await.unwrap();
+
+                assert!(tracker.database.load_statistics().unwrap().is_empty());
+            }
+
+            #[tokio::test]
+            async fn it_should_reset_the_statistics_in_memory_and_in_the_database() {
+                let tracker = tracker_with_metrics(Some(StatisticsPersistence::default()), sample_metrics());
+
+                tracker.save_statistics().await.unwrap();
+
+                tracker.reset_statistics().await.unwrap();
+
+                let counters = tracker.database.load_statistics().unwrap();
+
+                assert_eq!(*tracker.get_stats().await, Metrics::default());
+                assert_eq!(Metrics::from_counters(&counters), Metrics::default());
+            }
+        }
+
+        mod handling_the_metrics_history {
+            use std::env;
+
+            use camino::Utf8PathBuf;
+            use torrust_tracker_configuration::MetricsHistory;
+            use torrust_tracker_test_helpers::configuration;
+
+            use crate::core::services::tracker_factory;
+            use crate::core::tests::the_tracker::{public_tracker, sample_info_hash, sample_peer};
+            use crate::core::Tracker;
+
+            fn tracker_with_metrics_history(path: Option<Utf8PathBuf>) -> Tracker {
+                let mut configuration = configuration::ephemeral_public();
+                configuration.core.metrics_history = Some(MetricsHistory {
+                    path,
+                    ..Default::default()
+                });
+
+                tracker_factory(&configuration)
+            }
+
+            #[tokio::test]
+            async fn it_should_not_keep_the_metrics_history_when_it_is_disabled() {
+                let tracker = public_tracker();
+
+                tracker.sample_metrics().await;
+
+                assert_eq!(tracker.get_metrics_history(None, None, None), None);
+            }
+
+            #[tokio::test]
+            async fn it_should_add_a_sample_of_the_current_metrics_to_the_history() {
+                let tracker = tracker_with_metrics_history(None);
+
+                tracker.upsert_peer_and_get_stats(&sample_info_hash(), &sample_peer());
+
+                tracker.sample_metrics().await;
+
+                let samples = tracker.get_metrics_history(None, None, None).unwrap();
+
+                assert_eq!(samples.len(), 1);
+                assert_eq!(samples[0].torrents, 1);
+            }
+
+            #[tokio::test]
+            async fn it_should_restore_the_metrics_history_from_the_history_file() {
+                let dir = Utf8PathBuf::try_from(env::temp_dir()).unwrap();
+                let path = dir.join(format!("torrust_metrics_history_{}.json", uuid::Uuid::new_v4()));
+
+                let tracker = tracker_with_metrics_history(Some(path.clone()));
+
+                tracker.sample_metrics().await;
+
+                tracker.save_metrics_history().unwrap();
+
+                let restarted_tracker = tracker_with_metrics_history(Some(path));
+
+                restarted_tracker.load_metrics_history().unwrap();
+
+                assert_eq!(
+                    restarted_tracker.get_metrics_history(None, None, None),
+                    tracker.get_metrics_history(None, None, None)
+                );
+            }
+        }
     }
 }
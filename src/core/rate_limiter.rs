@@ -0,0 +1,286 @@
+//! Per client rate limiter for `connect`, `announce` and `scrape` requests.
+//!
+//! The limiter is disabled by default. It's enabled with the `core.rate_limit`
+//! section in the configuration:
+//!
+//! ```toml
+//! [core.rate_limit]
+//! ipv6_prefix_len = 64
+//!
+//! [core.rate_limit.connect]
+//! burst = 20
+//! per_minute = 60
+//!
+//! [core.rate_limit.announce]
+//! burst = 20
+//! per_minute = 60
+//!
+//! [core.rate_limit.scrape]
+//! burst = 10
+//! per_minute = 30
+//! ```
+//!
+//! Each client has a [token bucket](https://en.wikipedia.org/wiki/Token_bucket)
+//! for every type of request. A request takes one token from the bucket and
+//! it's rejected when the bucket is empty. Buckets are refilled at the
+//! `per_minute` rate up to `burst` tokens.
+//!
+//! IPv4 clients are identified by their IP address. IPv6 clients are grouped
+//! by network prefix (`/64` by default), otherwise a client could bypass the
+//! limiter just by using a different address from its own network.
+//! IPv4-mapped IPv6 addresses are treated as IPv4 addresses.
+//!
+//! The limiter lives in the core [`Tracker`](crate::core::Tracker), so the
+//! same budgets are shared by all the UDP and HTTP trackers.
+//!
+//! The HTTP tracker checks the budgets with a
+//! [middleware](crate::servers::http::v1::middlewares::rate_limit) in the
+//! router, before the requests are parsed. The UDP server
+//! [`Processor`](crate::servers::udp::server::processor::Processor) charges
+//! every packet to the `connect` budget before parsing it, except the
+//! `announce` and `scrape` requests, which are charged once their connection
+//! id has been validated. Otherwise, anyone could spend the budget of another
+//! client by spoofing its source address.
+use std::collections::HashMap;
+use std::fmt;
+use std::net::IpAddr;
+use std::sync::Mutex;
+
+use ipnet::Ipv6Net;
+use torrust_tracker_clock::clock::Time;
+use torrust_tracker_configuration::v2_0_0::rate_limit::Budget;
+use torrust_tracker_configuration::RateLimit;
+use torrust_tracker_primitives::DurationSinceUnixEpoch;
+
+use crate::CurrentClock;
+
+/// Minimum number of buckets before the limiter starts removing the buckets
+/// that are full again.
+const MIN_BUCKETS_BEFORE_CLEANUP: usize = 1024;
+
+/// The type of request. Each one has its own budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Request {
+    Connect,
+    Announce,
+    Scrape,
+}
+
+impl fmt::Display for Request {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Request::Connect => write!(f, "connect"),
+            Request::Announce => write!(f, "announce"),
+            Request::Scrape => write!(f, "scrape"),
+        }
+    }
+}
+
+/// A token bucket.
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: DurationSinceUnixEpoch,
+}
+
+impl Bucket {
+    fn full(budget: &Budget, now: DurationSinceUnixEpoch) -> Self {
+        Self {
+            tokens: f64::from(budget.burst),
+            updated: now,
+        }
+    }
+
+    fn refill(&mut self, budget: &Budget, now: DurationSinceUnixEpoch) {
+        let elapsed = now.saturating_sub(self.updated).as_secs_f64();
+
+        self.tokens = (self.tokens + elapsed * f64::from(budget.per_minute) / 60.0).min(f64::from(budget.burst));
+        self.updated = now;
+    }
+
+    fn take(&mut self) -> bool {
+        if self.tokens < 1.0 {
+            return false;
+        }
+
+        self.tokens -= 1.0;
+
+        true
+    }
+
+    fn is_full(&self, budget: &Budget) -> bool {
+        self.tokens >= f64::from(budget.burst)
+    }
+}
+
+#[derive(Debug)]
+struct Buckets {
+    buckets: HashMap<(IpAddr, Request), Bucket>,
+    next_cleanup: usize,
+}
+
+/// The rate limiter.
+#[derive(Debug)]
+pub struct RateLimiter {
+    config: RateLimit,
+    buckets: Mutex<Buckets>,
+}
+
+impl RateLimiter {
+    #[must_use]
+    pub fn new(config: &RateLimit) -> Self {
+        Self {
+            config: config.clone(),
+            buckets: Mutex::new(Buckets {
+                buckets: HashMap::new(),
+                next_cleanup: MIN_BUCKETS_BEFORE_CLEANUP,
+            }),
+        }
+    }
+
+    /// It takes a token from the client's bucket for the request type. It
+    /// returns `false` if the client has exceeded its budget.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if a previous check panicked while holding the lock on the
+    /// buckets.
+    pub fn check(&self, ip: &IpAddr, request: Request) -> bool {
+        let now = CurrentClock::now();
+        let client = self.client(ip);
+        let budget = self.budget(request);
+
+        let mut buckets = self.buckets.lock().expect("it should get the rate limiter buckets");
+
+        let allowed = {
+            let bucket = buckets
+                .buckets
+                .entry((client, request))
+                .or_insert_with(|| Bucket::full(budget, now));
+
+            bucket.refill(budget, now);
+            bucket.take()
+        };
+
+        if buckets.buckets.len() >= buckets.next_cleanup {
+            self.remove_full_buckets(&mut buckets, now);
+        }
+
+        allowed
+    }
+
+    /// The address used to identify the client: the IPv4 address or the IPv6
+    /// network prefix.
+    fn client(&self, ip: &IpAddr) -> IpAddr {
+        match ip {
+            IpAddr::V4(_) => *ip,
+            IpAddr::V6(ipv6) => match ipv6.to_ipv4_mapped() {
+                Some(ipv4) => IpAddr::V4(ipv4),
+                None => Ipv6Net::new(*ipv6, self.config.ipv6_prefix_len).map_or(*ip, |network| IpAddr::V6(network.network())),
+            },
+        }
+    }
+
+    fn budget(&self, request: Request) -> &Budget {
+        match request {
+            Request::Connect => &self.config.connect,
+            Request::Announce => &self.config.announce,
+            Request::Scrape => &self.config.scrape,
+        }
+    }
+
+    /// A full bucket is the same as no bucket, so they are removed to avoid
+    /// keeping one bucket for every client that has ever made a request.
+    fn remove_full_buckets(&self, buckets: &mut Buckets, now: DurationSinceUnixEpoch) {
+        buckets.buckets.retain(|(_, request), bucket| {
+            let budget = self.budget(*request);
+            bucket.refill(budget, now);
+            !bucket.is_full(budget)
+        });
+
+        buckets.next_cleanup = (buckets.buckets.len() * 2).max(MIN_BUCKETS_BEFORE_CLEANUP);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod the_rate_limiter {
+        use std::net::IpAddr;
+        use std::time::Duration;
+
+        use torrust_tracker_clock::clock::stopped::Stopped as _;
+        use torrust_tracker_clock::clock::{self};
+        use torrust_tracker_configuration::v2_0_0::rate_limit::Budget;
+        use torrust_tracker_configuration::RateLimit;
+
+        use crate::core::rate_limiter::{RateLimiter, Request};
+
+        fn ip(ip: &str) -> IpAddr {
+            ip.parse().unwrap()
+        }
+
+        fn rate_limiter_with_announce_budget(burst: u32, per_minute: u32) -> RateLimiter {
+            RateLimiter::new(&RateLimit {
+                announce: Budget::new(burst, per_minute),
+                ..Default::default()
+            })
+        }
+
+        #[test]
+        fn it_should_allow_a_burst_of_requests_up_to_the_budget() {
+            let rate_limiter = rate_limiter_with_announce_budget(2, 60);
+
+            assert!(rate_limiter.check(&ip("126.0.0.1"), Request::Announce));
+            assert!(rate_limiter.check(&ip("126.0.0.1"), Request::Announce));
+            assert!(!rate_limiter.check(&ip("126.0.0.1"), Request::Announce));
+        }
+
+        #[test]
+        fn it_should_refill_the_budget_over_time() {
+            clock::Stopped::local_set_to_unix_epoch();
+
+            let rate_limiter = rate_limiter_with_announce_budget(1, 60);
+
+            assert!(rate_limiter.check(&ip("126.0.0.1"), Request::Announce));
+            assert!(!rate_limiter.check(&ip("126.0.0.1"), Request::Announce));
+
+            clock::Stopped::local_add(&Duration::from_secs(1)).unwrap();
+
+            assert!(rate_limiter.check(&ip("126.0.0.1"), Request::Announce));
+        }
+
+        #[test]
+        fn it_should_keep_a_different_budget_for_each_client() {
+            let rate_limiter = rate_limiter_with_announce_budget(1, 60);
+
+            assert!(rate_limiter.check(&ip("126.0.0.1"), Request::Announce));
+            assert!(rate_limiter.check(&ip("126.0.0.2"), Request::Announce));
+        }
+
+        #[test]
+        fn it_should_keep_a_different_budget_for_each_type_of_request() {
+            let rate_limiter = rate_limiter_with_announce_budget(1, 60);
+
+            assert!(rate_limiter.check(&ip("126.0.0.1"), Request::Announce));
+            assert!(rate_limiter.check(&ip("126.0.0.1"), Request::Scrape));
+        }
+
+        #[test]
+        fn it_should_share_the_budget_between_the_ipv6_addresses_with_the_same_prefix() {
+            let rate_limiter = rate_limiter_with_announce_budget(1, 60);
+
+            assert!(rate_limiter.check(&ip("2001:db8::1"), Request::Announce));
+            assert!(!rate_limiter.check(&ip("2001:db8::2"), Request::Announce));
+            assert!(rate_limiter.check(&ip("2001:db8:0:1::1"), Request::Announce));
+        }
+
+        #[test]
+        fn it_should_share_the_budget_between_an_ipv4_address_and_its_ipv4_mapped_ipv6_address() {
+            let rate_limiter = rate_limiter_with_announce_budget(1, 60);
+
+            assert!(rate_limiter.check(&ip("126.0.0.1"), Request::Announce));
+            assert!(!rate_limiter.check(&ip("::ffff:126.0.0.1"), Request::Announce));
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! Cache of the swarm metadata returned in the `scrape` responses.
+//!
+//! The cache is disabled by default. It's enabled with the `core.scrape_cache`
+//! section in the configuration:
+//!
+//! ```toml
+//! [core.scrape_cache]
+//! ttl = 5
+//! max_entries = 100000
+//! ```
+//!
+//! Popular torrents can be scraped thousands of times per minute by clients
+//! and indexers. Without the cache, every scrape has to lock the torrent entry
+//! in the repository to get its swarm metadata.
+//!
+//! The [`Tracker`](crate::core::Tracker) discards the cached value for a
+//! torrent when its swarm metadata changes, and the whole cache when the
+//! torrents are cleaned up or imported. So, the `ttl` only limits how long an
+//! unused value is kept in memory.
+//!
+//! The cache lives in the core [`Tracker`](crate::core::Tracker), so it's
+//! shared by all the UDP and HTTP trackers. Hits and misses are counted in the
+//! tracker [`statistics`](crate::core::statistics).
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use torrust_tracker_clock::clock::Time;
+use torrust_tracker_configuration::ScrapeCache;
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
+use torrust_tracker_primitives::DurationSinceUnixEpoch;
+
+use crate::CurrentClock;
+
+#[derive(Debug, Clone, Copy)]
+struct Cached {
+    swarm_metadata: SwarmMetadata,
+    expires: DurationSinceUnixEpoch,
+}
+
+/// The cache of the swarm metadata for each torrent.
+#[derive(Debug)]
+pub struct SwarmMetadataCache {
+    ttl: Duration,
+    max_entries: usize,
+    entries: Mutex<HashMap<InfoHash, Cached>>,
+}
+
+impl SwarmMetadataCache {
+    #[must_use]
+    pub fn new(config: &ScrapeCache) -> Self {
+        Self {
+            ttl: Duration::from_secs(config.ttl),
+            max_entries: config.max_entries,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// It returns the cached swarm metadata for the torrent, if it has not
+    /// expired yet.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if another thread panicked while holding the lock on the
+    /// cache entries.
+    pub fn get(&self, info_hash: &InfoHash) -> Option<SwarmMetadata> {
+        let now = CurrentClock::now();
+
+        let mut entries = self.entries.lock().expect("it should get the scrape cache entries");
+
+        match entries.get(info_hash) {
+            Some(cached) if cached.expires > now => Some(cached.swarm_metadata),
+            Some(_) => {
+                entries.remove(info_hash);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// It caches the swarm metadata for the torrent. When the cache is full,
+    /// the expired values are removed first. The value is not cached if the
+    /// cache is still full.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if another thread panicked while holding the lock on the
+    /// cache entries.
+    pub fn insert(&self, info_hash: &InfoHash, swarm_metadata: SwarmMetadata) {
+        let now = CurrentClock::now();
+
+        let mut entries = self.entries.lock().expect("it should get the scrape cache entries");
+
+        if entries.len() >= self.max_entries && !entries.contains_key(info_hash) {
+            entries.retain(|_, cached| cached.expires > now);
+
+            if entries.len() >= self.max_entries {
+                return;
+            }
+        }
+
+        entries.insert(
+            *info_hash,
+            Cached {
+                swarm_metadata,
+                expires: now + self.ttl,
+            },
+        );
+    }
+
+    /// It discards the cached value for the torrent.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if another thread panicked while holding the lock on the
+    /// cache entries.
+    pub fn invalidate(&self, info_hash: &InfoHash) {
+        self.entries
+            .lock()
+            .expect("it should get the scrape cache entries")
+            .remove(info_hash);
+    }
+
+    /// It discards all the cached values.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if another thread panicked while holding the lock on the
+    /// cache entries.
+    pub fn clear(&self) {
+        self.entries.lock().expect("it should get the scrape cache entries").clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod the_swarm_metadata_cache {
+        use std::time::Duration;
+
+        use torrust_tracker_clock::clock::stopped::Stopped as _;
+        use torrust_tracker_clock::clock::{self};
+        use torrust_tracker_configuration::ScrapeCache;
+        use torrust_tracker_primitives::info_hash::InfoHash;
+        use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
+
+        use crate::core::scrape_cache::SwarmMetadataCache;
+
+        fn cache(ttl: u64, max_entries: usize) -> SwarmMetadataCache {
+            SwarmMetadataCache::new(&ScrapeCache { ttl, max_entries })
+        }
+
+        fn info_hash(byte: u8) -> InfoHash {
+            InfoHash::from_bytes(&[byte; 20])
+        }
+
+        fn swarm_metadata() -> SwarmMetadata {
+            SwarmMetadata {
+                complete: 1,
+                downloaded: 2,
+                incomplete: 3,
+            }
+        }
+
+        #[test]
+        fn it_should_return_the_cached_swarm_metadata() {
+            let cache = cache(5, 10);
+
+            cache.insert(&info_hash(1), swarm_metadata());
+
+            assert_eq!(cache.get(&info_hash(1)), Some(swarm_metadata()));
+        }
+
+        #[test]
+        fn it_should_not_return_the_swarm_metadata_after_the_ttl() {
+            clock::Stopped::local_set_to_unix_epoch();
+
+            let cache = cache(5, 10);
+
+            cache.insert(&info_hash(1), swarm_metadata());
+
+            clock::Stopped::local_add(&Duration::from_secs(5)).unwrap();
+
+            assert_eq!(cache.get(&info_hash(1)), None);
+        }
+
+        #[test]
+        fn it_should_not_return_the_swarm_metadata_after_it_has_been_invalidated() {
+            let cache = cache(5, 10);
+
+            cache.insert(&info_hash(1), swarm_metadata());
+            cache.invalidate(&info_hash(1));
+
+            assert_eq!(cache.get(&info_hash(1)), None);
+        }
+
+        #[test]
+        fn it_should_not_cache_more_torrents_than_the_maximum_number_of_entries() {
+            clock::Stopped::local_set_to_unix_epoch();
+
+            let cache = cache(5, 1);
+
+            cache.insert(&info_hash(1), swarm_metadata());
+            cache.insert(&info_hash(2), swarm_metadata());
+
+            assert_eq!(cache.get(&info_hash(2)), None);
+
+            clock::Stopped::local_add(&Duration::from_secs(5)).unwrap();
+
+            cache.insert(&info_hash(2), swarm_metadata());
+
+            assert_eq!(cache.get(&info_hash(2)), Some(swarm_metadata()));
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Accounting services.
+//!
+//! There are two services:
+//!
+//! - [`get_key_traffic`]: it returns the traffic of one authentication key.
+//! - [`get_keys_traffic_page`]: it returns a page of the traffic of the
+//!   authentication keys.
+//!
+//! The traffic is ordered by key, so that the pages are stable while no new
+//! keys announce.
+use std::sync::Arc;
+
+use torrust_tracker_primitives::pagination::Pagination;
+
+use crate::core::accounting::Traffic;
+use crate::core::auth::Key;
+use crate::core::Tracker;
+
+/// It returns the traffic of the authentication key. Keys without any
+/// accounted traffic yet have zero traffic. It returns `None` if the tracker
+/// does not know the key.
+pub async fn get_key_traffic(tracker: Arc<Tracker>, key: &Key) -> Option<Traffic> {
+    match tracker.get_key_traffic(key) {
+        Some(traffic) => Some(traffic),
+        None => tracker.get_auth_key(key).await.map(|_| Traffic::default()),
+    }
+}
+
+/// It returns the traffic of the authentication keys with accounted traffic,
+/// ordered by key.
+pub fn get_keys_traffic_page(tracker: &Arc<Tracker>, pagination: Option<&Pagination>) -> Vec<(Key, Traffic)> {
+    let keys_traffic = tracker.get_keys_traffic().into_iter();
+
+    match pagination {
+        Some(pagination) => keys_traffic
+            .skip(pagination.offset as usize)
+            .take(pagination.limit as usize)
+            .collect(),
+        None => keys_traffic.collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod getting_the_keys_traffic {
+        use std::sync::Arc;
+
+        use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes};
+        use torrust_tracker_primitives::info_hash::InfoHash;
+        use torrust_tracker_primitives::pagination::Pagination;
+        use torrust_tracker_primitives::peer;
+        use torrust_tracker_test_helpers::configuration;
+
+        use crate::core::accounting::Traffic;
+        use crate::core::auth::Key;
+        use crate::core::services::accounting::{get_key_traffic, get_keys_traffic_page};
+        use crate::core::services::tracker_factory;
+        use crate::core::Tracker;
+
+        fn key(c: char) -> Key {
+            c.to_string().repeat(32).parse::<Key>().unwrap()
+        }
+
+        fn started_peer(uploaded: i64, downloaded: i64) -> peer::Peer {
+            let mut peer = peer::fixture::PeerBuilder::leecher().build();
+            peer.uploaded = NumberOfBytes::new(uploaded);
+            peer.downloaded = NumberOfBytes::new(downloaded);
+            peer.event = AnnounceEvent::Started;
+            peer
+        }
+
+        fn private_tracker() -> Arc<Tracker> {
+            Arc::new(tracker_factory(&configuration::ephemeral_private()))
+        }
+
+        #[tokio::test]
+        async fn should_return_the_traffic_of_the_key() {
+            let tracker = private_tracker();
+
+            tracker.record_key_traffic(&key('A'), &InfoHash::from_bytes(&[1; 20]), &started_peer(10, 20));
+
+            assert_eq!(
+                get_key_traffic(tracker.clone(), &key('A')).await,
+                Some(Traffic {
+                    uploaded: 10,
+                    downloaded: 20
+                })
+            );
+        }
+
+        #[tokio::test]
+        async fn should_return_zero_traffic_for_a_known_key_without_traffic() {
+            let tracker = private_tracker();
+
+            tracker.add_auth_key(key('A'), None).await.unwrap();
+
+            assert_eq!(get_key_traffic(tracker.clone(), &key('A')).await, Some(Traffic::default()));
+        }
+
+        #[tokio::test]
+        async fn should_return_none_for_an_unknown_key() {
+            let tracker = private_tracker();
+
+            assert_eq!(get_key_traffic(tracker.clone(), &key('A')).await, None);
+        }
+
+        #[tokio::test]
+        async fn should_allow_paginating_the_traffic_of_the_keys() {
+            let tracker = private_tracker();
+
+            for c in ['C', 'A', 'B'] {
+                tracker.record_key_traffic(&key(c), &InfoHash::from_bytes(&[1; 20]), &started_peer(1, 1));
+            }
+
+            let keys: Vec<Key> = get_keys_traffic_page(&tracker, Some(&Pagination::new(1, 1)))
+                .into_iter()
+                .map(|(key, _)| key)
+                .collect();
+
+            assert_eq!(keys, vec![key('B')]);
+        }
+    }
+}
@@ -0,0 +1,188 @@
+//! Authentication key services.
+//!
+//! There are two services:
+//!
+//! - [`get_auth_key`]: it returns one authentication key.
+//! - [`get_auth_keys_page`]: it returns a page of the authentication keys
+//!   matching some [`KeyFilters`].
+//!
+//! Keys are ordered by key, so that the pages are stable while the keys do not
+//! change.
+use std::sync::Arc;
+
+use torrust_tracker_clock::clock::Time;
+use torrust_tracker_primitives::pagination::Pagination;
+use torrust_tracker_primitives::DurationSinceUnixEpoch;
+
+use crate::core::auth::{Key, PeerKey};
+use crate::core::Tracker;
+use crate::CurrentClock;
+
+/// Filters for the listed authentication keys. Keys must match all the
+/// filters. Use `None` to ignore a filter.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct KeyFilters {
+    /// Only the expired keys (`true`) or the keys that are still valid
+    /// (`false`). Permanent keys never expire.
+    pub expired: Option<bool>,
+    /// Only the permanent keys (`true`) or the keys with an expiration time
+    /// (`false`).
+    pub permanent: Option<bool>,
+    /// Only the keys that expire before this time. Permanent keys are not
+    /// included.
+    pub expiring_before: Option<DurationSinceUnixEpoch>,
+}
+
+impl KeyFilters {
+    fn matches(&self, peer_key: &PeerKey, now: DurationSinceUnixEpoch) -> bool {
+        let is_expired = peer_key.valid_until.is_some_and(|valid_until| valid_until < now);
+        let is_permanent = peer_key.valid_until.is_none();
+
+        self.expired.map_or(true, |expired| expired == is_expired)
+            && self.permanent.map_or(true, |permanent| permanent == is_permanent)
+            && self.expiring_before.map_or(true, |expiring_before| {
+                peer_key.valid_until.is_some_and(|valid_until| valid_until < expiring_before)
+            })
+    }
+}
+
+/// It returns the authentication key, if the tracker has it.
+pub async fn get_auth_key(tracker: Arc<Tracker>, key: &Key) -> Option<PeerKey> {
+    tracker.get_auth_key(key).await
+}
+
+/// It returns the authentication keys matching the `filters`, ordered by key.
+pub async fn get_auth_keys_page(tracker: Arc<Tracker>, filters: &KeyFilters, pagination: Option<&Pagination>) -> Vec<PeerKey> {
+    let now = CurrentClock::now();
+
+    let keys = tracker
+        .get_auth_keys()
+        .await
+        .into_iter()
+        .filter(|peer_key| filters.matches(peer_key, now));
+
+    match pagination {
+        Some(pagination) => keys
+            .skip(pagination.offset as usize)
+            .take(pagination.limit as usize)
+            .collect(),
+        None => keys.collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod searching_for_auth_keys {
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        use torrust_tracker_clock::clock::stopped::Stopped as _;
+        use torrust_tracker_clock::clock::{self};
+        use torrust_tracker_primitives::pagination::Pagination;
+        use torrust_tracker_test_helpers::configuration;
+
+        use crate::core::auth::{Key, PeerKey};
+        use crate::core::services::auth_key::{get_auth_key, get_auth_keys_page, KeyFilters};
+        use crate::core::services::tracker_factory;
+        use crate::core::Tracker;
+
+        fn key(c: char) -> Key {
+            c.to_string().repeat(32).parse::<Key>().unwrap()
+        }
+
+        /// A tracker with an expired key `A`, a valid key `B` and a permanent
+        /// key `C`. The current time is 100 seconds after the Unix epoch.
+        async fn tracker_with_keys() -> Arc<Tracker> {
+            clock::Stopped::local_set(&Duration::from_secs(100));
+
+            let tracker = Arc::new(tracker_factory(&configuration::ephemeral_private()));
+
+            tracker.add_auth_key(key('C'), None).await.unwrap();
+            tracker.add_auth_key(key('B'), Some(Duration::from_secs(200))).await.unwrap();
+            tracker.add_auth_key(key('A'), Some(Duration::from_secs(50))).await.unwrap();
+
+            tracker
+        }
+
+        fn keys(peer_keys: &[PeerKey]) -> Vec<Key> {
+            peer_keys.iter().map(PeerKey::key).collect()
+        }
+
+        #[tokio::test]
+        async fn should_return_the_auth_key() {
+            let tracker = tracker_with_keys().await;
+
+            let peer_key = get_auth_key(tracker.clone(), &key('B')).await;
+
+            assert_eq!(
+                peer_key,
+                Some(PeerKey {
+                    key: key('B'),
+                    valid_until: Some(Duration::from_secs(200))
+                })
+            );
+        }
+
+        #[tokio::test]
+        async fn should_return_all_the_auth_keys_ordered_by_key() {
+            let tracker = tracker_with_keys().await;
+
+            let peer_keys = get_auth_keys_page(tracker.clone(), &KeyFilters::default(), None).await;
+
+            assert_eq!(keys(&peer_keys), vec![key('A'), key('B'), key('C')]);
+        }
+
+        #[tokio::test]
+        async fn should_allow_paginating_the_auth_keys() {
+            let tracker = tracker_with_keys().await;
+
+            let peer_keys = get_auth_keys_page(tracker.clone(), &KeyFilters::default(), Some(&Pagination::new(1, 1))).await;
+
+            assert_eq!(keys(&peer_keys), vec![key('B')]);
+        }
+
+        #[tokio::test]
+        async fn should_allow_filtering_the_expired_auth_keys() {
+            let tracker = tracker_with_keys().await;
+
+            let filters = KeyFilters {
+                expired: Some(true),
+                ..Default::default()
+            };
+
+            let peer_keys = get_auth_keys_page(tracker.clone(), &filters, None).await;
+
+            assert_eq!(keys(&peer_keys), vec![key('A')]);
+        }
+
+        #[tokio::test]
+        async fn should_allow_filtering_the_permanent_auth_keys() {
+            let tracker = tracker_with_keys().await;
+
+            let filters = KeyFilters {
+                permanent: Some(true),
+                ..Default::default()
+            };
+
+            let peer_keys = get_auth_keys_page(tracker.clone(), &filters, None).await;
+
+            assert_eq!(keys(&peer_keys), vec![key('C')]);
+        }
+
+        #[tokio::test]
+        async fn should_allow_filtering_the_auth_keys_expiring_before_a_given_time() {
+            let tracker = tracker_with_keys().await;
+
+            let filters = KeyFilters {
+                expired: Some(false),
+                expiring_before: Some(Duration::from_secs(300)),
+                ..Default::default()
+            };
+
+            let peer_keys = get_auth_keys_page(tracker.clone(), &filters, None).await;
+
+            assert_eq!(keys(&peer_keys), vec![key('B')]);
+        }
+    }
+}
@@ -1,11 +1,17 @@
 //! Tracker domain services. Core and statistics services.
 //!
-//! There are two types of service:
+//! There are five types of service:
 //!
 //! - [Core tracker services](crate::core::services::torrent): related to the tracker main functionalities like getting info about torrents.
+//! - [Authentication key services](crate::core::services::auth_key): related to the authentication keys of private trackers.
+//! - [Accounting services](crate::core::services::accounting): related to the traffic of the authentication keys of private trackers.
+//! - [Whitelist services](crate::core::services::whitelist): related to the torrent whitelist of listed trackers.
 //! - [Services for statistics](crate::core::services::statistics): related to tracker metrics. Aggregate data about the tracker server.
+pub mod accounting;
+pub mod auth_key;
 pub mod statistics;
 pub mod torrent;
+pub mod whitelist;
 
 use std::sync::Arc;
 
@@ -21,7 +27,12 @@ use crate::core::Tracker;
 #[must_use]
 pub fn tracker_factory(config: &Configuration) -> Tracker {
     // Initialize statistics
-    let (stats_event_sender, stats_repository) = statistics::setup::factory(config.core.tracker_usage_statistics);
+    let (stats_event_sender, stats_repository) = match statistics::setup::factory(&config.core) {
+        Ok(statistics) => statistics,
+        Err(error) => {
+            panic!("{}", error)
+        }
+    };
 
     // Initialize Torrust tracker
     match Tracker::new(&Arc::new(config).core, stats_event_sender, stats_repository) {
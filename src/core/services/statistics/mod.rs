@@ -70,15 +70,29 @@ pub async fn get_metrics(tracker: Arc<Tracker>) -> TrackerMetrics {
             tcp4_connections_handled: stats.tcp4_connections_handled,
             tcp4_announces_handled: stats.tcp4_announces_handled,
             tcp4_scrapes_handled: stats.tcp4_scrapes_handled,
+            tcp4_requests_blocked: stats.tcp4_requests_blocked,
+            tcp4_requests_rate_limited: stats.tcp4_requests_rate_limited,
+            tcp4_early_announces: stats.tcp4_early_announces,
             tcp6_connections_handled: stats.tcp6_connections_handled,
             tcp6_announces_handled: stats.tcp6_announces_handled,
             tcp6_scrapes_handled: stats.tcp6_scrapes_handled,
+            tcp6_requests_blocked: stats.tcp6_requests_blocked,
+            tcp6_requests_rate_limited: stats.tcp6_requests_rate_limited,
+            tcp6_early_announces: stats.tcp6_early_announces,
             udp4_connections_handled: stats.udp4_connections_handled,
             udp4_announces_handled: stats.udp4_announces_handled,
             udp4_scrapes_handled: stats.udp4_scrapes_handled,
+            udp4_requests_blocked: stats.udp4_requests_blocked,
+            udp4_requests_rate_limited: stats.udp4_requests_rate_limited,
+            udp4_early_announces: stats.udp4_early_announces,
             udp6_connections_handled: stats.udp6_connections_handled,
             udp6_announces_handled: stats.udp6_announces_handled,
             udp6_scrapes_handled: stats.udp6_scrapes_handled,
+            udp6_requests_blocked: stats.udp6_requests_blocked,
+            udp6_requests_rate_limited: stats.udp6_requests_rate_limited,
+            udp6_early_announces: stats.udp6_early_announces,
+            scrape_cache_hits: stats.scrape_cache_hits,
+            scrape_cache_misses: stats.scrape_cache_misses,
         },
     }
 }
@@ -1,7 +1,10 @@
 //! Setup for the tracker statistics.
 //!
 //! The [`factory`] function builds the structs needed for handling the tracker metrics.
-use crate::core::statistics;
+use torrust_tracker_configuration::Core;
+
+use crate::core::databases::driver::Driver;
+use crate::core::{databases, statistics};
 
 /// It builds the structs needed for handling the tracker metrics.
 ///
@@ -10,40 +13,86 @@ use crate::core::statistics;
 /// - An statistics [`EventSender`](crate::core::statistics::EventSender) that allows you to send events related to statistics.
 /// - An statistics [`Repo`](crate::core::statistics::Repo) which is an in-memory repository for the tracker metrics.
 ///
-/// When the input argument `tracker_usage_statistics`is false the setup does not run the event listeners, consequently the statistics
+/// When the configuration option `tracker_usage_statistics` is false the setup does not run the event listeners, consequently the statistics
 /// events are sent are received but not dispatched to the handler.
-#[must_use]
-pub fn factory(tracker_usage_statistics: bool) -> (Option<Box<dyn statistics::EventSender>>, statistics::Repo) {
+///
+/// When the `statistics_persistence` section is present in the configuration,
+/// the metrics saved in the database are loaded into the repository, so the
+/// counters continue from the values they had before the restart.
+///
+/// # Errors
+///
+/// Will return a `database::Error` if the statistics persistence is enabled
+/// and the metrics can't be loaded from the database.
+pub fn factory(config: &Core) -> Result<(Option<Box<dyn statistics::EventSender>>, statistics::Repo), databases::error::Error> {
     let mut stats_event_sender = None;
 
-    let mut stats_tracker = statistics::Keeper::new();
+    let metrics = match config.statistics_persistence {
+        Some(_) => {
+            let database = databases::driver::build(&Driver::from(&config.database.driver), &config.database.path)?;
 
-    if tracker_usage_statistics {
+            statistics::Metrics::from_counters(&database.load_statistics()?)
+        }
+        None => statistics::Metrics::default(),
+    };
+
+    let mut stats_tracker = statistics::Keeper {
+        repository: statistics::Repo::with_metrics(metrics),
+    };
+
+    if config.tracker_usage_statistics {
         stats_event_sender = Some(stats_tracker.run_event_listener());
     }
 
-    (stats_event_sender, stats_tracker.repository)
+    Ok((stats_event_sender, stats_tracker.repository))
 }
 
 #[cfg(test)]
 mod test {
+    use torrust_tracker_configuration::StatisticsPersistence;
+    use torrust_tracker_test_helpers::configuration;
+
     use super::factory;
+    use crate::core::databases::driver::{self, Driver};
+    use crate::core::statistics::Metrics;
 
     #[tokio::test]
     async fn should_not_send_any_event_when_statistics_are_disabled() {
-        let tracker_usage_statistics = false;
+        let mut config = configuration::ephemeral().core;
+        config.tracker_usage_statistics = false;
 
-        let (stats_event_sender, _stats_repository) = factory(tracker_usage_statistics);
+        let (stats_event_sender, _stats_repository) = factory(&config).unwrap();
 
         assert!(stats_event_sender.is_none());
     }
 
     #[tokio::test]
     async fn should_send_events_when_statistics_are_enabled() {
-        let tracker_usage_statistics = true;
+        let mut config = configuration::ephemeral().core;
+        config.tracker_usage_statistics = true;
 
-        let (stats_event_sender, _stats_repository) = factory(tracker_usage_statistics);
+        let (stats_event_sender, _stats_repository) = factory(&config).unwrap();
 
         assert!(stats_event_sender.is_some());
     }
+
+    #[tokio::test]
+    async fn should_load_the_persisted_metrics_when_the_statistics_persistence_is_enabled() {
+        let mut config = configuration::ephemeral().core;
+        config.statistics_persistence = Some(StatisticsPersistence::default());
+
+        let metrics = Metrics {
+            udp4_announces_handled: 1,
+            ..Default::default()
+        };
+
+        driver::build(&Driver::from(&config.database.driver), &config.database.path)
+            .unwrap()
+            .save_statistics(&metrics.counters())
+            .unwrap();
+
+        let (_stats_event_sender, stats_repository) = factory(&config).unwrap();
+
+        assert_eq!(*stats_repository.get_stats().await, metrics);
+    }
 }
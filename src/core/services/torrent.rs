@@ -4,11 +4,18 @@
 //!
 //! - [`get_torrent_info`]: it returns all the data about one torrent.
 //! - [`get_torrents`]: it returns data about some torrent in bulk excluding the peer list.
+//!
+//! The torrents pages returned by [`get_torrents_page`] can be sorted by the
+//! torrent activity statistics. See [`SortBy`].
+use std::cmp::Ordering;
+use std::str::FromStr;
 use std::sync::Arc;
 
+use thiserror::Error;
 use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_primitives::pagination::Pagination;
 use torrust_tracker_primitives::peer;
+use torrust_tracker_primitives::torrent_stats::TorrentStats;
 use torrust_tracker_torrent_repository::entry::EntrySync;
 use torrust_tracker_torrent_repository::repository::Repository;
 
@@ -27,6 +34,9 @@ pub struct Info {
     pub leechers: u64,
     /// The swarm: the list of peers that are actively trying to download or serving this torrent
     pub peers: Option<Vec<peer::Peer>>,
+    /// The torrent activity statistics. Only when they are enabled in the
+    /// tracker configuration (`core.torrent_statistics`).
+    pub stats: Option<TorrentStats>,
 }
 
 /// It contains only part of the information the tracker has about a torrent
@@ -44,6 +54,49 @@ pub struct BasicInfo {
     pub leechers: u64,
 }
 
+/// The torrent activity statistic used to sort a page of torrents. Torrents
+/// are sorted from the highest to the lowest value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortBy {
+    Announces,
+    Scrapes,
+    LastActivity,
+    PeakSeeders,
+    PeakLeechers,
+}
+
+impl SortBy {
+    fn compare(self, a: &TorrentStats, b: &TorrentStats) -> Ordering {
+        match self {
+            SortBy::Announces => a.announces.cmp(&b.announces),
+            SortBy::Scrapes => a.scrapes.cmp(&b.scrapes),
+            SortBy::LastActivity => a.last_activity.cmp(&b.last_activity),
+            SortBy::PeakSeeders => a.peak_seeders.cmp(&b.peak_seeders),
+            SortBy::PeakLeechers => a.peak_leechers.cmp(&b.peak_leechers),
+        }
+    }
+}
+
+/// Error returned when the sort field can't be parsed.
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+#[error("Invalid sort field: {0}, it should be `announces`, `scrapes`, `last_activity`, `peak_seeders` or `peak_leechers`")]
+pub struct ParseSortByError(String);
+
+impl FromStr for SortBy {
+    type Err = ParseSortByError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "announces" => Ok(SortBy::Announces),
+            "scrapes" => Ok(SortBy::Scrapes),
+            "last_activity" => Ok(SortBy::LastActivity),
+            "peak_seeders" => Ok(SortBy::PeakSeeders),
+            "peak_leechers" => Ok(SortBy::PeakLeechers),
+            _ => Err(ParseSortByError(s.to_string())),
+        }
+    }
+}
+
 /// It returns all the information the tracker has about one torrent in a [Info] struct.
 pub async fn get_torrent_info(tracker: Arc<Tracker>, info_hash: &InfoHash) -> Option<Info> {
     let torrent_entry_option = tracker.torrents.get(info_hash);
@@ -56,20 +109,56 @@ pub async fn get_torrent_info(tracker: Arc<Tracker>, info_hash: &InfoHash) -> Op
 
     let peers = Some(peers.iter().map(|peer| (**peer)).collect());
 
+    let torrent_stats = tracker.config().torrent_statistics.then(|| torrent_entry.get_torrent_stats());
+
     Some(Info {
         info_hash: *info_hash,
         seeders: u64::from(stats.complete),
         completed: u64::from(stats.downloaded),
         leechers: u64::from(stats.incomplete),
         peers,
+        stats: torrent_stats,
     })
 }
 
 /// It returns all the information the tracker has about multiple torrents in a [`BasicInfo`] struct, excluding the peer list.
-pub async fn get_torrents_page(tracker: Arc<Tracker>, pagination: Option<&Pagination>) -> Vec<BasicInfo> {
+///
+/// Torrents are ordered by infohash, unless a [`SortBy`] field is provided.
+/// Torrents with the same value keep the infohash order.
+pub async fn get_torrents_page(
+    tracker: Arc<Tracker>,
+    pagination: Option<&Pagination>,
+    sort_by: Option<SortBy>,
+) -> Vec<BasicInfo> {
     let mut basic_infos: Vec<BasicInfo> = vec![];
 
-    for (info_hash, torrent_entry) in tracker.torrents.get_paginated(pagination) {
+    let torrents = match sort_by {
+        None => tracker.torrents.get_paginated(pagination),
+        Some(sort_by) => {
+            let mut torrents: Vec<_> = tracker
+                .torrents
+                .get_paginated(None)
+                .into_iter()
+                .map(|(info_hash, torrent_entry)| (info_hash, torrent_entry.get_torrent_stats(), torrent_entry))
+                .collect();
+
+            torrents.sort_by(|(_, a, _), (_, b, _)| sort_by.compare(b, a));
+
+            let torrents = torrents
+                .into_iter()
+                .map(|(info_hash, _, torrent_entry)| (info_hash, torrent_entry));
+
+            match pagination {
+                Some(pagination) => torrents
+                    .skip(pagination.offset as usize)
+                    .take(pagination.limit as usize)
+                    .collect(),
+                None => torrents.collect(),
+            }
+        }
+    };
+
+    for (info_hash, torrent_entry) in torrents {
         let stats = torrent_entry.get_swarm_metadata();
 
         basic_infos.push(BasicInfo {
@@ -112,6 +201,9 @@ mod tests {
         peer::Peer {
             peer_id: PeerId(*b"-qB00000000000000000"),
             peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080),
+            alt_peer_addr: None,
+            key: None,
+            crypto: peer::Crypto::NotSupported,
             updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
             uploaded: NumberOfBytes::new(0),
             downloaded: NumberOfBytes::new(0),
@@ -125,13 +217,17 @@ mod tests {
         use std::str::FromStr;
         use std::sync::Arc;
 
+        use torrust_tracker_clock::clock::Time;
         use torrust_tracker_configuration::Configuration;
         use torrust_tracker_primitives::info_hash::InfoHash;
+        use torrust_tracker_primitives::torrent_stats::TorrentStats;
         use torrust_tracker_test_helpers::configuration;
 
         use crate::core::services::torrent::tests::sample_peer;
         use crate::core::services::torrent::{get_torrent_info, Info};
         use crate::core::services::tracker_factory;
+        use crate::core::PeersWanted;
+        use crate::CurrentClock;
 
         pub fn tracker_configuration() -> Configuration {
             configuration::ephemeral()
@@ -168,9 +264,41 @@ mod tests {
                     completed: 0,
                     leechers: 0,
                     peers: Some(vec![sample_peer()]),
+                    stats: None,
                 }
             );
         }
+
+        #[tokio::test]
+        async fn should_return_the_torrent_activity_stats_when_they_are_enabled() {
+            let mut configuration = tracker_configuration();
+            configuration.core.torrent_statistics = true;
+            let tracker = Arc::new(tracker_factory(&configuration));
+
+            let info_hash = InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap();
+            tracker
+                .announce(
+                    &info_hash,
+                    &mut sample_peer(),
+                    &sample_peer().peer_addr.ip(),
+                    &PeersWanted::All,
+                )
+                .unwrap();
+            tracker.scrape(&vec![info_hash]).await;
+
+            let torrent_info = get_torrent_info(tracker.clone(), &info_hash).await.unwrap();
+
+            assert_eq!(
+                torrent_info.stats,
+                Some(TorrentStats {
+                    announces: 1,
+                    scrapes: 1,
+                    last_activity: Some(CurrentClock::now()),
+                    peak_seeders: 1,
+                    peak_leechers: 0,
+                })
+            );
+        }
     }
 
     mod searching_for_torrents {
@@ -183,7 +311,7 @@ mod tests {
         use torrust_tracker_test_helpers::configuration;
 
         use crate::core::services::torrent::tests::sample_peer;
-        use crate::core::services::torrent::{get_torrents_page, BasicInfo, Pagination};
+        use crate::core::services::torrent::{get_torrents_page, BasicInfo, Pagination, SortBy};
         use crate::core::services::tracker_factory;
 
         pub fn tracker_configuration() -> Configuration {
@@ -194,7 +322,7 @@ mod tests {
         async fn should_return_an_empty_result_if_the_tracker_does_not_have_any_torrent() {
             let tracker = Arc::new(tracker_factory(&tracker_configuration()));
 
-            let torrents = get_torrents_page(tracker.clone(), Some(&Pagination::default())).await;
+            let torrents = get_torrents_page(tracker.clone(), Some(&Pagination::default()), None).await;
 
             assert_eq!(torrents, vec![]);
         }
@@ -208,7 +336,7 @@ mod tests {
 
             tracker.upsert_peer_and_get_stats(&info_hash, &sample_peer());
 
-            let torrents = get_torrents_page(tracker.clone(), Some(&Pagination::default())).await;
+            let torrents = get_torrents_page(tracker.clone(), Some(&Pagination::default()), None).await;
 
             assert_eq!(
                 torrents,
@@ -236,7 +364,7 @@ mod tests {
             let offset = 0;
             let limit = 1;
 
-            let torrents = get_torrents_page(tracker.clone(), Some(&Pagination::new(offset, limit))).await;
+            let torrents = get_torrents_page(tracker.clone(), Some(&Pagination::new(offset, limit)), None).await;
 
             assert_eq!(torrents.len(), 1);
         }
@@ -256,7 +384,7 @@ mod tests {
             let offset = 1;
             let limit = 4000;
 
-            let torrents = get_torrents_page(tracker.clone(), Some(&Pagination::new(offset, limit))).await;
+            let torrents = get_torrents_page(tracker.clone(), Some(&Pagination::new(offset, limit)), None).await;
 
             assert_eq!(torrents.len(), 1);
             assert_eq!(
@@ -270,6 +398,34 @@ mod tests {
             );
         }
 
+        #[tokio::test]
+        async fn should_allow_sorting_the_torrents_by_their_activity_stats() {
+            let mut configuration = tracker_configuration();
+            configuration.core.torrent_statistics = true;
+            let tracker = Arc::new(tracker_factory(&configuration));
+
+            let info_hash1 = InfoHash::from_str("03840548643af2a7b63a9f5cbca348bc7150ca3a").unwrap();
+            let info_hash2 = InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap();
+
+            tracker.scrape(&vec![info_hash1, info_hash2]).await;
+            tracker.upsert_peer_and_get_stats(&info_hash1, &sample_peer());
+            tracker.upsert_peer_and_get_stats(&info_hash2, &sample_peer());
+            tracker.scrape(&vec![info_hash2]).await;
+
+            let torrents = get_torrents_page(tracker.clone(), Some(&Pagination::default()), Some(SortBy::Scrapes)).await;
+
+            assert_eq!(
+                torrents.iter().map(|torrent| torrent.info_hash).collect::<Vec<_>>(),
+                vec![info_hash2, info_hash1]
+            );
+        }
+
+        #[test]
+        fn should_fail_parsing_an_invalid_sort_field() {
+            assert_eq!("scrapes".parse::<SortBy>(), Ok(SortBy::Scrapes));
+            assert!("seeders".parse::<SortBy>().is_err());
+        }
+
         #[tokio::test]
         async fn should_return_torrents_ordered_by_info_hash() {
             let tracker = Arc::new(tracker_factory(&tracker_configuration()));
@@ -282,7 +438,7 @@ mod tests {
             let info_hash2 = InfoHash::from_str(&hash2).unwrap();
             tracker.upsert_peer_and_get_stats(&info_hash2, &sample_peer());
 
-            let torrents = get_torrents_page(tracker.clone(), Some(&Pagination::default())).await;
+            let torrents = get_torrents_page(tracker.clone(), Some(&Pagination::default()), None).await;
 
             assert_eq!(
                 torrents,
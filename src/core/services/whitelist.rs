@@ -0,0 +1,72 @@
+//! Whitelist services.
+//!
+//! - [`get_whitelist_page`]: it returns a page of the whitelisted torrents.
+//!
+//! Torrents are ordered by infohash, so that the pages are stable while the
+//! whitelist does not change.
+use std::sync::Arc;
+
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::pagination::Pagination;
+
+use crate::core::Tracker;
+
+/// It returns a page of the whitelisted torrents, ordered by infohash.
+pub async fn get_whitelist_page(tracker: Arc<Tracker>, pagination: Option<&Pagination>) -> Vec<InfoHash> {
+    let whitelist = tracker.get_whitelist().await.into_iter();
+
+    match pagination {
+        Some(pagination) => whitelist
+            .skip(pagination.offset as usize)
+            .take(pagination.limit as usize)
+            .collect(),
+        None => whitelist.collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod listing_the_whitelist {
+        use std::sync::Arc;
+
+        use torrust_tracker_primitives::info_hash::InfoHash;
+        use torrust_tracker_primitives::pagination::Pagination;
+        use torrust_tracker_test_helpers::configuration;
+
+        use crate::core::services::tracker_factory;
+        use crate::core::services::whitelist::get_whitelist_page;
+
+        fn info_hash(byte: u8) -> InfoHash {
+            InfoHash::from_bytes(&[byte; 20])
+        }
+
+        #[tokio::test]
+        async fn should_return_the_whitelisted_torrents_ordered_by_infohash() {
+            let tracker = Arc::new(tracker_factory(&configuration::ephemeral_listed()));
+
+            tracker
+                .add_torrents_to_whitelist(&[info_hash(3), info_hash(1), info_hash(2)])
+                .await
+                .unwrap();
+
+            let whitelist = get_whitelist_page(tracker.clone(), None).await;
+
+            assert_eq!(whitelist, vec![info_hash(1), info_hash(2), info_hash(3)]);
+        }
+
+        #[tokio::test]
+        async fn should_allow_paginating_the_whitelisted_torrents() {
+            let tracker = Arc::new(tracker_factory(&configuration::ephemeral_listed()));
+
+            tracker
+                .add_torrents_to_whitelist(&[info_hash(3), info_hash(1), info_hash(2)])
+                .await
+                .unwrap();
+
+            let whitelist = get_whitelist_page(tracker.clone(), Some(&Pagination::new(1, 1))).await;
+
+            assert_eq!(whitelist, vec![info_hash(2)]);
+        }
+    }
+}
@@ -0,0 +1,207 @@
+//! Torrents snapshot.
+//!
+//! The torrent repository is an in-memory structure. Only the number of
+//! completed downloads can be persisted into the database (see
+//! [`databases`](crate::core::databases)), so all the swarms are lost when the
+//! tracker is restarted, until the clients announce themselves again.
+//!
+//! When the snapshot is enabled in the core configuration:
+//!
+//! ```toml
+//! [core.snapshot]
+//! path = "./storage/tracker/lib/snapshot/torrents.json"
+//! interval = 300
+//! ```
+//!
+//! the tracker writes the whole torrent repository, peer lists included, into
+//! a JSON file:
+//!
+//! - Every `interval` seconds (unless the interval is `0`).
+//! - On graceful shutdown.
+//!
+//! And it restores it when it starts. Peers that have not been updated for
+//! more than `max_peer_timeout` seconds are discarded when the snapshot is
+//! restored, the same way the torrent cleanup job would remove them.
+//!
+//! The file is first written into a temporary file next to the snapshot and
+//! then renamed, so a crash while writing never leaves a corrupted snapshot.
+use std::net::SocketAddr;
+
+use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes, PeerId, PeerKey};
+use camino::Utf8Path;
+use serde::{Deserialize, Serialize};
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
+
+use super::json_file;
+
+/// The contents of the torrent repository at a given time.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    pub torrents: Vec<Torrent>,
+}
+
+/// A torrent entry in the snapshot.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Torrent {
+    pub info_hash: InfoHash,
+    /// The number of peers that have ever completed downloading.
+    pub downloaded: u32,
+    pub peers: Vec<Peer>,
+}
+
+/// A peer in the snapshot.
+///
+/// It mirrors [`peer::Peer`] using only types that can be deserialized.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Peer {
+    pub peer_id: [u8; 20],
+    pub peer_addr: SocketAddr,
+    /// The peer address in the other IP family. Snapshots saved before
+    /// dual-stack peers were supported don't have it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alt_peer_addr: Option<SocketAddr>,
+    /// The key the peer uses to prove its identity. Snapshots saved before
+    /// peer keys were supported don't have it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<i32>,
+    /// The support of the peer for encrypted connections. Snapshots saved
+    /// before it was stored don't have it.
+    #[serde(default)]
+    pub crypto: peer::Crypto,
+    pub updated: DurationSinceUnixEpoch,
+    pub uploaded: i64,
+    pub downloaded: i64,
+    pub left: i64,
+    pub event: Event,
+}
+
+/// The last announce event sent by the peer.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Started,
+    Stopped,
+    Completed,
+    None,
+}
+
+impl From<&peer::Peer> for Peer {
+    fn from(peer: &peer::Peer) -> Self {
+        Self {
+            peer_id: peer.peer_id.0,
+            peer_addr: peer.peer_addr,
+            alt_peer_addr: peer.alt_peer_addr,
+            key: peer.key.map(|key| key.0.get()),
+            crypto: peer.crypto,
+            updated: peer.updated,
+            uploaded: peer.uploaded.0.get(),
+            downloaded: peer.downloaded.0.get(),
+            left: peer.left.0.get(),
+            event: match peer.event {
+                AnnounceEvent::Started => Event::Started,
+                AnnounceEvent::Stopped => Event::Stopped,
+                AnnounceEvent::Completed => Event::Completed,
+                AnnounceEvent::None => Event::None,
+            },
+        }
+    }
+}
+
+impl From<Peer> for peer::Peer {
+    fn from(peer: Peer) -> Self {
+        Self {
+            peer_id: PeerId(peer.peer_id),
+            peer_addr: peer.peer_addr,
+            alt_peer_addr: peer.alt_peer_addr,
+            key: peer.key.map(PeerKey::new),
+            crypto: peer.crypto,
+            updated: peer.updated,
+            uploaded: NumberOfBytes::new(peer.uploaded),
+            downloaded: NumberOfBytes::new(peer.downloaded),
+            left: NumberOfBytes::new(peer.left),
+            event: match peer.event {
+                Event::Started => AnnounceEvent::Started,
+                Event::Stopped => AnnounceEvent::Stopped,
+                Event::Completed => AnnounceEvent::Completed,
+                Event::None => AnnounceEvent::None,
+            },
+        }
+    }
+}
+
+/// Errors writing or reading the snapshot file.
+pub use super::json_file::Error;
+
+/// It writes the snapshot into the file.
+///
+/// The parent directories are created if they do not exist.
+///
+/// # Errors
+///
+/// Will return an error if the snapshot can't be written into the file.
+pub fn save(snapshot: &Snapshot, path: &Utf8Path) -> Result<(), Error> {
+    json_file::save(snapshot, path)
+}
+
+/// It reads the snapshot from the file.
+///
+/// It returns `None` if the file does not exist yet.
+///
+/// # Errors
+///
+/// Will return an error if the file can't be read or it does not contain
+/// a valid snapshot.
+pub fn load(path: &Utf8Path) -> Result<Option<Snapshot>, Error> {
+    json_file::load(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use aquatic_udp_protocol::PeerKey;
+    use camino::Utf8PathBuf;
+    use torrust_tracker_primitives::info_hash::InfoHash;
+    use torrust_tracker_primitives::peer::fixture::PeerBuilder;
+
+    use crate::core::snapshot::{self, Snapshot, Torrent};
+
+    fn temp_snapshot_path() -> Utf8PathBuf {
+        let dir = Utf8PathBuf::try_from(env::temp_dir()).unwrap();
+        dir.join(format!("torrust_snapshot_{}", uuid::Uuid::new_v4()))
+            .join("torrents.json")
+    }
+
+    #[test]
+    fn it_should_load_the_same_snapshot_it_saved() {
+        let path = temp_snapshot_path();
+
+        let peer = PeerBuilder::default().build();
+
+        let snapshot = Snapshot {
+            torrents: vec![Torrent {
+                info_hash: "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".parse::<InfoHash>().unwrap(),
+                downloaded: 3,
+                peers: vec![(&peer).into()],
+            }],
+        };
+
+        snapshot::save(&snapshot, &path).unwrap();
+
+        assert_eq!(snapshot::load(&path).unwrap(), Some(snapshot));
+    }
+
+    #[test]
+    fn it_should_not_load_anything_when_the_snapshot_file_does_not_exist() {
+        assert_eq!(snapshot::load(&temp_snapshot_path()).unwrap(), None);
+    }
+
+    #[test]
+    fn it_should_convert_the_snapshot_peer_back_into_the_original_peer() {
+        let peer = PeerBuilder::default().with_key(&PeerKey::new(-281_766_442)).build();
+
+        let snapshot_peer: snapshot::Peer = (&peer).into();
+
+        assert_eq!(torrust_tracker_primitives::peer::Peer::from(snapshot_peer), peer);
+    }
+}
@@ -5,10 +5,16 @@
 //! - Number of connections handled
 //! - Number of `announce` requests handled
 //! - Number of `scrape` request handled
+//! - Number of requests blocked by the [IP access list](crate::core::ip_access_list)
+//! - Number of requests rejected by the [rate limiter](crate::core::rate_limiter)
+//! - Number of `announce` requests received before the minimum announce interval
 //!
 //! These metrics are collected for each connection type: UDP and HTTP and
 //! also for each IP version used by the peers: IPv4 and IPv6.
 //!
+//! The tracker also counts the hits and misses of the [scrape cache](crate::core::scrape_cache),
+//! for all the trackers together.
+//!
 //! > Notice: that UDP tracker have an specific `connection` request. For the HTTP metrics the counter counts one connection for each `announce` or `scrape` request.
 //!
 //! The data is collected by using an `event-sender -> event listener` model.
@@ -17,12 +23,18 @@
 //! The [`statistics::Keeper`](crate::core::statistics::Keeper) listens to new events and uses the [`statistics::Repo`](crate::core::statistics::Repo) to upgrade and store metrics.
 //!
 //! See the [`statistics::Event`](crate::core::statistics::Event) enum to check which events are available.
+//!
+//! The metrics are kept in memory. Optionally, they can be saved into the
+//! database periodically and on graceful shutdown, and loaded again when the
+//! tracker starts. Refer to [`Metrics::counters`](crate::core::statistics::Metrics::counters)
+//! for more information about how they are persisted.
 use std::sync::Arc;
 
 use futures::future::BoxFuture;
 use futures::FutureExt;
 #[cfg(test)]
 use mockall::{automock, predicate::str};
+use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc::error::SendError;
 use tokio::sync::{mpsc, RwLock, RwLockReadGuard};
 
@@ -34,6 +46,15 @@ const CHANNEL_BUFFER_SIZE: usize = 65_535;
 /// - `Udp` prefix means the event was triggered by the UDP tracker
 /// - `4` or `6` prefixes means the IP version used by the peer
 /// - Finally the event suffix is the type of request: `announce`, `scrape` or `connection`
+///   or `RequestBlocked` for `announce` and `scrape` requests rejected by the
+///   [IP access list](crate::core::ip_access_list), or `RequestRateLimited`
+///   for requests rejected by the [rate limiter](crate::core::rate_limiter),
+///   or `EarlyAnnounce` for `announce` requests received before the minimum
+///   announce interval.
+///
+/// The `ScrapeCache` events are not related to any tracker. They are sent
+/// for each torrent in a `scrape` request when the [scrape cache](crate::core::scrape_cache)
+/// is enabled.
 ///
 /// > NOTE: HTTP trackers do not use `connection` requests.
 #[derive(Debug, PartialEq, Eq)]
@@ -42,14 +63,28 @@ pub enum Event {
     // Attributes are enums too.
     Tcp4Announce,
     Tcp4Scrape,
+    Tcp4RequestBlocked,
+    Tcp4RequestRateLimited,
+    Tcp4EarlyAnnounce,
     Tcp6Announce,
     Tcp6Scrape,
+    Tcp6RequestBlocked,
+    Tcp6RequestRateLimited,
+    Tcp6EarlyAnnounce,
     Udp4Connect,
     Udp4Announce,
     Udp4Scrape,
+    Udp4RequestBlocked,
+    Udp4RequestRateLimited,
+    Udp4EarlyAnnounce,
     Udp6Connect,
     Udp6Announce,
     Udp6Scrape,
+    Udp6RequestBlocked,
+    Udp6RequestRateLimited,
+    Udp6EarlyAnnounce,
+    ScrapeCacheHit,
+    ScrapeCacheMiss,
 }
 
 /// Metrics collected by the tracker.
@@ -57,10 +92,16 @@ pub enum Event {
 /// - Number of connections handled
 /// - Number of `announce` requests handled
 /// - Number of `scrape` request handled
+/// - Number of requests blocked by the IP access list
+/// - Number of requests rejected by the rate limiter
+/// - Number of `announce` requests received before the minimum announce interval
 ///
 /// These metrics are collected for each connection type: UDP and HTTP
 /// and also for each IP version used by the peers: IPv4 and IPv6.
-#[derive(Debug, PartialEq, Default)]
+///
+/// It also contains the number of hits and misses of the scrape cache.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
 pub struct Metrics {
     /// Total number of TCP (HTTP tracker) connections from IPv4 peers.
     /// Since the HTTP tracker spec does not require a handshake, this metric
@@ -70,24 +111,126 @@ pub struct Metrics {
     pub tcp4_announces_handled: u64,
     /// Total number of TCP (HTTP tracker) `scrape` requests from IPv4 peers.
     pub tcp4_scrapes_handled: u64,
+    /// Total number of TCP (HTTP tracker) requests from IPv4 peers blocked by
+    /// the IP access list.
+    pub tcp4_requests_blocked: u64,
+    /// Total number of TCP (HTTP tracker) requests from IPv4 peers rejected by the rate
+    /// limiter.
+    pub tcp4_requests_rate_limited: u64,
+    /// Total number of TCP (HTTP tracker) `announce` requests from IPv4 peers that
+    /// announced again before the minimum announce interval.
+    pub tcp4_early_announces: u64,
     /// Total number of TCP (HTTP tracker) connections from IPv6 peers.
     pub tcp6_connections_handled: u64,
     /// Total number of TCP (HTTP tracker) `announce` requests from IPv6 peers.
     pub tcp6_announces_handled: u64,
     /// Total number of TCP (HTTP tracker) `scrape` requests from IPv6 peers.
     pub tcp6_scrapes_handled: u64,
+    /// Total number of TCP (HTTP tracker) requests from IPv6 peers blocked by
+    /// the IP access list.
+    pub tcp6_requests_blocked: u64,
+    /// Total number of TCP (HTTP tracker) requests from IPv6 peers rejected by the rate
+    /// limiter.
+    pub tcp6_requests_rate_limited: u64,
+    /// Total number of TCP (HTTP tracker) `announce` requests from IPv6 peers that
+    /// announced again before the minimum announce interval.
+    pub tcp6_early_announces: u64,
     /// Total number of UDP (UDP tracker) connections from IPv4 peers.
     pub udp4_connections_handled: u64,
     /// Total number of UDP (UDP tracker) `announce` requests from IPv4 peers.
     pub udp4_announces_handled: u64,
     /// Total number of UDP (UDP tracker) `scrape` requests from IPv4 peers.
     pub udp4_scrapes_handled: u64,
+    /// Total number of UDP (UDP tracker) requests from IPv4 peers blocked by
+    /// the IP access list.
+    pub udp4_requests_blocked: u64,
+    /// Total number of UDP (UDP tracker) requests from IPv4 peers rejected by the rate
+    /// limiter.
+    pub udp4_requests_rate_limited: u64,
+    /// Total number of UDP (UDP tracker) `announce` requests from IPv4 peers that
+    /// announced again before the minimum announce interval.
+    pub udp4_early_announces: u64,
     /// Total number of UDP (UDP tracker) `connection` requests from IPv6 peers.
     pub udp6_connections_handled: u64,
     /// Total number of UDP (UDP tracker) `announce` requests from IPv6 peers.
     pub udp6_announces_handled: u64,
     /// Total number of UDP (UDP tracker) `scrape` requests from IPv6 peers.
     pub udp6_scrapes_handled: u64,
+    /// Total number of UDP (UDP tracker) requests from IPv6 peers blocked by
+    /// the IP access list.
+    pub udp6_requests_blocked: u64,
+    /// Total number of UDP (UDP tracker) requests from IPv6 peers rejected by the rate
+    /// limiter.
+    pub udp6_requests_rate_limited: u64,
+    /// Total number of UDP (UDP tracker) `announce` requests from IPv6 peers that
+    /// announced again before the minimum announce interval.
+    pub udp6_early_announces: u64,
+    /// Total number of torrents in `scrape` requests whose swarm metadata was
+    /// found in the scrape cache.
+    pub scrape_cache_hits: u64,
+    /// Total number of torrents in `scrape` requests whose swarm metadata was
+    /// not found in the scrape cache.
+    pub scrape_cache_misses: u64,
+}
+
+impl Metrics {
+    /// It returns the name and the value of each counter. The name of the
+    /// counter is the name of the field.
+    ///
+    /// This is the format used to persist the metrics, so new counters don't
+    /// need any change in the database schema.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the metrics can't be serialized into a json object.
+    #[must_use]
+    pub fn counters(&self) -> Vec<(String, u64)> {
+        let serde_json::Value::Object(fields) = serde_json::to_value(self).expect("it should serialize the metrics") else {
+            panic!("the metrics should be serialized into a json object");
+        };
+
+        fields
+            .into_iter()
+            .filter_map(|(name, value)| value.as_u64().map(|value| (name, value)))
+            .collect()
+    }
+
+    /// It builds the metrics from the name and the value of each counter.
+    ///
+    /// Counters that are missing start at zero, and unknown counters are
+    /// ignored. A counter whose value can't be loaded is skipped and logged,
+    /// so the other counters keep their values.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the default metrics can't be serialized into a json
+    /// object.
+    #[must_use]
+    pub fn from_counters(counters: &[(String, u64)]) -> Self {
+        let serde_json::Value::Object(mut fields) =
+            serde_json::to_value(Self::default()).expect("it should serialize the metrics")
+        else {
+            panic!("the metrics should be serialized into a json object");
+        };
+
+        for (name, value) in counters {
+            if !fields.contains_key(name) {
+                continue;
+            }
+
+            let mut with_counter = fields.clone();
+            with_counter.insert(name.clone(), serde_json::Value::from(*value));
+
+            match serde_json::from_value::<Self>(serde_json::Value::Object(with_counter.clone())) {
+                Ok(_) => fields = with_counter,
+                Err(error) => {
+                    tracing::warn!(counter = %name, value, %error, "skipping a statistics counter that can't be loaded")
+                }
+            }
+        }
+
+        serde_json::from_value(serde_json::Value::Object(fields)).expect("the loaded counters should be valid metrics")
+    }
 }
 
 /// The service responsible for keeping tracker metrics (listening to statistics events and handle them).
@@ -147,6 +290,15 @@ async fn event_handler(event: Event, stats_repository: &Repo) {
             stats_repository.increase_tcp4_scrapes().await;
             stats_repository.increase_tcp4_connections().await;
         }
+        Event::Tcp4RequestBlocked => {
+            stats_repository.increase_tcp4_requests_blocked().await;
+        }
+        Event::Tcp4RequestRateLimited => {
+            stats_repository.increase_tcp4_requests_rate_limited().await;
+        }
+        Event::Tcp4EarlyAnnounce => {
+            stats_repository.increase_tcp4_early_announces().await;
+        }
 
         // TCP6
         Event::Tcp6Announce => {
@@ -157,6 +309,15 @@ async fn event_handler(event: Event, stats_repository: &Repo) {
             stats_repository.increase_tcp6_scrapes().await;
             stats_repository.increase_tcp6_connections().await;
         }
+        Event::Tcp6RequestBlocked => {
+            stats_repository.increase_tcp6_requests_blocked().await;
+        }
+        Event::Tcp6RequestRateLimited => {
+            stats_repository.increase_tcp6_requests_rate_limited().await;
+        }
+        Event::Tcp6EarlyAnnounce => {
+            stats_repository.increase_tcp6_early_announces().await;
+        }
 
         // UDP4
         Event::Udp4Connect => {
@@ -168,6 +329,15 @@ async fn event_handler(event: Event, stats_repository: &Repo) {
         Event::Udp4Scrape => {
             stats_repository.increase_udp4_scrapes().await;
         }
+        Event::Udp4RequestBlocked => {
+            stats_repository.increase_udp4_requests_blocked().await;
+        }
+        Event::Udp4RequestRateLimited => {
+            stats_repository.increase_udp4_requests_rate_limited().await;
+        }
+        Event::Udp4EarlyAnnounce => {
+            stats_repository.increase_udp4_early_announces().await;
+        }
 
         // UDP6
         Event::Udp6Connect => {
@@ -179,6 +349,23 @@ async fn event_handler(event: Event, stats_repository: &Repo) {
         Event::Udp6Scrape => {
             stats_repository.increase_udp6_scrapes().await;
         }
+        Event::Udp6RequestBlocked => {
+            stats_repository.increase_udp6_requests_blocked().await;
+        }
+        Event::Udp6RequestRateLimited => {
+            stats_repository.increase_udp6_requests_rate_limited().await;
+        }
+        Event::Udp6EarlyAnnounce => {
+            stats_repository.increase_udp6_early_announces().await;
+        }
+
+        // Scrape cache
+        Event::ScrapeCacheHit => {
+            stats_repository.increase_scrape_cache_hits().await;
+        }
+        Event::ScrapeCacheMiss => {
+            stats_repository.increase_scrape_cache_misses().await;
+        }
     }
 
     tracing::debug!("stats: {:?}", stats_repository.get_stats().await);
@@ -219,8 +406,15 @@ impl Default for Repo {
 impl Repo {
     #[must_use]
     pub fn new() -> Self {
+        Self::with_metrics(Metrics::default())
+    }
+
+    /// It builds a repository with the initial values of the metrics. For
+    /// example, the metrics loaded from the database.
+    #[must_use]
+    pub fn with_metrics(metrics: Metrics) -> Self {
         Self {
-            stats: Arc::new(RwLock::new(Metrics::default())),
+            stats: Arc::new(RwLock::new(metrics)),
         }
     }
 
@@ -228,6 +422,13 @@ impl Repo {
         self.stats.read().await
     }
 
+    /// It sets all the counters to zero.
+    pub async fn reset(&self) {
+        let mut stats_lock = self.stats.write().await;
+        *stats_lock = Metrics::default();
+        drop(stats_lock);
+    }
+
     pub async fn increase_tcp4_announces(&self) {
         let mut stats_lock = self.stats.write().await;
         stats_lock.tcp4_announces_handled += 1;
@@ -246,6 +447,24 @@ impl Repo {
         drop(stats_lock);
     }
 
+    pub async fn increase_tcp4_requests_blocked(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.tcp4_requests_blocked += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_tcp4_requests_rate_limited(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.tcp4_requests_rate_limited += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_tcp4_early_announces(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.tcp4_early_announces += 1;
+        drop(stats_lock);
+    }
+
     pub async fn increase_tcp6_announces(&self) {
         let mut stats_lock = self.stats.write().await;
         stats_lock.tcp6_announces_handled += 1;
@@ -264,6 +483,24 @@ impl Repo {
         drop(stats_lock);
     }
 
+    pub async fn increase_tcp6_requests_blocked(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.tcp6_requests_blocked += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_tcp6_requests_rate_limited(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.tcp6_requests_rate_limited += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_tcp6_early_announces(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.tcp6_early_announces += 1;
+        drop(stats_lock);
+    }
+
     pub async fn increase_udp4_connections(&self) {
         let mut stats_lock = self.stats.write().await;
         stats_lock.udp4_connections_handled += 1;
@@ -282,6 +519,24 @@ impl Repo {
         drop(stats_lock);
     }
 
+    pub async fn increase_udp4_requests_blocked(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.udp4_requests_blocked += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_udp4_requests_rate_limited(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.udp4_requests_rate_limited += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_udp4_early_announces(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.udp4_early_announces += 1;
+        drop(stats_lock);
+    }
+
     pub async fn increase_udp6_connections(&self) {
         let mut stats_lock = self.stats.write().await;
         stats_lock.udp6_connections_handled += 1;
@@ -299,11 +554,89 @@ impl Repo {
         stats_lock.udp6_scrapes_handled += 1;
         drop(stats_lock);
     }
+
+    pub async fn increase_udp6_requests_blocked(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.udp6_requests_blocked += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_udp6_requests_rate_limited(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.udp6_requests_rate_limited += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_udp6_early_announces(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.udp6_early_announces += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_scrape_cache_hits(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.scrape_cache_hits += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_scrape_cache_misses(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.scrape_cache_misses += 1;
+        drop(stats_lock);
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
+    mod metrics {
+        use crate::core::statistics::Metrics;
+
+        #[test]
+        fn should_be_converted_into_named_counters_and_back() {
+            let metrics = Metrics {
+                udp4_announces_handled: 1,
+                scrape_cache_hits: 2,
+                ..Default::default()
+            };
+
+            let counters = metrics.counters();
+
+            assert!(counters.contains(&("udp4_announces_handled".to_string(), 1)));
+            assert!(counters.contains(&("scrape_cache_hits".to_string(), 2)));
+            assert_eq!(Metrics::from_counters(&counters), metrics);
+        }
+
+        #[test]
+        fn should_start_the_missing_counters_at_zero_and_ignore_the_unknown_ones() {
+            let counters = vec![("udp4_announces_handled".to_string(), 1), ("removed_counter".to_string(), 2)];
+
+            assert_eq!(
+                Metrics::from_counters(&counters),
+                Metrics {
+                    udp4_announces_handled: 1,
+                    ..Default::default()
+                }
+            );
+        }
+    }
+
+    mod stats_repository {
+        use crate::core::statistics::{Metrics, Repo};
+
+        #[tokio::test]
+        async fn should_set_all_the_counters_to_zero_when_it_is_reset() {
+            let stats_repository = Repo::with_metrics(Metrics {
+                udp4_announces_handled: 1,
+                ..Default::default()
+            });
+
+            stats_repository.reset().await;
+
+            assert_eq!(*stats_repository.get_stats().await, Metrics::default());
+        }
+    }
+
     mod stats_tracker {
         use crate::core::statistics::{Event, Keeper, Metrics};
 
@@ -375,6 +708,39 @@ mod tests {
             assert_eq!(stats.tcp4_connections_handled, 1);
         }
 
+        #[tokio::test]
+        async fn should_increase_the_tcp4_requests_blocked_counter_when_it_receives_a_tcp4_request_blocked_event() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::Tcp4RequestBlocked, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.tcp4_requests_blocked, 1);
+        }
+
+        #[tokio::test]
+        async fn should_increase_the_tcp4_requests_rate_limited_counter_when_it_receives_a_tcp4_request_rate_limited_event() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::Tcp4RequestRateLimited, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.tcp4_requests_rate_limited, 1);
+        }
+
+        #[tokio::test]
+        async fn should_increase_the_tcp4_early_announces_counter_when_it_receives_a_tcp4_early_announce_event() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::Tcp4EarlyAnnounce, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.tcp4_early_announces, 1);
+        }
+
         #[tokio::test]
         async fn should_increase_the_tcp6_announces_counter_when_it_receives_a_tcp6_announce_event() {
             let stats_repository = Repo::new();
@@ -419,6 +785,39 @@ mod tests {
             assert_eq!(stats.tcp6_connections_handled, 1);
         }
 
+        #[tokio::test]
+        async fn should_increase_the_tcp6_requests_blocked_counter_when_it_receives_a_tcp6_request_blocked_event() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::Tcp6RequestBlocked, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.tcp6_requests_blocked, 1);
+        }
+
+        #[tokio::test]
+        async fn should_increase_the_tcp6_requests_rate_limited_counter_when_it_receives_a_tcp6_request_rate_limited_event() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::Tcp6RequestRateLimited, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.tcp6_requests_rate_limited, 1);
+        }
+
+        #[tokio::test]
+        async fn should_increase_the_tcp6_early_announces_counter_when_it_receives_a_tcp6_early_announce_event() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::Tcp6EarlyAnnounce, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.tcp6_early_announces, 1);
+        }
+
         #[tokio::test]
         async fn should_increase_the_udp4_connections_counter_when_it_receives_a_udp4_connect_event() {
             let stats_repository = Repo::new();
@@ -452,6 +851,39 @@ mod tests {
             assert_eq!(stats.udp4_scrapes_handled, 1);
         }
 
+        #[tokio::test]
+        async fn should_increase_the_udp4_requests_blocked_counter_when_it_receives_a_udp4_request_blocked_event() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::Udp4RequestBlocked, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.udp4_requests_blocked, 1);
+        }
+
+        #[tokio::test]
+        async fn should_increase_the_udp4_requests_rate_limited_counter_when_it_receives_a_udp4_request_rate_limited_event() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::Udp4RequestRateLimited, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.udp4_requests_rate_limited, 1);
+        }
+
+        #[tokio::test]
+        async fn should_increase_the_udp4_early_announces_counter_when_it_receives_a_udp4_early_announce_event() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::Udp4EarlyAnnounce, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.udp4_early_announces, 1);
+        }
+
         #[tokio::test]
         async fn should_increase_the_udp6_connections_counter_when_it_receives_a_udp6_connect_event() {
             let stats_repository = Repo::new();
@@ -484,5 +916,60 @@ mod tests {
 
             assert_eq!(stats.udp6_scrapes_handled, 1);
         }
+
+        #[tokio::test]
+        async fn should_increase_the_udp6_requests_blocked_counter_when_it_receives_a_udp6_request_blocked_event() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::Udp6RequestBlocked, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.udp6_requests_blocked, 1);
+        }
+
+        #[tokio::test]
+        async fn should_increase_the_udp6_requests_rate_limited_counter_when_it_receives_a_udp6_request_rate_limited_event() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::Udp6RequestRateLimited, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.udp6_requests_rate_limited, 1);
+        }
+
+        #[tokio::test]
+        async fn should_increase_the_udp6_early_announces_counter_when_it_receives_a_udp6_early_announce_event() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::Udp6EarlyAnnounce, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.udp6_early_announces, 1);
+        }
+
+        #[tokio::test]
+        async fn should_increase_the_scrape_cache_hits_counter_when_it_receives_a_scrape_cache_hit_event() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::ScrapeCacheHit, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.scrape_cache_hits, 1);
+        }
+
+        #[tokio::test]
+        async fn should_increase_the_scrape_cache_misses_counter_when_it_receives_a_scrape_cache_miss_event() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::ScrapeCacheMiss, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.scrape_cache_misses, 1);
+        }
     }
 }
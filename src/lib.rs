@@ -268,15 +268,29 @@
 //!     "tcp4_connections_handled": 0,
 //!     "tcp4_announces_handled": 0,
 //!     "tcp4_scrapes_handled": 0,
+//!     "tcp4_requests_blocked": 0,
+//!     "tcp4_requests_rate_limited": 0,
+//!     "tcp4_early_announces": 0,
 //!     "tcp6_connections_handled": 0,
 //!     "tcp6_announces_handled": 0,
 //!     "tcp6_scrapes_handled": 0,
+//!     "tcp6_requests_blocked": 0,
+//!     "tcp6_requests_rate_limited": 0,
+//!     "tcp6_early_announces": 0,
 //!     "udp4_connections_handled": 0,
 //!     "udp4_announces_handled": 0,
 //!     "udp4_scrapes_handled": 0,
+//!     "udp4_requests_blocked": 0,
+//!     "udp4_requests_rate_limited": 0,
+//!     "udp4_early_announces": 0,
 //!     "udp6_connections_handled": 0,
 //!     "udp6_announces_handled": 0,
-//!     "udp6_scrapes_handled": 0
+//!     "udp6_scrapes_handled": 0,
+//!     "udp6_requests_blocked": 0,
+//!     "udp6_requests_rate_limited": 0,
+//!     "udp6_early_announces": 0,
+//!     "scrape_cache_hits": 0,
+//!     "scrape_cache_misses": 0
 //! }
 //! ```
 //!
@@ -71,24 +71,53 @@
 //!   "tcp4_connections_handled": 0,
 //!   "tcp4_announces_handled": 0,
 //!   "tcp4_scrapes_handled": 0,
+//!   "tcp4_requests_blocked": 0,
+//!   "tcp4_requests_rate_limited": 0,
+//!   "tcp4_early_announces": 0,
 //!   "tcp6_connections_handled": 0,
 //!   "tcp6_announces_handled": 0,
 //!   "tcp6_scrapes_handled": 0,
+//!   "tcp6_requests_blocked": 0,
+//!   "tcp6_requests_rate_limited": 0,
+//!   "tcp6_early_announces": 0,
 //!   "udp4_connections_handled": 0,
 //!   "udp4_announces_handled": 0,
 //!   "udp4_scrapes_handled": 0,
+//!   "udp4_requests_blocked": 0,
+//!   "udp4_requests_rate_limited": 0,
+//!   "udp4_early_announces": 0,
 //!   "udp6_connections_handled": 0,
 //!   "udp6_announces_handled": 0,
-//!   "udp6_scrapes_handled": 0
+//!   "udp6_scrapes_handled": 0,
+//!   "udp6_requests_blocked": 0,
+//!   "udp6_requests_rate_limited": 0,
+//!   "udp6_early_announces": 0,
+//!   "scrape_cache_hits": 0,
+//!   "scrape_cache_misses": 0
 //! }
 //! ```
 //!
 //! # Authentication
 //!
-//! The API supports authentication using a GET parameter token.
+//! The API supports authentication using a bearer token in the `Authorization`
+//! header:
+//!
+//! ```text
+//! curl -H "Authorization: Bearer MyAccessToken" http://0.0.0.0:1212/api/v1/stats
+//! ```
+//!
+//! Or using a GET parameter token:
 //!
 //! <http://0.0.0.0:1212/api/v1/stats?token=MyAccessToken>
 //!
+//! The GET parameter can be disabled, so that tokens do not end up in the
+//! access logs:
+//!
+//! ```toml
+//! [http_api]
+//! query_token_enabled = false
+//! ```
+//!
 //! You can set as many tokens as you want in the configuration file:
 //!
 //! ```toml
@@ -96,8 +125,14 @@
 //! admin = "MyAccessToken"
 //! ```
 //!
-//! The token label is used to identify the token. All tokens have full access
-//! to the API.
+//! The token label is used to identify the token. By default, tokens have full
+//! access to the API. Their access can be limited to some scopes, for example,
+//! to have a read-only token for monitoring:
+//!
+//! ```toml
+//! [http_api.access_token_scopes]
+//! monitoring = ["stats:read", "torrents:read"]
+//! ```
 //!
 //! Refer to [`torrust-tracker-configuration`](torrust_tracker_configuration)
 //! for more information about the API configuration and to the
@@ -5,41 +5,50 @@
 //!
 //! All the API routes have the `/api` prefix and the version number as the
 //! first path segment. For example: `/api/v1/torrents`.
+//!
+//! The value of the `token` query param is redacted in the request spans, so
+//! the API access tokens are not logged.
 use std::sync::Arc;
 use std::time::Duration;
 
 use axum::error_handling::HandleErrorLayer;
-use axum::http::HeaderName;
+use axum::http::{HeaderName, Uri};
 use axum::response::Response;
 use axum::routing::get;
 use axum::{middleware, BoxError, Router};
 use hyper::{Request, StatusCode};
-use torrust_tracker_configuration::{AccessTokens, DEFAULT_TIMEOUT};
+use torrust_tracker_configuration::DEFAULT_TIMEOUT;
 use tower::timeout::TimeoutLayer;
 use tower::ServiceBuilder;
 use tower_http::compression::CompressionLayer;
 use tower_http::propagate_header::PropagateHeaderLayer;
 use tower_http::request_id::{MakeRequestUuid, SetRequestIdLayer};
-use tower_http::trace::{DefaultMakeSpan, TraceLayer};
+use tower_http::trace::TraceLayer;
 use tracing::{instrument, Level, Span};
+use url::form_urlencoded;
 
 use super::v1;
 use super::v1::context::health_check::handlers::health_check_handler;
 use super::v1::middlewares::auth::State;
+use crate::bootstrap::reload::Reloader;
 use crate::core::Tracker;
 use crate::servers::apis::API_LOG_TARGET;
 
 /// Add all API routes to the router.
 #[allow(clippy::needless_pass_by_value)]
-#[instrument(skip(tracker, access_tokens))]
-pub fn router(tracker: Arc<Tracker>, access_tokens: Arc<AccessTokens>) -> Router {
+#[instrument(skip(tracker, reloader))]
+pub fn router(tracker: Arc<Tracker>, reloader: Arc<Reloader>) -> Router {
     let router = Router::new();
 
     let api_url_prefix = "/api";
 
-    let router = v1::routes::add(api_url_prefix, router, tracker.clone());
+    let state = State {
+        access_tokens: reloader.access_tokens(),
+        access_token_scopes: reloader.access_token_scopes(),
+        query_token_enabled: reloader.query_token_enabled(),
+    };
 
-    let state = State { access_tokens };
+    let router = v1::routes::add(api_url_prefix, router, tracker.clone(), reloader);
 
     router
         .layer(middleware::from_fn_with_state(state, v1::middlewares::auth::auth))
@@ -49,10 +58,18 @@ pub fn router(tracker: Arc<Tracker>, access_tokens: Arc<AccessTokens>) -> Router
         .layer(PropagateHeaderLayer::new(HeaderName::from_static("x-request-id")))
         .layer(
             TraceLayer::new_for_http()
-                .make_span_with(DefaultMakeSpan::new().level(Level::INFO))
+                .make_span_with(|request: &Request<axum::body::Body>| {
+                    tracing::span!(
+                        Level::INFO,
+                        "request",
+                        method = %request.method(),
+                        uri = %redacted_uri(request.uri()),
+                        version = ?request.version()
+                    )
+                })
                 .on_request(|request: &Request<axum::body::Body>, _span: &Span| {
                     let method = request.method().to_string();
-                    let uri = request.uri().to_string();
+                    let uri = redacted_uri(request.uri());
                     let request_id = request
                         .headers()
                         .get("x-request-id")
@@ -86,3 +103,60 @@ pub fn router(tracker: Arc<Tracker>, access_tokens: Arc<AccessTokens>) -> Router
                 .layer(TimeoutLayer::new(DEFAULT_TIMEOUT)),
         )
 }
+
+/// It returns the request URI with the value of the `token` query param
+/// replaced by `***`.
+///
+/// The param names are percent-decoded, the same way the auth middleware does,
+/// so an encoded name like `%74oken` is also redacted.
+fn redacted_uri(uri: &Uri) -> String {
+    let Some(query) = uri.query() else {
+        return uri.to_string();
+    };
+
+    let query = query
+        .split('&')
+        .map(|param| {
+            let is_token = form_urlencoded::parse(param.as_bytes())
+                .next()
+                .is_some_and(|(name, _)| name == "token");
+
+            if is_token {
+                "token=***"
+            } else {
+                param
+            }
+        })
+        .collect::<Vec<&str>>()
+        .join("&");
+
+    format!("{}?{query}", uri.path())
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::Uri;
+
+    use super::redacted_uri;
+
+    #[test]
+    fn it_should_redact_the_token_query_param_in_the_uri() {
+        let uri: Uri = "/api/v1/torrents?limit=1&token=MyAccessToken&offset=2".parse().unwrap();
+
+        assert_eq!(redacted_uri(&uri), "/api/v1/torrents?limit=1&token=***&offset=2");
+    }
+
+    #[test]
+    fn it_should_redact_the_token_query_param_when_its_name_is_percent_encoded() {
+        let uri: Uri = "/api/v1/torrents?limit=1&%74oken=MyAccessToken".parse().unwrap();
+
+        assert_eq!(redacted_uri(&uri), "/api/v1/torrents?limit=1&token=***");
+    }
+
+    #[test]
+    fn it_should_not_change_the_uri_when_there_is_no_token_query_param() {
+        let uri: Uri = "/api/v1/torrents?limit=1".parse().unwrap();
+
+        assert_eq!(redacted_uri(&uri), "/api/v1/torrents?limit=1");
+    }
+}
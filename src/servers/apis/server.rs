@@ -33,11 +33,11 @@ use derive_more::Constructor;
 use futures::future::BoxFuture;
 use thiserror::Error;
 use tokio::sync::oneshot::{Receiver, Sender};
-use torrust_tracker_configuration::AccessTokens;
 use tracing::{instrument, Level};
 
 use super::routes::router;
 use crate::bootstrap::jobs::Started;
+use crate::bootstrap::reload::Reloader;
 use crate::core::Tracker;
 use crate::servers::apis::API_LOG_TARGET;
 use crate::servers::custom_axum_server::{self, TimeoutAcceptor};
@@ -122,12 +122,12 @@ impl ApiServer<Stopped> {
     /// # Panics
     ///
     /// It would panic if the bound socket address cannot be sent back to this starter.
-    #[instrument(skip(self, tracker, form, access_tokens), err, ret(Display, level = Level::INFO))]
+    #[instrument(skip(self, tracker, form, reloader), err, ret(Display, level = Level::INFO))]
     pub async fn start(
         self,
         tracker: Arc<Tracker>,
         form: ServiceRegistrationForm,
-        access_tokens: Arc<AccessTokens>,
+        reloader: Arc<Reloader>,
     ) -> Result<ApiServer<Running>, Error> {
         let (tx_start, rx_start) = tokio::sync::oneshot::channel::<Started>();
         let (tx_halt, rx_halt) = tokio::sync::oneshot::channel::<Halted>();
@@ -137,7 +137,7 @@ impl ApiServer<Stopped> {
         let task = tokio::spawn(async move {
             tracing::debug!(target: API_LOG_TARGET, "Starting with launcher in spawned task ...");
 
-            let _task = launcher.start(tracker, access_tokens, tx_start, rx_halt).await;
+            let _task = launcher.start(tracker, reloader, tx_start, rx_halt).await;
 
             tracing::debug!(target: API_LOG_TARGET, "Started with launcher in spawned task");
 
@@ -235,15 +235,15 @@ impl Launcher {
     ///
     /// Will panic if unable to bind to the socket, or unable to get the address of the bound socket.
     /// Will also panic if unable to send message regarding the bound socket address.
-    #[instrument(skip(self, tracker, access_tokens, tx_start, rx_halt))]
+    #[instrument(skip(self, tracker, reloader, tx_start, rx_halt))]
     pub fn start(
         &self,
         tracker: Arc<Tracker>,
-        access_tokens: Arc<AccessTokens>,
+        reloader: Arc<Reloader>,
         tx_start: Sender<Started>,
         rx_halt: Receiver<Halted>,
     ) -> BoxFuture<'static, ()> {
-        let router = router(tracker, access_tokens);
+        let router = router(tracker, reloader);
         let socket = std::net::TcpListener::bind(self.bind_to).expect("Could not bind tcp_listener to address.");
         let address = socket.local_addr().expect("Could not get local_addr from tcp_listener.");
 
@@ -297,6 +297,7 @@ mod tests {
 
     use crate::bootstrap::app::initialize_with_configuration;
     use crate::bootstrap::jobs::make_rust_tls;
+    use crate::bootstrap::reload::Reloader;
     use crate::servers::apis::server::{ApiServer, Launcher};
     use crate::servers::registar::Registar;
 
@@ -313,14 +314,19 @@ mod tests {
             .await
             .map(|tls| tls.expect("tls config failed"));
 
-        let access_tokens = Arc::new(config.access_tokens.clone());
+        let loaded_cfg = cfg.clone();
+        let reloader = Arc::new(Reloader::new(
+            &cfg,
+            tracker.clone(),
+            Box::new(move || Ok((*loaded_cfg).clone())),
+        ));
 
         let stopped = ApiServer::new(Launcher::new(bind_to, tls));
 
         let register = &Registar::default();
 
         let started = stopped
-            .start(tracker, register.give_form(), access_tokens)
+            .start(tracker, register.give_form(), reloader)
             .await
             .expect("it should start the server");
         let stopped = started.stop().await.expect("it should stop the server");
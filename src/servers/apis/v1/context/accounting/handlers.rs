@@ -0,0 +1,68 @@
+//! API handlers for the [`accounting`](crate::servers::apis::v1::context::accounting)
+//! API context.
+use std::str::FromStr;
+use std::sync::Arc;
+
+use axum::extract::{self, Path, State};
+use axum::response::Response;
+use serde::Deserialize;
+use torrust_tracker_primitives::pagination::Pagination;
+
+use super::resources::KeyTraffic;
+use super::responses::{key_traffic_response, keys_traffic_response};
+use crate::core::auth::Key;
+use crate::core::services::accounting::{get_key_traffic, get_keys_traffic_page};
+use crate::core::Tracker;
+use crate::servers::apis::v1::context::auth_key::responses::auth_key_not_known_response;
+use crate::servers::apis::v1::responses::invalid_auth_key_param_response;
+
+/// A container for the URL query parameters of the keys traffic.
+///
+/// Pagination: `offset` and `limit`.
+#[derive(Deserialize, Debug)]
+pub struct QueryParams {
+    /// The offset of the first page to return. Starts at 0.
+    pub offset: Option<u32>,
+    /// The maximum number of items to return per page.
+    pub limit: Option<u32>,
+}
+
+/// A container for the `key` parameter extracted from the URL PATH.
+#[derive(Deserialize)]
+pub struct KeyParam(String);
+
+/// It handles the request to get the traffic of the authentication keys.
+///
+/// It returns a `200` response with a json array with [`KeyTraffic`]
+/// resources.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::accounting#list-the-traffic-of-the-keys)
+/// for more information about this endpoint.
+pub async fn get_keys_traffic_handler(
+    State(tracker): State<Arc<Tracker>>,
+    extract::Query(params): extract::Query<QueryParams>,
+) -> Response {
+    let keys_traffic = get_keys_traffic_page(&tracker, Some(&Pagination::new_with_options(params.offset, params.limit)));
+
+    keys_traffic_response(&keys_traffic.into_iter().map(KeyTraffic::from).collect())
+}
+
+/// It handles the request to get the traffic of an authentication key.
+///
+/// It returns:
+///
+/// - `200` with a json [`KeyTraffic`] resource. If the tracker has the key.
+/// - `200` with the json string `"auth key not known"`. If the tracker does
+///   not have the key.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::accounting#get-the-traffic-of-a-key)
+/// for more information about this endpoint.
+pub async fn get_key_traffic_handler(State(tracker): State<Arc<Tracker>>, Path(key): Path<KeyParam>) -> Response {
+    match Key::from_str(&key.0) {
+        Err(_) => invalid_auth_key_param_response(&key.0),
+        Ok(key) => match get_key_traffic(tracker.clone(), &key).await {
+            Some(traffic) => key_traffic_response(&KeyTraffic::from((key, traffic))),
+            None => auth_key_not_known_response(),
+        },
+    }
+}
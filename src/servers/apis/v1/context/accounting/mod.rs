@@ -0,0 +1,96 @@
+//! Accounting API context.
+//!
+//! This API context is responsible for handling all the requests related to
+//! the traffic of the authentication keys.
+//!
+//! In private mode, the tracker links each peer to the authentication key it
+//! announced with and accumulates the bytes the peers uploaded and downloaded
+//! for each key. Refer to the [`accounting`](crate::core::accounting) module
+//! for more information about how the traffic is accounted.
+//!
+//! # Endpoints
+//!
+//! - [List the traffic of the keys](#list-the-traffic-of-the-keys)
+//! - [Get the traffic of a key](#get-the-traffic-of-a-key)
+//!
+//! # List the traffic of the keys
+//!
+//! `GET /accounting`
+//!
+//! It returns the traffic of the keys that have announced any traffic, ordered
+//! by key.
+//!
+//! **Query parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `offset` | positive integer | The page number, starting at 0. | No | `1`
+//! `limit` | positive integer | Page size. The number of results per page. | No | `10`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/accounting?token=MyAccessToken&offset=0&limit=10"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! [
+//!     {
+//!         "key": "xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6",
+//!         "uploaded": 3145728,
+//!         "downloaded": 2097152,
+//!         "ratio": 1.5
+//!     }
+//! ]
+//! ```
+//!
+//! **Resource**
+//!
+//! Refer to the API [`KeyTraffic`](crate::servers::apis::v1::context::accounting::resources::KeyTraffic)
+//! resource for more information about the response attributes.
+//!
+//! # Get the traffic of a key
+//!
+//! `GET /accounting/:key`
+//!
+//! It returns the traffic of an authentication key. Keys that have not
+//! announced any traffic yet have zero traffic.
+//!
+//! **Path parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `key` | 32-char string (0-9, a-z, A-Z) | The authentication key | Yes | `xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/accounting/xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "key": "xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6",
+//!     "uploaded": 0,
+//!     "downloaded": 0,
+//!     "ratio": null
+//! }
+//! ```
+//!
+//! The `ratio` is `null` when the key has not downloaded anything yet.
+//!
+//! **Not Found response** `200`
+//!
+//! This response is returned when the tracker does not know the key.
+//!
+//! ```json
+//! "auth key not known"
+//! ```
+pub mod handlers;
+pub mod resources;
+pub mod responses;
+pub mod routes;
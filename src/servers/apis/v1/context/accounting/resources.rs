@@ -0,0 +1,59 @@
+//! API resources for the [`accounting`](crate::servers::apis::v1::context::accounting)
+//! API context.
+use serde::{Deserialize, Serialize};
+
+use crate::core::accounting::Traffic;
+use crate::core::auth::Key;
+
+/// A resource that represents the traffic of an authentication key.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct KeyTraffic {
+    /// The authentication key.
+    pub key: String,
+    /// The total number of bytes uploaded by the peers using the key.
+    pub uploaded: u64,
+    /// The total number of bytes downloaded by the peers using the key.
+    pub downloaded: u64,
+    /// The uploaded/downloaded ratio. It's `None` when the peers have not
+    /// downloaded anything yet.
+    pub ratio: Option<f64>,
+}
+
+impl From<(Key, Traffic)> for KeyTraffic {
+    fn from((key, traffic): (Key, Traffic)) -> Self {
+        Self {
+            key: key.to_string(),
+            uploaded: traffic.uploaded,
+            downloaded: traffic.downloaded,
+            ratio: traffic.ratio(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeyTraffic;
+    use crate::core::accounting::Traffic;
+    use crate::core::auth::Key;
+
+    #[test]
+    fn it_should_be_convertible_from_the_key_traffic() {
+        let key = "xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6".parse::<Key>().unwrap();
+
+        assert_eq!(
+            KeyTraffic::from((
+                key,
+                Traffic {
+                    uploaded: 30,
+                    downloaded: 20
+                }
+            )),
+            KeyTraffic {
+                key: "xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6".to_string(),
+                uploaded: 30,
+                downloaded: 20,
+                ratio: Some(1.5),
+            }
+        );
+    }
+}
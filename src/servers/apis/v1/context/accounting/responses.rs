@@ -0,0 +1,36 @@
+//! API responses for the [`accounting`](crate::servers::apis::v1::context::accounting)
+//! API context.
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+
+use super::resources::KeyTraffic;
+
+/// `200` response that contains a `KeyTraffic` resource as json.
+///
+/// # Panics
+///
+/// Will panic if it can't convert the `KeyTraffic` resource to json
+#[must_use]
+pub fn key_traffic_response(key_traffic: &KeyTraffic) -> Response {
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "application/json; charset=utf-8")],
+        serde_json::to_string(key_traffic).unwrap(),
+    )
+        .into_response()
+}
+
+/// `200` response that contains an array of `KeyTraffic` resources as json.
+///
+/// # Panics
+///
+/// Will panic if it can't convert the `KeyTraffic` resources to json
+#[must_use]
+pub fn keys_traffic_response(keys_traffic: &Vec<KeyTraffic>) -> Response {
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "application/json; charset=utf-8")],
+        serde_json::to_string(keys_traffic).unwrap(),
+    )
+        .into_response()
+}
@@ -0,0 +1,35 @@
+//! API routes for the [`accounting`](crate::servers::apis::v1::context::accounting)
+//! API context.
+//!
+//! - `GET /accounting`
+//! - `GET /accounting/:key`
+//!
+//! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::accounting).
+use std::sync::Arc;
+
+use axum::routing::get;
+use axum::{middleware, Router};
+use torrust_tracker_configuration::Scope;
+
+use super::handlers::{get_key_traffic_handler, get_keys_traffic_handler};
+use crate::core::Tracker;
+use crate::servers::apis::v1::middlewares::auth::require_scope;
+
+/// It adds the routes to the router for the [`accounting`](crate::servers::apis::v1::context::accounting) API context.
+pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
+    let prefix = format!("{prefix}/accounting");
+
+    router
+        .route(
+            &prefix,
+            get(get_keys_traffic_handler)
+                .route_layer(middleware::from_fn_with_state(Scope::AccountingRead, require_scope))
+                .with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/:key"),
+            get(get_key_traffic_handler)
+                .route_layer(middleware::from_fn_with_state(Scope::AccountingRead, require_scope))
+                .with_state(tracker),
+        )
+}
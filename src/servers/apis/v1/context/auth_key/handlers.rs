@@ -6,13 +6,16 @@ use std::time::Duration;
 use axum::extract::{self, Path, State};
 use axum::response::Response;
 use serde::Deserialize;
+use torrust_tracker_primitives::pagination::Pagination;
 
 use super::forms::AddKeyForm;
 use super::responses::{
-    auth_key_response, failed_to_delete_key_response, failed_to_generate_key_response, failed_to_reload_keys_response,
-    invalid_auth_key_duration_response, invalid_auth_key_response,
+    auth_key_list_response, auth_key_not_known_response, auth_key_response, failed_to_delete_key_response,
+    failed_to_generate_key_response, failed_to_reload_keys_response, invalid_auth_key_duration_response,
+    invalid_auth_key_response,
 };
 use crate::core::auth::Key;
+use crate::core::services::auth_key::{get_auth_key, get_auth_keys_page, KeyFilters};
 use crate::core::{AddKeyRequest, Tracker};
 use crate::servers::apis::v1::context::auth_key::resources::AuthKey;
 use crate::servers::apis::v1::responses::{invalid_auth_key_param_response, ok_response};
@@ -91,6 +94,78 @@ pub async fn generate_auth_key_handler(State(tracker): State<Arc<Tracker>>, Path
 #[derive(Deserialize)]
 pub struct KeyParam(String);
 
+/// It handles the request to get an authentication key.
+///
+/// It returns:
+///
+/// - `200` with a json [`AuthKey`] resource. If the tracker has the key.
+/// - `200` with the json string `"auth key not known"`. If the tracker does
+///   not have the key.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::auth_key#get-an-authentication-key)
+/// for more information about this endpoint.
+pub async fn get_auth_key_handler(State(tracker): State<Arc<Tracker>>, Path(seconds_valid_or_key): Path<KeyParam>) -> Response {
+    match Key::from_str(&seconds_valid_or_key.0) {
+        Err(_) => invalid_auth_key_param_response(&seconds_valid_or_key.0),
+        Ok(key) => match get_auth_key(tracker.clone(), &key).await {
+            Some(peer_key) => auth_key_response(&AuthKey::from(peer_key)),
+            None => auth_key_not_known_response(),
+        },
+    }
+}
+
+/// A container for the URL query parameters of the authentication key list.
+///
+/// Pagination: `offset` and `limit`.
+/// Filters: `expired`, `permanent` and `expiring_before`.
+///
+/// For example, the expiring keys that are still valid but will expire before
+/// `2023-03-28 13:25:00 UTC`:
+///
+/// <http://127.0.0.1:1212/api/v1/keys?token=MyAccessToken&permanent=false&expired=false&expiring_before=1680009900>
+#[derive(Deserialize, Debug)]
+pub struct QueryParams {
+    /// The offset of the first page to return. Starts at 0.
+    pub offset: Option<u32>,
+    /// The maximum number of items to return per page.
+    pub limit: Option<u32>,
+    /// Only the expired keys (`true`) or the keys that are still valid
+    /// (`false`).
+    pub expired: Option<bool>,
+    /// Only the permanent keys (`true`) or the keys with an expiration time
+    /// (`false`).
+    pub permanent: Option<bool>,
+    /// Only the keys that expire before this timestamp, in seconds since the
+    /// Unix epoch.
+    pub expiring_before: Option<u64>,
+}
+
+/// It handles the request to get a list of authentication keys.
+///
+/// It returns a `200` response with a json array with [`AuthKey`] resources.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::auth_key#list-authentication-keys)
+/// for more information about this endpoint.
+pub async fn get_auth_keys_handler(
+    State(tracker): State<Arc<Tracker>>,
+    extract::Query(params): extract::Query<QueryParams>,
+) -> Response {
+    let filters = KeyFilters {
+        expired: params.expired,
+        permanent: params.permanent,
+        expiring_before: params.expiring_before.map(Duration::from_secs),
+    };
+
+    let peer_keys = get_auth_keys_page(
+        tracker.clone(),
+        &filters,
+        Some(&Pagination::new_with_options(params.offset, params.limit)),
+    )
+    .await;
+
+    auth_key_list_response(&peer_keys.into_iter().map(AuthKey::from).collect::<Vec<AuthKey>>())
+}
+
 /// It handles the request to delete an authentication key.
 ///
 /// It returns two types of responses:
@@ -16,10 +16,91 @@
 //!
 //! # Endpoints
 //!
+//! - [List authentication keys](#list-authentication-keys)
+//! - [Get an authentication key](#get-an-authentication-key)
 //! - [Generate a new authentication key](#generate-a-new-authentication-key)
 //! - [Delete an authentication key](#delete-an-authentication-key)
 //! - [Reload authentication keys](#reload-authentication-keys)
 //!
+//! # List authentication keys
+//!
+//! `GET /keys`
+//!
+//! It returns the authentication keys ordered by key.
+//!
+//! **Query parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `offset` | positive integer | The page number, starting at 0. | No | `1`
+//! `limit` | positive integer | Page size. The number of results per page. | No | `10`
+//! `expired` | boolean | Only the expired keys (`true`) or the keys that are still valid (`false`). | No | `true`
+//! `permanent` | boolean | Only the permanent keys (`true`) or the keys with an expiration time (`false`). | No | `false`
+//! `expiring_before` | positive integer | Only the keys that expire before this timestamp, in seconds since the Unix epoch. | No | `1680009900`
+//!
+//! Keys must match all the filters.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/keys?token=MyAccessToken&expired=false&expiring_before=1680009900"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! [
+//!     {
+//!         "key": "xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6",
+//!         "valid_until": 1680009900,
+//!         "expiry_time": "2023-03-28 13:25:00.058085050 UTC"
+//!     }
+//! ]
+//! ```
+//!
+//! **Resource**
+//!
+//! Refer to the API [`AuthKey`](crate::servers::apis::v1::context::auth_key::resources::AuthKey)
+//! resource for more information about the response attributes.
+//!
+//! # Get an authentication key
+//!
+//! `GET /key/:key`
+//!
+//! It returns the authentication key with its expiration time.
+//!
+//! **Path parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `key` | 32-char string | The `key` to get. | Yes | `xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/key/xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "key": "xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6",
+//!     "valid_until": null,
+//!     "expiry_time": null
+//! }
+//! ```
+//!
+//! Permanent keys do not have `valid_until` and `expiry_time`.
+//!
+//! **Not Found response** `200`
+//!
+//! This response is returned when the tracker does not have the key.
+//!
+//! ```json
+//! "auth key not known"
+//! ```
+//!
 //! # Generate a new authentication key
 //!
 //! `POST /keys`
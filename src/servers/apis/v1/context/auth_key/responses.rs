@@ -2,7 +2,8 @@
 use std::error::Error;
 
 use axum::http::{header, StatusCode};
-use axum::response::{IntoResponse, Response};
+use axum::response::{IntoResponse, Json, Response};
+use serde_json::json;
 
 use crate::servers::apis::v1::context::auth_key::resources::AuthKey;
 use crate::servers::apis::v1::responses::{bad_request_response, unhandled_rejection_response};
@@ -22,6 +23,28 @@ pub fn auth_key_response(auth_key: &AuthKey) -> Response {
         .into_response()
 }
 
+/// `200` response that contains an array of `AuthKey` resources as json.
+///
+/// # Panics
+///
+/// Will panic if it can't convert the `AuthKey` resources to json
+#[must_use]
+pub fn auth_key_list_response(auth_keys: &Vec<AuthKey>) -> Response {
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "application/json; charset=utf-8")],
+        serde_json::to_string(auth_keys).unwrap(),
+    )
+        .into_response()
+}
+
+/// `200` response with the json string `"auth key not known"`, returned when
+/// the tracker does not have the authentication key.
+#[must_use]
+pub fn auth_key_not_known_response() -> Response {
+    Json(json!("auth key not known")).into_response()
+}
+
 // Error responses
 
 /// `500` error response when a new authentication key cannot be generated.
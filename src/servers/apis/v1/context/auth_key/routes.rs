@@ -2,17 +2,25 @@
 //! API context.
 //!
 //! - `POST /key/:seconds_valid`
+//! - `GET /key/:key`
 //! - `DELETE /key/:key`
+//! - `GET /keys`
+//! - `POST /keys`
 //! - `GET /keys/reload`
 //!
 //! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::auth_key).
 use std::sync::Arc;
 
 use axum::routing::{get, post};
-use axum::Router;
+use axum::{middleware, Router};
+use torrust_tracker_configuration::Scope;
 
-use super::handlers::{add_auth_key_handler, delete_auth_key_handler, generate_auth_key_handler, reload_keys_handler};
+use super::handlers::{
+    add_auth_key_handler, delete_auth_key_handler, generate_auth_key_handler, get_auth_key_handler, get_auth_keys_handler,
+    reload_keys_handler,
+};
 use crate::core::Tracker;
+use crate::servers::apis::v1::middlewares::auth::require_scope;
 
 /// It adds the routes to the router for the [`auth_key`](crate::servers::apis::v1::context::auth_key) API context.
 pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
@@ -29,14 +37,33 @@ pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
             // Use POST /keys
             &format!("{prefix}/key/:seconds_valid_or_key"),
             post(generate_auth_key_handler)
-                .with_state(tracker.clone())
                 .delete(delete_auth_key_handler)
+                .route_layer(middleware::from_fn_with_state(Scope::KeysWrite, require_scope))
+                .with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/key/:seconds_valid_or_key"),
+            get(get_auth_key_handler)
+                .route_layer(middleware::from_fn_with_state(Scope::KeysRead, require_scope))
                 .with_state(tracker.clone()),
         )
         // Keys command
         .route(
             &format!("{prefix}/keys/reload"),
-            get(reload_keys_handler).with_state(tracker.clone()),
+            get(reload_keys_handler)
+                .route_layer(middleware::from_fn_with_state(Scope::KeysWrite, require_scope))
+                .with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/keys"),
+            get(get_auth_keys_handler)
+                .route_layer(middleware::from_fn_with_state(Scope::KeysRead, require_scope))
+                .with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/keys"),
+            post(add_auth_key_handler)
+                .route_layer(middleware::from_fn_with_state(Scope::KeysWrite, require_scope))
+                .with_state(tracker),
         )
-        .route(&format!("{prefix}/keys"), post(add_auth_key_handler).with_state(tracker))
 }
@@ -0,0 +1,62 @@
+//! Configuration API context.
+//!
+//! This API context is responsible for handling the requests related to the
+//! tracker configuration.
+//!
+//! # Endpoints
+//!
+//! - [Reload the configuration](#reload-the-configuration)
+//!
+//! # Reload the configuration
+//!
+//! `POST /config/reload`
+//!
+//! It loads the configuration again from the same sources used when the
+//! tracker started (the config file and the env vars) and applies the settings
+//! that can be changed without restarting the tracker. The same reload is
+//! triggered when the tracker process receives the `SIGHUP` signal.
+//!
+//! The response contains the settings that have changed: the ones that have
+//! been applied and the ones that will only be used after restarting the
+//! tracker, like the `bind_address` of a service.
+//!
+//! Refer to the [`reload`](crate::bootstrap::reload) module for the list of
+//! settings that can be changed without restarting the tracker.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X POST "http://127.0.0.1:1212/api/v1/config/reload?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "applied": [
+//!         "core.announce_policy",
+//!         "http_api.access_tokens"
+//!     ],
+//!     "requires_restart": [
+//!         "http_api.bind_address"
+//!     ]
+//! }
+//! ```
+//!
+//! **Resource**
+//!
+//! Refer to the API [`ReloadReport`](crate::servers::apis::v1::context::config::resources::ReloadReport)
+//! resource for more information about the response attributes.
+//!
+//! **Example error response** `500`
+//!
+//! When the configuration can't be loaded or it's not valid. In that case no
+//! setting is changed.
+//!
+//! ```text
+//! failed to reload configuration: invalid configuration: ...
+//! ```
+pub mod handlers;
+pub mod resources;
+pub mod responses;
+pub mod routes;
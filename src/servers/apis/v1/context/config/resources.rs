@@ -0,0 +1,25 @@
+//! API resources for the [`config`](crate::servers::apis::v1::context::config)
+//! API context.
+use serde::{Deserialize, Serialize};
+
+use crate::bootstrap::reload::Report;
+
+/// It contains the settings that have changed after reloading the
+/// configuration.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct ReloadReport {
+    /// Settings that have been applied. For example: `core.announce_policy`.
+    pub applied: Vec<String>,
+    /// Settings that have changed but are only used after restarting the
+    /// tracker. For example: `http_api.bind_address`.
+    pub requires_restart: Vec<String>,
+}
+
+impl From<Report> for ReloadReport {
+    fn from(report: Report) -> Self {
+        Self {
+            applied: report.applied,
+            requires_restart: report.requires_restart,
+        }
+    }
+}
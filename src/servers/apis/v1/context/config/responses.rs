@@ -0,0 +1,20 @@
+//! API responses for the [`config`](crate::servers::apis::v1::context::config)
+//! API context.
+use std::error::Error;
+
+use axum::response::{Json, Response};
+
+use super::resources::ReloadReport;
+use crate::bootstrap::reload::Report;
+use crate::servers::apis::v1::responses::unhandled_rejection_response;
+
+/// `200` response that contains the [`ReloadReport`] resource as json.
+pub fn reload_report_response(report: Report) -> Json<ReloadReport> {
+    Json(ReloadReport::from(report))
+}
+
+/// `500` error response when the configuration cannot be reloaded.
+#[must_use]
+pub fn failed_to_reload_configuration_response<E: Error>(e: E) -> Response {
+    unhandled_rejection_response(format!("failed to reload configuration: {e}"))
+}
@@ -0,0 +1,24 @@
+//! API routes for the [`config`](crate::servers::apis::v1::context::config) API context.
+//!
+//! - `POST /config/reload`
+//!
+//! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::config).
+use std::sync::Arc;
+
+use axum::routing::post;
+use axum::{middleware, Router};
+use torrust_tracker_configuration::Scope;
+
+use super::handlers::reload_configuration_handler;
+use crate::bootstrap::reload::Reloader;
+use crate::servers::apis::v1::middlewares::auth::require_scope;
+
+/// It adds the routes to the router for the [`config`](crate::servers::apis::v1::context::config) API context.
+pub fn add(prefix: &str, router: Router, reloader: Arc<Reloader>) -> Router {
+    router.route(
+        &format!("{prefix}/config/reload"),
+        post(reload_configuration_handler)
+            .route_layer(middleware::from_fn_with_state(Scope::ConfigWrite, require_scope))
+            .with_state(reloader),
+    )
+}
@@ -0,0 +1,56 @@
+//! API handlers for the [`events`](crate::servers::apis::v1::context::events)
+//! API context.
+use std::str::FromStr;
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::response::{IntoResponse, Response};
+use axum::Extension;
+use axum_extra::extract::Query;
+use serde::Deserialize;
+use torrust_tracker_primitives::info_hash::InfoHash;
+
+use super::responses::events_response;
+use crate::core::Tracker;
+use crate::servers::apis::v1::middlewares::auth::GrantedScopes;
+use crate::servers::apis::v1::responses::invalid_info_hash_param_response;
+
+/// A container for the URL query parameters.
+///
+/// Array of infohashes: `info_hash`. Only the events about those torrents are
+/// sent. For example:
+///
+/// <http://127.0.0.1:1212/api/v1/events?token=MyAccessToken&info_hash=9c38422213e30bff212b30c360d26f9a02136422>
+#[derive(Deserialize, Debug)]
+pub struct QueryParams {
+    /// A list of infohashes to filter the events.
+    #[serde(default, rename = "info_hash")]
+    pub info_hashes: Vec<String>,
+}
+
+/// It handles the request to subscribe to the real-time tracker events.
+///
+/// It returns:
+///
+/// - `200` response with a stream of [`Event`](crate::servers::apis::v1::context::events::resources::Event)
+///   resources (Server-Sent Events).
+/// - `400` with an error if any of the infohashes is not valid.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::events#subscribe-to-the-events)
+/// for more information about this endpoint.
+pub async fn get_events_handler(
+    State(tracker): State<Arc<Tracker>>,
+    Extension(granted_scopes): Extension<GrantedScopes>,
+    params: Query<QueryParams>,
+) -> Response {
+    let mut info_hashes = vec![];
+
+    for info_hash in &params.0.info_hashes {
+        match InfoHash::from_str(info_hash) {
+            Ok(info_hash) => info_hashes.push(info_hash),
+            Err(_) => return invalid_info_hash_param_response(info_hash),
+        }
+    }
+
+    events_response(tracker.subscribe_to_events(), info_hashes, granted_scopes).into_response()
+}
@@ -0,0 +1,70 @@
+//! Events API context.
+//!
+//! This API context is responsible for streaming the real-time tracker events,
+//! so that clients like dashboards don't have to poll the other endpoints to
+//! notice the changes.
+//!
+//! Events are sent when:
+//!
+//! - A peer joins a swarm, leaves it or completes the download.
+//! - A torrent is added to the tracker or removed by the cleanup.
+//! - A torrent is added to or removed from the whitelist.
+//! - An authentication key is added or removed.
+//!
+//! Refer to the [`events`](crate::core::events) module for more information
+//! about the tracker events.
+//!
+//! # Endpoints
+//!
+//! - [Subscribe to the events](#subscribe-to-the-events)
+//!
+//! # Subscribe to the events
+//!
+//! `GET /events`
+//!
+//! It returns a [Server-Sent Events](https://html.spec.whatwg.org/multipage/server-sent-events.html)
+//! stream. The data of each event is an [`Event`](crate::servers::apis::v1::context::events::resources::Event)
+//! resource in json. The stream only contains the events that happen after
+//! the client subscribes.
+//!
+//! **Query parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `info_hash` | 40-char string | Only send the events about this torrent. It can be repeated | No | `9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d`
+//!
+//! Authentication key events are not related to any torrent, so they are not
+//! sent when the events are filtered by infohash. They contain the key, so they
+//! are only sent when the token also has the `keys:read` scope.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -N "http://127.0.0.1:1212/api/v1/events?token=MyAccessToken&info_hash=9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```text
+//! data: {"type":"torrent_created","info_hash":"9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d"}
+//!
+//! data: {"type":"peer_joined","info_hash":"9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d","peer_id":{"id":"0x2d7142343431302d2a64465a3844484944704579","client":"qBittorrent"},"peer_addr":"192.168.1.88:17548"}
+//!
+//! data: {"type":"peer_completed","info_hash":"9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d","peer_id":{"id":"0x2d7142343431302d2a64465a3844484944704579","client":"qBittorrent"},"peer_addr":"192.168.1.88:17548"}
+//! ```
+//!
+//! Clients that are too slow to read the events miss the oldest ones. In that
+//! case, they get a `lagged` event with the number of missed events:
+//!
+//! ```text
+//! data: {"type":"lagged","missed_events":12}
+//! ```
+//!
+//! **Resource**
+//!
+//! Refer to the API [`Event`](crate::servers::apis::v1::context::events::resources::Event)
+//! resource for more information about the event types and attributes.
+pub mod handlers;
+pub mod resources;
+pub mod responses;
+pub mod routes;
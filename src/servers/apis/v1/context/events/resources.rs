@@ -0,0 +1,133 @@
+//! API resources for the [`events`](crate::servers::apis::v1::context::events) API context.
+use serde::{Deserialize, Serialize};
+
+use crate::core::events;
+use crate::servers::apis::v1::context::torrent::resources::peer::Id;
+
+/// A resource that represents a real-time tracker event.
+///
+/// It's serialized as a json object with the event `type` and the event
+/// attributes. For example:
+///
+/// ```json
+/// {
+///     "type": "torrent_created",
+///     "info_hash": "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d"
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    /// A peer has announced for the first time in the swarm.
+    PeerJoined {
+        info_hash: String,
+        peer_id: Id,
+        peer_addr: String,
+    },
+    /// A peer has announced the `stopped` event, or it has been removed
+    /// because it was inactive.
+    PeerLeft {
+        info_hash: String,
+        peer_id: Id,
+        peer_addr: String,
+    },
+    /// A peer has announced the `completed` event.
+    PeerCompleted {
+        info_hash: String,
+        peer_id: Id,
+        peer_addr: String,
+    },
+    /// The first peer of a torrent has announced.
+    TorrentCreated { info_hash: String },
+    /// The torrent has been removed by the peerless torrents cleanup.
+    TorrentRemoved { info_hash: String },
+    /// The torrent has been added to the whitelist.
+    TorrentWhitelisted { info_hash: String },
+    /// The torrent has been removed from the whitelist.
+    TorrentRemovedFromWhitelist { info_hash: String },
+    /// An authentication key has been added. `valid_until` is the expiration
+    /// timestamp in seconds, it's `null` for permanent keys.
+    KeyAdded { key: String, valid_until: Option<u64> },
+    /// An authentication key has been removed.
+    KeyRemoved { key: String },
+    /// The client was too slow and it has missed some events.
+    Lagged { missed_events: u64 },
+}
+
+impl From<events::Event> for Event {
+    fn from(event: events::Event) -> Self {
+        match event {
+            events::Event::PeerJoined {
+                info_hash,
+                peer_id,
+                peer_addr,
+            } => Event::PeerJoined {
+                info_hash: info_hash.to_string(),
+                peer_id: Id::from(peer_id),
+                peer_addr: peer_addr.to_string(),
+            },
+            events::Event::PeerLeft {
+                info_hash,
+                peer_id,
+                peer_addr,
+            } => Event::PeerLeft {
+                info_hash: info_hash.to_string(),
+                peer_id: Id::from(peer_id),
+                peer_addr: peer_addr.to_string(),
+            },
+            events::Event::PeerCompleted {
+                info_hash,
+                peer_id,
+                peer_addr,
+            } => Event::PeerCompleted {
+                info_hash: info_hash.to_string(),
+                peer_id: Id::from(peer_id),
+                peer_addr: peer_addr.to_string(),
+            },
+            events::Event::TorrentCreated { info_hash } => Event::TorrentCreated {
+                info_hash: info_hash.to_string(),
+            },
+            events::Event::TorrentRemoved { info_hash } => Event::TorrentRemoved {
+                info_hash: info_hash.to_string(),
+            },
+            events::Event::TorrentWhitelisted { info_hash } => Event::TorrentWhitelisted {
+                info_hash: info_hash.to_string(),
+            },
+            events::Event::TorrentRemovedFromWhitelist { info_hash } => Event::TorrentRemovedFromWhitelist {
+                info_hash: info_hash.to_string(),
+            },
+            events::Event::KeyAdded { key, valid_until } => Event::KeyAdded {
+                key: key.to_string(),
+                valid_until: valid_until.map(|valid_until| valid_until.as_secs()),
+            },
+            events::Event::KeyRemoved { key } => Event::KeyRemoved { key: key.to_string() },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+    use aquatic_udp_protocol::PeerId;
+    use torrust_tracker_primitives::info_hash::InfoHash;
+
+    use super::Event;
+    use crate::core::events;
+
+    #[test]
+    fn it_should_be_convertible_from_a_tracker_event() {
+        let info_hash = "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".parse::<InfoHash>().unwrap(); // DevSkim: ignore DS173237
+
+        let event = Event::from(events::Event::PeerJoined {
+            info_hash,
+            peer_id: PeerId(*b"-qB4410-*dFZ8DHIDpEy"),
+            peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080),
+        });
+
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"type":"peer_joined","info_hash":"9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d","peer_id":{"id":"0x2d7142343431302d2a64465a3844484944704579","client":"qBittorrent"},"peer_addr":"126.0.0.1:8080"}"# // DevSkim: ignore DS173237
+        );
+    }
+}
@@ -0,0 +1,69 @@
+//! API responses for the [`events`](crate::servers::apis::v1::context::events)
+//! API context.
+use std::convert::Infallible;
+
+use axum::response::sse::{self, KeepAlive, Sse};
+use futures::Stream;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use torrust_tracker_configuration::Scope;
+use torrust_tracker_primitives::info_hash::InfoHash;
+
+use super::resources::Event;
+use crate::core::events;
+use crate::servers::apis::v1::middlewares::auth::GrantedScopes;
+
+/// `200` response with a stream of [`Event`] resources (Server-Sent Events).
+///
+/// When the list of `info_hashes` is not empty, only the events about those
+/// torrents are sent. Authentication key events contain the key, so they are
+/// only sent when the token has the `keys:read` scope. The stream ends when the
+/// tracker stops.
+pub fn events_response(
+    receiver: broadcast::Receiver<events::Event>,
+    info_hashes: Vec<InfoHash>,
+    granted_scopes: GrantedScopes,
+) -> Sse<impl Stream<Item = Result<sse::Event, Infallible>>> {
+    let stream = futures::stream::unfold(receiver, move |mut receiver| {
+        let info_hashes = info_hashes.clone();
+        let granted_scopes = granted_scopes.clone();
+
+        async move {
+            loop {
+                let event = match receiver.recv().await {
+                    Ok(event) => {
+                        if !is_included(&event, &info_hashes) || !is_granted(&event, &granted_scopes) {
+                            continue;
+                        }
+                        Event::from(event)
+                    }
+                    Err(RecvError::Lagged(missed_events)) => Event::Lagged { missed_events },
+                    Err(RecvError::Closed) => return None,
+                };
+
+                let sse_event = sse::Event::default()
+                    .json_data(event)
+                    .expect("the event resource should be serializable");
+
+                return Some((Ok(sse_event), receiver));
+            }
+        }
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+fn is_included(event: &events::Event, info_hashes: &[InfoHash]) -> bool {
+    if info_hashes.is_empty() {
+        return true;
+    }
+
+    event.info_hash().is_some_and(|info_hash| info_hashes.contains(info_hash))
+}
+
+fn is_granted(event: &events::Event, granted_scopes: &GrantedScopes) -> bool {
+    match event {
+        events::Event::KeyAdded { .. } | events::Event::KeyRemoved { .. } => granted_scopes.contains(Scope::KeysRead),
+        _ => true,
+    }
+}
@@ -0,0 +1,24 @@
+//! API routes for the [`events`](crate::servers::apis::v1::context::events) API context.
+//!
+//! - `GET /events`
+//!
+//! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::events).
+use std::sync::Arc;
+
+use axum::routing::get;
+use axum::{middleware, Router};
+use torrust_tracker_configuration::Scope;
+
+use super::handlers::get_events_handler;
+use crate::core::Tracker;
+use crate::servers::apis::v1::middlewares::auth::require_scope;
+
+/// It adds the routes to the router for the [`events`](crate::servers::apis::v1::context::events) API context.
+pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
+    router.route(
+        &format!("{prefix}/events"),
+        get(get_events_handler)
+            .route_layer(middleware::from_fn_with_state(Scope::EventsRead, require_scope))
+            .with_state(tracker),
+    )
+}
@@ -0,0 +1,12 @@
+//! API forms for the [`ip_access_list`](crate::servers::apis::v1::context::ip_access_list)
+//! API context.
+use serde::{Deserialize, Serialize};
+
+/// This type contains the info needed to add a new rule to the IP access list.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AddIpAccessRuleForm {
+    /// A network in CIDR notation or a single IP address.
+    pub network: String,
+    /// The policy for the network: `allow` or `deny`.
+    pub policy: String,
+}
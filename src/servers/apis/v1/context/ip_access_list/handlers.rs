@@ -0,0 +1,103 @@
+//! API handlers for the [`ip_access_list`](crate::servers::apis::v1::context::ip_access_list)
+//! API context.
+use std::str::FromStr;
+use std::sync::Arc;
+
+use axum::extract::{self, Path, State};
+use axum::response::{IntoResponse, Response};
+
+use super::forms::AddIpAccessRuleForm;
+use super::resources::IpAccessRule;
+use super::responses::{
+    failed_to_add_ip_access_rule_response, failed_to_reload_ip_access_list_response, failed_to_remove_ip_access_rule_response,
+    invalid_network_response, invalid_policy_response, ip_access_rules_response,
+};
+use crate::core::ip_access_list::{parse_network, Policy, Rule};
+use crate::core::Tracker;
+use crate::servers::apis::v1::responses::ok_response;
+
+/// It handles the request to get all the rules in the IP access list.
+///
+/// It returns a `200` response with a json array of [`IpAccessRule`] resources.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::ip_access_list#list-the-rules)
+/// for more information about this endpoint.
+pub async fn get_ip_access_rules_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    ip_access_rules_response(
+        tracker
+            .get_ip_access_rules()
+            .await
+            .into_iter()
+            .map(IpAccessRule::from)
+            .collect(),
+    )
+    .into_response()
+}
+
+/// It handles the request to add a rule to the IP access list.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `400` with an error if the network or the policy are not valid.
+/// - `500` with serialized error in debug format if the rule couldn't be added.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::ip_access_list#add-a-rule)
+/// for more information about this endpoint.
+pub async fn add_ip_access_rule_handler(
+    State(tracker): State<Arc<Tracker>>,
+    extract::Json(add_rule_form): extract::Json<AddIpAccessRuleForm>,
+) -> Response {
+    let network = match parse_network(&add_rule_form.network) {
+        Ok(network) => network,
+        Err(e) => return invalid_network_response(e),
+    };
+
+    let policy = match Policy::from_str(&add_rule_form.policy) {
+        Ok(policy) => policy,
+        Err(e) => return invalid_policy_response(e),
+    };
+
+    match tracker.add_ip_access_rule(&Rule::new(network, policy)).await {
+        Ok(()) => ok_response(),
+        Err(e) => failed_to_add_ip_access_rule_response(e),
+    }
+}
+
+/// It handles the request to remove a rule from the IP access list.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `400` with an error if the network is not valid.
+/// - `500` with serialized error in debug format if the rule couldn't be
+///   removed.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::ip_access_list#remove-a-rule)
+/// for more information about this endpoint.
+pub async fn remove_ip_access_rule_handler(State(tracker): State<Arc<Tracker>>, Path(network): Path<String>) -> Response {
+    match parse_network(&network) {
+        Err(e) => invalid_network_response(e),
+        Ok(network) => match tracker.remove_ip_access_rule(&network).await {
+            Ok(_) => ok_response(),
+            Err(e) => failed_to_remove_ip_access_rule_response(e),
+        },
+    }
+}
+
+/// It handles the request to reload the IP access list from the database.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `500` with serialized error in debug format if the IP access list
+///   couldn't be reloaded from the database.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::ip_access_list#reload-the-ip-access-list)
+/// for more information about this endpoint.
+pub async fn reload_ip_access_list_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    match tracker.load_ip_access_list_from_database().await {
+        Ok(()) => ok_response(),
+        Err(e) => failed_to_reload_ip_access_list_response(e),
+    }
+}
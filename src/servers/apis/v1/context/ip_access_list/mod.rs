@@ -0,0 +1,138 @@
+//! IP access list API context.
+//!
+//! This API context is responsible for handling all the requests related to
+//! the IP access list.
+//!
+//! The IP access list contains rules to allow or deny the `announce` and
+//! `scrape` requests depending on the client IP address. Each rule applies a
+//! policy (`allow` or `deny`) to a network in CIDR notation. Blocked requests
+//! get a failure response (HTTP tracker) or an error packet (UDP tracker).
+//!
+//! Refer to the [`ip_access_list`](crate::core::ip_access_list) module for
+//! more information about how the rules are applied.
+//!
+//! # Endpoints
+//!
+//! - [List the rules](#list-the-rules)
+//! - [Add a rule](#add-a-rule)
+//! - [Remove a rule](#remove-a-rule)
+//! - [Reload the IP access list](#reload-the-ip-access-list)
+//!
+//! # List the rules
+//!
+//! `GET /ip-access-list`
+//!
+//! It returns all the rules in the IP access list. The `deny` rules go first.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/ip-access-list?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! [
+//!     {
+//!         "network": "203.0.113.0/24",
+//!         "policy": "deny"
+//!     },
+//!     {
+//!         "network": "2001:db8::/32",
+//!         "policy": "allow"
+//!     }
+//! ]
+//! ```
+//!
+//! **Resource**
+//!
+//! Refer to the API [`IpAccessRule`](crate::servers::apis::v1::context::ip_access_list::resources::IpAccessRule)
+//! resource for more information about the response attributes.
+//!
+//! # Add a rule
+//!
+//! `POST /ip-access-list`
+//!
+//! It adds a rule to the IP access list. If there is already a rule for the
+//! same network, its policy is replaced.
+//!
+//! **POST parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `network` | string | A network in CIDR notation or a single IP address | Yes | `203.0.113.0/24`
+//! `policy` | string | `allow` or `deny` | Yes | `deny`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X POST http://localhost:1212/api/v1/ip-access-list?token=MyAccessToken \
+//!      -H "Content-Type: application/json" \
+//!      -d '{
+//!            "network": "203.0.113.0/24",
+//!            "policy": "deny"
+//!          }'
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
+//! > **NOTICE**: when there is at least one `allow` rule, only the requests
+//! > from the allowed networks are accepted.
+//!
+//! # Remove a rule
+//!
+//! `DELETE /ip-access-list/:network`
+//!
+//! It removes the rule for the network from the IP access list.
+//!
+//! **Path parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `network` | string | A network in CIDR notation or a single IP address. The `/` must be URL-encoded | Yes | `203.0.113.0%2F24`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X DELETE "http://127.0.0.1:1212/api/v1/ip-access-list/203.0.113.0%2F24?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
+//! # Reload the IP access list
+//!
+//! `GET /ip-access-list/reload`
+//!
+//! It reloads the IP access list from the database.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/ip-access-list/reload?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+pub mod forms;
+pub mod handlers;
+pub mod resources;
+pub mod responses;
+pub mod routes;
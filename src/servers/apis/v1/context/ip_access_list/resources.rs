@@ -0,0 +1,40 @@
+//! API resources for the [`ip_access_list`](crate::servers::apis::v1::context::ip_access_list)
+//! API context.
+use serde::{Deserialize, Serialize};
+
+use crate::core::ip_access_list::Rule;
+
+/// A resource that represents a rule in the IP access list.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct IpAccessRule {
+    /// The network in CIDR notation. For example: `203.0.113.0/24`.
+    pub network: String,
+    /// The policy for the network: `allow` or `deny`.
+    pub policy: String,
+}
+
+impl From<Rule> for IpAccessRule {
+    fn from(rule: Rule) -> Self {
+        Self {
+            network: rule.network.to_string(),
+            policy: rule.policy.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IpAccessRule;
+    use crate::core::ip_access_list::{parse_network, Policy, Rule};
+
+    #[test]
+    fn it_should_be_converted_from_an_ip_access_list_rule() {
+        assert_eq!(
+            IpAccessRule::from(Rule::new(parse_network("203.0.113.0/24").unwrap(), Policy::Deny)),
+            IpAccessRule {
+                network: "203.0.113.0/24".to_string(),
+                policy: "deny".to_string(),
+            }
+        );
+    }
+}
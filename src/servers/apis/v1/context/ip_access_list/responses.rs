@@ -0,0 +1,46 @@
+//! API responses for the [`ip_access_list`](crate::servers::apis::v1::context::ip_access_list)
+//! API context.
+use std::error::Error;
+
+use axum::response::{Json, Response};
+
+use super::resources::IpAccessRule;
+use crate::servers::apis::v1::responses::{bad_request_response, unhandled_rejection_response};
+
+/// `200` response that contains an array of [`IpAccessRule`] resources as json.
+pub fn ip_access_rules_response(rules: Vec<IpAccessRule>) -> Json<Vec<IpAccessRule>> {
+    Json(rules)
+}
+
+// Error responses
+
+/// `400` error response when the network is not valid.
+#[must_use]
+pub fn invalid_network_response<E: Error>(e: E) -> Response {
+    bad_request_response(&format!("Invalid network: {e}"))
+}
+
+/// `400` error response when the policy is not valid.
+#[must_use]
+pub fn invalid_policy_response<E: Error>(e: E) -> Response {
+    bad_request_response(&format!("Invalid policy: {e}"))
+}
+
+/// `500` error response when a rule cannot be added to the IP access list.
+#[must_use]
+pub fn failed_to_add_ip_access_rule_response<E: Error>(e: E) -> Response {
+    unhandled_rejection_response(format!("failed to add IP access rule: {e}"))
+}
+
+/// `500` error response when a rule cannot be removed from the IP access list.
+#[must_use]
+pub fn failed_to_remove_ip_access_rule_response<E: Error>(e: E) -> Response {
+    unhandled_rejection_response(format!("failed to remove IP access rule: {e}"))
+}
+
+/// `500` error response when the IP access list cannot be reloaded from the
+/// database.
+#[must_use]
+pub fn failed_to_reload_ip_access_list_response<E: Error>(e: E) -> Response {
+    unhandled_rejection_response(format!("failed to reload IP access list: {e}"))
+}
@@ -0,0 +1,52 @@
+//! API routes for the [`ip_access_list`](crate::servers::apis::v1::context::ip_access_list) API context.
+//!
+//! - `GET /ip-access-list`
+//! - `POST /ip-access-list`
+//! - `DELETE /ip-access-list/:network`
+//! - `GET /ip-access-list/reload`
+//!
+//! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::ip_access_list).
+use std::sync::Arc;
+
+use axum::routing::{delete, get, post};
+use axum::{middleware, Router};
+use torrust_tracker_configuration::Scope;
+
+use super::handlers::{
+    add_ip_access_rule_handler, get_ip_access_rules_handler, reload_ip_access_list_handler, remove_ip_access_rule_handler,
+};
+use crate::core::Tracker;
+use crate::servers::apis::v1::middlewares::auth::require_scope;
+
+/// It adds the routes to the router for the [`ip_access_list`](crate::servers::apis::v1::context::ip_access_list) API context.
+pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
+    let prefix = format!("{prefix}/ip-access-list");
+
+    router
+        // Rules
+        .route(
+            &prefix,
+            get(get_ip_access_rules_handler)
+                .route_layer(middleware::from_fn_with_state(Scope::IpAccessListRead, require_scope))
+                .with_state(tracker.clone()),
+        )
+        .route(
+            &prefix,
+            post(add_ip_access_rule_handler)
+                .route_layer(middleware::from_fn_with_state(Scope::IpAccessListWrite, require_scope))
+                .with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/:network"),
+            delete(remove_ip_access_rule_handler)
+                .route_layer(middleware::from_fn_with_state(Scope::IpAccessListWrite, require_scope))
+                .with_state(tracker.clone()),
+        )
+        // IP access list commands
+        .route(
+            &format!("{prefix}/reload"),
+            get(reload_ip_access_list_handler)
+                .route_layer(middleware::from_fn_with_state(Scope::IpAccessListWrite, require_scope))
+                .with_state(tracker),
+        )
+}
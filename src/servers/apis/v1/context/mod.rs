@@ -2,8 +2,12 @@
 //!
 //! Each context is a module that contains the API endpoints related to a
 //! specific resource group.
+pub mod accounting;
 pub mod auth_key;
+pub mod config;
+pub mod events;
 pub mod health_check;
+pub mod ip_access_list;
 pub mod stats;
 pub mod torrent;
 pub mod whitelist;
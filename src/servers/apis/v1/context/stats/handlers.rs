@@ -3,19 +3,110 @@
 use std::sync::Arc;
 
 use axum::extract::State;
-use axum::response::Json;
+use axum::response::{IntoResponse, Response};
+use axum_extra::extract::Query;
+use serde::Deserialize;
 
-use super::resources::Stats;
-use super::responses::stats_response;
+use super::responses::{
+    failed_to_reset_stats_response, metrics_history_disabled_response, openmetrics_response, stats_history_response,
+    stats_response,
+};
 use crate::core::services::statistics::get_metrics;
 use crate::core::Tracker;
+use crate::servers::apis::v1::responses::ok_response;
+
+/// The format of the tracker statistics.
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    /// The [`Stats`](crate::servers::apis::v1::context::stats::resources::Stats) json resource.
+    #[default]
+    Json,
+    /// The [`OpenMetrics`](crate::servers::apis::v1::context::stats::resources::OpenMetrics)
+    /// text format, the format scraped by Prometheus.
+    Prometheus,
+}
+
+/// A container for the URL query parameters.
+///
+/// Output format: `format`. For example:
+///
+/// <http://127.0.0.1:1212/api/v1/stats?token=MyAccessToken&format=prometheus>
+#[derive(Deserialize, Debug)]
+pub struct QueryParams {
+    /// The format of the statistics. Defaults to `json`.
+    #[serde(default)]
+    pub format: Format,
+}
 
 /// It handles the request to get the tracker statistics.
 ///
-/// It returns a `200` response with a json [`Stats`]
+/// It returns a `200` response with a json [`Stats`](crate::servers::apis::v1::context::stats::resources::Stats)
+/// or, when the `prometheus` format is requested, with the
+/// [`OpenMetrics`](crate::servers::apis::v1::context::stats::resources::OpenMetrics)
+/// text.
 ///
 /// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::stats#get-tracker-statistics)
 /// for more information about this endpoint.
-pub async fn get_stats_handler(State(tracker): State<Arc<Tracker>>) -> Json<Stats> {
-    stats_response(get_metrics(tracker.clone()).await)
+pub async fn get_stats_handler(State(tracker): State<Arc<Tracker>>, params: Query<QueryParams>) -> Response {
+    let tracker_metrics = get_metrics(tracker.clone()).await;
+
+    match params.format {
+        Format::Json => stats_response(tracker_metrics).into_response(),
+        Format::Prometheus => openmetrics_response(&tracker_metrics),
+    }
+}
+
+/// It handles the request to reset the tracker statistics.
+///
+/// It sets all the usage statistics counters to zero. When the statistics
+/// persistence is enabled, the counters are also reset in the database.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `500` with serialized error in debug format if the statistics couldn't be
+///   reset in the database.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::stats#reset-tracker-statistics)
+/// for more information about this endpoint.
+pub async fn reset_stats_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    match tracker.reset_statistics().await {
+        Ok(()) => ok_response(),
+        Err(e) => failed_to_reset_stats_response(e),
+    }
+}
+
+/// A container for the URL query parameters of the metrics history.
+///
+/// Time range: `from` and `to`, and minimum time between points: `step`. All
+/// of them in seconds. For example:
+///
+/// <http://127.0.0.1:1212/api/v1/stats/history?token=MyAccessToken&from=1726444800&to=1726531200&step=300>
+#[derive(Deserialize, Debug)]
+pub struct HistoryQueryParams {
+    /// Only the samples taken since this time, in seconds since the Unix epoch.
+    pub from: Option<u64>,
+    /// Only the samples taken until this time, in seconds since the Unix epoch.
+    pub to: Option<u64>,
+    /// Minimum number of seconds between two points. Defaults to all the
+    /// samples.
+    pub step: Option<u64>,
+}
+
+/// It handles the request to get the history of the tracker metrics.
+///
+/// It returns:
+///
+/// - `200` response with a json array of [`HistoryPoint`](crate::servers::apis::v1::context::stats::resources::HistoryPoint)
+///   resources.
+/// - `400` response if the metrics history is disabled.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::stats#get-the-history-of-the-tracker-metrics)
+/// for more information about this endpoint.
+pub async fn get_stats_history_handler(State(tracker): State<Arc<Tracker>>, params: Query<HistoryQueryParams>) -> Response {
+    match tracker.get_metrics_history(params.from, params.to, params.step) {
+        Some(samples) => stats_history_response(&samples).into_response(),
+        None => metrics_history_disabled_response(),
+    }
 }
@@ -6,6 +6,8 @@
 //! # Endpoints
 //!
 //! - [Get tracker statistics](#get-tracker-statistics)
+//! - [Reset tracker statistics](#reset-tracker-statistics)
+//! - [Get the history of the tracker metrics](#get-the-history-of-the-tracker-metrics)
 //!
 //! # Get tracker statistics
 //!
@@ -30,15 +32,29 @@
 //!     "tcp4_connections_handled": 0,
 //!     "tcp4_announces_handled": 0,
 //!     "tcp4_scrapes_handled": 0,
+//!     "tcp4_requests_blocked": 0,
+//!     "tcp4_requests_rate_limited": 0,
+//!     "tcp4_early_announces": 0,
 //!     "tcp6_connections_handled": 0,
 //!     "tcp6_announces_handled": 0,
 //!     "tcp6_scrapes_handled": 0,
+//!     "tcp6_requests_blocked": 0,
+//!     "tcp6_requests_rate_limited": 0,
+//!     "tcp6_early_announces": 0,
 //!     "udp4_connections_handled": 0,
 //!     "udp4_announces_handled": 0,
 //!     "udp4_scrapes_handled": 0,
+//!     "udp4_requests_blocked": 0,
+//!     "udp4_requests_rate_limited": 0,
+//!     "udp4_early_announces": 0,
 //!     "udp6_connections_handled": 0,
 //!     "udp6_announces_handled": 0,
-//!     "udp6_scrapes_handled": 0
+//!     "udp6_scrapes_handled": 0,
+//!     "udp6_requests_blocked": 0,
+//!     "udp6_requests_rate_limited": 0,
+//!     "udp6_early_announces": 0,
+//!     "scrape_cache_hits": 0,
+//!     "scrape_cache_misses": 0
 //!   }
 //! ```
 //!
@@ -46,6 +62,128 @@
 //!
 //! Refer to the API [`Stats`](crate::servers::apis::v1::context::stats::resources::Stats)
 //! resource for more information about the response attributes.
+//!
+//! **Prometheus**
+//!
+//! The statistics can also be exported in the [OpenMetrics](https://openmetrics.io/)
+//! text format, so that Prometheus can scrape them directly, with the
+//! `format=prometheus` query parameter:
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/stats?token=MyAccessToken&format=prometheus"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```text
+//! # TYPE torrust_tracker_torrents gauge
+//! # HELP torrust_tracker_torrents Number of torrents.
+//! torrust_tracker_torrents 0
+//! ...
+//! # TYPE torrust_tracker_announces_handled counter
+//! # HELP torrust_tracker_announces_handled Number of announce requests handled.
+//! torrust_tracker_announces_handled_total{protocol="tcp",family="ipv4"} 0
+//! torrust_tracker_announces_handled_total{protocol="tcp",family="ipv6"} 0
+//! torrust_tracker_announces_handled_total{protocol="udp",family="ipv4"} 0
+//! torrust_tracker_announces_handled_total{protocol="udp",family="ipv6"} 0
+//! ...
+//! # EOF
+//! ```
+//!
+//! Refer to the API [`OpenMetrics`](crate::servers::apis::v1::context::stats::resources::OpenMetrics)
+//! resource for more information about the exported metrics.
+//!
+//! # Reset tracker statistics
+//!
+//! `DELETE /stats`
+//!
+//! It sets all the usage statistics counters (requests handled, blocked,
+//! etcetera) to zero. The torrent metrics (number of torrents, seeders, ...)
+//! are not affected.
+//!
+//! When the `core.statistics_persistence` section is present in the
+//! configuration, the counters are also reset in the database. Otherwise,
+//! they are only kept in memory and reset on every restart.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X DELETE "http://127.0.0.1:1212/api/v1/stats?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
+//! # Get the history of the tracker metrics
+//!
+//! `GET /stats/history`
+//!
+//! Returns the samples of the tracker metrics taken over time, with the
+//! number of requests per second between two consecutive samples. Refer to
+//! the [`metrics_history`](crate::core::metrics_history) module for more
+//! information about how the samples are taken.
+//!
+//! The history has to be enabled with the `core.metrics_history` section in
+//! the configuration. Otherwise, the endpoint returns a `400` response.
+//!
+//! **Query parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `from` | positive integer | Only the samples taken since this time, in seconds since the Unix epoch | No | `1726444800`
+//! `to` | positive integer | Only the samples taken until this time, in seconds since the Unix epoch | No | `1726531200`
+//! `step` | positive integer | Minimum number of seconds between two points | No | `300`
+//!
+//! By default, it returns all the samples in the history. The `step` should
+//! be a multiple of the sampling `interval`. For example, with the default
+//! interval of 60 seconds, a `step` of 300 returns one point every five
+//! minutes.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/stats/history?token=MyAccessToken&from=1726444800&step=300"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! [
+//!     {
+//!         "timestamp": 1726444800,
+//!         "torrents": 4,
+//!         "seeders": 1,
+//!         "completed": 2,
+//!         "leechers": 3,
+//!         "rates": null
+//!     },
+//!     {
+//!         "timestamp": 1726445100,
+//!         "torrents": 5,
+//!         "seeders": 2,
+//!         "completed": 2,
+//!         "leechers": 3,
+//!         "rates": {
+//!             "connections": { "tcp4": 0.5, "tcp6": 0.0, "udp4": 2.0, "udp6": 0.0 },
+//!             "announces": { "tcp4": 0.5, "tcp6": 0.0, "udp4": 1.5, "udp6": 0.0 },
+//!             "scrapes": { "tcp4": 0.0, "tcp6": 0.0, "udp4": 0.5, "udp6": 0.0 }
+//!         }
+//!     }
+//! ]
+//! ```
+//!
+//! The `rates` of the first point are `null` because there is no previous
+//! point to compare with.
+//!
+//! **Resource**
+//!
+//! Refer to the API [`HistoryPoint`](crate::servers::apis::v1::context::stats::resources::HistoryPoint)
+//! resource for more information about the response attributes.
 pub mod handlers;
 pub mod resources;
 pub mod responses;
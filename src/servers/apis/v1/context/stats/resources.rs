@@ -1,8 +1,12 @@
 //! API resources for the [`stats`](crate::servers::apis::v1::context::stats)
 //! API context.
+use std::fmt::{self, Write};
+
 use serde::{Deserialize, Serialize};
 
+use crate::core::metrics_history::Sample;
 use crate::core::services::statistics::TrackerMetrics;
+use crate::core::statistics::Metrics;
 
 /// It contains all the statistics generated by the tracker.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
@@ -26,24 +30,68 @@ pub struct Stats {
     pub tcp4_announces_handled: u64,
     /// Total number of TCP (HTTP tracker) `scrape` requests from IPv4 peers.
     pub tcp4_scrapes_handled: u64,
+    /// Total number of TCP (HTTP tracker) requests from IPv4 peers blocked by the IP
+    /// access list.
+    pub tcp4_requests_blocked: u64,
+    /// Total number of TCP (HTTP tracker) requests from IPv4 peers rejected by the rate
+    /// limiter.
+    pub tcp4_requests_rate_limited: u64,
+    /// Total number of TCP (HTTP tracker) `announce` requests from IPv4 peers that
+    /// announced again before the minimum announce interval.
+    pub tcp4_early_announces: u64,
     /// Total number of TCP (HTTP tracker) connections from IPv6 peers.
     pub tcp6_connections_handled: u64,
     /// Total number of TCP (HTTP tracker) `announce` requests from IPv6 peers.
     pub tcp6_announces_handled: u64,
     /// Total number of TCP (HTTP tracker) `scrape` requests from IPv6 peers.
     pub tcp6_scrapes_handled: u64,
+    /// Total number of TCP (HTTP tracker) requests from IPv6 peers blocked by the IP
+    /// access list.
+    pub tcp6_requests_blocked: u64,
+    /// Total number of TCP (HTTP tracker) requests from IPv6 peers rejected by the rate
+    /// limiter.
+    pub tcp6_requests_rate_limited: u64,
+    /// Total number of TCP (HTTP tracker) `announce` requests from IPv6 peers that
+    /// announced again before the minimum announce interval.
+    pub tcp6_early_announces: u64,
     /// Total number of UDP (UDP tracker) connections from IPv4 peers.
     pub udp4_connections_handled: u64,
     /// Total number of UDP (UDP tracker) `announce` requests from IPv4 peers.
     pub udp4_announces_handled: u64,
     /// Total number of UDP (UDP tracker) `scrape` requests from IPv4 peers.
     pub udp4_scrapes_handled: u64,
+    /// Total number of UDP (UDP tracker) requests from IPv4 peers blocked by the IP
+    /// access list.
+    pub udp4_requests_blocked: u64,
+    /// Total number of UDP (UDP tracker) requests from IPv4 peers rejected by the rate
+    /// limiter.
+    pub udp4_requests_rate_limited: u64,
+    /// Total number of UDP (UDP tracker) `announce` requests from IPv4 peers that
+    /// announced again before the minimum announce interval.
+    pub udp4_early_announces: u64,
     /// Total number of UDP (UDP tracker) `connection` requests from IPv6 peers.
     pub udp6_connections_handled: u64,
     /// Total number of UDP (UDP tracker) `announce` requests from IPv6 peers.
     pub udp6_announces_handled: u64,
     /// Total number of UDP (UDP tracker) `scrape` requests from IPv6 peers.
     pub udp6_scrapes_handled: u64,
+    /// Total number of UDP (UDP tracker) requests from IPv6 peers blocked by the IP
+    /// access list.
+    pub udp6_requests_blocked: u64,
+    /// Total number of UDP (UDP tracker) requests from IPv6 peers rejected by the rate
+    /// limiter.
+    pub udp6_requests_rate_limited: u64,
+    /// Total number of UDP (UDP tracker) `announce` requests from IPv6 peers that
+    /// announced again before the minimum announce interval.
+    pub udp6_early_announces: u64,
+
+    // Scrape cache metrics
+    /// Total number of torrents in `scrape` requests whose swarm metadata was
+    /// found in the scrape cache.
+    pub scrape_cache_hits: u64,
+    /// Total number of torrents in `scrape` requests whose swarm metadata was
+    /// not found in the scrape cache.
+    pub scrape_cache_misses: u64,
 }
 
 impl From<TrackerMetrics> for Stats {
@@ -56,27 +104,453 @@ impl From<TrackerMetrics> for Stats {
             tcp4_connections_handled: metrics.protocol_metrics.tcp4_connections_handled,
             tcp4_announces_handled: metrics.protocol_metrics.tcp4_announces_handled,
             tcp4_scrapes_handled: metrics.protocol_metrics.tcp4_scrapes_handled,
+            tcp4_requests_blocked: metrics.protocol_metrics.tcp4_requests_blocked,
+            tcp4_requests_rate_limited: metrics.protocol_metrics.tcp4_requests_rate_limited,
+            tcp4_early_announces: metrics.protocol_metrics.tcp4_early_announces,
             tcp6_connections_handled: metrics.protocol_metrics.tcp6_connections_handled,
             tcp6_announces_handled: metrics.protocol_metrics.tcp6_announces_handled,
             tcp6_scrapes_handled: metrics.protocol_metrics.tcp6_scrapes_handled,
+            tcp6_requests_blocked: metrics.protocol_metrics.tcp6_requests_blocked,
+            tcp6_requests_rate_limited: metrics.protocol_metrics.tcp6_requests_rate_limited,
+            tcp6_early_announces: metrics.protocol_metrics.tcp6_early_announces,
             udp4_connections_handled: metrics.protocol_metrics.udp4_connections_handled,
             udp4_announces_handled: metrics.protocol_metrics.udp4_announces_handled,
             udp4_scrapes_handled: metrics.protocol_metrics.udp4_scrapes_handled,
+            udp4_requests_blocked: metrics.protocol_metrics.udp4_requests_blocked,
+            udp4_requests_rate_limited: metrics.protocol_metrics.udp4_requests_rate_limited,
+            udp4_early_announces: metrics.protocol_metrics.udp4_early_announces,
             udp6_connections_handled: metrics.protocol_metrics.udp6_connections_handled,
             udp6_announces_handled: metrics.protocol_metrics.udp6_announces_handled,
             udp6_scrapes_handled: metrics.protocol_metrics.udp6_scrapes_handled,
+            udp6_requests_blocked: metrics.protocol_metrics.udp6_requests_blocked,
+            udp6_requests_rate_limited: metrics.protocol_metrics.udp6_requests_rate_limited,
+            udp6_early_announces: metrics.protocol_metrics.udp6_early_announces,
+            scrape_cache_hits: metrics.protocol_metrics.scrape_cache_hits,
+            scrape_cache_misses: metrics.protocol_metrics.scrape_cache_misses,
         }
     }
 }
 
+/// A point in the history of the tracker metrics.
+///
+/// It contains the torrent metrics at the time the sample was taken, and the
+/// number of requests per second since the previous point. For example:
+///
+/// ```json
+/// {
+///     "timestamp": 1726444860,
+///     "torrents": 4,
+///     "seeders": 1,
+///     "completed": 2,
+///     "leechers": 3,
+///     "rates": {
+///         "connections": { "tcp4": 0.5, "tcp6": 0.0, "udp4": 2.0, "udp6": 0.0 },
+///         "announces": { "tcp4": 0.5, "tcp6": 0.0, "udp4": 1.5, "udp6": 0.0 },
+///         "scrapes": { "tcp4": 0.0, "tcp6": 0.0, "udp4": 0.5, "udp6": 0.0 }
+///     }
+/// }
+/// ```
+///
+/// The `rates` are `null` for the first point, because there is no previous
+/// point to compare with.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct HistoryPoint {
+    /// Time the sample was taken, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// Total number of torrents.
+    pub torrents: u64,
+    /// Total number of seeders for all torrents.
+    pub seeders: u64,
+    /// Total number of peers that have ever completed downloading for all torrents.
+    pub completed: u64,
+    /// Total number of leechers for all torrents.
+    pub leechers: u64,
+    /// Number of requests per second since the previous point.
+    pub rates: Option<Rates>,
+}
+
+impl HistoryPoint {
+    /// It builds the history points from the samples, which have to be
+    /// ordered from the oldest to the newest.
+    #[must_use]
+    pub fn new_vec(samples: &[Sample]) -> Vec<Self> {
+        let previous_samples = std::iter::once(None).chain(samples.iter().map(Some));
+
+        samples
+            .iter()
+            .zip(previous_samples)
+            .map(|(sample, previous)| Self {
+                timestamp: sample.timestamp,
+                torrents: sample.torrents,
+                seeders: sample.seeders,
+                completed: sample.completed,
+                leechers: sample.leechers,
+                rates: previous.and_then(|previous| Rates::between(previous, sample)),
+            })
+            .collect()
+    }
+}
+
+/// Number of requests per second, for each type of request.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+pub struct Rates {
+    /// Connections per second. HTTP trackers count every `announce` and
+    /// `scrape` request as a connection.
+    pub connections: ProtocolRates,
+    /// `announce` requests per second.
+    pub announces: ProtocolRates,
+    /// `scrape` requests per second.
+    pub scrapes: ProtocolRates,
+}
+
+impl Rates {
+    /// It returns the rates between two samples, or `None` if they were
+    /// taken at the same time.
+    ///
+    /// Counters that decreased (for example, because the statistics were
+    /// reset) have a rate of `0`.
+    #[must_use]
+    pub fn between(previous: &Sample, current: &Sample) -> Option<Self> {
+        let seconds = current
+            .timestamp
+            .checked_sub(previous.timestamp)
+            .filter(|seconds| *seconds > 0)?;
+
+        let rates = |counters: fn(&Metrics) -> [u64; 4]| {
+            let [tcp4, tcp6, udp4, udp6] = counters(&current.metrics);
+            let [previous_tcp4, previous_tcp6, previous_udp4, previous_udp6] = counters(&previous.metrics);
+
+            #[allow(clippy::cast_precision_loss)]
+            let rate = |current: u64, previous: u64| current.saturating_sub(previous) as f64 / seconds as f64;
+
+            ProtocolRates {
+                tcp4: rate(tcp4, previous_tcp4),
+                tcp6: rate(tcp6, previous_tcp6),
+                udp4: rate(udp4, previous_udp4),
+                udp6: rate(udp6, previous_udp6),
+            }
+        };
+
+        Some(Self {
+            connections: rates(|metrics| {
+                [
+                    metrics.tcp4_connections_handled,
+                    metrics.tcp6_connections_handled,
+                    metrics.udp4_connections_handled,
+                    metrics.udp6_connections_handled,
+                ]
+            }),
+            announces: rates(|metrics| {
+                [
+                    metrics.tcp4_announces_handled,
+                    metrics.tcp6_announces_handled,
+                    metrics.udp4_announces_handled,
+                    metrics.udp6_announces_handled,
+                ]
+            }),
+            scrapes: rates(|metrics| {
+                [
+                    metrics.tcp4_scrapes_handled,
+                    metrics.tcp6_scrapes_handled,
+                    metrics.udp4_scrapes_handled,
+                    metrics.udp6_scrapes_handled,
+                ]
+            }),
+        })
+    }
+}
+
+/// Number of requests per second for each protocol (`tcp` for the HTTP
+/// trackers and `udp` for the UDP trackers) and IP version.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+pub struct ProtocolRates {
+    pub tcp4: f64,
+    pub tcp6: f64,
+    pub udp4: f64,
+    pub udp6: f64,
+}
+
+/// The tracker statistics in the [OpenMetrics](https://openmetrics.io/) text
+/// exposition format, the format scraped by Prometheus.
+///
+/// Instead of flattening the protocol and the IP family into the metric name
+/// (like the [`Stats`] resource does with `tcp4_announces_handled`), they are
+/// exposed as the `protocol` (`tcp` or `udp`) and `family` (`ipv4` or `ipv6`)
+/// labels:
+///
+/// ```text
+/// # TYPE torrust_tracker_announces_handled counter
+/// # HELP torrust_tracker_announces_handled Number of announce requests handled.
+/// torrust_tracker_announces_handled_total{protocol="tcp",family="ipv4"} 6
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub struct OpenMetrics(String);
+
+/// The type of an `OpenMetrics` metric family.
+#[derive(Clone, Copy)]
+enum MetricType {
+    Gauge,
+    Counter,
+}
+
+impl MetricType {
+    fn as_str(self) -> &'static str {
+        match self {
+            MetricType::Gauge => "gauge",
+            MetricType::Counter => "counter",
+        }
+    }
+}
+
+/// The metric samples for each combination of `protocol` and `family` labels.
+struct PerProtocolAndFamily {
+    tcp4: u64,
+    tcp6: u64,
+    udp4: u64,
+    udp6: u64,
+}
+
+impl OpenMetrics {
+    const PREFIX: &'static str = "torrust_tracker";
+
+    fn write_metric(out: &mut String, name: &str, metric_type: MetricType, help: &str, value: u64) -> fmt::Result {
+        Self::write_metadata(out, name, metric_type, help)?;
+        writeln!(out, "{}_{name}{} {value}", Self::PREFIX, Self::suffix(metric_type))
+    }
+
+    fn write_labeled_metric(
+        out: &mut String,
+        name: &str,
+        metric_type: MetricType,
+        help: &str,
+        values: &PerProtocolAndFamily,
+    ) -> fmt::Result {
+        Self::write_metadata(out, name, metric_type, help)?;
+
+        let samples = [
+            ("tcp", "ipv4", values.tcp4),
+            ("tcp", "ipv6", values.tcp6),
+            ("udp", "ipv4", values.udp4),
+            ("udp", "ipv6", values.udp6),
+        ];
+
+        for (protocol, family, value) in samples {
+            writeln!(
+                out,
+                "{}_{name}{}{{protocol=\"{protocol}\",family=\"{family}\"}} {value}",
+                Self::PREFIX,
+                Self::suffix(metric_type)
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn write_metadata(out: &mut String, name: &str, metric_type: MetricType, help: &str) -> fmt::Result {
+        writeln!(out, "# TYPE {}_{name} {}", Self::PREFIX, metric_type.as_str())?;
+        writeln!(out, "# HELP {}_{name} {help}", Self::PREFIX)
+    }
+
+    fn suffix(metric_type: MetricType) -> &'static str {
+        match metric_type {
+            MetricType::Gauge => "",
+            MetricType::Counter => "_total",
+        }
+    }
+
+    fn render(metrics: &TrackerMetrics) -> Result<String, fmt::Error> {
+        let torrents = &metrics.torrents_metrics;
+        let protocol = &metrics.protocol_metrics;
+
+        let mut out = String::new();
+
+        // Torrent metrics
+        Self::write_metric(
+            &mut out,
+            "torrents",
+            MetricType::Gauge,
+            "Number of torrents.",
+            torrents.torrents,
+        )?;
+        Self::write_metric(
+            &mut out,
+            "seeders",
+            MetricType::Gauge,
+            "Number of seeders for all torrents.",
+            torrents.complete,
+        )?;
+        Self::write_metric(
+            &mut out,
+            "leechers",
+            MetricType::Gauge,
+            "Number of leechers for all torrents.",
+            torrents.incomplete,
+        )?;
+        // It's not a counter because it decreases when torrents are removed.
+        Self::write_metric(
+            &mut out,
+            "completed",
+            MetricType::Gauge,
+            "Number of peers that have ever completed downloading for all torrents.",
+            torrents.downloaded,
+        )?;
+
+        // Protocol metrics
+        Self::write_labeled_metric(
+            &mut out,
+            "connections_handled",
+            MetricType::Counter,
+            "Number of connections handled. For the HTTP tracker, every request counts as a connection.",
+            &PerProtocolAndFamily {
+                tcp4: protocol.tcp4_connections_handled,
+                tcp6: protocol.tcp6_connections_handled,
+                udp4: protocol.udp4_connections_handled,
+                udp6: protocol.udp6_connections_handled,
+            },
+        )?;
+        Self::write_labeled_metric(
+            &mut out,
+            "announces_handled",
+            MetricType::Counter,
+            "Number of announce requests handled.",
+            &PerProtocolAndFamily {
+                tcp4: protocol.tcp4_announces_handled,
+                tcp6: protocol.tcp6_announces_handled,
+                udp4: protocol.udp4_announces_handled,
+                udp6: protocol.udp6_announces_handled,
+            },
+        )?;
+        Self::write_labeled_metric(
+            &mut out,
+            "scrapes_handled",
+            MetricType::Counter,
+            "Number of scrape requests handled.",
+            &PerProtocolAndFamily {
+                tcp4: protocol.tcp4_scrapes_handled,
+                tcp6: protocol.tcp6_scrapes_handled,
+                udp4: protocol.udp4_scrapes_handled,
+                udp6: protocol.udp6_scrapes_handled,
+            },
+        )?;
+        Self::write_labeled_metric(
+            &mut out,
+            "requests_blocked",
+            MetricType::Counter,
+            "Number of announce and scrape requests blocked by the IP access list.",
+            &PerProtocolAndFamily {
+                tcp4: protocol.tcp4_requests_blocked,
+                tcp6: protocol.tcp6_requests_blocked,
+                udp4: protocol.udp4_requests_blocked,
+                udp6: protocol.udp6_requests_blocked,
+            },
+        )?;
+        Self::write_labeled_metric(
+            &mut out,
+            "requests_rate_limited",
+            MetricType::Counter,
+            "Number of connect, announce and scrape requests rejected by the rate limiter.",
+            &PerProtocolAndFamily {
+                tcp4: protocol.tcp4_requests_rate_limited,
+                tcp6: protocol.tcp6_requests_rate_limited,
+                udp4: protocol.udp4_requests_rate_limited,
+                udp6: protocol.udp6_requests_rate_limited,
+            },
+        )?;
+        Self::write_labeled_metric(
+            &mut out,
+            "early_announces",
+            MetricType::Counter,
+            "Number of announce requests received before the minimum announce interval.",
+            &PerProtocolAndFamily {
+                tcp4: protocol.tcp4_early_announces,
+                tcp6: protocol.tcp6_early_announces,
+                udp4: protocol.udp4_early_announces,
+                udp6: protocol.udp6_early_announces,
+            },
+        )?;
+
+        // Scrape cache metrics
+        Self::write_metric(
+            &mut out,
+            "scrape_cache_hits",
+            MetricType::Counter,
+            "Number of torrents in scrape requests found in the scrape cache.",
+            protocol.scrape_cache_hits,
+        )?;
+        Self::write_metric(
+            &mut out,
+            "scrape_cache_misses",
+            MetricType::Counter,
+            "Number of torrents in scrape requests not found in the scrape cache.",
+            protocol.scrape_cache_misses,
+        )?;
+
+        writeln!(out, "# EOF")?;
+
+        Ok(out)
+    }
+}
+
+impl From<&TrackerMetrics> for OpenMetrics {
+    fn from(metrics: &TrackerMetrics) -> Self {
+        Self(Self::render(metrics).expect("writing into a string should not fail"))
+    }
+}
+
+impl fmt::Display for OpenMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use torrust_tracker_primitives::torrent_metrics::TorrentsMetrics;
 
-    use super::Stats;
+    use super::{HistoryPoint, OpenMetrics, Stats};
+    use crate::core::metrics_history::Sample;
     use crate::core::services::statistics::TrackerMetrics;
     use crate::core::statistics::Metrics;
 
+    fn sample_tracker_metrics() -> TrackerMetrics {
+        TrackerMetrics {
+            torrents_metrics: TorrentsMetrics {
+                complete: 1,
+                downloaded: 2,
+                incomplete: 3,
+                torrents: 4,
+            },
+            protocol_metrics: Metrics {
+                tcp4_connections_handled: 5,
+                tcp4_announces_handled: 6,
+                tcp4_scrapes_handled: 7,
+                tcp4_requests_blocked: 17,
+                tcp4_requests_rate_limited: 21,
+                tcp4_early_announces: 25,
+                tcp6_connections_handled: 8,
+                tcp6_announces_handled: 9,
+                tcp6_scrapes_handled: 10,
+                tcp6_requests_blocked: 18,
+                tcp6_requests_rate_limited: 22,
+                tcp6_early_announces: 26,
+                udp4_connections_handled: 11,
+                udp4_announces_handled: 12,
+                udp4_scrapes_handled: 13,
+                udp4_requests_blocked: 19,
+                udp4_requests_rate_limited: 23,
+                udp4_early_announces: 27,
+                udp6_connections_handled: 14,
+                udp6_announces_handled: 15,
+                udp6_scrapes_handled: 16,
+                udp6_requests_blocked: 20,
+                udp6_requests_rate_limited: 24,
+                udp6_early_announces: 28,
+                scrape_cache_hits: 29,
+                scrape_cache_misses: 30,
+            },
+        }
+    }
+
     #[test]
     fn stats_resource_should_be_converted_from_tracker_metrics() {
         assert_eq!(
@@ -91,15 +565,29 @@ mod tests {
                     tcp4_connections_handled: 5,
                     tcp4_announces_handled: 6,
                     tcp4_scrapes_handled: 7,
+                    tcp4_requests_blocked: 17,
+                    tcp4_requests_rate_limited: 21,
+                    tcp4_early_announces: 25,
                     tcp6_connections_handled: 8,
                     tcp6_announces_handled: 9,
                     tcp6_scrapes_handled: 10,
+                    tcp6_requests_blocked: 18,
+                    tcp6_requests_rate_limited: 22,
+                    tcp6_early_announces: 26,
                     udp4_connections_handled: 11,
                     udp4_announces_handled: 12,
                     udp4_scrapes_handled: 13,
+                    udp4_requests_blocked: 19,
+                    udp4_requests_rate_limited: 23,
+                    udp4_early_announces: 27,
                     udp6_connections_handled: 14,
                     udp6_announces_handled: 15,
-                    udp6_scrapes_handled: 16
+                    udp6_scrapes_handled: 16,
+                    udp6_requests_blocked: 20,
+                    udp6_requests_rate_limited: 24,
+                    udp6_early_announces: 28,
+                    scrape_cache_hits: 29,
+                    scrape_cache_misses: 30
                 }
             }),
             Stats {
@@ -110,16 +598,115 @@ mod tests {
                 tcp4_connections_handled: 5,
                 tcp4_announces_handled: 6,
                 tcp4_scrapes_handled: 7,
+                tcp4_requests_blocked: 17,
+                tcp4_requests_rate_limited: 21,
+                tcp4_early_announces: 25,
                 tcp6_connections_handled: 8,
                 tcp6_announces_handled: 9,
                 tcp6_scrapes_handled: 10,
+                tcp6_requests_blocked: 18,
+                tcp6_requests_rate_limited: 22,
+                tcp6_early_announces: 26,
                 udp4_connections_handled: 11,
                 udp4_announces_handled: 12,
                 udp4_scrapes_handled: 13,
+                udp4_requests_blocked: 19,
+                udp4_requests_rate_limited: 23,
+                udp4_early_announces: 27,
                 udp6_connections_handled: 14,
                 udp6_announces_handled: 15,
-                udp6_scrapes_handled: 16
+                udp6_scrapes_handled: 16,
+                udp6_requests_blocked: 20,
+                udp6_requests_rate_limited: 24,
+                udp6_early_announces: 28,
+                scrape_cache_hits: 29,
+                scrape_cache_misses: 30
             }
         );
     }
+
+    #[test]
+    fn history_points_should_contain_the_request_rates_since_the_previous_sample() {
+        let sample = |timestamp: u64, udp4_announces_handled: u64| Sample {
+            timestamp,
+            torrents: 1,
+            seeders: 2,
+            completed: 3,
+            leechers: 4,
+            metrics: Metrics {
+                udp4_announces_handled,
+                ..Default::default()
+            },
+        };
+
+        let points = HistoryPoint::new_vec(&[sample(60, 0), sample(120, 30)]);
+
+        assert_eq!(points[0].rates, None);
+        assert_eq!(points[1].timestamp, 120);
+        assert_eq!(points[1].torrents, 1);
+        assert_eq!(points[1].rates.unwrap().announces.udp4, 0.5);
+        assert_eq!(points[1].rates.unwrap().announces.tcp4, 0.0);
+    }
+
+    #[test]
+    fn openmetrics_resource_should_be_converted_from_tracker_metrics() {
+        let expected = r#"# TYPE torrust_tracker_torrents gauge
+# HELP torrust_tracker_torrents Number of torrents.
+torrust_tracker_torrents 4
+# TYPE torrust_tracker_seeders gauge
+# HELP torrust_tracker_seeders Number of seeders for all torrents.
+torrust_tracker_seeders 1
+# TYPE torrust_tracker_leechers gauge
+# HELP torrust_tracker_leechers Number of leechers for all torrents.
+torrust_tracker_leechers 3
+# TYPE torrust_tracker_completed gauge
+# HELP torrust_tracker_completed Number of peers that have ever completed downloading for all torrents.
+torrust_tracker_completed 2
+# TYPE torrust_tracker_connections_handled counter
+# HELP torrust_tracker_connections_handled Number of connections handled. For the HTTP tracker, every request counts as a connection.
+torrust_tracker_connections_handled_total{protocol="tcp",family="ipv4"} 5
+torrust_tracker_connections_handled_total{protocol="tcp",family="ipv6"} 8
+torrust_tracker_connections_handled_total{protocol="udp",family="ipv4"} 11
+torrust_tracker_connections_handled_total{protocol="udp",family="ipv6"} 14
+# TYPE torrust_tracker_announces_handled counter
+# HELP torrust_tracker_announces_handled Number of announce requests handled.
+torrust_tracker_announces_handled_total{protocol="tcp",family="ipv4"} 6
+torrust_tracker_announces_handled_total{protocol="tcp",family="ipv6"} 9
+torrust_tracker_announces_handled_total{protocol="udp",family="ipv4"} 12
+torrust_tracker_announces_handled_total{protocol="udp",family="ipv6"} 15
+# TYPE torrust_tracker_scrapes_handled counter
+# HELP torrust_tracker_scrapes_handled Number of scrape requests handled.
+torrust_tracker_scrapes_handled_total{protocol="tcp",family="ipv4"} 7
+torrust_tracker_scrapes_handled_total{protocol="tcp",family="ipv6"} 10
+torrust_tracker_scrapes_handled_total{protocol="udp",family="ipv4"} 13
+torrust_tracker_scrapes_handled_total{protocol="udp",family="ipv6"} 16
+# TYPE torrust_tracker_requests_blocked counter
+# HELP torrust_tracker_requests_blocked Number of announce and scrape requests blocked by the IP access list.
+torrust_tracker_requests_blocked_total{protocol="tcp",family="ipv4"} 17
+torrust_tracker_requests_blocked_total{protocol="tcp",family="ipv6"} 18
+torrust_tracker_requests_blocked_total{protocol="udp",family="ipv4"} 19
+torrust_tracker_requests_blocked_total{protocol="udp",family="ipv6"} 20
+# TYPE torrust_tracker_requests_rate_limited counter
+# HELP torrust_tracker_requests_rate_limited Number of connect, announce and scrape requests rejected by the rate limiter.
+torrust_tracker_requests_rate_limited_total{protocol="tcp",family="ipv4"} 21
+torrust_tracker_requests_rate_limited_total{protocol="tcp",family="ipv6"} 22
+torrust_tracker_requests_rate_limited_total{protocol="udp",family="ipv4"} 23
+torrust_tracker_requests_rate_limited_total{protocol="udp",family="ipv6"} 24
+# TYPE torrust_tracker_early_announces counter
+# HELP torrust_tracker_early_announces Number of announce requests received before the minimum announce interval.
+torrust_tracker_early_announces_total{protocol="tcp",family="ipv4"} 25
+torrust_tracker_early_announces_total{protocol="tcp",family="ipv6"} 26
+torrust_tracker_early_announces_total{protocol="udp",family="ipv4"} 27
+torrust_tracker_early_announces_total{protocol="udp",family="ipv6"} 28
+# TYPE torrust_tracker_scrape_cache_hits counter
+# HELP torrust_tracker_scrape_cache_hits Number of torrents in scrape requests found in the scrape cache.
+torrust_tracker_scrape_cache_hits_total 29
+# TYPE torrust_tracker_scrape_cache_misses counter
+# HELP torrust_tracker_scrape_cache_misses Number of torrents in scrape requests not found in the scrape cache.
+torrust_tracker_scrape_cache_misses_total 30
+# EOF
+"#;
+
+        assert_eq!(OpenMetrics::from(&sample_tracker_metrics()).to_string(), expected);
+    }
 }
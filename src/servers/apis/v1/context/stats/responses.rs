@@ -1,11 +1,47 @@
 //! API responses for the [`stats`](crate::servers::apis::v1::context::stats)
 //! API context.
-use axum::response::Json;
+use std::error::Error;
 
-use super::resources::Stats;
+use axum::http::header;
+use axum::response::{IntoResponse, Json, Response};
+
+use super::resources::{HistoryPoint, OpenMetrics, Stats};
+use crate::core::metrics_history::Sample;
 use crate::core::services::statistics::TrackerMetrics;
+use crate::servers::apis::v1::responses::{bad_request_response, unhandled_rejection_response};
 
 /// `200` response that contains the [`Stats`] resource as json.
 pub fn stats_response(tracker_metrics: TrackerMetrics) -> Json<Stats> {
     Json(Stats::from(tracker_metrics))
 }
+
+/// `200` response that contains the [`OpenMetrics`] resource as text.
+pub fn openmetrics_response(tracker_metrics: &TrackerMetrics) -> Response {
+    (
+        [(
+            header::CONTENT_TYPE,
+            "application/openmetrics-text; version=1.0.0; charset=utf-8",
+        )],
+        OpenMetrics::from(tracker_metrics).to_string(),
+    )
+        .into_response()
+}
+
+/// `200` response that contains an array of [`HistoryPoint`] resources as
+/// json.
+pub fn stats_history_response(samples: &[Sample]) -> Json<Vec<HistoryPoint>> {
+    Json(HistoryPoint::new_vec(samples))
+}
+
+/// `400` error response when the metrics history is requested but it's not
+/// enabled in the tracker configuration.
+#[must_use]
+pub fn metrics_history_disabled_response() -> Response {
+    bad_request_response("The metrics history is disabled in the tracker configuration")
+}
+
+/// `500` error response when the tracker statistics cannot be reset.
+#[must_use]
+pub fn failed_to_reset_stats_response<E: Error>(e: E) -> Response {
+    unhandled_rejection_response(format!("failed to reset statistics: {e}"))
+}
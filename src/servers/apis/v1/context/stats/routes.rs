@@ -1,17 +1,39 @@
 //! API routes for the [`stats`](crate::servers::apis::v1::context::stats) API context.
 //!
 //! - `GET /stats`
+//! - `DELETE /stats`
+//! - `GET /stats/history`
 //!
 //! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::stats).
 use std::sync::Arc;
 
-use axum::routing::get;
-use axum::Router;
+use axum::routing::{delete, get};
+use axum::{middleware, Router};
+use torrust_tracker_configuration::Scope;
 
-use super::handlers::get_stats_handler;
+use super::handlers::{get_stats_handler, get_stats_history_handler, reset_stats_handler};
 use crate::core::Tracker;
+use crate::servers::apis::v1::middlewares::auth::require_scope;
 
 /// It adds the routes to the router for the [`stats`](crate::servers::apis::v1::context::stats) API context.
 pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
-    router.route(&format!("{prefix}/stats"), get(get_stats_handler).with_state(tracker))
+    router
+        .route(
+            &format!("{prefix}/stats"),
+            get(get_stats_handler)
+                .route_layer(middleware::from_fn_with_state(Scope::StatsRead, require_scope))
+                .with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/stats"),
+            delete(reset_stats_handler)
+                .route_layer(middleware::from_fn_with_state(Scope::StatsWrite, require_scope))
+                .with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/stats/history"),
+            get(get_stats_history_handler)
+                .route_layer(middleware::from_fn_with_state(Scope::StatsRead, require_scope))
+                .with_state(tracker),
+        )
 }
@@ -13,7 +13,7 @@ use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_primitives::pagination::Pagination;
 
 use super::responses::{torrent_info_response, torrent_list_response, torrent_not_known_response};
-use crate::core::services::torrent::{get_torrent_info, get_torrents, get_torrents_page};
+use crate::core::services::torrent::{get_torrent_info, get_torrents, get_torrents_page, SortBy};
 use crate::core::Tracker;
 use crate::servers::apis::v1::responses::invalid_info_hash_param_response;
 use crate::servers::apis::InfoHashParam;
@@ -40,6 +40,7 @@ pub async fn get_torrent_handler(State(tracker): State<Arc<Tracker>>, Path(info_
 /// A container for the URL query parameters.
 ///
 /// Pagination: `offset` and `limit`.
+/// Sorting: `sort`.
 /// Array of infohashes: `info_hash`.
 ///
 /// You can either get all torrents with pagination or get a list of torrents
@@ -64,6 +65,10 @@ pub struct QueryParams {
     /// The maximum number of items to return per page.
     #[serde(default, deserialize_with = "empty_string_as_none")]
     pub limit: Option<u32>,
+    /// The torrent activity stat used to sort the torrents, in descending
+    /// order. See [`SortBy`].
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub sort: Option<SortBy>,
     /// A list of infohashes to retrieve.
     #[serde(default, rename = "info_hash")]
     pub info_hashes: Vec<String>,
@@ -83,6 +88,7 @@ pub async fn get_torrents_handler(State(tracker): State<Arc<Tracker>>, paginatio
             &get_torrents_page(
                 tracker.clone(),
                 Some(&Pagination::new_with_options(pagination.0.offset, pagination.0.limit)),
+                pagination.0.sort,
             )
             .await,
         )
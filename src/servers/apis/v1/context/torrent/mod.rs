@@ -52,6 +52,26 @@
 //! }
 //! ```
 //!
+//! When the torrent activity statistics are enabled with the
+//! `core.torrent_statistics` option, the response also includes them:
+//!
+//! ```json
+//! {
+//!     "info_hash": "5452869be36f9f3350ccee6b4544e7e76caaadab",
+//!     "seeders": 1,
+//!     "completed": 0,
+//!     "leechers": 0,
+//!     "peers": [],
+//!     "stats": {
+//!       "announces": 12,
+//!       "scrapes": 3,
+//!       "last_activity": 1680082693,
+//!       "peak_seeders": 4,
+//!       "peak_leechers": 7
+//!     }
+//! }
+//! ```
+//!
 //! **Not Found response** `200`
 //!
 //! This response is returned when the tracker does not have the torrent.
@@ -79,6 +99,11 @@
 //! ---|---|---|---|---
 //! `offset` | positive integer | The page number, starting at 0 | No | `1`
 //! `limit` | positive integer | Page size. The number of results per page | No | `10`
+//! `sort` | string | Sort the torrents by an activity stat, in descending order: `announces`, `scrapes`, `last_activity`, `peak_seeders` or `peak_leechers` | No | `announces`
+//!
+//! Sorting requires the torrent activity statistics to be enabled with the
+//! `core.torrent_statistics` option. Otherwise, all the stats are zero and
+//! the torrents keep their default order.
 //!
 //! **Example request**
 //!
@@ -5,6 +5,7 @@
 //!   include a `peers` field but it is always `None` in the struct and `null` in
 //!   the JSON response.
 use serde::{Deserialize, Serialize};
+use torrust_tracker_primitives::torrent_stats::TorrentStats;
 
 use crate::core::services::torrent::{BasicInfo, Info};
 
@@ -25,6 +26,28 @@ pub struct Torrent {
     /// The torrent's peers. See [`Peer`](crate::servers::apis::v1::context::torrent::resources::peer::Peer).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub peers: Option<Vec<super::peer::Peer>>,
+    /// The torrent's activity statistics. Only when they are enabled in the
+    /// tracker configuration. See [`Stats`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stats: Option<Stats>,
+}
+
+/// Torrent activity `Stats` API resource.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Stats {
+    /// The number of `announce` requests for the torrent.
+    pub announces: u64,
+    /// The number of `scrape` requests that included the torrent.
+    pub scrapes: u64,
+    /// The time of the last `announce` or `scrape` request, in seconds since
+    /// the Unix epoch.
+    pub last_activity: Option<u64>,
+    /// The maximum number of seeders the torrent has ever had at the same
+    /// time.
+    pub peak_seeders: u32,
+    /// The maximum number of leechers the torrent has ever had at the same
+    /// time.
+    pub peak_leechers: u32,
 }
 
 /// `ListItem` API resource. A list item on a torrent list.
@@ -77,6 +100,19 @@ impl From<Info> for Torrent {
             completed: info.completed,
             leechers: info.leechers,
             peers,
+            stats: info.stats.map(Stats::from),
+        }
+    }
+}
+
+impl From<TorrentStats> for Stats {
+    fn from(stats: TorrentStats) -> Self {
+        Self {
+            announces: stats.announces,
+            scrapes: stats.scrapes,
+            last_activity: stats.last_activity.map(|last_activity| last_activity.as_secs()),
+            peak_seeders: stats.peak_seeders,
+            peak_leechers: stats.peak_leechers,
         }
     }
 }
@@ -110,6 +146,9 @@ mod tests {
         peer::Peer {
             peer_id: PeerId(*b"-qB00000000000000000"),
             peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080),
+            alt_peer_addr: None,
+            key: None,
+            crypto: peer::Crypto::NotSupported,
             updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
             uploaded: NumberOfBytes::new(0),
             downloaded: NumberOfBytes::new(0),
@@ -127,6 +166,7 @@ mod tests {
                 completed: 2,
                 leechers: 3,
                 peers: Some(vec![sample_peer()]),
+                stats: None,
             }),
             Torrent {
                 info_hash: "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_string(),
@@ -134,6 +174,7 @@ mod tests {
                 completed: 2,
                 leechers: 3,
                 peers: Some(vec![Peer::from(sample_peer())]),
+                stats: None,
             }
         );
     }
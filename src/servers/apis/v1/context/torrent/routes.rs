@@ -7,10 +7,12 @@
 use std::sync::Arc;
 
 use axum::routing::get;
-use axum::Router;
+use axum::{middleware, Router};
+use torrust_tracker_configuration::Scope;
 
 use super::handlers::{get_torrent_handler, get_torrents_handler};
 use crate::core::Tracker;
+use crate::servers::apis::v1::middlewares::auth::require_scope;
 
 /// It adds the routes to the router for the [`torrent`](crate::servers::apis::v1::context::torrent) API context.
 pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
@@ -18,7 +20,14 @@ pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
     router
         .route(
             &format!("{prefix}/torrent/:info_hash"),
-            get(get_torrent_handler).with_state(tracker.clone()),
+            get(get_torrent_handler)
+                .route_layer(middleware::from_fn_with_state(Scope::TorrentsRead, require_scope))
+                .with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/torrents"),
+            get(get_torrents_handler)
+                .route_layer(middleware::from_fn_with_state(Scope::TorrentsRead, require_scope))
+                .with_state(tracker),
         )
-        .route(&format!("{prefix}/torrents"), get(get_torrents_handler).with_state(tracker))
 }
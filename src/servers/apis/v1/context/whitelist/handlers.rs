@@ -3,17 +3,80 @@
 use std::str::FromStr;
 use std::sync::Arc;
 
-use axum::extract::{Path, State};
+use axum::extract::{self, Path, State};
 use axum::response::Response;
+use serde::Deserialize;
 use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::pagination::Pagination;
 
+use super::resources::WhitelistedTorrent;
 use super::responses::{
     failed_to_reload_whitelist_response, failed_to_remove_torrent_from_whitelist_response, failed_to_whitelist_torrent_response,
+    invalid_info_hash_response, torrent_not_whitelisted_response, whitelist_response, whitelisted_torrent_response,
 };
+use crate::core::services::whitelist::get_whitelist_page;
 use crate::core::Tracker;
 use crate::servers::apis::v1::responses::{invalid_info_hash_param_response, ok_response};
 use crate::servers::apis::InfoHashParam;
 
+/// A container for the URL query parameters of the whitelist.
+///
+/// Pagination: `offset` and `limit`.
+#[derive(Deserialize, Debug)]
+pub struct QueryParams {
+    /// The offset of the first page to return. Starts at 0.
+    pub offset: Option<u32>,
+    /// The maximum number of items to return per page.
+    pub limit: Option<u32>,
+}
+
+/// It handles the request to get the whitelisted torrents.
+///
+/// It returns a `200` response with a json array with
+/// [`WhitelistedTorrent`] resources.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::whitelist#list-the-whitelisted-torrents)
+/// for more information about this endpoint.
+pub async fn get_whitelist_handler(
+    State(tracker): State<Arc<Tracker>>,
+    extract::Query(params): extract::Query<QueryParams>,
+) -> Response {
+    let whitelist = get_whitelist_page(
+        tracker.clone(),
+        Some(&Pagination::new_with_options(params.offset, params.limit)),
+    )
+    .await;
+
+    whitelist_response(&whitelist.into_iter().map(WhitelistedTorrent::from).collect())
+}
+
+/// It handles the request to get a whitelisted torrent.
+///
+/// It returns:
+///
+/// - `200` response with a json [`WhitelistedTorrent`] resource if the
+///   torrent is whitelisted.
+/// - `200` response with the json string `"torrent not whitelisted"`
+///   otherwise.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::whitelist#get-a-whitelisted-torrent)
+/// for more information about this endpoint.
+pub async fn get_whitelisted_torrent_handler(
+    State(tracker): State<Arc<Tracker>>,
+    Path(info_hash): Path<InfoHashParam>,
+) -> Response {
+    match InfoHash::from_str(&info_hash.0) {
+        Err(_) => invalid_info_hash_param_response(&info_hash.0),
+        Ok(info_hash) => {
+            if tracker.is_info_hash_whitelisted(&info_hash).await {
+                whitelisted_torrent_response(&WhitelistedTorrent::from(info_hash))
+            } else {
+                torrent_not_whitelisted_response()
+            }
+        }
+    }
+}
+
 /// It handles the request to add a torrent to the whitelist.
 ///
 /// It returns:
@@ -75,3 +138,62 @@ pub async fn reload_whitelist_handler(State(tracker): State<Arc<Tracker>>) -> Re
         Err(e) => failed_to_reload_whitelist_response(e),
     }
 }
+
+/// It handles the request to add many torrents to the whitelist at once.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `400` with an error if any of the infohashes is not valid. No torrent is
+///   whitelisted in that case.
+/// - `500` with serialized error in debug format if the torrents couldn't be
+///   whitelisted.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::whitelist#add-many-torrents-to-the-whitelist)
+/// for more information about this endpoint.
+pub async fn add_torrents_to_whitelist_handler(
+    State(tracker): State<Arc<Tracker>>,
+    extract::Json(info_hashes): extract::Json<Vec<String>>,
+) -> Response {
+    match parse_info_hashes(&info_hashes) {
+        Err(invalid_info_hash) => invalid_info_hash_response(invalid_info_hash),
+        Ok(info_hashes) => match tracker.add_torrents_to_whitelist(&info_hashes).await {
+            Ok(()) => ok_response(),
+            Err(e) => failed_to_whitelist_torrent_response(e),
+        },
+    }
+}
+
+/// It handles the request to remove many torrents from the whitelist at once.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `400` with an error if any of the infohashes is not valid. No torrent is
+///   removed from the whitelist in that case.
+/// - `500` with serialized error in debug format if the torrents couldn't be
+///   removed from the whitelist.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::whitelist#remove-many-torrents-from-the-whitelist)
+/// for more information about this endpoint.
+pub async fn remove_torrents_from_whitelist_handler(
+    State(tracker): State<Arc<Tracker>>,
+    extract::Json(info_hashes): extract::Json<Vec<String>>,
+) -> Response {
+    match parse_info_hashes(&info_hashes) {
+        Err(invalid_info_hash) => invalid_info_hash_response(invalid_info_hash),
+        Ok(info_hashes) => match tracker.remove_torrents_from_whitelist(&info_hashes).await {
+            Ok(()) => ok_response(),
+            Err(e) => failed_to_remove_torrent_from_whitelist_response(e),
+        },
+    }
+}
+
+/// It parses all the infohashes. It returns the first invalid infohash if
+/// any of them is not valid.
+fn parse_info_hashes(info_hashes: &[String]) -> Result<Vec<InfoHash>, &str> {
+    info_hashes
+        .iter()
+        .map(|info_hash| InfoHash::from_str(info_hash).map_err(|_| info_hash.as_str()))
+        .collect()
+}
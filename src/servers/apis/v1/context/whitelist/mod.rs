@@ -20,10 +20,82 @@
 //!
 //! # Endpoints
 //!
+//! - [List the whitelisted torrents](#list-the-whitelisted-torrents)
+//! - [Get a whitelisted torrent](#get-a-whitelisted-torrent)
 //! - [Add a torrent to the whitelist](#add-a-torrent-to-the-whitelist)
+//! - [Add many torrents to the whitelist](#add-many-torrents-to-the-whitelist)
 //! - [Remove a torrent from the whitelist](#remove-a-torrent-from-the-whitelist)
+//! - [Remove many torrents from the whitelist](#remove-many-torrents-from-the-whitelist)
 //! - [Reload the whitelist](#reload-the-whitelist)
 //!
+//! # List the whitelisted torrents
+//!
+//! `GET /whitelist`
+//!
+//! It returns the whitelisted torrents ordered by infohash.
+//!
+//! **Query parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `offset` | positive integer | The page number, starting at 0. | No | `1`
+//! `limit` | positive integer | Page size. The number of results per page. | No | `10`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/whitelist?token=MyAccessToken&offset=0&limit=10"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! [
+//!     {
+//!         "info_hash": "5452869be36f9f3350ccee6b4544e7e76caaadab"
+//!     }
+//! ]
+//! ```
+//!
+//! **Resource**
+//!
+//! Refer to the API [`WhitelistedTorrent`](crate::servers::apis::v1::context::whitelist::resources::WhitelistedTorrent)
+//! resource for more information about the response attributes.
+//!
+//! # Get a whitelisted torrent
+//!
+//! `GET /whitelist/:info_hash`
+//!
+//! It checks if a torrent is in the whitelist.
+//!
+//! **Path parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `info_hash` | 40-char string | The Info Hash v1 | Yes | `5452869be36f9f3350ccee6b4544e7e76caaadab`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/whitelist/5452869be36f9f3350ccee6b4544e7e76caaadab?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "info_hash": "5452869be36f9f3350ccee6b4544e7e76caaadab"
+//! }
+//! ```
+//!
+//! **Not Found response** `200`
+//!
+//! This response is returned when the torrent is not in the whitelist.
+//!
+//! ```json
+//! "torrent not whitelisted"
+//! ```
+//!
 //! # Add a torrent to the whitelist
 //!
 //! `POST /whitelist/:info_hash`
@@ -50,6 +122,31 @@
 //! }
 //! ```
 //!
+//! # Add many torrents to the whitelist
+//!
+//! `POST /whitelist`
+//!
+//! It adds all the infohashes in the JSON array of the request body to the
+//! whitelist. Torrents that are already whitelisted are ignored.
+//!
+//! Nothing is added if any of the infohashes is not valid.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X POST "http://127.0.0.1:1212/api/v1/whitelist?token=MyAccessToken" \
+//!      -H "Content-Type: application/json" \
+//!      -d '["5452869be36f9f3350ccee6b4544e7e76caaadab", "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d"]'
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
 //! # Remove a torrent from the whitelist
 //!
 //! `DELETE /whitelist/:info_hash`
@@ -76,6 +173,31 @@
 //! }
 //! ```
 //!
+//! # Remove many torrents from the whitelist
+//!
+//! `DELETE /whitelist`
+//!
+//! It removes all the infohashes in the JSON array of the request body from
+//! the whitelist. Torrents that are not whitelisted are ignored.
+//!
+//! Nothing is removed if any of the infohashes is not valid.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X DELETE "http://127.0.0.1:1212/api/v1/whitelist?token=MyAccessToken" \
+//!      -H "Content-Type: application/json" \
+//!      -d '["5452869be36f9f3350ccee6b4544e7e76caaadab", "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d"]'
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
 //! # Reload the whitelist
 //!
 //! It reloads the whitelist from the database.
@@ -94,5 +216,6 @@
 //! }
 //! ```
 pub mod handlers;
+pub mod resources;
 pub mod responses;
 pub mod routes;
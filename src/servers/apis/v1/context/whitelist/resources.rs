@@ -0,0 +1,38 @@
+//! API resources for the [`whitelist`](crate::servers::apis::v1::context::whitelist)
+//! API context.
+use serde::{Deserialize, Serialize};
+use torrust_tracker_primitives::info_hash::InfoHash;
+
+/// A resource that represents a whitelisted torrent.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct WhitelistedTorrent {
+    /// The torrent's info hash v1.
+    pub info_hash: String,
+}
+
+impl From<InfoHash> for WhitelistedTorrent {
+    fn from(info_hash: InfoHash) -> Self {
+        Self {
+            info_hash: info_hash.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use torrust_tracker_primitives::info_hash::InfoHash;
+
+    use super::WhitelistedTorrent;
+
+    #[test]
+    fn it_should_be_convertible_from_an_info_hash() {
+        let info_hash = "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".parse::<InfoHash>().unwrap(); // DevSkim: ignore DS173237
+
+        assert_eq!(
+            WhitelistedTorrent::from(info_hash),
+            WhitelistedTorrent {
+                info_hash: "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_string() // DevSkim: ignore DS173237
+            }
+        );
+    }
+}
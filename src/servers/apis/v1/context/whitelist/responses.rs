@@ -2,9 +2,61 @@
 //! API context.
 use std::error::Error;
 
-use axum::response::Response;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Json, Response};
+use serde_json::json;
 
-use crate::servers::apis::v1::responses::unhandled_rejection_response;
+use super::resources::WhitelistedTorrent;
+use crate::servers::apis::v1::responses::{bad_request_response, unhandled_rejection_response};
+
+/// `200` response that contains a `WhitelistedTorrent` resource as json.
+///
+/// # Panics
+///
+/// Will panic if it can't convert the `WhitelistedTorrent` resource to json
+#[must_use]
+pub fn whitelisted_torrent_response(torrent: &WhitelistedTorrent) -> Response {
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "application/json; charset=utf-8")],
+        serde_json::to_string(torrent).unwrap(),
+    )
+        .into_response()
+}
+
+/// `200` response that contains an array of `WhitelistedTorrent` resources as
+/// json.
+///
+/// # Panics
+///
+/// Will panic if it can't convert the `WhitelistedTorrent` resources to json
+#[must_use]
+pub fn whitelist_response(torrents: &Vec<WhitelistedTorrent>) -> Response {
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "application/json; charset=utf-8")],
+        serde_json::to_string(torrents).unwrap(),
+    )
+        .into_response()
+}
+
+/// `200` response with the json string `"torrent not whitelisted"`, returned
+/// when the torrent is not in the whitelist.
+#[must_use]
+pub fn torrent_not_whitelisted_response() -> Response {
+    Json(json!("torrent not whitelisted")).into_response()
+}
+
+// Error responses
+
+/// `400` error response when one of the infohashes in the request body is not
+/// valid.
+#[must_use]
+pub fn invalid_info_hash_response(info_hash: &str) -> Response {
+    bad_request_response(&format!(
+        "invalid infohash: string \"{info_hash}\", expected a 40 character long string"
+    ))
+}
 
 /// `500` error response when a torrent cannot be removed from the whitelist.
 #[must_use]
@@ -1,17 +1,26 @@
 //! API routes for the [`whitelist`](crate::servers::apis::v1::context::whitelist) API context.
 //!
+//! - `GET /whitelist`
+//! - `POST /whitelist`
+//! - `DELETE /whitelist`
+//! - `GET /whitelist/:info_hash`
 //! - `POST /whitelist/:info_hash`
 //! - `DELETE /whitelist/:info_hash`
 //! - `GET /whitelist/reload`
 //!
-//! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::torrent).
+//! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::whitelist).
 use std::sync::Arc;
 
 use axum::routing::{delete, get, post};
-use axum::Router;
+use axum::{middleware, Router};
+use torrust_tracker_configuration::Scope;
 
-use super::handlers::{add_torrent_to_whitelist_handler, reload_whitelist_handler, remove_torrent_from_whitelist_handler};
+use super::handlers::{
+    add_torrent_to_whitelist_handler, add_torrents_to_whitelist_handler, get_whitelist_handler, get_whitelisted_torrent_handler,
+    reload_whitelist_handler, remove_torrent_from_whitelist_handler, remove_torrents_from_whitelist_handler,
+};
 use crate::core::Tracker;
+use crate::servers::apis::v1::middlewares::auth::require_scope;
 
 /// It adds the routes to the router for the [`whitelist`](crate::servers::apis::v1::context::whitelist) API context.
 pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
@@ -19,14 +28,42 @@ pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
 
     router
         // Whitelisted torrents
+        .route(
+            &prefix,
+            get(get_whitelist_handler)
+                .route_layer(middleware::from_fn_with_state(Scope::WhitelistRead, require_scope))
+                .with_state(tracker.clone()),
+        )
+        .route(
+            &prefix,
+            post(add_torrents_to_whitelist_handler)
+                .delete(remove_torrents_from_whitelist_handler)
+                .route_layer(middleware::from_fn_with_state(Scope::WhitelistWrite, require_scope))
+                .with_state(tracker.clone()),
+        )
         .route(
             &format!("{prefix}/:info_hash"),
-            post(add_torrent_to_whitelist_handler).with_state(tracker.clone()),
+            get(get_whitelisted_torrent_handler)
+                .route_layer(middleware::from_fn_with_state(Scope::WhitelistRead, require_scope))
+                .with_state(tracker.clone()),
         )
         .route(
             &format!("{prefix}/:info_hash"),
-            delete(remove_torrent_from_whitelist_handler).with_state(tracker.clone()),
+            post(add_torrent_to_whitelist_handler)
+                .route_layer(middleware::from_fn_with_state(Scope::WhitelistWrite, require_scope))
+                .with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/:info_hash"),
+            delete(remove_torrent_from_whitelist_handler)
+                .route_layer(middleware::from_fn_with_state(Scope::WhitelistWrite, require_scope))
+                .with_state(tracker.clone()),
         )
         // Whitelist commands
-        .route(&format!("{prefix}/reload"), get(reload_whitelist_handler).with_state(tracker))
+        .route(
+            &format!("{prefix}/reload"),
+            get(reload_whitelist_handler)
+                .route_layer(middleware::from_fn_with_state(Scope::WhitelistWrite, require_scope))
+                .with_state(tracker),
+        )
 }
@@ -1,13 +1,21 @@
 //! Authentication middleware for the API.
 //!
-//! It uses a "token" GET param to authenticate the user. URLs must be of the
-//! form:
+//! The token can be sent in the `Authorization` header using the `Bearer`
+//! scheme:
+//!
+//! `Authorization: Bearer <token>`
+//!
+//! Or in the "token" GET param. In that case, URLs must be of the form:
 //!
 //! `http://<host>:<port>/api/v1/<context>?token=<token>`.
 //!
 //! > **NOTICE**: the token can be at any position in the URL, not just at the
 //! > beginning or at the end.
 //!
+//! The header is preferred because URLs usually end up in the access logs of
+//! the proxies. The GET param can be disabled with the `query_token_enabled`
+//! option. When both are sent, the header is used.
+//!
 //! The token must be one of the `access_tokens` in the tracker
 //! [HTTP API configuration](torrust_tracker_configuration::HttpApi).
 //!
@@ -18,17 +26,49 @@
 //! admin = "MyAccessToken"
 //! ```
 //!
-//! All the tokes have the same permissions, so it is not possible to have
-//! different permissions for different tokens. The label is only used to
-//! identify the token.
-use std::sync::Arc;
+//! By default, all the tokens have all the permissions. The permissions of a
+//! token can be limited to a list of [`Scope`]s using its label:
+//!
+//! ```toml
+//! [http_api.access_tokens]
+//! admin = "MyAccessToken"
+//! monitoring = "MyMonitoringToken"
+//!
+//! [http_api.access_token_scopes]
+//! monitoring = ["stats:read", "torrents:read"]
+//! ```
+//!
+//! Each route requires one scope. The [`auth`] middleware only checks the
+//! token is valid, and the [`require_scope`] middleware, added to each route,
+//! checks the token has the scope the route requires. These are the available
+//! scopes:
+//!
+//! Scope                  | Endpoints
+//! -----------------------|----------------------------------------------
+//! `accounting:read`      | `GET /accounting`, `GET /accounting/:key`
+//! `config:write`         | `POST /config/reload`
+//! `events:read`          | `GET /events`
+//! `ip_access_list:read`  | `GET /ip-access-list`
+//! `ip_access_list:write` | `POST /ip-access-list`, `DELETE /ip-access-list/:network`, `GET /ip-access-list/reload`
+//! `keys:read`            | `GET /key/:key`, `GET /keys` and the authentication key events in `GET /events`
+//! `keys:write`           | `POST /key/:seconds_valid`, `DELETE /key/:key`, `POST /keys`, `GET /keys/reload`
+//! `stats:read`           | `GET /stats`, `GET /stats/history`
+//! `stats:write`          | `DELETE /stats`
+//! `torrents:read`        | `GET /torrent/:info_hash`, `GET /torrents`
+//! `whitelist:read`       | `GET /whitelist`, `GET /whitelist/:info_hash`
+//! `whitelist:write`      | `POST /whitelist`, `DELETE /whitelist`, `POST /whitelist/:info_hash`, `DELETE /whitelist/:info_hash`, `GET /whitelist/reload`
+//!
+//! The tokens and their scopes can be changed without restarting the tracker
+//! by [reloading the configuration](crate::bootstrap::reload).
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
 
 use axum::extract::{self};
-use axum::http::Request;
+use axum::http::{header, HeaderMap, Request};
 use axum::middleware::Next;
 use axum::response::{IntoResponse, Response};
 use serde::Deserialize;
-use torrust_tracker_configuration::AccessTokens;
+use torrust_tracker_configuration::{AccessTokenScopes, AccessTokens, Scope};
 
 use crate::servers::apis::v1::responses::unhandled_rejection_response;
 
@@ -40,33 +80,89 @@ pub struct QueryParams {
 
 #[derive(Clone, Debug)]
 pub struct State {
-    pub access_tokens: Arc<AccessTokens>,
+    pub access_tokens: Arc<RwLock<AccessTokens>>,
+    pub access_token_scopes: Arc<RwLock<AccessTokenScopes>>,
+    pub query_token_enabled: Arc<AtomicBool>,
+}
+
+/// The scopes granted to the token used in the request. `None` means the token
+/// has all the scopes.
+#[derive(Clone, Debug)]
+pub struct GrantedScopes(Option<Vec<Scope>>);
+
+impl GrantedScopes {
+    #[must_use]
+    pub fn contains(&self, scope: Scope) -> bool {
+        self.0.as_ref().map_or(true, |scopes| scopes.contains(&scope))
+    }
 }
 
-/// Middleware for authentication using a "token" GET param.
+/// Middleware for authentication using the `Authorization: Bearer` header or
+/// the "token" GET param.
 /// The token must be one of the tokens in the tracker [HTTP API configuration](torrust_tracker_configuration::HttpApi).
+///
+/// The scopes granted to the token are added to the request extensions, so
+/// that the [`require_scope`] middleware can check them.
 pub async fn auth(
     extract::State(state): extract::State<State>,
     extract::Query(params): extract::Query<QueryParams>,
-    request: Request<axum::body::Body>,
+    mut request: Request<axum::body::Body>,
     next: Next,
 ) -> Response {
-    let Some(token) = params.token else {
-        return AuthError::Unauthorized.into_response();
+    let token = match bearer_token(request.headers()) {
+        Some(token) => token,
+        None if state.query_token_enabled.load(Ordering::Relaxed) => match params.token {
+            Some(token) => token,
+            None => return AuthError::Unauthorized.into_response(),
+        },
+        None => return AuthError::Unauthorized.into_response(),
     };
 
-    if !authenticate(&token, &state.access_tokens) {
+    let Some(label) = authenticate(
+        &token,
+        &state.access_tokens.read().expect("it should get the API access tokens"),
+    ) else {
         return AuthError::TokenNotValid.into_response();
-    }
+    };
+
+    let granted_scopes = GrantedScopes(
+        state
+            .access_token_scopes
+            .read()
+            .expect("it should get the API access token scopes")
+            .get(&label)
+            .cloned(),
+    );
+
+    request.extensions_mut().insert(granted_scopes);
 
     next.run(request).await
 }
 
+/// Middleware that only lets the request through if the token has the
+/// [`Scope`] the route requires.
+///
+/// It must run after the [`auth`] middleware. The request is rejected if the
+/// token was not authenticated.
+pub async fn require_scope(
+    extract::State(scope): extract::State<Scope>,
+    request: Request<axum::body::Body>,
+    next: Next,
+) -> Response {
+    match request.extensions().get::<GrantedScopes>() {
+        Some(granted_scopes) if granted_scopes.contains(scope) => next.run(request).await,
+        Some(_) => AuthError::ScopeNotGranted(scope).into_response(),
+        None => AuthError::Unauthorized.into_response(),
+    }
+}
+
 enum AuthError {
     /// Missing token for authentication.
     Unauthorized,
     /// Token was provided but it is not valid.
     TokenNotValid,
+    /// The token is valid but it does not have the scope required by the route.
+    ScopeNotGranted(Scope),
 }
 
 impl IntoResponse for AuthError {
@@ -74,12 +170,25 @@ impl IntoResponse for AuthError {
         match self {
             AuthError::Unauthorized => unauthorized_response(),
             AuthError::TokenNotValid => token_not_valid_response(),
+            AuthError::ScopeNotGranted(scope) => scope_not_granted_response(scope),
         }
     }
 }
 
-fn authenticate(token: &str, tokens: &AccessTokens) -> bool {
-    tokens.values().any(|t| t == token)
+/// It returns the token in the `Authorization` header, if the header uses the
+/// `Bearer` scheme. The scheme is case-insensitive.
+fn bearer_token(headers: &HeaderMap) -> Option<String> {
+    let (scheme, token) = headers.get(header::AUTHORIZATION)?.to_str().ok()?.split_once(' ')?;
+
+    scheme.eq_ignore_ascii_case("bearer").then(|| token.trim().to_string())
+}
+
+/// It returns the label of the token if it's one of the access tokens.
+fn authenticate(token: &str, tokens: &AccessTokens) -> Option<String> {
+    tokens
+        .iter()
+        .find(|(_, access_token)| *access_token == token)
+        .map(|(label, _)| label.clone())
 }
 
 /// `500` error response returned when the token is missing.
@@ -93,3 +202,10 @@ pub fn unauthorized_response() -> Response {
 pub fn token_not_valid_response() -> Response {
     unhandled_rejection_response("token not valid".to_string())
 }
+
+/// `500` error response when the token does not have the scope required by the
+/// route.
+#[must_use]
+pub fn scope_not_granted_response(scope: Scope) -> Response {
+    unhandled_rejection_response(format!("token does not have the scope {scope}"))
+}
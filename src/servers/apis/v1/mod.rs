@@ -7,7 +7,10 @@
 //! `Stats` | Tracker statistics | [`v1`](crate::servers::apis::v1::context::stats)
 //! `Torrents` | Torrents | [`v1`](crate::servers::apis::v1::context::torrent)
 //! `Whitelist` | Torrents whitelist | [`v1`](crate::servers::apis::v1::context::whitelist)
+//! `IP access list` | Allowed and denied client networks | [`v1`](crate::servers::apis::v1::context::ip_access_list)
 //! `Authentication keys` | Authentication keys | [`v1`](crate::servers::apis::v1::context::auth_key)
+//! `Configuration` | Tracker configuration | [`v1`](crate::servers::apis::v1::context::config)
+//! `Events` | Real-time tracker events | [`v1`](crate::servers::apis::v1::context::events)
 //!
 //! > **NOTICE**:
 //! - The authentication keys are only used by the HTTP tracker.
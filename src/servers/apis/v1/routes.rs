@@ -1,18 +1,27 @@
 //! Route initialization for the v1 API.
+//!
+//! Each context adds the [`require_scope`](crate::servers::apis::v1::middlewares::auth::require_scope)
+//! middleware to its routes with the [`Scope`](torrust_tracker_configuration::Scope)
+//! the access token needs to use them.
 use std::sync::Arc;
 
 use axum::Router;
 
-use super::context::{auth_key, stats, torrent, whitelist};
+use super::context::{accounting, auth_key, config, events, ip_access_list, stats, torrent, whitelist};
+use crate::bootstrap::reload::Reloader;
 use crate::core::Tracker;
 
 /// Add the routes for the v1 API.
-pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
+pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>, reloader: Arc<Reloader>) -> Router {
     let v1_prefix = format!("{prefix}/v1");
 
     let router = auth_key::routes::add(&v1_prefix, router, tracker.clone());
+    let router = accounting::routes::add(&v1_prefix, router, tracker.clone());
     let router = stats::routes::add(&v1_prefix, router, tracker.clone());
     let router = whitelist::routes::add(&v1_prefix, router, tracker.clone());
+    let router = ip_access_list::routes::add(&v1_prefix, router, tracker.clone());
+    let router = events::routes::add(&v1_prefix, router, tracker.clone());
+    let router = config::routes::add(&v1_prefix, router, reloader);
 
     torrent::routes::add(&v1_prefix, router, tracker)
 }
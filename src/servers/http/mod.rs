@@ -53,6 +53,14 @@
 //! [`event`](crate::servers::http::v1::requests::announce::Announce::event) | positive integer | The event that triggered the `Announce` request: `started`, `completed`, `stopped` | No | `None` | `completed`
 //! [`compact`](crate::servers::http::v1::requests::announce::Announce::compact) | `0` or `1` | Whether the tracker should return a compact peer list. | No | `None` | `0`
 //! `numwant` | positive integer | **Not implemented**. The maximum number of peers you want in the reply. | No | `50` | `50`
+//! [`ip`](crate::servers::http::v1::requests::announce::Announce::ip) | IPv4 or IPv6 address | The IP address of the peer. | No | No | `2.137.87.41`
+//! [`ipv4`](crate::servers::http::v1::requests::announce::Announce::ipv4) | IPv4 address, with optional port | The IPv4 address of the peer. | No | No | `2.137.87.41:17548`
+//! [`ipv6`](crate::servers::http::v1::requests::announce::Announce::ipv6) | IPv6 address, with optional port | The IPv6 address of the peer. | No | No | `[2001:db8::1]:17548`
+//! [`key`](crate::servers::http::v1::requests::announce::Announce::key) | string | A key the peer uses to prove its identity if its IP address changes. | No | No | `EF3495D6`
+//! [`no_peer_id`](crate::servers::http::v1::requests::announce::Announce::no_peer_id) | `0` or `1` | Whether the tracker can leave out the peer IDs in the non-compact peer list. | No | `0` | `1`
+//! `supportcrypto` | `0` or `1` | Whether the peer supports encrypted connections. | No | `0` | `1`
+//! `requirecrypto` | `0` or `1` | Whether the peer only accepts encrypted connections. | No | `0` | `1`
+//! [`cryptoport`](crate::servers::http::v1::requests::announce::Announce::cryptoport) | positive integer | The port used by the peer for encrypted connections. | No | No | `17549`
 //!
 //! Refer to the [`Announce`](crate::servers::http::v1::requests::announce::Announce)
 //! request for more information about the parameters.
@@ -70,6 +78,36 @@
 //! > request or the right-most-ip in the `X-Forwarded-For` header if the tracker
 //! > is behind a reverse proxy.
 //!
+//! > **NOTICE**: the `ip`, `ipv4` and `ipv6` parameters are ignored by
+//! > default too. They are only used when the request comes from a network
+//! > the tracker trusts. See the `core.net.client_supplied_ip` option in the
+//! > [`Network`](torrust_tracker_configuration::v2_0_0::network::Network)
+//! > configuration. Then, the `ip` parameter replaces the peer IP address.
+//!
+//! > **NOTICE**: the `ip`, `ipv4` and `ipv6` parameters are used as described
+//! > in [BEP 07. IPv6 Tracker Extension](https://www.bittorrent.org/beps/bep_0007.html)
+//! > when the tracker trusts them. The tracker always uses the request IP
+//! > address for its own IP family. The parameters only provide the peer's
+//! > address in the other IP family, so that dual-stack peers are returned to
+//! > both IPv4 and IPv6 clients. The `ipv4` and `ipv6` parameters take
+//! > precedence over the `ip` parameter.
+//! > The tracker also remembers the address of a peer that announces using
+//! > both IP families.
+//!
+//! > **NOTICE**: the `key` param is only used to verify the identity of the
+//! > peer when the `core.on_peer_identity_mismatch` option is enabled. Then,
+//! > a peer that sent a `key` must send the same `key` in the following
+//! > announces, and a peer that did not send a `key` must announce from the
+//! > same IP address.
+//!
+//! > **NOTICE**: the `supportcrypto` and `requirecrypto` params are not
+//! > defined in any BEP. The tracker stores whether each peer supports
+//! > encrypted connections. Peers that require encryption only get the peers
+//! > that support it, and they are announced with the `cryptoport`, if they
+//! > sent it. The responses to peers that support encryption include the
+//! > `crypto_flags` key: a string with one byte per peer in the `peers` list,
+//! > `1` if the peer requires encryption and `0` otherwise.
+//!
 //! > **NOTICE**: the maximum number of peers that the tracker can return is
 //! > `74`. Defined with a hardcoded const [`TORRENT_PEERS_LIMIT`](torrust_tracker_configuration::TORRENT_PEERS_LIMIT).
 //! > Refer to [issue 262](https://github.com/torrust/torrust-tracker/issues/262)
@@ -190,6 +228,8 @@
 //! 0000070: 7065                                     pe
 //! ```
 //!
+//! Dual-stack peers are included in both the `peers` and the `peers6` lists.
+//!
 //! Refer to the [`Compact`](crate::servers::http::v1::responses::announce::Compact)
 //! response for more information about the response.
 //!
@@ -220,7 +260,8 @@
 //!
 //! Parameter | Type | Description | Required |  Default | Example
 //! ---|---|---|---|---|---
-//! [`info_hash`](crate::servers::http::v1::requests::scrape::Scrape::info_hashes) | percent encoded of 20-byte array | The `Info Hash` of the torrent. | Yes | No | `%81%00%00%00%00%00%00%00%00%00%00%00%00%00%00%00%00%00%00%00`
+//! [`info_hash`](crate::servers::http::v1::requests::scrape::Scrape::info_hashes) | percent encoded of 20-byte array | The `Info Hash` of the torrent. | No | No | `%81%00%00%00%00%00%00%00%00%00%00%00%00%00%00%00%00%00%00%00`
+//! [`admin_key`](crate::servers::http::v1::requests::scrape::Scrape::admin_key) | string | The key to get the [full scrape](#full-scrape) when it's not public. | No | No | `MyAdminKey`
 //!
 //! > **NOTICE**: you can scrape multiple torrents at the same time by passing
 //! > multiple `info_hash` parameters.
@@ -280,6 +321,45 @@
 //! 00000050: 65                                       e
 //! ```
 //!
+//! **Flags**
+//!
+//! The response also contains the `flags` dictionary from BEP 48 with the
+//! `min_request_interval`: the number of seconds clients should wait between
+//! scrapes. It's the [`interval_min`](torrust_tracker_configuration::AnnouncePolicy::interval_min)
+//! of the announce policy.
+//!
+//! ```text
+//! d5:filesd20:iiiiiiiiiiiiiiiiiiiid8:completei1e10:downloadedi2e10:incompletei3eee5:flagsd20:min_request_intervali120eee
+//! ```
+//!
+//! The BEP 48 `name` field is not included because the tracker does not know
+//! the torrent names.
+//!
+//! **Full scrape**
+//!
+//! A `scrape` request without any `info_hash` is a full scrape request: the
+//! response contains the swarm metadata of all the torrents in the tracker.
+//! The full scrape is disabled by default, and the requests without infohashes
+//! are rejected. It's enabled with the `core.full_scrape` section in the
+//! configuration:
+//!
+//! ```toml
+//! [core.full_scrape]
+//! public = false
+//! admin_key = "MyAdminKey"
+//! page_size = 1000
+//! ```
+//!
+//! When it's not `public`, clients have to send the `admin_key` param:
+//!
+//! <http://0.0.0.0:7070/scrape?admin_key=MyAdminKey>
+//!
+//! The response can be very big, so the body is streamed while the torrents
+//! are read from the repository, `page_size` torrents at a time. When the
+//! tracker runs in `listed` mode only the whitelisted torrents are included.
+//! When it runs in `private` mode and the client is not authenticated, the
+//! `files` dictionary is empty.
+//!
 //! **Protocol**
 //!
 //! If you want to know more about the `scrape` request:
@@ -97,6 +97,7 @@ mod tests {
 
     use aquatic_udp_protocol::{NumberOfBytes, PeerId};
     use torrust_tracker_primitives::info_hash::InfoHash;
+    use torrust_tracker_primitives::peer::Crypto;
 
     use super::extract_announce_from;
     use crate::servers::http::v1::requests::announce::{Announce, Compact, Event};
@@ -127,6 +128,13 @@ mod tests {
                 event: Some(Event::Completed),
                 compact: Some(Compact::NotAccepted),
                 numwant: Some(50),
+                ip: None,
+                ipv4: None,
+                ipv6: None,
+                key: None,
+                no_peer_id: false,
+                crypto: Crypto::NotSupported,
+                cryptoport: None,
             }
         );
     }
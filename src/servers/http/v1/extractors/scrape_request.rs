@@ -8,7 +8,8 @@
 //! information about the returned structure.
 //!
 //! It returns a bencoded [`Error`](crate::servers::http::v1::responses::error)
-//! response (`500`) if the query parameters are missing or invalid.
+//! response (`500`) if the query parameters are invalid. A request without
+//! query parameters is a full scrape request.
 //!
 //! **Sample scrape request**
 //!
@@ -16,19 +17,11 @@
 //!
 //! **Sample error response**
 //!
-//! Missing query params for scrape request: <http://0.0.0.0:7070/scrape>
-//!
-//! ```text
-//! d14:failure reason143:Cannot parse query params for scrape request: missing query params for scrape request in src/servers/http/v1/extractors/scrape_request.rs:52:23e
-//! ```
-//!
 //! Invalid query params for scrape request: <http://0.0.0.0:7070/scrape?info_hash=invalid>
 //!
 //! ```text
 //! d14:failure reason235:Cannot parse query params for scrape request: invalid param value invalid for info_hash in not enough bytes for infohash: got 7 bytes, expected 20 src/shared/bit_torrent/info_hash.rs:240:27, src/servers/http/v1/requests/scrape.rs:66:46e
 //! ```
-use std::panic::Location;
-
 use axum::extract::FromRequestParts;
 use axum::http::request::Parts;
 use axum::response::{IntoResponse, Response};
@@ -36,7 +29,7 @@ use futures::future::BoxFuture;
 use futures::FutureExt;
 
 use crate::servers::http::v1::query::Query;
-use crate::servers::http::v1::requests::scrape::{ParseScrapeQueryError, Scrape};
+use crate::servers::http::v1::requests::scrape::Scrape;
 use crate::servers::http::v1::responses;
 
 /// Extractor for the [`Scrape`]
@@ -70,13 +63,14 @@ where
 }
 
 fn extract_scrape_from(maybe_raw_query: Option<&str>) -> Result<Scrape, responses::error::Error> {
-    if maybe_raw_query.is_none() {
-        return Err(responses::error::Error::from(ParseScrapeQueryError::MissingParams {
-            location: Location::caller(),
-        }));
-    }
+    let Some(raw_query) = maybe_raw_query else {
+        return Ok(Scrape {
+            info_hashes: vec![],
+            admin_key: None,
+        });
+    };
 
-    let query = maybe_raw_query.unwrap().parse::<Query>();
+    let query = raw_query.parse::<Query>();
 
     if let Err(error) = query {
         return Err(responses::error::Error::from(error));
@@ -132,6 +126,7 @@ mod tests {
             scrape,
             Scrape {
                 info_hashes: vec![info_hash.value],
+                admin_key: None,
             }
         );
     }
@@ -148,18 +143,16 @@ mod tests {
             scrape,
             Scrape {
                 info_hashes: vec![info_hash.value, info_hash.value],
+                admin_key: None,
             }
         );
     }
 
     #[test]
-    fn it_should_reject_a_request_without_query_params() {
-        let response = extract_scrape_from(None).unwrap_err();
+    fn it_should_extract_a_full_scrape_request_when_there_are_no_query_params() {
+        let scrape = extract_scrape_from(None).unwrap();
 
-        assert_error_response(
-            &response,
-            "Cannot parse query params for scrape request: missing query params for scrape request",
-        );
+        assert!(scrape.is_full_scrape());
     }
 
     #[test]
@@ -172,7 +165,7 @@ mod tests {
 
     #[test]
     fn it_should_reject_a_request_with_a_query_that_cannot_be_parsed_into_a_scrape_request() {
-        let response = extract_scrape_from(Some("param1=value1")).unwrap_err();
+        let response = extract_scrape_from(Some("info_hash=INVALID_INFO_HASH_VALUE")).unwrap_err();
 
         assert_error_response(&response, "Cannot parse query params for scrape request");
     }
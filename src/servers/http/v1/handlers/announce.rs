@@ -20,7 +20,7 @@ use crate::core::{AnnounceData, PeersWanted, Tracker};
 use crate::servers::http::v1::extractors::announce_request::ExtractRequest;
 use crate::servers::http::v1::extractors::authentication_key::Extract as ExtractKey;
 use crate::servers::http::v1::extractors::client_ip_sources::Extract as ExtractClientIpSources;
-use crate::servers::http::v1::handlers::common::auth;
+use crate::servers::http::v1::handlers::common::{auth, ip_access};
 use crate::servers::http::v1::requests::announce::{Announce, Compact, Event};
 use crate::servers::http::v1::responses::{self};
 use crate::servers::http::v1::services::peer_ip_resolver::ClientIpSources;
@@ -85,8 +85,8 @@ async fn handle_announce(
 ) -> Result<AnnounceData, responses::error::Error> {
     // Authentication
     if tracker.requires_authentication() {
-        match maybe_key {
-            Some(key) => match tracker.authenticate(&key).await {
+        match &maybe_key {
+            Some(key) => match tracker.authenticate(key).await {
                 Ok(()) => (),
                 Err(error) => return Err(responses::error::Error::from(error)),
             },
@@ -109,35 +109,66 @@ async fn handle_announce(
         Err(error) => return Err(responses::error::Error::from(error)),
     };
 
-    let mut peer = peer_from_request(announce_request, &peer_ip);
+    ip_access::authorize(tracker, &peer_ip).await?;
+
+    let source_ip = peer_ip;
+    let peer_ip = tracker.resolve_peer_ip(&source_ip, announce_request.ip);
+    let alt_peer_addr = tracker.resolve_alt_peer_addr(&source_ip, announce_request.alt_peer_addr(&peer_ip));
+
+    let mut peer = peer_from_request(announce_request, &peer_ip, alt_peer_addr);
     let peers_wanted = match announce_request.numwant {
         Some(numwant) => PeersWanted::only(numwant),
         None => PeersWanted::All,
     };
 
-    let announce_data = services::announce::invoke(tracker.clone(), announce_request.info_hash, &mut peer, &peers_wanted).await;
+    let announce_data =
+        match services::announce::invoke(tracker.clone(), announce_request.info_hash, &mut peer, &peers_wanted).await {
+            Ok(announce_data) => announce_data,
+            Err(error) => return Err(responses::error::Error::from(error)),
+        };
+
+    // Accounting
+    if let Some(key) = &maybe_key {
+        tracker.record_key_traffic(key, &announce_request.info_hash, &peer);
+    }
 
     Ok(announce_data)
 }
 
+/// It builds the compact or normal response. Clients that support encryption
+/// also get the `crypto_flags` of the returned peers.
 fn build_response(announce_request: &Announce, announce_data: AnnounceData) -> Response {
     if announce_request.compact.as_ref().is_some_and(|f| *f == Compact::Accepted) {
-        let response: responses::Announce<responses::Compact> = announce_data.into();
-        response.into_response()
+        let mut compact = responses::Compact::from(announce_data);
+        if announce_request.crypto.is_supported() {
+            compact = compact.with_crypto_flags();
+        }
+        responses::Announce::new(compact).into_response()
     } else {
-        let response: responses::Announce<responses::Normal> = announce_data.into();
-        response.into_response()
+        let mut normal = responses::Normal::from(announce_data);
+        if announce_request.no_peer_id {
+            normal = normal.without_peer_ids();
+        }
+        if announce_request.crypto.is_supported() {
+            normal = normal.with_crypto_flags();
+        }
+        responses::Announce::new(normal).into_response()
     }
 }
 
 /// It builds a `Peer` from the announce request.
 ///
-/// It ignores the peer address in the announce request params.
+/// The peer IP and the address in the other IP family are the ones resolved
+/// by the tracker. The `ip`, `ipv4` and `ipv6` params are only used when the
+/// tracker trusts the addresses supplied by the client.
 #[must_use]
-fn peer_from_request(announce_request: &Announce, peer_ip: &IpAddr) -> peer::Peer {
+fn peer_from_request(announce_request: &Announce, peer_ip: &IpAddr, alt_peer_addr: Option<SocketAddr>) -> peer::Peer {
     peer::Peer {
         peer_id: announce_request.peer_id,
-        peer_addr: SocketAddr::new(*peer_ip, announce_request.port),
+        peer_addr: SocketAddr::new(*peer_ip, announce_request.peer_port()),
+        alt_peer_addr,
+        key: announce_request.key,
+        crypto: announce_request.crypto,
         updated: CurrentClock::now(),
         uploaded: announce_request.uploaded.unwrap_or(NumberOfBytes::new(0)),
         downloaded: announce_request.downloaded.unwrap_or(NumberOfBytes::new(0)),
@@ -175,6 +206,7 @@ mod tests {
 
     use aquatic_udp_protocol::PeerId;
     use torrust_tracker_primitives::info_hash::InfoHash;
+    use torrust_tracker_primitives::peer;
     use torrust_tracker_test_helpers::configuration;
 
     use crate::core::services::tracker_factory;
@@ -210,6 +242,13 @@ mod tests {
             event: None,
             compact: None,
             numwant: None,
+            ip: None,
+            ipv4: None,
+            ipv6: None,
+            key: None,
+            no_peer_id: false,
+            crypto: peer::Crypto::NotSupported,
+            cryptoport: None,
         }
     }
 
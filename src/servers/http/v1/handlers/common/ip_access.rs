@@ -0,0 +1,31 @@
+//! Logic to reject the requests from clients whose IP address is not allowed
+//! by the tracker [IP access list](crate::core::ip_access_list).
+use std::net::IpAddr;
+
+use crate::core::{statistics, Tracker};
+use crate::servers::http::v1::responses;
+
+/// It checks if the resolved peer IP is allowed to make the request.
+///
+/// Blocked requests are counted in the tracker statistics.
+///
+/// # Errors
+///
+/// Will return an error response if the IP address is not allowed.
+pub async fn authorize(tracker: &Tracker, peer_ip: &IpAddr) -> Result<(), responses::error::Error> {
+    match tracker.authorize_client_ip(peer_ip).await {
+        Ok(()) => Ok(()),
+        Err(error) => {
+            match peer_ip {
+                IpAddr::V4(_) => {
+                    tracker.send_stats_event(statistics::Event::Tcp4RequestBlocked).await;
+                }
+                IpAddr::V6(_) => {
+                    tracker.send_stats_event(statistics::Event::Tcp6RequestBlocked).await;
+                }
+            }
+
+            Err(responses::error::Error::from(error))
+        }
+    }
+}
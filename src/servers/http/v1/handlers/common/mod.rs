@@ -1,3 +1,4 @@
 //! Common logic for HTTP handlers.
 pub mod auth;
+pub mod ip_access;
 pub mod peer_ip;
@@ -5,6 +5,8 @@
 //!
 //! The handlers perform the authentication and authorization of the request,
 //! and resolve the client IP address.
+use std::net::IpAddr;
+use std::panic::Location;
 use std::sync::Arc;
 
 use axum::extract::State;
@@ -15,7 +17,8 @@ use crate::core::{ScrapeData, Tracker};
 use crate::servers::http::v1::extractors::authentication_key::Extract as ExtractKey;
 use crate::servers::http::v1::extractors::client_ip_sources::Extract as ExtractClientIpSources;
 use crate::servers::http::v1::extractors::scrape_request::ExtractRequest;
-use crate::servers::http::v1::requests::scrape::Scrape;
+use crate::servers::http::v1::handlers::common::ip_access;
+use crate::servers::http::v1::requests::scrape::{ParseScrapeQueryError, Scrape};
 use crate::servers::http::v1::services::peer_ip_resolver::{self, ClientIpSources};
 use crate::servers::http::v1::{responses, services};
 
@@ -54,11 +57,18 @@ async fn handle(
     client_ip_sources: &ClientIpSources,
     maybe_key: Option<Key>,
 ) -> Response {
+    if scrape_request.is_full_scrape() {
+        return match handle_full_scrape(tracker, scrape_request, client_ip_sources, maybe_key).await {
+            Ok(response) => response,
+            Err(error) => error.into_response(),
+        };
+    }
+
     let scrape_data = match handle_scrape(tracker, scrape_request, client_ip_sources, maybe_key).await {
         Ok(scrape_data) => scrape_data,
         Err(error) => return error.into_response(),
     };
-    build_response(scrape_data)
+    build_response(tracker, scrape_data)
 }
 
 /* code-review: authentication, authorization and peer IP resolution could be moved
@@ -73,26 +83,12 @@ async fn handle_scrape(
     client_ip_sources: &ClientIpSources,
     maybe_key: Option<Key>,
 ) -> Result<ScrapeData, responses::error::Error> {
-    // Authentication
-    let return_real_scrape_data = if tracker.requires_authentication() {
-        match maybe_key {
-            Some(key) => match tracker.authenticate(&key).await {
-                Ok(()) => true,
-                Err(_error) => false,
-            },
-            None => false,
-        }
-    } else {
-        true
-    };
+    let return_real_scrape_data = is_authenticated(tracker, maybe_key).await;
 
     // Authorization for scrape requests is handled at the `Tracker` level
     // for each torrent.
 
-    let peer_ip = match peer_ip_resolver::invoke(tracker.is_behind_reverse_proxy(), client_ip_sources) {
-        Ok(peer_ip) => peer_ip,
-        Err(error) => return Err(responses::error::Error::from(error)),
-    };
+    let peer_ip = resolve_and_check_client_ip(tracker, client_ip_sources).await?;
 
     if return_real_scrape_data {
         Ok(services::scrape::invoke(tracker, &scrape_request.info_hashes, &peer_ip).await)
@@ -101,8 +97,68 @@ async fn handle_scrape(
     }
 }
 
-fn build_response(scrape_data: ScrapeData) -> Response {
-    responses::scrape::Bencoded::from(scrape_data).into_response()
+/// It handles a `scrape` request without infohashes.
+///
+/// When the full scrape is disabled the request is rejected like any other
+/// request without query params. When the tracker is `private` and the
+/// client is not authenticated, the response does not contain any torrent.
+async fn handle_full_scrape(
+    tracker: &Arc<Tracker>,
+    scrape_request: &Scrape,
+    client_ip_sources: &ClientIpSources,
+    maybe_key: Option<Key>,
+) -> Result<Response, responses::error::Error> {
+    let Some(full_scrape) = tracker.get_full_scrape_config() else {
+        return Err(responses::error::Error::from(ParseScrapeQueryError::MissingParams {
+            location: Location::caller(),
+        }));
+    };
+
+    let return_real_scrape_data = is_authenticated(tracker, maybe_key).await;
+
+    let peer_ip = resolve_and_check_client_ip(tracker, client_ip_sources).await?;
+
+    tracker.authorize_full_scrape(scrape_request.admin_key.as_deref())?;
+
+    // Empty pages do not contain any torrent.
+    let page_size = if return_real_scrape_data { full_scrape.page_size } else { 0 };
+
+    let pages = services::scrape::full(tracker, page_size, &peer_ip).await;
+
+    Ok(responses::scrape::FullScrape::new(pages)
+        .with_min_request_interval(tracker.get_announce_policy().interval_min)
+        .into_response())
+}
+
+async fn is_authenticated(tracker: &Arc<Tracker>, maybe_key: Option<Key>) -> bool {
+    if !tracker.requires_authentication() {
+        return true;
+    }
+
+    match maybe_key {
+        Some(key) => tracker.authenticate(&key).await.is_ok(),
+        None => false,
+    }
+}
+
+async fn resolve_and_check_client_ip(
+    tracker: &Arc<Tracker>,
+    client_ip_sources: &ClientIpSources,
+) -> Result<IpAddr, responses::error::Error> {
+    let peer_ip = match peer_ip_resolver::invoke(tracker.is_behind_reverse_proxy(), client_ip_sources) {
+        Ok(peer_ip) => peer_ip,
+        Err(error) => return Err(responses::error::Error::from(error)),
+    };
+
+    ip_access::authorize(tracker, &peer_ip).await?;
+
+    Ok(peer_ip)
+}
+
+fn build_response(tracker: &Tracker, scrape_data: ScrapeData) -> Response {
+    responses::scrape::Bencoded::from(scrape_data)
+        .with_min_request_interval(tracker.get_announce_policy().interval_min)
+        .into_response()
 }
 
 #[cfg(test)]
@@ -138,6 +194,7 @@ mod tests {
     fn sample_scrape_request() -> Scrape {
         Scrape {
             info_hashes: vec!["3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap()],
+            admin_key: None,
         }
     }
 
@@ -221,6 +278,58 @@ mod tests {
         }
     }
 
+    mod with_a_full_scrape_request {
+        use std::sync::Arc;
+
+        use torrust_tracker_configuration::FullScrape;
+        use torrust_tracker_test_helpers::configuration;
+
+        use super::sample_client_ip_sources;
+        use crate::core::services::tracker_factory;
+        use crate::servers::http::v1::handlers::scrape::handle_full_scrape;
+        use crate::servers::http::v1::handlers::scrape::tests::assert_error_response;
+        use crate::servers::http::v1::requests::scrape::Scrape;
+
+        fn full_scrape_request(admin_key: Option<&str>) -> Scrape {
+            Scrape {
+                info_hashes: vec![],
+                admin_key: admin_key.map(ToOwned::to_owned),
+            }
+        }
+
+        #[tokio::test]
+        async fn it_should_reject_the_request_as_a_request_without_params_when_the_full_scrape_is_disabled() {
+            let tracker = Arc::new(tracker_factory(&configuration::ephemeral_public()));
+
+            let response = handle_full_scrape(&tracker, &full_scrape_request(None), &sample_client_ip_sources(), None)
+                .await
+                .unwrap_err();
+
+            assert_error_response(&response, "missing query params for scrape request");
+        }
+
+        #[tokio::test]
+        async fn it_should_fail_when_the_admin_key_is_wrong() {
+            let mut configuration = configuration::ephemeral_public();
+            configuration.core.full_scrape = Some(FullScrape {
+                admin_key: Some("MyAdminKey".to_owned()),
+                ..Default::default()
+            });
+            let tracker = Arc::new(tracker_factory(&configuration));
+
+            let response = handle_full_scrape(
+                &tracker,
+                &full_scrape_request(Some("AnotherKey")),
+                &sample_client_ip_sources(),
+                None,
+            )
+            .await
+            .unwrap_err();
+
+            assert_error_response(&response, "The full scrape is not allowed");
+        }
+    }
+
     mod with_tracker_on_reverse_proxy {
         use std::sync::Arc;
 
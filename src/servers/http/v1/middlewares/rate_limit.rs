@@ -0,0 +1,54 @@
+//! Middleware to reject the requests from clients that have exceeded their
+//! budget in the tracker [rate limiter](crate::core::rate_limiter).
+//!
+//! It's added to the `announce` and `scrape` routes, so it runs before the
+//! request is parsed and before the client IP is checked against the tracker
+//! [IP access list](crate::core::ip_access_list). Invalid or not allowed
+//! requests also consume the client budget.
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::Request;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use crate::core::{rate_limiter, statistics, Tracker};
+use crate::servers::http::v1::extractors::client_ip_sources::Extract as ExtractClientIpSources;
+use crate::servers::http::v1::responses;
+use crate::servers::http::v1::services::peer_ip_resolver;
+
+/// Middleware that only lets the request through if the client has not
+/// exceeded its budget for the type of request the route handles.
+///
+/// The client is identified by the resolved peer IP. When the peer IP can't
+/// be resolved, the request is let through and the handler returns the
+/// resolution error.
+///
+/// Rejected requests are counted in the tracker statistics.
+pub async fn rate_limit(
+    State((tracker, request_type)): State<(Arc<Tracker>, rate_limiter::Request)>,
+    ExtractClientIpSources(client_ip_sources): ExtractClientIpSources,
+    request: Request<axum::body::Body>,
+    next: Next,
+) -> Response {
+    let Ok(peer_ip) = peer_ip_resolver::invoke(tracker.is_behind_reverse_proxy(), &client_ip_sources) else {
+        return next.run(request).await;
+    };
+
+    match tracker.check_rate_limit(&peer_ip, request_type) {
+        Ok(()) => next.run(request).await,
+        Err(error) => {
+            match peer_ip {
+                IpAddr::V4(_) => {
+                    tracker.send_stats_event(statistics::Event::Tcp4RequestRateLimited).await;
+                }
+                IpAddr::V6(_) => {
+                    tracker.send_stats_event(statistics::Event::Tcp6RequestRateLimited).await;
+                }
+            }
+
+            responses::error::Error::from(error).into_response()
+        }
+    }
+}
@@ -2,10 +2,11 @@
 //!
 //! Data structures and logic for parsing the `announce` request.
 use std::fmt;
+use std::net::{IpAddr, SocketAddr, SocketAddrV4, SocketAddrV6};
 use std::panic::Location;
 use std::str::FromStr;
 
-use aquatic_udp_protocol::{NumberOfBytes, PeerId};
+use aquatic_udp_protocol::{NumberOfBytes, PeerId, PeerKey};
 use thiserror::Error;
 use torrust_tracker_located_error::{Located, LocatedError};
 use torrust_tracker_primitives::info_hash::{self, InfoHash};
@@ -25,6 +26,14 @@ const LEFT: &str = "left";
 const EVENT: &str = "event";
 const COMPACT: &str = "compact";
 const NUMWANT: &str = "numwant";
+const IP: &str = "ip";
+const IPV4: &str = "ipv4";
+const IPV6: &str = "ipv6";
+const KEY: &str = "key";
+const NO_PEER_ID: &str = "no_peer_id";
+const SUPPORTCRYPTO: &str = "supportcrypto";
+const REQUIRECRYPTO: &str = "requirecrypto";
+const CRYPTOPORT: &str = "cryptoport";
 
 /// The `Announce` request. Fields use the domain types after parsing the
 /// query params of the request.
@@ -33,6 +42,7 @@ const NUMWANT: &str = "numwant";
 /// use aquatic_udp_protocol::{NumberOfBytes, PeerId};
 /// use torrust_tracker::servers::http::v1::requests::announce::{Announce, Compact, Event};
 /// use torrust_tracker_primitives::info_hash::InfoHash;
+/// use torrust_tracker_primitives::peer::Crypto;
 ///
 /// let request = Announce {
 ///     // Mandatory params
@@ -45,7 +55,14 @@ const NUMWANT: &str = "numwant";
 ///     left: Some(NumberOfBytes::new(1)),
 ///     event: Some(Event::Started),
 ///     compact: Some(Compact::NotAccepted),
-///     numwant: Some(50)
+///     numwant: Some(50),
+///     ip: None,
+///     ipv4: None,
+///     ipv6: Some("[2001:db8::1]:17548".parse().unwrap()),
+///     key: None,
+///     no_peer_id: false,
+///     crypto: Crypto::NotSupported,
+///     cryptoport: None,
 /// };
 /// ```
 ///
@@ -53,9 +70,10 @@ const NUMWANT: &str = "numwant";
 /// > specifies that only the peer `IP` and `event`are optional. However, the
 /// > tracker defines default values for some of the mandatory params.
 ///
-/// > **NOTICE**: The struct does not contain the `IP` of the peer. It's not
-/// > mandatory and it's not used by the tracker. The `IP` is obtained from the
-/// > request itself.
+/// > **NOTICE**: The peer address is obtained from the request itself. The
+/// > `ip`, `ipv4` and `ipv6` params are only used to get the address of the
+/// > peer in the other IP family, as described in
+/// > [BEP 07. IPv6 Tracker Extension](https://www.bittorrent.org/beps/bep_0007.html).
 #[derive(Debug, PartialEq)]
 pub struct Announce {
     // Mandatory params
@@ -88,6 +106,62 @@ pub struct Announce {
     /// Number of peers that the client would receive from the tracker. The
     /// value is permitted to be zero.
     pub numwant: Option<u32>,
+
+    /// The IP address of the peer. It can be an IPv4 or an IPv6 address. It's
+    /// `None` when the param is not an IP address, for example, a DNS name.
+    pub ip: Option<IpAddr>,
+
+    /// The IPv4 address of the peer. When the param does not include the
+    /// port, the `port` param is used.
+    pub ipv4: Option<SocketAddrV4>,
+
+    /// The IPv6 address of the peer. When the param does not include the
+    /// port, the `port` param is used.
+    pub ipv6: Option<SocketAddrV6>,
+
+    /// A key the peer uses to prove its identity if its IP address changes.
+    /// See [`extract_key`] for how the param is converted into a key.
+    pub key: Option<PeerKey>,
+
+    /// Whether the peer IDs can be left out of the non-compact response.
+    pub no_peer_id: bool,
+
+    /// Whether the peer supports or requires encrypted connections. It's
+    /// obtained from the `supportcrypto` and `requirecrypto` params.
+    pub crypto: peer::Crypto,
+
+    /// The port the peer listens on for encrypted connections. Peers that
+    /// require encryption are announced with this port instead of `port`.
+    pub cryptoport: Option<u16>,
+}
+
+impl Announce {
+    /// The port the peer is announced with: the `cryptoport` if the peer
+    /// requires encryption and it has sent one, the `port` otherwise.
+    #[must_use]
+    pub fn peer_port(&self) -> u16 {
+        match (self.crypto, self.cryptoport) {
+            (peer::Crypto::Required, Some(cryptoport)) => cryptoport,
+            _ => self.port,
+        }
+    }
+
+    /// The address of the peer in the other IP family from the given peer
+    /// IP, if the client has sent it.
+    ///
+    /// The `ipv4` and `ipv6` params take precedence over the `ip` param.
+    #[must_use]
+    pub fn alt_peer_addr(&self, peer_ip: &IpAddr) -> Option<SocketAddr> {
+        let from_ip_param = self
+            .ip
+            .map(|ip| SocketAddr::new(ip, self.port))
+            .filter(|addr| addr.is_ipv4() != peer_ip.is_ipv4());
+
+        match peer_ip {
+            IpAddr::V4(_) => self.ipv6.map(SocketAddr::V6).or(from_ip_param),
+            IpAddr::V6(_) => self.ipv4.map(SocketAddr::V4).or(from_ip_param),
+        }
+    }
 }
 
 /// Errors that can occur when parsing the `Announce` request.
@@ -243,16 +317,25 @@ impl TryFrom<Query> for Announce {
     type Error = ParseAnnounceQueryError;
 
     fn try_from(query: Query) -> Result<Self, Self::Error> {
+        let port = extract_port(&query)?;
+
         Ok(Self {
             info_hash: extract_info_hash(&query)?,
             peer_id: extract_peer_id(&query)?,
-            port: extract_port(&query)?,
+            port,
             downloaded: extract_downloaded(&query)?,
             uploaded: extract_uploaded(&query)?,
             left: extract_left(&query)?,
             event: extract_event(&query)?,
             compact: extract_compact(&query)?,
             numwant: extract_numwant(&query)?,
+            ip: extract_ip(&query),
+            ipv4: extract_ipv4(&query, port)?,
+            ipv6: extract_ipv6(&query, port)?,
+            key: extract_key(&query),
+            no_peer_id: extract_flag(NO_PEER_ID, &query)?,
+            crypto: extract_crypto(&query)?,
+            cryptoport: extract_cryptoport(&query)?,
         })
     }
 }
@@ -373,17 +456,131 @@ fn extract_numwant(query: &Query) -> Result<Option<u32>, ParseAnnounceQueryError
     }
 }
 
+/// The `ip` param can also be a DNS name ([BEP 03](https://www.bittorrent.org/beps/bep_0003.html)).
+/// The tracker does not resolve names, so any value that is not an IP address
+/// is ignored.
+fn extract_ip(query: &Query) -> Option<IpAddr> {
+    query.get_param(IP).and_then(|raw_param| IpAddr::from_str(&raw_param).ok())
+}
+
+/// The `ipv4` param can be an IPv4 address (`126.0.0.1`) or an IPv4 address
+/// with port (`126.0.0.1:17548`).
+fn extract_ipv4(query: &Query, port: u16) -> Result<Option<SocketAddrV4>, ParseAnnounceQueryError> {
+    match query.get_param(IPV4) {
+        Some(raw_param) => match (SocketAddrV4::from_str(&raw_param), IpAddr::from_str(&raw_param)) {
+            (Ok(addr), _) => Ok(Some(addr)),
+            (_, Ok(IpAddr::V4(ip))) => Ok(Some(SocketAddrV4::new(ip, port))),
+            _ => Err(ParseAnnounceQueryError::InvalidParam {
+                param_name: IPV4.to_owned(),
+                param_value: raw_param.clone(),
+                location: Location::caller(),
+            }),
+        },
+        None => Ok(None),
+    }
+}
+
+/// The `ipv6` param can be an IPv6 address (`2001:db8::1`) or an IPv6 address
+/// with port (`[2001:db8::1]:17548`).
+fn extract_ipv6(query: &Query, port: u16) -> Result<Option<SocketAddrV6>, ParseAnnounceQueryError> {
+    match query.get_param(IPV6) {
+        Some(raw_param) => match (SocketAddrV6::from_str(&raw_param), IpAddr::from_str(&raw_param)) {
+            (Ok(addr), _) => Ok(Some(addr)),
+            (_, Ok(IpAddr::V6(ip))) => Ok(Some(SocketAddrV6::new(ip, port, 0, 0))),
+            _ => Err(ParseAnnounceQueryError::InvalidParam {
+                param_name: IPV6.to_owned(),
+                param_value: raw_param.clone(),
+                location: Location::caller(),
+            }),
+        },
+        None => Ok(None),
+    }
+}
+
+/// The `key` param is not defined in the BEP 03 but most clients send the
+/// same 32-bit key they use in the [UDP tracker protocol](https://www.bittorrent.org/beps/bep_0015.html),
+/// as an hexadecimal string (`D695F7B1`). Those keys are used as they are, so
+/// that the key is the same for both protocols. Any other string is hashed
+/// into a 32-bit key.
+fn extract_key(query: &Query) -> Option<PeerKey> {
+    query.get_param(KEY).map(|raw_param| {
+        let key = match u32::from_str_radix(&raw_param, 16) {
+            Ok(key) if raw_param.len() <= 8 => key,
+            _ => fnv1a(raw_param.as_bytes()),
+        };
+
+        PeerKey::new(i32::from_be_bytes(key.to_be_bytes()))
+    })
+}
+
+/// Flag params are `1` when they are enabled and `0` when they are not. A
+/// missing flag is not enabled.
+fn extract_flag(param_name: &str, query: &Query) -> Result<bool, ParseAnnounceQueryError> {
+    match query.get_param(param_name) {
+        Some(raw_param) => match raw_param.as_str() {
+            "1" => Ok(true),
+            "0" => Ok(false),
+            _ => Err(ParseAnnounceQueryError::InvalidParam {
+                param_name: param_name.to_owned(),
+                param_value: raw_param.clone(),
+                location: Location::caller(),
+            }),
+        },
+        None => Ok(false),
+    }
+}
+
+/// The `supportcrypto` and `requirecrypto` params are not defined in any BEP,
+/// but they are widely used by clients that support the
+/// [Message Stream Encryption](https://wiki.vuze.com/w/Message_Stream_Encryption)
+/// protocol. `requirecrypto` takes precedence.
+fn extract_crypto(query: &Query) -> Result<peer::Crypto, ParseAnnounceQueryError> {
+    if extract_flag(REQUIRECRYPTO, query)? {
+        return Ok(peer::Crypto::Required);
+    }
+
+    if extract_flag(SUPPORTCRYPTO, query)? {
+        return Ok(peer::Crypto::Supported);
+    }
+
+    Ok(peer::Crypto::NotSupported)
+}
+
+fn extract_cryptoport(query: &Query) -> Result<Option<u16>, ParseAnnounceQueryError> {
+    match query.get_param(CRYPTOPORT) {
+        Some(raw_param) => Ok(Some(u16::from_str(&raw_param).map_err(|_e| {
+            ParseAnnounceQueryError::InvalidParam {
+                param_name: CRYPTOPORT.to_owned(),
+                param_value: raw_param.clone(),
+                location: Location::caller(),
+            }
+        })?)),
+        None => Ok(None),
+    }
+}
+
+/// 32-bit [FNV-1a](https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function)
+/// hash. It's stable across releases, unlike the standard library hasher,
+/// and keys are kept in the torrents snapshot.
+fn fnv1a(bytes: &[u8]) -> u32 {
+    bytes
+        .iter()
+        .fold(0x811c_9dc5, |hash, byte| (hash ^ u32::from(*byte)).wrapping_mul(0x0100_0193))
+}
+
 #[cfg(test)]
 mod tests {
 
     mod announce_request {
 
-        use aquatic_udp_protocol::{NumberOfBytes, PeerId};
+        use aquatic_udp_protocol::{NumberOfBytes, PeerId, PeerKey};
         use torrust_tracker_primitives::info_hash::InfoHash;
+        use torrust_tracker_primitives::peer::Crypto;
 
         use crate::servers::http::v1::query::Query;
         use crate::servers::http::v1::requests::announce::{
-            Announce, Compact, Event, COMPACT, DOWNLOADED, EVENT, INFO_HASH, LEFT, NUMWANT, PEER_ID, PORT, UPLOADED,
+            Announce, Compact, Event, COMPACT, CRYPTOPORT, DOWNLOADED, EVENT, INFO_HASH, IP, IPV4, IPV6, KEY, LEFT, NO_PEER_ID,
+            NUMWANT, PEER_ID, PORT, REQUIRECRYPTO, SUPPORTCRYPTO, UPLOADED,
         };
 
         #[test]
@@ -411,6 +608,13 @@ mod tests {
                     event: None,
                     compact: None,
                     numwant: None,
+                    ip: None,
+                    ipv4: None,
+                    ipv6: None,
+                    key: None,
+                    no_peer_id: false,
+                    crypto: Crypto::NotSupported,
+                    cryptoport: None,
                 }
             );
         }
@@ -427,6 +631,14 @@ mod tests {
                 (EVENT, "started"),
                 (COMPACT, "0"),
                 (NUMWANT, "50"),
+                (IP, "126.0.0.1"),
+                (IPV4, "126.0.0.2:17549"),
+                (IPV6, "2001:db8::1"),
+                (KEY, "EF3495D6"),
+                (NO_PEER_ID, "1"),
+                (SUPPORTCRYPTO, "1"),
+                (REQUIRECRYPTO, "1"),
+                (CRYPTOPORT, "17550"),
             ])
             .to_string();
 
@@ -446,6 +658,13 @@ mod tests {
                     event: Some(Event::Started),
                     compact: Some(Compact::NotAccepted),
                     numwant: Some(50),
+                    ip: Some("126.0.0.1".parse().unwrap()),
+                    ipv4: Some("126.0.0.2:17549".parse().unwrap()),
+                    ipv6: Some("[2001:db8::1]:17548".parse().unwrap()),
+                    key: Some(PeerKey::new(-281_766_442)),
+                    no_peer_id: true,
+                    crypto: Crypto::Required,
+                    cryptoport: Some(17550),
                 }
             );
         }
@@ -454,7 +673,8 @@ mod tests {
 
             use crate::servers::http::v1::query::Query;
             use crate::servers::http::v1::requests::announce::{
-                Announce, COMPACT, DOWNLOADED, EVENT, INFO_HASH, LEFT, NUMWANT, PEER_ID, PORT, UPLOADED,
+                Announce, COMPACT, CRYPTOPORT, DOWNLOADED, EVENT, INFO_HASH, IP, IPV4, IPV6, LEFT, NO_PEER_ID, NUMWANT, PEER_ID,
+                PORT, UPLOADED,
             };
 
             #[test]
@@ -586,6 +806,235 @@ mod tests {
 
                 assert!(Announce::try_from(raw_query.parse::<Query>().unwrap()).is_err());
             }
+
+            #[test]
+            fn it_should_ignore_the_ip_param_when_it_is_not_an_ip_address() {
+                let raw_query = Query::from(vec![
+                    (INFO_HASH, "%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0"),
+                    (PEER_ID, "-qB00000000000000001"),
+                    (PORT, "17548"),
+                    (IP, "peer.example.com"),
+                ])
+                .to_string();
+
+                let announce = Announce::try_from(raw_query.parse::<Query>().unwrap()).unwrap();
+
+                assert_eq!(announce.ip, None);
+            }
+
+            #[test]
+            fn it_should_fail_if_the_ipv4_param_is_not_an_ipv4_address() {
+                let raw_query = Query::from(vec![
+                    (INFO_HASH, "%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0"),
+                    (PEER_ID, "-qB00000000000000001"),
+                    (PORT, "17548"),
+                    (IPV4, "2001:db8::1"),
+                ])
+                .to_string();
+
+                assert!(Announce::try_from(raw_query.parse::<Query>().unwrap()).is_err());
+            }
+
+            #[test]
+            fn it_should_fail_if_the_ipv6_param_is_not_an_ipv6_address() {
+                let raw_query = Query::from(vec![
+                    (INFO_HASH, "%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0"),
+                    (PEER_ID, "-qB00000000000000001"),
+                    (PORT, "17548"),
+                    (IPV6, "126.0.0.1"),
+                ])
+                .to_string();
+
+                assert!(Announce::try_from(raw_query.parse::<Query>().unwrap()).is_err());
+            }
+
+            #[test]
+            fn it_should_fail_if_the_no_peer_id_param_is_invalid() {
+                let raw_query = Query::from(vec![
+                    (INFO_HASH, "%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0"),
+                    (PEER_ID, "-qB00000000000000001"),
+                    (PORT, "17548"),
+                    (NO_PEER_ID, "INVALID_NO_PEER_ID_VALUE"),
+                ])
+                .to_string();
+
+                assert!(Announce::try_from(raw_query.parse::<Query>().unwrap()).is_err());
+            }
+
+            #[test]
+            fn it_should_fail_if_the_cryptoport_param_is_invalid() {
+                let raw_query = Query::from(vec![
+                    (INFO_HASH, "%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0"),
+                    (PEER_ID, "-qB00000000000000001"),
+                    (PORT, "17548"),
+                    (CRYPTOPORT, "INVALID_CRYPTOPORT_VALUE"),
+                ])
+                .to_string();
+
+                assert!(Announce::try_from(raw_query.parse::<Query>().unwrap()).is_err());
+            }
+        }
+
+        mod the_encryption_support {
+            use torrust_tracker_primitives::peer::Crypto;
+
+            use crate::servers::http::v1::query::Query;
+            use crate::servers::http::v1::requests::announce::{
+                Announce, CRYPTOPORT, INFO_HASH, PEER_ID, PORT, REQUIRECRYPTO, SUPPORTCRYPTO,
+            };
+
+            fn announce_request_with(params: Vec<(&str, &str)>) -> Announce {
+                let mut query = vec![
+                    (INFO_HASH, "%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0"),
+                    (PEER_ID, "-qB00000000000000001"),
+                    (PORT, "17548"),
+                ];
+                query.extend(params);
+
+                Announce::try_from(Query::from(query).to_string().parse::<Query>().unwrap()).unwrap()
+            }
+
+            #[test]
+            fn it_should_be_supported_when_the_peer_supports_encryption() {
+                assert_eq!(announce_request_with(vec![(SUPPORTCRYPTO, "1")]).crypto, Crypto::Supported);
+            }
+
+            #[test]
+            fn it_should_be_required_when_the_peer_requires_encryption() {
+                assert_eq!(announce_request_with(vec![(REQUIRECRYPTO, "1")]).crypto, Crypto::Required);
+            }
+
+            #[test]
+            fn it_should_announce_the_peer_with_the_cryptoport_when_it_requires_encryption() {
+                let announce_request = announce_request_with(vec![(REQUIRECRYPTO, "1"), (CRYPTOPORT, "17550")]);
+
+                assert_eq!(announce_request.peer_port(), 17550);
+            }
+
+            #[test]
+            fn it_should_announce_the_peer_with_the_port_when_it_does_not_require_encryption() {
+                let announce_request = announce_request_with(vec![(SUPPORTCRYPTO, "1"), (CRYPTOPORT, "17550")]);
+
+                assert_eq!(announce_request.peer_port(), 17548);
+            }
+        }
+
+        mod the_alternative_peer_address {
+            use std::net::IpAddr;
+
+            use aquatic_udp_protocol::PeerId;
+            use torrust_tracker_primitives::info_hash::InfoHash;
+            use torrust_tracker_primitives::peer::Crypto;
+
+            use crate::servers::http::v1::requests::announce::Announce;
+
+            fn sample_announce_request() -> Announce {
+                Announce {
+                    info_hash: "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap(),
+                    peer_id: PeerId(*b"-qB00000000000000001"),
+                    port: 17548,
+                    downloaded: None,
+                    uploaded: None,
+                    left: None,
+                    event: None,
+                    compact: None,
+                    numwant: None,
+                    ip: None,
+                    ipv4: None,
+                    ipv6: None,
+                    key: None,
+                    no_peer_id: false,
+                    crypto: Crypto::NotSupported,
+                    cryptoport: None,
+                }
+            }
+
+            fn ip(ip: &str) -> IpAddr {
+                ip.parse().unwrap()
+            }
+
+            #[test]
+            fn it_should_be_the_ipv6_address_for_a_peer_using_ipv4() {
+                let announce_request = Announce {
+                    ipv4: Some("126.0.0.2:17548".parse().unwrap()),
+                    ipv6: Some("[2001:db8::1]:17549".parse().unwrap()),
+                    ..sample_announce_request()
+                };
+
+                assert_eq!(
+                    announce_request.alt_peer_addr(&ip("126.0.0.1")),
+                    Some("[2001:db8::1]:17549".parse().unwrap())
+                );
+            }
+
+            #[test]
+            fn it_should_be_the_ipv4_address_for_a_peer_using_ipv6() {
+                let announce_request = Announce {
+                    ipv4: Some("126.0.0.2:17549".parse().unwrap()),
+                    ipv6: Some("[2001:db8::2]:17548".parse().unwrap()),
+                    ..sample_announce_request()
+                };
+
+                assert_eq!(
+                    announce_request.alt_peer_addr(&ip("2001:db8::1")),
+                    Some("126.0.0.2:17549".parse().unwrap())
+                );
+            }
+
+            #[test]
+            fn it_should_fall_back_to_the_ip_param_when_it_is_in_the_other_ip_family() {
+                let announce_request = Announce {
+                    ip: Some(ip("2001:db8::1")),
+                    ..sample_announce_request()
+                };
+
+                assert_eq!(
+                    announce_request.alt_peer_addr(&ip("126.0.0.1")),
+                    Some("[2001:db8::1]:17548".parse().unwrap())
+                );
+            }
+
+            #[test]
+            fn it_should_not_use_the_ip_param_when_it_is_in_the_same_ip_family() {
+                let announce_request = Announce {
+                    ip: Some(ip("126.0.0.2")),
+                    ..sample_announce_request()
+                };
+
+                assert_eq!(announce_request.alt_peer_addr(&ip("126.0.0.1")), None);
+            }
+        }
+
+        mod the_peer_key {
+            use aquatic_udp_protocol::PeerKey;
+
+            use crate::servers::http::v1::query::Query;
+            use crate::servers::http::v1::requests::announce::{Announce, INFO_HASH, KEY, PEER_ID, PORT};
+
+            fn key_from_param(key: &str) -> Option<PeerKey> {
+                let raw_query = Query::from(vec![
+                    (INFO_HASH, "%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0"),
+                    (PEER_ID, "-qB00000000000000001"),
+                    (PORT, "17548"),
+                    (KEY, key),
+                ])
+                .to_string();
+
+                Announce::try_from(raw_query.parse::<Query>().unwrap()).unwrap().key
+            }
+
+            #[test]
+            fn it_should_be_the_same_key_used_in_the_udp_tracker_protocol_when_it_is_an_hexadecimal_string() {
+                assert_eq!(key_from_param("EF3495D6"), Some(PeerKey::new(-281_766_442)));
+                assert_eq!(key_from_param("ef3495d6"), Some(PeerKey::new(-281_766_442)));
+            }
+
+            #[test]
+            fn it_should_hash_any_other_string_into_a_key() {
+                assert_eq!(key_from_param("2F8X1L0Q"), key_from_param("2F8X1L0Q"));
+                assert_ne!(key_from_param("2F8X1L0Q"), key_from_param("2F8X1L0R"));
+                assert_ne!(key_from_param("EF3495D6EF"), key_from_param("EF3495D6"));
+            }
         }
     }
 }
@@ -1,6 +1,9 @@
 //! `Scrape` request for the HTTP tracker.
 //!
 //! Data structures and logic for parsing the `scrape` request.
+//!
+//! A request without any `info_hash` param is a full scrape request. Refer
+//! to the [HTTP server](crate::servers::http) docs for more information.
 use std::panic::Location;
 
 use thiserror::Error;
@@ -13,21 +16,29 @@ use crate::servers::http::v1::responses;
 
 // Query param names
 const INFO_HASH: &str = "info_hash";
+const ADMIN_KEY: &str = "admin_key";
 
 #[derive(Debug, PartialEq)]
 pub struct Scrape {
+    /// The torrents to scrape. It's empty for a full scrape request.
     pub info_hashes: Vec<InfoHash>,
+    /// The key required to get the full scrape when it's not public.
+    pub admin_key: Option<String>,
+}
+
+impl Scrape {
+    /// Returns `true` if the client is asking for the stats of all the
+    /// torrents.
+    #[must_use]
+    pub fn is_full_scrape(&self) -> bool {
+        self.info_hashes.is_empty()
+    }
 }
 
 #[derive(Error, Debug)]
 pub enum ParseScrapeQueryError {
     #[error("missing query params for scrape request in {location}")]
     MissingParams { location: &'static Location<'static> },
-    #[error("missing param {param_name} in {location}")]
-    MissingParam {
-        location: &'static Location<'static>,
-        param_name: String,
-    },
     #[error("invalid param value {param_value} for {param_name} in {source}")]
     InvalidInfoHashParam {
         param_name: String,
@@ -50,6 +61,7 @@ impl TryFrom<Query> for Scrape {
     fn try_from(query: Query) -> Result<Self, Self::Error> {
         Ok(Self {
             info_hashes: extract_info_hashes(&query)?,
+            admin_key: query.get_param(ADMIN_KEY),
         })
     }
 }
@@ -72,10 +84,7 @@ fn extract_info_hashes(query: &Query) -> Result<Vec<InfoHash>, ParseScrapeQueryE
 
             Ok(info_hashes)
         }
-        None => Err(ParseScrapeQueryError::MissingParam {
-            location: Location::caller(),
-            param_name: INFO_HASH.to_owned(),
-        }),
+        None => Ok(vec![]),
     }
 }
 
@@ -87,7 +96,7 @@ mod tests {
         use torrust_tracker_primitives::info_hash::InfoHash;
 
         use crate::servers::http::v1::query::Query;
-        use crate::servers::http::v1::requests::scrape::{Scrape, INFO_HASH};
+        use crate::servers::http::v1::requests::scrape::{Scrape, ADMIN_KEY, INFO_HASH};
 
         #[test]
         fn should_be_instantiated_from_the_url_query_with_only_one_infohash() {
@@ -101,22 +110,28 @@ mod tests {
                 scrape_request,
                 Scrape {
                     info_hashes: vec!["3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap()],
+                    admin_key: None,
                 }
             );
         }
 
+        #[test]
+        fn should_be_a_full_scrape_request_when_the_url_query_does_not_include_any_infohash() {
+            let raw_query = Query::from(vec![(ADMIN_KEY, "MyAdminKey")]).to_string();
+
+            let query = raw_query.parse::<Query>().unwrap();
+
+            let scrape_request = Scrape::try_from(query).unwrap();
+
+            assert!(scrape_request.is_full_scrape());
+            assert_eq!(scrape_request.admin_key, Some("MyAdminKey".to_owned()));
+        }
+
         mod when_it_is_instantiated_from_the_url_query_params {
 
             use crate::servers::http::v1::query::Query;
             use crate::servers::http::v1::requests::scrape::{Scrape, INFO_HASH};
 
-            #[test]
-            fn it_should_fail_if_the_query_does_not_include_the_info_hash_param() {
-                let raw_query_without_info_hash = "another_param=NOT_RELEVANT";
-
-                assert!(Scrape::try_from(raw_query_without_info_hash.parse::<Query>().unwrap()).is_err());
-            }
-
             #[test]
             fn it_should_fail_if_the_info_hash_param_is_invalid() {
                 let raw_query = Query::from(vec![(INFO_HASH, "INVALID_INFO_HASH_VALUE")]).to_string();
@@ -1,8 +1,9 @@
 //! `Announce` response for the HTTP tracker [`announce`](crate::servers::http::v1::requests::announce::Announce) request.
 //!
 //! Data structures and logic to build the `announce` response.
+use std::borrow::Cow;
 use std::io::Write;
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 
 use axum::http::StatusCode;
 use derive_more::{AsRef, Constructor, From};
@@ -70,6 +71,26 @@ pub struct Normal {
     interval: i64,
     min_interval: i64,
     peers: Vec<NormalPeer>,
+    crypto_flags: Vec<u8>,
+    with_peer_ids: bool,
+    with_crypto_flags: bool,
+}
+
+impl Normal {
+    /// It leaves the peer IDs out of the peer list, as requested by clients
+    /// with the `no_peer_id` param.
+    #[must_use]
+    pub fn without_peer_ids(mut self) -> Self {
+        self.with_peer_ids = false;
+        self
+    }
+
+    /// It adds the `crypto_flags` key to the response. See [`crypto_flags`].
+    #[must_use]
+    pub fn with_crypto_flags(mut self) -> Self {
+        self.with_crypto_flags = true;
+        self
+    }
 }
 
 impl From<AnnounceData> for Normal {
@@ -80,6 +101,9 @@ impl From<AnnounceData> for Normal {
             interval: data.policy.interval.into(),
             min_interval: data.policy.interval_min.into(),
             peers: data.peers.iter().map(AsRef::as_ref).copied().collect(),
+            crypto_flags: crypto_flags(data.peers.iter().map(|peer| peer.crypto)),
+            with_peer_ids: true,
+            with_crypto_flags: false,
         }
     }
 }
@@ -90,21 +114,36 @@ impl Into<Vec<u8>> for Normal {
         let mut peers_list = ben_list!();
         let peers_list_mut = peers_list.list_mut().unwrap();
         for peer in &self.peers {
-            peers_list_mut.push(peer.into());
+            let mut peer_dict: BencodeMut<'_> = peer.into();
+            if !self.with_peer_ids {
+                peer_dict.dict_mut().unwrap().remove(b"peer id".as_slice());
+            }
+            peers_list_mut.push(peer_dict);
         }
 
-        (ben_map! {
+        let mut response = ben_map! {
             "complete" => ben_int!(self.complete),
             "incomplete" => ben_int!(self.incomplete),
             "interval" => ben_int!(self.interval),
             "min interval" => ben_int!(self.min_interval),
             "peers" => peers_list.clone()
-        })
-        .encode()
+        };
+
+        if self.with_crypto_flags {
+            response
+                .dict_mut()
+                .unwrap()
+                .insert(Cow::from(b"crypto_flags".as_slice()), ben_bytes!(self.crypto_flags));
+        }
+
+        response.encode()
     }
 }
 
 /// Format of the [`Compact`] Encoding
+///
+/// IPv4 peers are in the `peers` list and IPv6 peers in the `peers6` list.
+/// Dual-stack peers are in both lists.
 pub struct Compact {
     complete: i64,
     incomplete: i64,
@@ -112,11 +151,35 @@ pub struct Compact {
     min_interval: i64,
     peers: Vec<u8>,
     peers6: Vec<u8>,
+    crypto_flags: Vec<u8>,
+    with_crypto_flags: bool,
+}
+
+impl Compact {
+    /// It adds the `crypto_flags` key to the response. See [`crypto_flags`].
+    ///
+    /// The flags are only for the peers in the `peers` list. There is no
+    /// standard key for the peers in the `peers6` list.
+    #[must_use]
+    pub fn with_crypto_flags(mut self) -> Self {
+        self.with_crypto_flags = true;
+        self
+    }
 }
 
 impl From<AnnounceData> for Compact {
     fn from(data: AnnounceData) -> Self {
-        let compact_peers: Vec<CompactPeer> = data.peers.iter().map(AsRef::as_ref).copied().collect();
+        let compact_peers: Vec<CompactPeer> = data
+            .peers
+            .iter()
+            .flat_map(|peer| peer.addresses())
+            .map(CompactPeer::from)
+            .collect();
+
+        let ipv4_peers_crypto = data
+            .peers
+            .iter()
+            .flat_map(|peer| peer.addresses().filter(SocketAddr::is_ipv4).map(|_| peer.crypto));
 
         let (peers, peers6): (Vec<CompactPeerData<Ipv4Addr>>, Vec<CompactPeerData<Ipv6Addr>>) =
             compact_peers.into_iter().collect();
@@ -131,6 +194,8 @@ impl From<AnnounceData> for Compact {
             min_interval: data.policy.interval_min.into(),
             peers: peers_encoded.0,
             peers6: peers_encoded_6.0,
+            crypto_flags: crypto_flags(ipv4_peers_crypto),
+            with_crypto_flags: false,
         }
     }
 }
@@ -138,18 +203,37 @@ impl From<AnnounceData> for Compact {
 #[allow(clippy::from_over_into)]
 impl Into<Vec<u8>> for Compact {
     fn into(self) -> Vec<u8> {
-        (ben_map! {
+        let mut response = ben_map! {
             "complete" => ben_int!(self.complete),
             "incomplete" => ben_int!(self.incomplete),
             "interval" => ben_int!(self.interval),
             "min interval" => ben_int!(self.min_interval),
             "peers" => ben_bytes!(self.peers),
             "peers6" => ben_bytes!(self.peers6)
-        })
-        .encode()
+        };
+
+        if self.with_crypto_flags {
+            response
+                .dict_mut()
+                .unwrap()
+                .insert(Cow::from(b"crypto_flags".as_slice()), ben_bytes!(self.crypto_flags));
+        }
+
+        response.encode()
     }
 }
 
+/// The `crypto_flags` value: one byte per returned peer, in the same order,
+/// with `1` if the peer requires encrypted connections and `0` otherwise.
+///
+/// Clients that support encryption use it to know which peers they have to
+/// connect to with an encrypted connection.
+fn crypto_flags(peers_crypto: impl Iterator<Item = peer::Crypto>) -> Vec<u8> {
+    peers_crypto
+        .map(|crypto| u8::from(crypto == peer::Crypto::Required))
+        .collect()
+}
+
 /// A [`NormalPeer`], for the [`Normal`] form.
 ///
 /// ```rust
@@ -229,7 +313,13 @@ impl peer::Encoding for CompactPeer {}
 
 impl From<peer::Peer> for CompactPeer {
     fn from(peer: peer::Peer) -> Self {
-        match (peer.peer_addr.ip(), peer.peer_addr.port()) {
+        peer.peer_addr.into()
+    }
+}
+
+impl From<SocketAddr> for CompactPeer {
+    fn from(peer_addr: SocketAddr) -> Self {
+        match (peer_addr.ip(), peer_addr.port()) {
             (IpAddr::V4(ip), port) => Self::V4(CompactPeerData { ip, port }),
             (IpAddr::V6(ip), port) => Self::V6(CompactPeerData { ip, port }),
         }
@@ -303,6 +393,7 @@ mod tests {
     use aquatic_udp_protocol::PeerId;
     use torrust_tracker_configuration::AnnouncePolicy;
     use torrust_tracker_primitives::peer::fixture::PeerBuilder;
+    use torrust_tracker_primitives::peer::Crypto;
     use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
 
     use crate::core::AnnounceData;
@@ -339,7 +430,7 @@ mod tests {
         let peers = vec![Arc::new(peer_ipv4), Arc::new(peer_ipv6)];
         let stats = SwarmMetadata::new(333, 333, 444);
 
-        AnnounceData::new(peers, stats, policy)
+        AnnounceData::new(peers, stats, policy, None)
     }
 
     #[test]
@@ -356,6 +447,85 @@ mod tests {
         );
     }
 
+    #[test]
+    fn non_compact_announce_response_can_leave_out_the_peer_ids() {
+        let response = Announce::new(Normal::from(setup_announce_data()).without_peer_ids());
+        let bytes = response.body().expect("it should encode the response");
+
+        // cspell:disable-next-line
+        let expected_bytes = b"d8:completei333e10:incompletei444e8:intervali111e12:min intervali222e5:peersld2:ip15:105.105.105.1054:porti28784eed2:ip39:6969:6969:6969:6969:6969:6969:6969:69694:porti28784eeee";
+
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            String::from_utf8(expected_bytes.to_vec()).unwrap()
+        );
+    }
+
+    #[test]
+    fn non_compact_announce_response_can_include_the_crypto_flags_of_the_peers() {
+        let mut announce_data = setup_announce_data();
+        announce_data.peers[1] = Arc::new(
+            PeerBuilder::default()
+                .with_peer_id(&PeerId(*b"-qB00000000000000002"))
+                .with_peer_addr(&SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0x69, 0x69, 0x69, 0x70)), 0x7070))
+                .with_crypto(Crypto::Required)
+                .build(),
+        );
+
+        let response = Announce::new(Normal::from(announce_data).without_peer_ids().with_crypto_flags());
+        let bytes = response.body().expect("it should encode the response");
+
+        // cspell:disable-next-line
+        let expected_bytes = b"d8:completei333e12:crypto_flags2:\x00\x0110:incompletei444e8:intervali111e12:min intervali222e5:peersld2:ip15:105.105.105.1054:porti28784eed2:ip15:105.105.105.1124:porti28784eeee";
+
+        assert_eq!(bytes, expected_bytes.to_vec());
+    }
+
+    #[test]
+    fn compact_announce_response_can_include_the_crypto_flags_of_the_ipv4_peers() {
+        let response = Announce::new(Compact::from(setup_announce_data()).with_crypto_flags());
+        let bytes = response.body().expect("it should encode the response");
+
+        let expected_bytes =
+            // cspell:disable-next-line
+            b"d8:completei333e12:crypto_flags1:\x0010:incompletei444e8:intervali111e12:min intervali222e5:peers6:iiiipp6:peers618:iiiiiiiiiiiiiiiippe";
+
+        assert_eq!(bytes, expected_bytes.to_vec());
+    }
+
+    #[test]
+    fn compact_announce_response_should_include_dual_stack_peers_in_both_peer_lists() {
+        let policy = AnnouncePolicy::new(111, 222);
+
+        let dual_stack_peer = PeerBuilder::default()
+            .with_peer_id(&PeerId(*b"-qB00000000000000001"))
+            .with_peer_addr(&SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0x69, 0x69, 0x69, 0x69)), 0x7070))
+            .with_alt_peer_addr(&SocketAddr::new(
+                IpAddr::V6(Ipv6Addr::new(0x6969, 0x6969, 0x6969, 0x6969, 0x6969, 0x6969, 0x6969, 0x6969)),
+                0x7070,
+            ))
+            .build();
+
+        let announce_data = AnnounceData::new(
+            vec![Arc::new(dual_stack_peer)],
+            SwarmMetadata::new(333, 333, 444),
+            policy,
+            None,
+        );
+
+        let response: Announce<Compact> = announce_data.into();
+        let bytes = response.body().expect("it should encode the response");
+
+        let expected_bytes =
+            // cspell:disable-next-line
+            b"d8:completei333e10:incompletei444e8:intervali111e12:min intervali222e5:peers6:iiiipp6:peers618:iiiiiiiiiiiiiiiippe";
+
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            String::from_utf8(expected_bytes.to_vec()).unwrap()
+        );
+    }
+
     #[test]
     fn compact_announce_response_can_be_bencoded() {
         let response: Announce<Compact> = setup_announce_data().into();
@@ -1,11 +1,20 @@
 //! `Scrape` response for the HTTP tracker [`scrape`](crate::servers::http::v1::requests::scrape::Scrape) request.
 //!
 //! Data structures and logic to build the `scrape` response.
+//!
+//! Both responses can include the `flags` dictionary from
+//! [BEP 48](https://www.bittorrent.org/beps/bep_0048.html) with the
+//! `min_request_interval` the clients should wait between scrapes.
 use std::borrow::Cow;
+use std::convert::Infallible;
 
+use axum::body::Body;
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
-use torrust_tracker_contrib_bencode::{ben_int, ben_map, BMutAccess};
+use futures::{future, stream, Stream, StreamExt};
+use torrust_tracker_contrib_bencode::{ben_int, ben_map, BMutAccess, BencodeMut};
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
 
 use crate::core::ScrapeData;
 
@@ -44,9 +53,20 @@ use crate::core::ScrapeData;
 pub struct Bencoded {
     /// The scrape data to be bencoded.
     scrape_data: ScrapeData,
+    /// The `min_request_interval` in the `flags` dictionary. The dictionary
+    /// is not included when it's `None`.
+    min_request_interval: Option<u32>,
 }
 
 impl Bencoded {
+    /// It includes the `flags` dictionary with the `min_request_interval`
+    /// in the response.
+    #[must_use]
+    pub fn with_min_request_interval(mut self, min_request_interval: u32) -> Self {
+        self.min_request_interval = Some(min_request_interval);
+        self
+    }
+
     /// Returns the bencoded representation of the `Scrape` struct.
     ///
     /// # Panics
@@ -59,26 +79,30 @@ impl Bencoded {
         let scrape_list_mut = scrape_list.dict_mut().unwrap();
 
         for (info_hash, value) in &self.scrape_data.files {
-            scrape_list_mut.insert(
-                Cow::from(info_hash.bytes().to_vec()),
-                ben_map! {
-                    "complete" => ben_int!(i64::from(value.complete)),
-                    "downloaded" => ben_int!(i64::from(value.downloaded)),
-                    "incomplete" => ben_int!(i64::from(value.incomplete))
-                },
-            );
+            scrape_list_mut.insert(Cow::from(info_hash.bytes().to_vec()), file(value));
         }
 
-        (ben_map! {
+        let mut response = ben_map! {
             "files" => scrape_list
-        })
-        .encode()
+        };
+
+        if let Some(min_request_interval) = self.min_request_interval {
+            response
+                .dict_mut()
+                .unwrap()
+                .insert(Cow::from(b"flags".as_slice()), flags(min_request_interval));
+        }
+
+        response.encode()
     }
 }
 
 impl From<ScrapeData> for Bencoded {
     fn from(scrape_data: ScrapeData) -> Self {
-        Self { scrape_data }
+        Self {
+            scrape_data,
+            min_request_interval: None,
+        }
     }
 }
 
@@ -88,6 +112,118 @@ impl IntoResponse for Bencoded {
     }
 }
 
+/// The full `scrape` response for the HTTP tracker.
+///
+/// It contains the stats of all the torrents. The body is streamed, one chunk
+/// for every page of torrents, so the response does not need to be kept in
+/// memory. The torrents must be sorted by infohash, like in the tracker
+/// repository, because bencoded dictionaries are sorted by key.
+///
+/// ```rust
+/// use futures::{stream, StreamExt};
+/// use torrust_tracker::servers::http::v1::responses::scrape::FullScrape;
+/// use torrust_tracker_primitives::info_hash::InfoHash;
+/// use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
+///
+/// let page = vec![(
+///     InfoHash::from_bytes(&[0x69; 20]),
+///     SwarmMetadata {
+///         complete: 1,
+///         downloaded: 2,
+///         incomplete: 3,
+///     },
+/// )];
+///
+/// let response = FullScrape::new(stream::iter(vec![page])).with_min_request_interval(120);
+///
+/// let bytes = futures::executor::block_on(response.body().concat());
+///
+/// // cspell:disable-next-line
+/// let expected_bytes = b"d5:filesd20:iiiiiiiiiiiiiiiiiiiid8:completei1e10:downloadedi2e10:incompletei3eee5:flagsd20:min_request_intervali120eee";
+///
+/// assert_eq!(
+///     String::from_utf8(bytes).unwrap(),
+///     String::from_utf8(expected_bytes.to_vec()).unwrap()
+/// );
+/// ```
+pub struct FullScrape<S> {
+    /// The pages of torrents to be bencoded.
+    pages: S,
+    /// The `min_request_interval` in the `flags` dictionary. The dictionary
+    /// is not included when it's `None`.
+    min_request_interval: Option<u32>,
+}
+
+impl<S> FullScrape<S>
+where
+    S: Stream<Item = Vec<(InfoHash, SwarmMetadata)>> + Send + 'static,
+{
+    #[must_use]
+    pub fn new(pages: S) -> Self {
+        Self {
+            pages,
+            min_request_interval: None,
+        }
+    }
+
+    /// It includes the `flags` dictionary with the `min_request_interval`
+    /// in the response.
+    #[must_use]
+    pub fn with_min_request_interval(mut self, min_request_interval: u32) -> Self {
+        self.min_request_interval = Some(min_request_interval);
+        self
+    }
+
+    /// Returns the chunks of the bencoded response.
+    pub fn body(self) -> impl Stream<Item = Vec<u8>> + Send {
+        let head = b"d5:filesd".to_vec();
+
+        let mut tail = b"e".to_vec();
+        if let Some(min_request_interval) = self.min_request_interval {
+            tail.extend_from_slice(b"5:flags");
+            tail.extend(flags(min_request_interval).encode());
+        }
+        tail.extend_from_slice(b"e");
+
+        stream::once(future::ready(head))
+            .chain(self.pages.map(|page| {
+                let mut chunk = vec![];
+                for (info_hash, swarm_metadata) in &page {
+                    chunk.extend_from_slice(b"20:");
+                    chunk.extend_from_slice(&info_hash.bytes());
+                    chunk.extend(file(swarm_metadata).encode());
+                }
+                chunk
+            }))
+            .chain(stream::once(future::ready(tail)))
+    }
+}
+
+impl<S> IntoResponse for FullScrape<S>
+where
+    S: Stream<Item = Vec<(InfoHash, SwarmMetadata)>> + Send + 'static,
+{
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Body::from_stream(self.body().map(Ok::<_, Infallible>))).into_response()
+    }
+}
+
+/// The bencoded stats of one torrent.
+fn file(swarm_metadata: &SwarmMetadata) -> BencodeMut<'static> {
+    ben_map! {
+        "complete" => ben_int!(i64::from(swarm_metadata.complete)),
+        "downloaded" => ben_int!(i64::from(swarm_metadata.downloaded)),
+        "incomplete" => ben_int!(i64::from(swarm_metadata.incomplete))
+    }
+}
+
+/// The BEP 48 `flags` dictionary.
+fn flags(min_request_interval: u32) -> BencodeMut<'static> {
+    ben_map! {
+        "min_request_interval" => ben_int!(i64::from(min_request_interval))
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -119,7 +255,8 @@ mod tests {
             assert_eq!(
                 response,
                 Bencoded {
-                    scrape_data: sample_scrape_data()
+                    scrape_data: sample_scrape_data(),
+                    min_request_interval: None,
                 }
             );
         }
@@ -128,6 +265,7 @@ mod tests {
         fn should_be_bencoded() {
             let response = Bencoded {
                 scrape_data: sample_scrape_data(),
+                min_request_interval: None,
             };
 
             let bytes = response.body();
@@ -140,5 +278,64 @@ mod tests {
                 String::from_utf8(expected_bytes.to_vec()).unwrap()
             );
         }
+
+        #[test]
+        fn should_include_the_min_request_interval_flag() {
+            let response = Bencoded::from(sample_scrape_data()).with_min_request_interval(120);
+
+            let bytes = response.body();
+
+            // cspell:disable-next-line
+            let expected_bytes = b"d5:filesd20:iiiiiiiiiiiiiiiiiiiid8:completei1e10:downloadedi2e10:incompletei3eee5:flagsd20:min_request_intervali120eee";
+
+            assert_eq!(
+                String::from_utf8(bytes).unwrap(),
+                String::from_utf8(expected_bytes.to_vec()).unwrap()
+            );
+        }
+    }
+
+    mod full_scrape_response {
+        use futures::{stream, StreamExt};
+        use torrust_tracker_primitives::info_hash::InfoHash;
+        use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
+
+        use crate::servers::http::v1::responses::scrape::FullScrape;
+
+        fn sample_file(byte: u8, complete: u32) -> (InfoHash, SwarmMetadata) {
+            (
+                InfoHash::from_bytes(&[byte; 20]),
+                SwarmMetadata {
+                    complete,
+                    downloaded: 0,
+                    incomplete: 0,
+                },
+            )
+        }
+
+        #[tokio::test]
+        async fn should_be_bencoded_as_a_single_dictionary_from_many_pages() {
+            let pages = vec![vec![sample_file(0x61, 1)], vec![sample_file(0x62, 2)]];
+
+            let bytes = FullScrape::new(stream::iter(pages)).body().concat().await;
+
+            // cspell:disable-next-line
+            let expected_bytes = b"d5:filesd20:aaaaaaaaaaaaaaaaaaaad8:completei1e10:downloadedi0e10:incompletei0ee20:bbbbbbbbbbbbbbbbbbbbd8:completei2e10:downloadedi0e10:incompletei0eeee";
+
+            assert_eq!(
+                String::from_utf8(bytes).unwrap(),
+                String::from_utf8(expected_bytes.to_vec()).unwrap()
+            );
+        }
+
+        #[tokio::test]
+        async fn should_be_an_empty_dictionary_when_there_are_no_torrents() {
+            let bytes = FullScrape::new(stream::iter(Vec::<Vec<(InfoHash, SwarmMetadata)>>::new()))
+                .body()
+                .concat()
+                .await;
+
+            assert_eq!(bytes, b"d5:filesdee".to_vec());
+        }
     }
 }
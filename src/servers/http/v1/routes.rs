@@ -7,7 +7,7 @@ use axum::error_handling::HandleErrorLayer;
 use axum::http::HeaderName;
 use axum::response::Response;
 use axum::routing::get;
-use axum::{BoxError, Router};
+use axum::{middleware, BoxError, Router};
 use axum_client_ip::SecureClientIpSource;
 use hyper::{Request, StatusCode};
 use torrust_tracker_configuration::DEFAULT_TIMEOUT;
@@ -20,13 +20,17 @@ use tower_http::trace::{DefaultMakeSpan, TraceLayer};
 use tracing::{instrument, Level, Span};
 
 use super::handlers::{announce, health_check, scrape};
-use crate::core::Tracker;
+use super::middlewares::rate_limit::rate_limit;
+use crate::core::{rate_limiter, Tracker};
 use crate::servers::http::HTTP_TRACKER_LOG_TARGET;
 
 /// It adds the routes to the router.
 ///
 /// > **NOTICE**: it's added a layer to get the client IP from the connection
 /// > info. The tracker could use the connection info to get the client IP.
+///
+/// The `announce` and `scrape` routes have the [`rate_limit`] middleware, so
+/// the tracker rate limiter is checked before the requests are handled.
 #[allow(clippy::needless_pass_by_value)]
 #[instrument(skip(tracker, server_socket_addr))]
 pub fn router(tracker: Arc<Tracker>, server_socket_addr: SocketAddr) -> Router {
@@ -34,11 +38,43 @@ pub fn router(tracker: Arc<Tracker>, server_socket_addr: SocketAddr) -> Router {
         // Health check
         .route("/health_check", get(health_check::handler))
         // Announce request
-        .route("/announce", get(announce::handle_without_key).with_state(tracker.clone()))
-        .route("/announce/:key", get(announce::handle_with_key).with_state(tracker.clone()))
+        .route(
+            "/announce",
+            get(announce::handle_without_key)
+                .route_layer(middleware::from_fn_with_state(
+                    (tracker.clone(), rate_limiter::Request::Announce),
+                    rate_limit,
+                ))
+                .with_state(tracker.clone()),
+        )
+        .route(
+            "/announce/:key",
+            get(announce::handle_with_key)
+                .route_layer(middleware::from_fn_with_state(
+                    (tracker.clone(), rate_limiter::Request::Announce),
+                    rate_limit,
+                ))
+                .with_state(tracker.clone()),
+        )
         // Scrape request
-        .route("/scrape", get(scrape::handle_without_key).with_state(tracker.clone()))
-        .route("/scrape/:key", get(scrape::handle_with_key).with_state(tracker))
+        .route(
+            "/scrape",
+            get(scrape::handle_without_key)
+                .route_layer(middleware::from_fn_with_state(
+                    (tracker.clone(), rate_limiter::Request::Scrape),
+                    rate_limit,
+                ))
+                .with_state(tracker.clone()),
+        )
+        .route(
+            "/scrape/:key",
+            get(scrape::handle_with_key)
+                .route_layer(middleware::from_fn_with_state(
+                    (tracker.clone(), rate_limiter::Request::Scrape),
+                    rate_limit,
+                ))
+                .with_state(tracker),
+        )
         // Add extension to get the client IP from the connection info
         .layer(SecureClientIpSource::ConnectInfo.into_extension())
         .layer(CompressionLayer::new())
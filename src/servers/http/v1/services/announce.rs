@@ -11,9 +11,11 @@
 use std::net::IpAddr;
 use std::sync::Arc;
 
+use torrust_tracker_configuration::OnEarlyAnnounce;
 use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_primitives::peer;
 
+use crate::core::error::Error;
 use crate::core::{statistics, AnnounceData, PeersWanted, Tracker};
 
 /// The HTTP tracker `announce` service.
@@ -26,27 +28,58 @@ use crate::core::{statistics, AnnounceData, PeersWanted, Tracker};
 /// > **NOTICE**: as the HTTP tracker does not requires a connection request
 /// > like the UDP tracker, the number of TCP connections is incremented for
 /// > each `announce` request.
+///
+/// When the peer announces again before the minimum announce interval, it
+/// sends an early announce event instead. The `announce` request is also
+/// counted as handled only if the tracker is configured to accept early
+/// announces. No event is sent when the tracker rejects the announce for any
+/// other reason.
+///
+/// # Errors
+///
+/// Will return an error if the tracker rejects the announce. Refer to
+/// [`Tracker::announce`].
 pub async fn invoke(
     tracker: Arc<Tracker>,
     info_hash: InfoHash,
     peer: &mut peer::Peer,
     peers_wanted: &PeersWanted,
-) -> AnnounceData {
+) -> Result<AnnounceData, Error> {
     let original_peer_ip = peer.peer_addr.ip();
 
     // The tracker could change the original peer ip
-    let announce_data = tracker.announce(&info_hash, peer, &original_peer_ip, peers_wanted);
+    let result = tracker.announce(&info_hash, peer, &original_peer_ip, peers_wanted);
+
+    let (early, counted) = match &result {
+        Ok(announce_data) => match announce_data.early_announce {
+            None => (false, true),
+            Some(OnEarlyAnnounce::Accept) => (true, true),
+            Some(_) => (true, false),
+        },
+        Err(Error::AnnounceTooEarly { .. }) => (true, false),
+        Err(_) => (false, false),
+    };
 
     match original_peer_ip {
         IpAddr::V4(_) => {
-            tracker.send_stats_event(statistics::Event::Tcp4Announce).await;
+            if early {
+                tracker.send_stats_event(statistics::Event::Tcp4EarlyAnnounce).await;
+            }
+            if counted {
+                tracker.send_stats_event(statistics::Event::Tcp4Announce).await;
+            }
         }
         IpAddr::V6(_) => {
-            tracker.send_stats_event(statistics::Event::Tcp6Announce).await;
+            if early {
+                tracker.send_stats_event(statistics::Event::Tcp6EarlyAnnounce).await;
+            }
+            if counted {
+                tracker.send_stats_event(statistics::Event::Tcp6Announce).await;
+            }
         }
     }
 
-    announce_data
+    result
 }
 
 #[cfg(test)]
@@ -86,6 +119,9 @@ mod tests {
         peer::Peer {
             peer_id: PeerId(*b"-qB00000000000000000"),
             peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080),
+            alt_peer_addr: None,
+            key: None,
+            crypto: peer::Crypto::NotSupported,
             updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
             uploaded: NumberOfBytes::new(0),
             downloaded: NumberOfBytes::new(0),
@@ -100,11 +136,13 @@ mod tests {
         use std::sync::Arc;
 
         use mockall::predicate::eq;
+        use torrust_tracker_configuration::OnPeerIdentityMismatch;
         use torrust_tracker_primitives::peer;
         use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
         use torrust_tracker_test_helpers::configuration;
 
         use super::{sample_peer_using_ipv4, sample_peer_using_ipv6};
+        use crate::core::error::Error;
         use crate::core::{statistics, AnnounceData, PeersWanted, Tracker};
         use crate::servers::http::v1::services::announce::invoke;
         use crate::servers::http::v1::services::announce::tests::{public_tracker, sample_info_hash, sample_peer};
@@ -115,7 +153,9 @@ mod tests {
 
             let mut peer = sample_peer();
 
-            let announce_data = invoke(tracker.clone(), sample_info_hash(), &mut peer, &PeersWanted::All).await;
+            let announce_data = invoke(tracker.clone(), sample_info_hash(), &mut peer, &PeersWanted::All)
+                .await
+                .unwrap();
 
             let expected_announce_data = AnnounceData {
                 peers: vec![],
@@ -125,6 +165,7 @@ mod tests {
                     incomplete: 0,
                 },
                 policy: tracker.get_announce_policy(),
+                early_announce: None,
             };
 
             assert_eq!(announce_data, expected_announce_data);
@@ -219,5 +260,39 @@ mod tests {
 
             let _announce_data = invoke(tracker, sample_info_hash(), &mut peer, &PeersWanted::All).await;
         }
+
+        #[tokio::test]
+        async fn it_should_not_send_the_early_announce_event_when_the_announce_is_rejected_for_a_peer_identity_mismatch() {
+            let mut stats_event_sender_mock = statistics::MockEventSender::new();
+            stats_event_sender_mock
+                .expect_send_event()
+                .with(eq(statistics::Event::Tcp4Announce))
+                .times(1)
+                .returning(|_| Box::pin(future::ready(Some(Ok(())))));
+            stats_event_sender_mock
+                .expect_send_event()
+                .with(eq(statistics::Event::Tcp4EarlyAnnounce))
+                .never();
+            let stats_event_sender = Box::new(stats_event_sender_mock);
+
+            let mut configuration = configuration::ephemeral();
+            configuration.core.on_peer_identity_mismatch = OnPeerIdentityMismatch::Reject;
+
+            let tracker = Arc::new(Tracker::new(&configuration.core, Some(stats_event_sender), statistics::Repo::new()).unwrap());
+
+            let mut peer = sample_peer_using_ipv4();
+
+            invoke(tracker.clone(), sample_info_hash(), &mut peer, &PeersWanted::All)
+                .await
+                .unwrap();
+
+            // The same `peer_id` from another IP address
+            let mut peer = sample_peer_using_ipv4();
+            peer.peer_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2)), 8080);
+
+            let result = invoke(tracker, sample_info_hash(), &mut peer, &PeersWanted::All).await;
+
+            assert!(matches!(result, Err(Error::PeerIdentityMismatch { .. })));
+        }
     }
 }
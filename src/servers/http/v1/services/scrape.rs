@@ -11,7 +11,9 @@
 use std::net::IpAddr;
 use std::sync::Arc;
 
+use futures::stream::{self, Stream};
 use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
 
 use crate::core::{statistics, ScrapeData, Tracker};
 
@@ -45,6 +47,54 @@ pub async fn fake(tracker: &Arc<Tracker>, info_hashes: &Vec<InfoHash>, original_
     ScrapeData::zeroed(info_hashes)
 }
 
+/// The HTTP tracker full `scrape` service.
+///
+/// It returns a stream with the pages of the full scrape. The infohashes of
+/// all the torrents are listed once, when the stream is created. Then, the
+/// swarm metadata is read from the tracker `page_size` torrents at a time,
+/// only when the previous page has been consumed, so the whole response is
+/// never kept in memory. Torrents added while the response is streamed are not
+/// included and torrents removed are skipped, so every torrent is returned
+/// only once.
+///
+/// A `page_size` of `0` returns an empty stream.
+///
+/// It sends the same statistics event as the [`invoke`] service.
+pub async fn full(
+    tracker: &Arc<Tracker>,
+    page_size: u32,
+    original_peer_ip: &IpAddr,
+) -> impl Stream<Item = Vec<(InfoHash, SwarmMetadata)>> {
+    send_scrape_event(original_peer_ip, tracker).await;
+
+    let tracker = tracker.clone();
+
+    let info_hashes: Arc<[InfoHash]> = if page_size == 0 {
+        Arc::new([])
+    } else {
+        tracker.get_torrent_info_hashes().into()
+    };
+
+    let page_size = page_size as usize;
+
+    stream::unfold(0, move |offset: usize| {
+        let tracker = tracker.clone();
+        let info_hashes = info_hashes.clone();
+
+        async move {
+            if offset >= info_hashes.len() {
+                return None;
+            }
+
+            let end = info_hashes.len().min(offset.saturating_add(page_size));
+
+            let page = tracker.full_scrape_page(&info_hashes[offset..end]).await;
+
+            Some((page, end))
+        }
+    })
+}
+
 async fn send_scrape_event(original_peer_ip: &IpAddr, tracker: &Arc<Tracker>) {
     match original_peer_ip {
         IpAddr::V4(_) => {
@@ -85,6 +135,9 @@ mod tests {
         peer::Peer {
             peer_id: PeerId(*b"-qB00000000000000000"),
             peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080),
+            alt_peer_addr: None,
+            key: None,
+            crypto: peer::Crypto::NotSupported,
             updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
             uploaded: NumberOfBytes::new(0),
             downloaded: NumberOfBytes::new(0),
@@ -119,7 +172,9 @@ mod tests {
             // Announce a new peer to force scrape data to contain not zeroed data
             let mut peer = sample_peer();
             let original_peer_ip = peer.ip();
-            tracker.announce(&info_hash, &mut peer, &original_peer_ip, &PeersWanted::All);
+            tracker
+                .announce(&info_hash, &mut peer, &original_peer_ip, &PeersWanted::All)
+                .unwrap();
 
             let scrape_data = invoke(&tracker, &info_hashes, &original_peer_ip).await;
 
@@ -210,7 +265,9 @@ mod tests {
             // Announce a new peer to force scrape data to contain not zeroed data
             let mut peer = sample_peer();
             let original_peer_ip = peer.ip();
-            tracker.announce(&info_hash, &mut peer, &original_peer_ip, &PeersWanted::All);
+            tracker
+                .announce(&info_hash, &mut peer, &original_peer_ip, &PeersWanted::All)
+                .unwrap();
 
             let scrape_data = fake(&tracker, &info_hashes, &original_peer_ip).await;
 
@@ -267,4 +324,87 @@ mod tests {
             fake(&tracker, &sample_info_hashes(), &peer_ip).await;
         }
     }
+    mod with_full_scrape_data {
+        use std::sync::Arc;
+
+        use futures::StreamExt;
+        use torrust_tracker_primitives::info_hash::InfoHash;
+        use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
+
+        use crate::core::PeersWanted;
+        use crate::servers::http::v1::services::scrape::full;
+        use crate::servers::http::v1::services::scrape::tests::{public_tracker, sample_info_hash, sample_peer};
+
+        #[tokio::test]
+        async fn it_should_return_the_pages_with_the_scrape_data_of_all_the_torrents() {
+            let tracker = Arc::new(public_tracker());
+
+            let info_hash = sample_info_hash();
+
+            let mut peer = sample_peer();
+            let original_peer_ip = peer.ip();
+            tracker
+                .announce(&info_hash, &mut peer, &original_peer_ip, &PeersWanted::All)
+                .unwrap();
+
+            let pages: Vec<_> = full(&tracker, 10, &original_peer_ip).await.collect().await;
+
+            assert_eq!(
+                pages,
+                vec![vec![(
+                    info_hash,
+                    SwarmMetadata {
+                        complete: 1,
+                        downloaded: 0,
+                        incomplete: 0,
+                    }
+                )]]
+            );
+        }
+
+        #[tokio::test]
+        async fn it_should_return_every_torrent_only_once_when_torrents_are_added_while_streaming_the_pages() {
+            let tracker = Arc::new(public_tracker());
+
+            let info_hashes = [
+                "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap(),
+                "99c82bb73505a3c0b453f9fa0e881d6e5a32a0c1".parse::<InfoHash>().unwrap(),
+            ];
+
+            let mut peer = sample_peer();
+            let original_peer_ip = peer.ip();
+
+            for info_hash in &info_hashes {
+                tracker
+                    .announce(info_hash, &mut peer, &original_peer_ip, &PeersWanted::All)
+                    .unwrap();
+            }
+
+            let mut pages = Box::pin(full(&tracker, 1, &original_peer_ip).await);
+
+            let mut scraped: Vec<InfoHash> = pages
+                .next()
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|(info_hash, _)| info_hash)
+                .collect();
+
+            // A new torrent that goes before the torrents already scraped
+            tracker
+                .announce(
+                    &"0000000000000000000000000000000000000001".parse::<InfoHash>().unwrap(),
+                    &mut peer,
+                    &original_peer_ip,
+                    &PeersWanted::All,
+                )
+                .unwrap();
+
+            while let Some(page) = pages.next().await {
+                scraped.extend(page.into_iter().map(|(info_hash, _)| info_hash));
+            }
+
+            assert_eq!(scraped, info_hashes.to_vec());
+        }
+    }
 }
@@ -30,7 +30,21 @@ pub enum Error {
         source: LocatedError<'static, dyn std::error::Error + Send + Sync>,
     },
 
-    /// Error returned when tracker requires authentication.
-    #[error("domain tracker requires authentication but is not supported in current UDP implementation. Location: {location}")]
+    /// Error returned when tracker requires authentication but the request
+    /// does not include the key in the BEP 41 `URLData` option.
+    #[error("domain tracker requires authentication: missing authentication key in the URL data. Location: {location}")]
     TrackerAuthenticationRequired { location: &'static Location<'static> },
+
+    /// Error returned when the authentication key in the BEP 41 `URLData`
+    /// option has an invalid format.
+    #[error("invalid format for authentication key in the URL data: {source}")]
+    InvalidAuthenticationKey {
+        source: LocatedError<'static, dyn std::error::Error + Send + Sync>,
+    },
+
+    /// Error returned when the authentication key could not be verified.
+    #[error("authentication error: {source}")]
+    AuthenticationFailed {
+        source: LocatedError<'static, dyn std::error::Error + Send + Sync>,
+    },
 }
@@ -0,0 +1,244 @@
+//! UDP tracker protocol extensions.
+//!
+//! Refer to [BEP 41. UDP Tracker Protocol Extensions](https://www.bittorrent.org/beps/bep_0041.html).
+//!
+//! Extensions are a list of options appended to the end of the `announce`
+//! request packet (offset `98`). Each option starts with one byte for the
+//! option type:
+//!
+//! Option type | Name           | Format
+//! ------------|----------------|------------------------------------
+//! `0x0`       | `EndOfOptions` | `<1 byte type>`
+//! `0x1`       | `NOP`          | `<1 byte type>`
+//! `0x2`       | `URLData`      | `<1 byte type> <1 byte length> <N bytes>`
+//!
+//! The `URLData` option contains the path and query string of the tracker URL.
+//! When the path and query string is longer than 255 bytes it is split into
+//! several consecutive `URLData` options which have to be concatenated.
+//!
+//! For example, for the tracker URL `udp://tracker.example.com:6969/announce/YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ`
+//! the `URLData` would be `/announce/YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ`.
+//!
+//! The tracker uses the `URLData` to get the authentication [`Key`] when it
+//! runs in `private` mode, in the same way the HTTP tracker gets it from the
+//! URL path: `/announce/:key`.
+use crate::core::auth::{Key, ParseKeyError};
+
+/// Size in bytes of the fixed part of the `announce` request. Extension
+/// options start right after it.
+pub const ANNOUNCE_REQUEST_SIZE: usize = 98;
+
+/// Option type: end of options. The rest of the packet is ignored.
+const END_OF_OPTIONS: u8 = 0x0;
+/// Option type: no operation. Used for padding.
+const NOP: u8 = 0x1;
+/// Option type: a chunk of the URL path and query string.
+const URL_DATA: u8 = 0x2;
+
+/// The options (extensions) included at the end of an `announce` request.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Options {
+    /// The concatenation of all the `URLData` options.
+    pub url_data: Vec<u8>,
+}
+
+impl Options {
+    /// It parses the options from the whole `announce` request packet.
+    #[must_use]
+    pub fn from_announce_packet(payload: &[u8]) -> Self {
+        match payload.get(ANNOUNCE_REQUEST_SIZE..) {
+            Some(option_bytes) => Self::parse(option_bytes),
+            None => Self::default(),
+        }
+    }
+
+    /// It parses the options from the bytes following the fixed part of the
+    /// `announce` request.
+    ///
+    /// Parsing stops at the `EndOfOptions` option, at the end of the packet or
+    /// at the first truncated option. Options with an unknown type are
+    /// skipped using their length byte.
+    #[must_use]
+    pub fn parse(bytes: &[u8]) -> Self {
+        let mut options = Self::default();
+        let mut position = 0;
+
+        while let Some(&option_type) = bytes.get(position) {
+            match option_type {
+                END_OF_OPTIONS => break,
+                NOP => position += 1,
+                _ => {
+                    let Some(&length) = bytes.get(position + 1) else {
+                        break;
+                    };
+
+                    let start = position + 2;
+                    let end = start + usize::from(length);
+
+                    let Some(data) = bytes.get(start..end) else {
+                        break;
+                    };
+
+                    if option_type == URL_DATA {
+                        options.url_data.extend_from_slice(data);
+                    }
+
+                    position = end;
+                }
+            }
+        }
+
+        options
+    }
+
+    /// It returns the URL path and query string sent by the client, if any.
+    #[must_use]
+    pub fn path_and_query(&self) -> Option<String> {
+        if self.url_data.is_empty() {
+            return None;
+        }
+
+        Some(String::from_utf8_lossy(&self.url_data).into_owned())
+    }
+
+    /// It returns the authentication key included in the URL path.
+    ///
+    /// The key is the last segment of the path, with or without the `announce`
+    /// prefix. For example: `/announce/YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ` or
+    /// `/YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ`.
+    ///
+    /// It returns `None` if the path does not contain any key.
+    #[must_use]
+    pub fn auth_key(&self) -> Option<Result<Key, ParseKeyError>> {
+        let path_and_query = self.path_and_query()?;
+
+        let path = path_and_query.split(['?', '#']).next().unwrap_or_default();
+
+        let mut segments = path.split('/').filter(|segment| !segment.is_empty()).peekable();
+
+        if segments.peek() == Some(&"announce") {
+            segments.next();
+        }
+
+        segments.last().map(Key::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod parsing_the_announce_options {
+        use crate::servers::udp::extensions::{Options, ANNOUNCE_REQUEST_SIZE};
+
+        fn url_data_option(data: &[u8]) -> Vec<u8> {
+            let mut option = vec![0x2, u8::try_from(data.len()).unwrap()];
+            option.extend_from_slice(data);
+            option
+        }
+
+        #[test]
+        fn it_should_be_empty_when_the_packet_does_not_contain_options() {
+            let packet = [0u8; ANNOUNCE_REQUEST_SIZE];
+
+            assert_eq!(Options::from_announce_packet(&packet), Options::default());
+        }
+
+        #[test]
+        fn it_should_read_the_options_after_the_fixed_part_of_the_announce_request() {
+            let mut packet = vec![0u8; ANNOUNCE_REQUEST_SIZE];
+            packet.extend(url_data_option(b"/announce"));
+
+            assert_eq!(
+                Options::from_announce_packet(&packet).path_and_query(),
+                Some("/announce".to_string())
+            );
+        }
+
+        #[test]
+        fn it_should_concatenate_consecutive_url_data_options() {
+            let mut bytes = url_data_option(b"/announce");
+            bytes.extend(url_data_option(b"?a=1"));
+
+            assert_eq!(Options::parse(&bytes).path_and_query(), Some("/announce?a=1".to_string()));
+        }
+
+        #[test]
+        fn it_should_skip_nop_options() {
+            let mut bytes = vec![0x1, 0x1];
+            bytes.extend(url_data_option(b"/announce"));
+
+            assert_eq!(Options::parse(&bytes).path_and_query(), Some("/announce".to_string()));
+        }
+
+        #[test]
+        fn it_should_stop_at_the_end_of_options() {
+            let mut bytes = url_data_option(b"/announce");
+            bytes.push(0x0);
+            bytes.extend(url_data_option(b"/ignored"));
+
+            assert_eq!(Options::parse(&bytes).path_and_query(), Some("/announce".to_string()));
+        }
+
+        #[test]
+        fn it_should_skip_options_with_an_unknown_type() {
+            let mut bytes = vec![0x7, 0x2, 0xA, 0xB];
+            bytes.extend(url_data_option(b"/announce"));
+
+            assert_eq!(Options::parse(&bytes).path_and_query(), Some("/announce".to_string()));
+        }
+
+        #[test]
+        fn it_should_ignore_a_truncated_option() {
+            let mut bytes = url_data_option(b"/announce");
+            bytes.extend([0x2, 0xFF, b'/']);
+
+            assert_eq!(Options::parse(&bytes).path_and_query(), Some("/announce".to_string()));
+        }
+    }
+
+    mod getting_the_authentication_key {
+        use crate::core::auth::Key;
+        use crate::servers::udp::extensions::Options;
+
+        fn options_with_url_data(url_data: &str) -> Options {
+            Options {
+                url_data: url_data.as_bytes().to_vec(),
+            }
+        }
+
+        #[test]
+        fn it_should_be_the_path_segment_after_announce() {
+            let options = options_with_url_data("/announce/YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ");
+
+            assert_eq!(
+                options.auth_key().unwrap().unwrap(),
+                Key::new("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap()
+            );
+        }
+
+        #[test]
+        fn it_should_be_the_last_path_segment_when_the_path_does_not_start_with_announce() {
+            let options = options_with_url_data("/YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ?info=1");
+
+            assert_eq!(
+                options.auth_key().unwrap().unwrap(),
+                Key::new("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap()
+            );
+        }
+
+        #[test]
+        fn it_should_be_missing_when_there_is_no_url_data() {
+            assert!(Options::default().auth_key().is_none());
+        }
+
+        #[test]
+        fn it_should_be_missing_when_the_path_only_contains_announce() {
+            assert!(options_with_url_data("/announce?a=1").auth_key().is_none());
+        }
+
+        #[test]
+        fn it_should_fail_when_the_key_is_invalid() {
+            assert!(options_with_url_data("/announce/INVALID").auth_key().unwrap().is_err());
+        }
+    }
+}
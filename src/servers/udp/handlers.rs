@@ -1,6 +1,6 @@
 //! Handlers for the UDP server.
 use std::fmt;
-use std::net::{IpAddr, SocketAddr};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::panic::Location;
 use std::sync::Arc;
 use std::time::Instant;
@@ -10,6 +10,7 @@ use aquatic_udp_protocol::{
     ErrorResponse, Ipv4AddrBytes, Ipv6AddrBytes, NumberOfDownloads, NumberOfPeers, Port, Request, Response, ResponsePeer,
     ScrapeRequest, ScrapeResponse, TorrentScrapeStatistics, TransactionId,
 };
+use torrust_tracker_configuration::OnEarlyAnnounce;
 use torrust_tracker_located_error::DynError;
 use torrust_tracker_primitives::info_hash::InfoHash;
 use tracing::{instrument, Level};
@@ -18,12 +19,19 @@ use zerocopy::network_endian::I32;
 
 use super::connection_cookie::{check, from_connection_id, into_connection_id, make};
 use super::RawRequest;
-use crate::core::{statistics, PeersWanted, ScrapeData, Tracker};
+use crate::core::rate_limiter::Request as RateLimitedRequest;
+use crate::core::{self, statistics, PeersWanted, ScrapeData, Tracker};
 use crate::servers::udp::error::Error;
 use crate::servers::udp::logging::{log_bad_request, log_error_response, log_request, log_response};
-use crate::servers::udp::peer_builder;
+use crate::servers::udp::{extensions, peer_builder};
 use crate::shared::bit_torrent::common::MAX_SCRAPE_TORRENTS;
 
+/// The `action` field of the `announce` requests.
+const ANNOUNCE_ACTION: i32 = 1;
+
+/// The `action` field of the `scrape` requests.
+const SCRAPE_ACTION: i32 = 2;
+
 /// It handles the incoming UDP packets.
 ///
 /// It's responsible for:
@@ -55,7 +63,7 @@ pub(crate) async fn handle_packet(udp_request: RawRequest, tracker: &Tracker, lo
                 Request::Scrape(scrape_request) => scrape_request.transaction_id,
             };
 
-            let response = match handle_request(request, udp_request.from, tracker).await {
+            let response = match handle_request(request, &udp_request.payload, udp_request.from, tracker).await {
                 Ok(response) => response,
                 Err(e) => handle_error(&e, transaction_id),
             };
@@ -85,16 +93,27 @@ pub(crate) async fn handle_packet(udp_request: RawRequest, tracker: &Tracker, lo
 
 /// It dispatches the request to the correct handler.
 ///
+/// The raw `payload` is needed to read the [BEP 41](https://www.bittorrent.org/beps/bep_0041.html)
+/// options appended to the `announce` requests.
+///
 /// # Errors
 ///
 /// If a error happens in the `handle_request` function, it will just return the  `ServerError`.
-#[instrument(skip(request, remote_addr, tracker))]
-pub async fn handle_request(request: Request, remote_addr: SocketAddr, tracker: &Tracker) -> Result<Response, Error> {
+#[instrument(skip(request, payload, remote_addr, tracker))]
+pub async fn handle_request(
+    request: Request,
+    payload: &[u8],
+    remote_addr: SocketAddr,
+    tracker: &Tracker,
+) -> Result<Response, Error> {
     tracing::trace!("handle request");
 
     match request {
         Request::Connect(connect_request) => handle_connect(remote_addr, &connect_request, tracker).await,
-        Request::Announce(announce_request) => handle_announce(remote_addr, &announce_request, tracker).await,
+        Request::Announce(announce_request) => {
+            let options = extensions::Options::from_announce_packet(payload);
+            handle_announce(remote_addr, &announce_request, &options, tracker).await
+        }
         Request::Scrape(scrape_request) => handle_scrape(remote_addr, &scrape_request, tracker).await,
     }
 }
@@ -133,6 +152,10 @@ pub async fn handle_connect(remote_addr: SocketAddr, request: &ConnectRequest, t
 /// It handles the `Announce` request. Refer to [`Announce`](crate::servers::udp#announce)
 /// request for more information.
 ///
+/// When the tracker is private, the authentication key is taken from the
+/// [BEP 41](https://www.bittorrent.org/beps/bep_0041.html) `URLData` option.
+/// Refer to [`extensions`] for more information.
+///
 /// # Errors
 ///
 /// If a error happens in the `handle_announce` function, it will just return the  `ServerError`.
@@ -140,18 +163,41 @@ pub async fn handle_connect(remote_addr: SocketAddr, request: &ConnectRequest, t
 pub async fn handle_announce(
     remote_addr: SocketAddr,
     announce_request: &AnnounceRequest,
+    options: &extensions::Options,
     tracker: &Tracker,
 ) -> Result<Response, Error> {
     tracing::trace!("handle announce");
 
+    check(&remote_addr, &from_connection_id(&announce_request.connection_id))?;
+
+    check_rate_limit(remote_addr, RateLimitedRequest::Announce, tracker).await?;
+
+    authorize_client_ip(remote_addr, tracker).await?;
+
     // Authentication
-    if tracker.requires_authentication() {
-        return Err(Error::TrackerAuthenticationRequired {
-            location: Location::caller(),
-        });
-    }
+    let maybe_key = if tracker.requires_authentication() {
+        let key = match options.auth_key() {
+            Some(Ok(key)) => key,
+            Some(Err(e)) => {
+                return Err(Error::InvalidAuthenticationKey {
+                    source: (Arc::new(e) as DynError).into(),
+                })
+            }
+            None => {
+                return Err(Error::TrackerAuthenticationRequired {
+                    location: Location::caller(),
+                })
+            }
+        };
 
-    check(&remote_addr, &from_connection_id(&announce_request.connection_id))?;
+        tracker.authenticate(&key).await.map_err(|e| Error::AuthenticationFailed {
+            source: (Arc::new(e) as DynError).into(),
+        })?;
+
+        Some(key)
+    } else {
+        None
+    };
 
     let info_hash = announce_request.info_hash.into();
     let remote_client_ip = remote_addr.ip();
@@ -161,20 +207,55 @@ pub async fn handle_announce(
         source: (Arc::new(e) as Arc<dyn std::error::Error + Send + Sync>).into(),
     })?;
 
-    let mut peer = peer_builder::from_request(announce_request, &remote_client_ip);
+    let client_supplied_ip = match Ipv4Addr::from(announce_request.ip_address) {
+        ip if ip.is_unspecified() => None,
+        ip => Some(IpAddr::V4(ip)),
+    };
+    let peer_ip = tracker.resolve_peer_ip(&remote_client_ip, client_supplied_ip);
+
+    let mut peer = peer_builder::from_request(announce_request, &peer_ip);
     let peers_wanted: PeersWanted = i32::from(announce_request.peers_wanted.0).into();
 
-    let response = tracker.announce(&info_hash, &mut peer, &remote_client_ip, &peers_wanted);
+    let result = tracker.announce(&info_hash, &mut peer, &peer_ip, &peers_wanted);
+
+    let (early, counted) = match &result {
+        Ok(announce_data) => match announce_data.early_announce {
+            None => (false, true),
+            Some(OnEarlyAnnounce::Accept) => (true, true),
+            Some(_) => (true, false),
+        },
+        Err(core::error::Error::AnnounceTooEarly { .. }) => (true, false),
+        Err(_) => (false, false),
+    };
 
     match remote_client_ip {
         IpAddr::V4(_) => {
-            tracker.send_stats_event(statistics::Event::Udp4Announce).await;
+            if early {
+                tracker.send_stats_event(statistics::Event::Udp4EarlyAnnounce).await;
+            }
+            if counted {
+                tracker.send_stats_event(statistics::Event::Udp4Announce).await;
+            }
         }
         IpAddr::V6(_) => {
-            tracker.send_stats_event(statistics::Event::Udp6Announce).await;
+            if early {
+                tracker.send_stats_event(statistics::Event::Udp6EarlyAnnounce).await;
+            }
+            if counted {
+                tracker.send_stats_event(statistics::Event::Udp6Announce).await;
+            }
         }
     }
 
+    let response = result.map_err(|e| Error::TrackerError {
+        source: (Arc::new(e) as Arc<dyn std::error::Error + Send + Sync>).into(),
+    })?;
+
+    // Accounting
+    if let Some(key) = &maybe_key {
+        tracker.record_key_traffic(key, &info_hash, &peer);
+    }
+
     #[allow(clippy::cast_possible_truncation)]
     if remote_addr.is_ipv4() {
         let announce_response = AnnounceResponse {
@@ -187,15 +268,12 @@ pub async fn handle_announce(
             peers: response
                 .peers
                 .iter()
-                .filter_map(|peer| {
-                    if let IpAddr::V4(ip) = peer.peer_addr.ip() {
-                        Some(ResponsePeer::<Ipv4AddrBytes> {
-                            ip_address: ip.into(),
-                            port: Port(peer.peer_addr.port().into()),
-                        })
-                    } else {
-                        None
-                    }
+                .filter_map(|peer| match peer.addr_in_family_of(&remote_client_ip) {
+                    Some(SocketAddr::V4(peer_addr)) => Some(ResponsePeer::<Ipv4AddrBytes> {
+                        ip_address: (*peer_addr.ip()).into(),
+                        port: Port(peer_addr.port().into()),
+                    }),
+                    _ => None,
                 })
                 .collect(),
         };
@@ -212,15 +290,12 @@ pub async fn handle_announce(
             peers: response
                 .peers
                 .iter()
-                .filter_map(|peer| {
-                    if let IpAddr::V6(ip) = peer.peer_addr.ip() {
-                        Some(ResponsePeer::<Ipv6AddrBytes> {
-                            ip_address: ip.into(),
-                            port: Port(peer.peer_addr.port().into()),
-                        })
-                    } else {
-                        None
-                    }
+                .filter_map(|peer| match peer.addr_in_family_of(&remote_client_ip) {
+                    Some(SocketAddr::V6(peer_addr)) => Some(ResponsePeer::<Ipv6AddrBytes> {
+                        ip_address: (*peer_addr.ip()).into(),
+                        port: Port(peer_addr.port().into()),
+                    }),
+                    _ => None,
                 })
                 .collect(),
         };
@@ -234,11 +309,20 @@ pub async fn handle_announce(
 ///
 /// # Errors
 ///
-/// This function does not ever return an error.
+/// Will return an error if the connection id is not valid, the client has
+/// exceeded its `scrape` budget in the tracker
+/// [rate limiter](crate::core::rate_limiter) or the client IP address is not
+/// allowed by the tracker [IP access list](crate::core::ip_access_list).
 #[instrument(skip(tracker), err, ret(level = Level::TRACE))]
 pub async fn handle_scrape(remote_addr: SocketAddr, request: &ScrapeRequest, tracker: &Tracker) -> Result<Response, Error> {
     tracing::trace!("handle scrape");
 
+    check(&remote_addr, &from_connection_id(&request.connection_id))?;
+
+    check_rate_limit(remote_addr, RateLimitedRequest::Scrape, tracker).await?;
+
+    authorize_client_ip(remote_addr, tracker).await?;
+
     // Convert from aquatic infohashes
     let mut info_hashes: Vec<InfoHash> = vec![];
     for info_hash in &request.info_hashes {
@@ -286,6 +370,95 @@ pub async fn handle_scrape(remote_addr: SocketAddr, request: &ScrapeRequest, tra
     Ok(Response::from(response))
 }
 
+/// It checks if the client IP is allowed by the tracker IP access list.
+///
+/// Blocked requests are counted in the tracker statistics.
+async fn authorize_client_ip(remote_addr: SocketAddr, tracker: &Tracker) -> Result<(), Error> {
+    if let Err(e) = tracker.authorize_client_ip(&remote_addr.ip()).await {
+        match remote_addr {
+            SocketAddr::V4(_) => {
+                tracker.send_stats_event(statistics::Event::Udp4RequestBlocked).await;
+            }
+            SocketAddr::V6(_) => {
+                tracker.send_stats_event(statistics::Event::Udp6RequestBlocked).await;
+            }
+        }
+
+        return Err(Error::TrackerError {
+            source: (Arc::new(e) as Arc<dyn std::error::Error + Send + Sync>).into(),
+        });
+    }
+
+    Ok(())
+}
+
+/// It checks if the client has not exceeded its `connect` budget in the
+/// tracker [rate limiter](crate::core::rate_limiter).
+///
+/// The check runs before the packet is parsed, so malformed packets are also
+/// charged to the `connect` budget. Packets with the `announce` or `scrape`
+/// action are not charged here: their handlers charge them once the connection
+/// id has been validated, so a client can't spend the budget of another client
+/// by spoofing its source address.
+///
+/// Rejected packets are counted in the tracker statistics. They must be
+/// dropped without a response, because the source address has not been
+/// validated yet.
+pub(crate) async fn is_within_connect_rate_limit(udp_request: &RawRequest, tracker: &Tracker) -> bool {
+    if matches!(peek_action(&udp_request.payload), Some(ANNOUNCE_ACTION | SCRAPE_ACTION)) {
+        return true;
+    }
+
+    if tracker
+        .check_rate_limit(&udp_request.from.ip(), RateLimitedRequest::Connect)
+        .is_ok()
+    {
+        return true;
+    }
+
+    send_rate_limited_event(udp_request.from, tracker).await;
+
+    false
+}
+
+/// It reads the `action` of the request without parsing the packet.
+///
+/// All the requests start with the 64-bit connection id (or protocol id),
+/// followed by the 32-bit `action`.
+fn peek_action(payload: &[u8]) -> Option<i32> {
+    payload
+        .get(8..12)
+        .map(|bytes| i32::from_be_bytes(bytes.try_into().expect("it should be a 4 bytes slice")))
+}
+
+/// It checks if the client has not exceeded its budget for the type of request
+/// in the tracker rate limiter.
+///
+/// It must only be called once the connection id has been validated.
+/// Rejected requests are counted in the tracker statistics.
+async fn check_rate_limit(remote_addr: SocketAddr, request: RateLimitedRequest, tracker: &Tracker) -> Result<(), Error> {
+    if let Err(e) = tracker.check_rate_limit(&remote_addr.ip(), request) {
+        send_rate_limited_event(remote_addr, tracker).await;
+
+        return Err(Error::TrackerError {
+            source: (Arc::new(e) as Arc<dyn std::error::Error + Send + Sync>).into(),
+        });
+    }
+
+    Ok(())
+}
+
+async fn send_rate_limited_event(remote_addr: SocketAddr, tracker: &Tracker) {
+    match remote_addr {
+        SocketAddr::V4(_) => {
+            tracker.send_stats_event(statistics::Event::Udp4RequestRateLimited).await;
+        }
+        SocketAddr::V6(_) => {
+            tracker.send_stats_event(statistics::Event::Udp6RequestRateLimited).await;
+        }
+    }
+}
+
 fn handle_error(e: &Error, transaction_id: TransactionId) -> Response {
     let message = e.to_string();
     Response::from(ErrorResponse {
@@ -316,7 +489,7 @@ mod tests {
     use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
     use std::sync::Arc;
 
-    use aquatic_udp_protocol::{NumberOfBytes, PeerId};
+    use aquatic_udp_protocol::{NumberOfBytes, PeerId, PeerKey};
     use torrust_tracker_clock::clock::Time;
     use torrust_tracker_configuration::Configuration;
     use torrust_tracker_primitives::peer;
@@ -394,6 +567,12 @@ mod tests {
             self
         }
 
+        #[must_use]
+        pub fn with_key(mut self, key: PeerKey) -> Self {
+            self.peer.key = Some(key);
+            self
+        }
+
         #[must_use]
         pub fn with_number_of_bytes_left(mut self, left: i64) -> Self {
             self.peer.left = NumberOfBytes::new(left);
@@ -603,6 +782,11 @@ mod tests {
                 self
             }
 
+            pub fn with_key(mut self, key: i32) -> Self {
+                self.request.key = PeerKey::new(key);
+                self
+            }
+
             pub fn into(self) -> AnnounceRequest {
                 self.request
             }
@@ -616,15 +800,19 @@ mod tests {
 
             use aquatic_udp_protocol::{
                 AnnounceInterval, AnnounceResponse, InfoHash as AquaticInfoHash, Ipv4AddrBytes, Ipv6AddrBytes, NumberOfPeers,
-                PeerId as AquaticPeerId, Response, ResponsePeer,
+                PeerId as AquaticPeerId, Port, Response, ResponsePeer,
             };
             use mockall::predicate::eq;
+            use torrust_tracker_configuration::{ClientSuppliedIp, OnPeerIdentityMismatch};
+            use torrust_tracker_primitives::peer;
+            use torrust_tracker_test_helpers::configuration;
 
             use crate::core::{self, statistics};
             use crate::servers::udp::connection_cookie::{into_connection_id, make};
+            use crate::servers::udp::extensions;
             use crate::servers::udp::handlers::tests::announce_request::AnnounceRequestBuilder;
             use crate::servers::udp::handlers::tests::{
-                public_tracker, sample_ipv4_socket_address, tracker_configuration, TorrentPeerBuilder,
+                initialized_tracker, public_tracker, sample_ipv4_socket_address, tracker_configuration, TorrentPeerBuilder,
             };
             use crate::servers::udp::handlers::{handle_announce, AnnounceResponseFixedData};
 
@@ -647,12 +835,15 @@ mod tests {
                     .with_port(client_port)
                     .into();
 
-                handle_announce(remote_addr, &request, &tracker).await.unwrap();
+                handle_announce(remote_addr, &request, &extensions::Options::default(), &tracker)
+                    .await
+                    .unwrap();
 
                 let peers = tracker.get_torrent_peers(&info_hash.0.into());
 
                 let expected_peer = TorrentPeerBuilder::new()
                     .with_peer_id(peer_id)
+                    .with_key(request.key)
                     .with_peer_address(SocketAddr::new(IpAddr::V4(client_ip), client_port))
                     .into();
 
@@ -667,7 +858,9 @@ mod tests {
                     .with_connection_id(into_connection_id(&make(&remote_addr)))
                     .into();
 
-                let response = handle_announce(remote_addr, &request, &public_tracker()).await.unwrap();
+                let response = handle_announce(remote_addr, &request, &extensions::Options::default(), &public_tracker())
+                    .await
+                    .unwrap();
 
                 let empty_peer_vector: Vec<ResponsePeer<Ipv4AddrBytes>> = vec![];
                 assert_eq!(
@@ -710,13 +903,45 @@ mod tests {
                     .with_port(client_port)
                     .into();
 
-                handle_announce(remote_addr, &request, &tracker).await.unwrap();
+                handle_announce(remote_addr, &request, &extensions::Options::default(), &tracker)
+                    .await
+                    .unwrap();
 
                 let peers = tracker.get_torrent_peers(&info_hash.0.into());
 
                 assert_eq!(peers[0].peer_addr, SocketAddr::new(IpAddr::V4(remote_client_ip), client_port));
             }
 
+            #[tokio::test]
+            async fn the_tracker_should_use_the_peer_address_in_the_announce_request_when_the_remote_client_is_in_a_trusted_network(
+            ) {
+                let mut configuration = configuration::ephemeral_public();
+                configuration.core.net.client_supplied_ip = ClientSuppliedIp::TrustedNetworks;
+                configuration.core.net.trusted_networks = vec!["126.0.0.0/24".parse().unwrap()];
+                let tracker = initialized_tracker(&configuration);
+
+                let info_hash = AquaticInfoHash([0u8; 20]);
+                let client_port = 8080;
+                let peer_address = Ipv4Addr::new(126, 0, 1, 2);
+
+                let remote_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8081);
+
+                let request = AnnounceRequestBuilder::default()
+                    .with_connection_id(into_connection_id(&make(&remote_addr)))
+                    .with_info_hash(info_hash)
+                    .with_ip_address(peer_address)
+                    .with_port(client_port)
+                    .into();
+
+                handle_announce(remote_addr, &request, &extensions::Options::default(), &tracker)
+                    .await
+                    .unwrap();
+
+                let peers = tracker.get_torrent_peers(&info_hash.0.into());
+
+                assert_eq!(peers[0].peer_addr, SocketAddr::new(IpAddr::V4(peer_address), client_port));
+            }
+
             fn add_a_torrent_peer_using_ipv6(tracker: &Arc<core::Tracker>) {
                 let info_hash = AquaticInfoHash([0u8; 20]);
 
@@ -739,7 +964,9 @@ mod tests {
                     .with_connection_id(into_connection_id(&make(&remote_addr)))
                     .into();
 
-                handle_announce(remote_addr, &request, &tracker).await.unwrap()
+                handle_announce(remote_addr, &request, &extensions::Options::default(), &tracker)
+                    .await
+                    .unwrap()
             }
 
             #[tokio::test]
@@ -759,6 +986,37 @@ mod tests {
                 assert!(no_ipv6_peers);
             }
 
+            #[tokio::test]
+            async fn when_the_announce_request_comes_from_a_client_using_ipv4_the_response_should_include_the_ipv4_address_of_dual_stack_peers(
+            ) {
+                let tracker = public_tracker();
+
+                let info_hash = AquaticInfoHash([0u8; 20]);
+                let peer_ipv4_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2)), 8080);
+
+                let mut dual_stack_peer: peer::Peer = TorrentPeerBuilder::new()
+                    .with_peer_id(AquaticPeerId([1u8; 20]))
+                    .with_peer_address(SocketAddr::new(IpAddr::V6("2001:db8::1".parse().unwrap()), 8080))
+                    .into();
+                dual_stack_peer.change_alt_addr(Some(peer_ipv4_addr));
+
+                tracker.upsert_peer_and_get_stats(&info_hash.0.into(), &dual_stack_peer);
+
+                let response = announce_a_new_peer_using_ipv4(tracker.clone()).await;
+
+                let peers: Option<Vec<ResponsePeer<Ipv4AddrBytes>>> = match response {
+                    Response::AnnounceIpv4(announce_response) => Some(announce_response.peers),
+                    _ => None,
+                };
+                assert_eq!(
+                    peers,
+                    Some(vec![ResponsePeer::<Ipv4AddrBytes> {
+                        ip_address: Ipv4Addr::new(126, 0, 0, 2).into(),
+                        port: Port(8080.into()),
+                    }])
+                );
+            }
+
             #[tokio::test]
             async fn should_send_the_upd4_announce_event() {
                 let mut stats_event_sender_mock = statistics::MockEventSender::new();
@@ -781,10 +1039,51 @@ mod tests {
                 handle_announce(
                     sample_ipv4_socket_address(),
                     &AnnounceRequestBuilder::default().into(),
+                    &extensions::Options::default(),
+                    &tracker,
+                )
+                .await
+                .unwrap();
+            }
+
+            #[tokio::test]
+            async fn should_not_send_the_udp4_early_announce_event_when_the_announce_is_rejected_for_a_peer_identity_mismatch() {
+                let mut stats_event_sender_mock = statistics::MockEventSender::new();
+                stats_event_sender_mock
+                    .expect_send_event()
+                    .with(eq(statistics::Event::Udp4Announce))
+                    .times(1)
+                    .returning(|_| Box::pin(future::ready(Some(Ok(())))));
+                stats_event_sender_mock
+                    .expect_send_event()
+                    .with(eq(statistics::Event::Udp4EarlyAnnounce))
+                    .never();
+                let stats_event_sender = Box::new(stats_event_sender_mock);
+
+                let mut configuration = tracker_configuration();
+                configuration.core.on_peer_identity_mismatch = OnPeerIdentityMismatch::Reject;
+
+                let tracker =
+                    Arc::new(core::Tracker::new(&configuration.core, Some(stats_event_sender), statistics::Repo::new()).unwrap());
+
+                handle_announce(
+                    sample_ipv4_socket_address(),
+                    &AnnounceRequestBuilder::default().with_key(1).into(),
+                    &extensions::Options::default(),
                     &tracker,
                 )
                 .await
                 .unwrap();
+
+                let result = handle_announce(
+                    sample_ipv4_socket_address(),
+                    &AnnounceRequestBuilder::default().with_key(2).into(),
+                    &extensions::Options::default(),
+                    &tracker,
+                )
+                .await;
+
+                assert!(result.is_err());
             }
 
             mod from_a_loopback_ip {
@@ -794,6 +1093,7 @@ mod tests {
                 use aquatic_udp_protocol::{InfoHash as AquaticInfoHash, PeerId as AquaticPeerId};
 
                 use crate::servers::udp::connection_cookie::{into_connection_id, make};
+                use crate::servers::udp::extensions;
                 use crate::servers::udp::handlers::handle_announce;
                 use crate::servers::udp::handlers::tests::announce_request::AnnounceRequestBuilder;
                 use crate::servers::udp::handlers::tests::{public_tracker, TorrentPeerBuilder};
@@ -817,7 +1117,9 @@ mod tests {
                         .with_port(client_port)
                         .into();
 
-                    handle_announce(remote_addr, &request, &tracker).await.unwrap();
+                    handle_announce(remote_addr, &request, &extensions::Options::default(), &tracker)
+                        .await
+                        .unwrap();
 
                     let peers = tracker.get_torrent_peers(&info_hash.0.into());
 
@@ -825,6 +1127,7 @@ mod tests {
 
                     let expected_peer = TorrentPeerBuilder::new()
                         .with_peer_id(peer_id)
+                        .with_key(request.key)
                         .with_peer_address(SocketAddr::new(external_ip_in_tracker_configuration, client_port))
                         .into();
 
@@ -847,6 +1150,7 @@ mod tests {
 
             use crate::core::{self, statistics};
             use crate::servers::udp::connection_cookie::{into_connection_id, make};
+            use crate::servers::udp::extensions;
             use crate::servers::udp::handlers::tests::announce_request::AnnounceRequestBuilder;
             use crate::servers::udp::handlers::tests::{
                 public_tracker, sample_ipv6_remote_addr, tracker_configuration, TorrentPeerBuilder,
@@ -873,12 +1177,15 @@ mod tests {
                     .with_port(client_port)
                     .into();
 
-                handle_announce(remote_addr, &request, &tracker).await.unwrap();
+                handle_announce(remote_addr, &request, &extensions::Options::default(), &tracker)
+                    .await
+                    .unwrap();
 
                 let peers = tracker.get_torrent_peers(&info_hash.0.into());
 
                 let expected_peer = TorrentPeerBuilder::new()
                     .with_peer_id(peer_id)
+                    .with_key(request.key)
                     .with_peer_address(SocketAddr::new(IpAddr::V6(client_ip_v6), client_port))
                     .into();
 
@@ -896,7 +1203,9 @@ mod tests {
                     .with_connection_id(into_connection_id(&make(&remote_addr)))
                     .into();
 
-                let response = handle_announce(remote_addr, &request, &public_tracker()).await.unwrap();
+                let response = handle_announce(remote_addr, &request, &extensions::Options::default(), &public_tracker())
+                    .await
+                    .unwrap();
 
                 let empty_peer_vector: Vec<ResponsePeer<Ipv6AddrBytes>> = vec![];
                 assert_eq!(
@@ -939,7 +1248,9 @@ mod tests {
                     .with_port(client_port)
                     .into();
 
-                handle_announce(remote_addr, &request, &tracker).await.unwrap();
+                handle_announce(remote_addr, &request, &extensions::Options::default(), &tracker)
+                    .await
+                    .unwrap();
 
                 let peers = tracker.get_torrent_peers(&info_hash.0.into());
 
@@ -971,7 +1282,9 @@ mod tests {
                     .with_connection_id(into_connection_id(&make(&remote_addr)))
                     .into();
 
-                handle_announce(remote_addr, &request, &tracker).await.unwrap()
+                handle_announce(remote_addr, &request, &extensions::Options::default(), &tracker)
+                    .await
+                    .unwrap()
             }
 
             #[tokio::test]
@@ -1016,7 +1329,9 @@ mod tests {
                     .with_connection_id(into_connection_id(&make(&remote_addr)))
                     .into();
 
-                handle_announce(remote_addr, &announce_request, &tracker).await.unwrap();
+                handle_announce(remote_addr, &announce_request, &extensions::Options::default(), &tracker)
+                    .await
+                    .unwrap();
             }
 
             mod from_a_loopback_ip {
@@ -1028,6 +1343,7 @@ mod tests {
                 use crate::core;
                 use crate::core::statistics::Keeper;
                 use crate::servers::udp::connection_cookie::{into_connection_id, make};
+                use crate::servers::udp::extensions;
                 use crate::servers::udp::handlers::handle_announce;
                 use crate::servers::udp::handlers::tests::announce_request::AnnounceRequestBuilder;
                 use crate::servers::udp::handlers::tests::TrackerConfigurationBuilder;
@@ -1059,7 +1375,9 @@ mod tests {
                         .with_port(client_port)
                         .into();
 
-                    handle_announce(remote_addr, &request, &tracker).await.unwrap();
+                    handle_announce(remote_addr, &request, &extensions::Options::default(), &tracker)
+                        .await
+                        .unwrap();
 
                     let peers = tracker.get_torrent_peers(&info_hash.0.into());
 
@@ -1076,6 +1394,126 @@ mod tests {
                 }
             }
         }
+        mod with_a_private_tracker {
+            use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+            use std::time::Duration;
+
+            use aquatic_udp_protocol::InfoHash as AquaticInfoHash;
+
+            use crate::servers::udp::connection_cookie::{into_connection_id, make};
+            use crate::servers::udp::error::Error;
+            use crate::servers::udp::extensions;
+            use crate::servers::udp::handlers::handle_announce;
+            use crate::servers::udp::handlers::tests::announce_request::AnnounceRequestBuilder;
+            use crate::servers::udp::handlers::tests::private_tracker;
+
+            fn sample_remote_addr() -> SocketAddr {
+                SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080)
+            }
+
+            fn options_with_url_data(url_data: &str) -> extensions::Options {
+                extensions::Options {
+                    url_data: url_data.as_bytes().to_vec(),
+                }
+            }
+
+            #[tokio::test]
+            async fn it_should_fail_when_the_authentication_key_is_missing() {
+                let tracker = private_tracker();
+
+                let remote_addr = sample_remote_addr();
+                let request = AnnounceRequestBuilder::default()
+                    .with_connection_id(into_connection_id(&make(&remote_addr)))
+                    .into();
+
+                let response = handle_announce(remote_addr, &request, &extensions::Options::default(), &tracker).await;
+
+                assert!(matches!(response, Err(Error::TrackerAuthenticationRequired { .. })));
+            }
+
+            #[tokio::test]
+            async fn it_should_fail_when_the_authentication_key_has_an_invalid_format() {
+                let tracker = private_tracker();
+
+                let remote_addr = sample_remote_addr();
+                let request = AnnounceRequestBuilder::default()
+                    .with_connection_id(into_connection_id(&make(&remote_addr)))
+                    .into();
+
+                let response =
+                    handle_announce(remote_addr, &request, &options_with_url_data("/announce/INVALID"), &tracker).await;
+
+                assert!(matches!(response, Err(Error::InvalidAuthenticationKey { .. })));
+            }
+
+            #[tokio::test]
+            async fn it_should_fail_when_the_authentication_key_is_not_registered() {
+                let tracker = private_tracker();
+
+                let remote_addr = sample_remote_addr();
+                let request = AnnounceRequestBuilder::default()
+                    .with_connection_id(into_connection_id(&make(&remote_addr)))
+                    .into();
+
+                let unregistered_key = "/announce/YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ";
+
+                let response = handle_announce(remote_addr, &request, &options_with_url_data(unregistered_key), &tracker).await;
+
+                assert!(matches!(response, Err(Error::AuthenticationFailed { .. })));
+            }
+
+            #[tokio::test]
+            async fn it_should_add_the_peer_when_the_authentication_key_is_valid() {
+                let tracker = private_tracker();
+
+                let peer_key = tracker.generate_auth_key(Some(Duration::from_secs(100))).await.unwrap();
+
+                let remote_addr = sample_remote_addr();
+                let info_hash = AquaticInfoHash([0u8; 20]);
+                let request = AnnounceRequestBuilder::default()
+                    .with_connection_id(into_connection_id(&make(&remote_addr)))
+                    .with_info_hash(info_hash)
+                    .into();
+
+                let options = options_with_url_data(&format!("/announce/{}", peer_key.key()));
+
+                handle_announce(remote_addr, &request, &options, &tracker).await.unwrap();
+
+                assert_eq!(tracker.get_torrent_peers(&info_hash.0.into()).len(), 1);
+            }
+        }
+
+        mod from_a_client_ip_that_is_not_allowed {
+            use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+            use crate::core::ip_access_list::{parse_network, Policy, Rule};
+            use crate::servers::udp::connection_cookie::{into_connection_id, make};
+            use crate::servers::udp::error::Error;
+            use crate::servers::udp::extensions;
+            use crate::servers::udp::handlers::handle_announce;
+            use crate::servers::udp::handlers::tests::announce_request::AnnounceRequestBuilder;
+            use crate::servers::udp::handlers::tests::public_tracker;
+
+            #[tokio::test]
+            async fn it_should_fail_and_not_add_the_peer() {
+                let tracker = public_tracker();
+
+                tracker
+                    .add_ip_access_rule(&Rule::new(parse_network("126.0.0.0/24").unwrap(), Policy::Deny))
+                    .await
+                    .unwrap();
+
+                let remote_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080);
+                let request = AnnounceRequestBuilder::default()
+                    .with_connection_id(into_connection_id(&make(&remote_addr)))
+                    .into();
+
+                let response = handle_announce(remote_addr, &request, &extensions::Options::default(), &tracker).await;
+
+                assert!(matches!(response, Err(Error::TrackerError { .. })));
+                assert_eq!(tracker.get_torrents_metrics().torrents, 0);
+            }
+        }
     }
 
     mod scrape_request {
@@ -1295,9 +1733,11 @@ mod tests {
             use std::future;
             use std::sync::Arc;
 
+            use ipnet::IpNet;
             use mockall::predicate::eq;
 
             use super::sample_scrape_request;
+            use crate::core::ip_access_list::{Policy, Rule};
             use crate::core::{self, statistics};
             use crate::servers::udp::handlers::handle_scrape;
             use crate::servers::udp::handlers::tests::{sample_ipv4_remote_addr, tracker_configuration};
@@ -1326,6 +1766,36 @@ mod tests {
                     .await
                     .unwrap();
             }
+
+            #[tokio::test]
+            async fn should_send_the_upd4_request_blocked_event_when_the_client_ip_is_not_allowed() {
+                let mut stats_event_sender_mock = statistics::MockEventSender::new();
+                stats_event_sender_mock
+                    .expect_send_event()
+                    .with(eq(statistics::Event::Udp4RequestBlocked))
+                    .times(1)
+                    .returning(|_| Box::pin(future::ready(Some(Ok(())))));
+                let stats_event_sender = Box::new(stats_event_sender_mock);
+
+                let remote_addr = sample_ipv4_remote_addr();
+                let tracker = Arc::new(
+                    core::Tracker::new(
+                        &tracker_configuration().core,
+                        Some(stats_event_sender),
+                        statistics::Repo::new(),
+                    )
+                    .unwrap(),
+                );
+
+                tracker
+                    .add_ip_access_rule(&Rule::new(IpNet::from(remote_addr.ip()), Policy::Deny))
+                    .await
+                    .unwrap();
+
+                let response = handle_scrape(remote_addr, &sample_scrape_request(&remote_addr), &tracker).await;
+
+                assert!(response.is_err());
+            }
         }
 
         mod using_ipv6 {
@@ -1365,4 +1835,125 @@ mod tests {
             }
         }
     }
+
+    mod rate_limiting {
+        use std::future;
+        use std::net::SocketAddr;
+        use std::sync::Arc;
+
+        use aquatic_udp_protocol::{ConnectionId, ScrapeRequest, TransactionId};
+        use mockall::predicate::eq;
+        use torrust_tracker_configuration::v2_0_0::rate_limit::Budget;
+        use torrust_tracker_configuration::RateLimit;
+        use torrust_tracker_test_helpers::configuration;
+
+        use super::sample_ipv4_socket_address;
+        use crate::core::{self, statistics};
+        use crate::servers::udp::connection_cookie::{into_connection_id, make};
+        use crate::servers::udp::handlers::{handle_scrape, is_within_connect_rate_limit};
+        use crate::servers::udp::RawRequest;
+
+        const PROTOCOL_ID: i64 = 0x0417_2710_1980;
+
+        fn packet(action: i32) -> Vec<u8> {
+            let mut payload = PROTOCOL_ID.to_be_bytes().to_vec();
+            payload.extend_from_slice(&action.to_be_bytes());
+            payload.extend_from_slice(&0i32.to_be_bytes());
+            payload
+        }
+
+        fn raw_request(payload: Vec<u8>, from: SocketAddr) -> RawRequest {
+            RawRequest { payload, from }
+        }
+
+        fn scrape_request(connection_id: ConnectionId) -> ScrapeRequest {
+            ScrapeRequest {
+                connection_id,
+                transaction_id: TransactionId::new(0i32),
+                info_hashes: vec![],
+            }
+        }
+
+        fn tracker_with_rate_limit(rate_limit: RateLimit) -> core::Tracker {
+            let configuration = configuration::ephemeral_with_rate_limit(rate_limit);
+            core::Tracker::new(&configuration.core, None, statistics::Repo::new()).unwrap()
+        }
+
+        #[tokio::test]
+        async fn it_should_drop_the_packet_and_send_the_udp4_request_rate_limited_event_when_the_client_exceeds_its_connect_budget(
+        ) {
+            let mut stats_event_sender_mock = statistics::MockEventSender::new();
+            stats_event_sender_mock
+                .expect_send_event()
+                .with(eq(statistics::Event::Udp4RequestRateLimited))
+                .times(1)
+                .returning(|_| Box::pin(future::ready(Some(Ok(())))));
+            let stats_event_sender = Box::new(stats_event_sender_mock);
+
+            let configuration = configuration::ephemeral_with_rate_limit(RateLimit {
+                connect: Budget::new(1, 1),
+                ..Default::default()
+            });
+
+            let tracker = core::Tracker::new(&configuration.core, Some(stats_event_sender), statistics::Repo::new()).unwrap();
+
+            let request = raw_request(packet(0), sample_ipv4_socket_address());
+
+            assert!(is_within_connect_rate_limit(&request, &tracker).await);
+            assert!(!is_within_connect_rate_limit(&request, &tracker).await);
+        }
+
+        #[tokio::test]
+        async fn it_should_charge_the_packets_that_can_not_be_parsed_to_the_connect_budget() {
+            let tracker = tracker_with_rate_limit(RateLimit {
+                connect: Budget::new(1, 1),
+                ..Default::default()
+            });
+
+            let request = raw_request(vec![0; 3], sample_ipv4_socket_address());
+
+            assert!(is_within_connect_rate_limit(&request, &tracker).await);
+            assert!(!is_within_connect_rate_limit(&request, &tracker).await);
+        }
+
+        #[tokio::test]
+        async fn it_should_not_charge_the_announce_and_scrape_packets_before_their_connection_id_is_validated() {
+            let tracker = tracker_with_rate_limit(RateLimit {
+                announce: Budget::new(1, 1),
+                scrape: Budget::new(1, 1),
+                ..Default::default()
+            });
+
+            let announce = raw_request(packet(1), sample_ipv4_socket_address());
+            let scrape = raw_request(packet(2), sample_ipv4_socket_address());
+
+            for _ in 0..3 {
+                assert!(is_within_connect_rate_limit(&announce, &tracker).await);
+                assert!(is_within_connect_rate_limit(&scrape, &tracker).await);
+            }
+        }
+
+        #[tokio::test]
+        async fn it_should_not_charge_the_scrape_budget_when_the_connection_id_is_not_valid() {
+            let tracker = Arc::new(tracker_with_rate_limit(RateLimit {
+                scrape: Budget::new(1, 1),
+                ..Default::default()
+            }));
+
+            let remote_addr = sample_ipv4_socket_address();
+
+            assert!(handle_scrape(remote_addr, &scrape_request(ConnectionId::new(0)), &tracker)
+                .await
+                .is_err());
+
+            let valid_connection_id = into_connection_id(&make(&remote_addr));
+
+            assert!(handle_scrape(remote_addr, &scrape_request(valid_connection_id), &tracker)
+                .await
+                .is_ok());
+            assert!(handle_scrape(remote_addr, &scrape_request(valid_connection_id), &tracker)
+                .await
+                .is_err());
+        }
+    }
 }
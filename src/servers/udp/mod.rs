@@ -21,8 +21,8 @@
 //! and to [BEP 41. UDP Tracker Protocol Extensions](https://www.bittorrent.org/beps/bep_0041.html)
 //! for more information about the UDP tracker protocol.
 //!
-//! > **NOTICE**: [BEP-41](https://www.bittorrent.org/beps/bep_0041.html) is not
-//! > implemented yet.
+//! > **NOTICE**: only the `URLData` option of [BEP-41](https://www.bittorrent.org/beps/bep_0041.html)
+//! > is used. It contains the authentication key when the tracker is private.
 //!
 //! > **NOTICE**: we are using the [`aquatic_udp_protocol`](https://crates.io/crates/aquatic_udp_protocol)
 //! > crate so requests and responses are handled by it.
@@ -221,20 +221,29 @@
 //! 64     | [`i64`](std::i64) | `left`           | The number of bytes left to download by the peer.            | `0x00_00_00_00_00_00_00_00`                                     | `0`
 //! 72     | [`i64`](std::i64) | `uploaded`       | The number of bytes the peer has uploaded so far.            | `0x00_00_00_00_00_00_00_00`                                     | `0`
 //! 80     | [`i32`](std::i32) | `event`          | The event the peer is reporting to the tracker.              | `0x0`, `0x1`, `0x2`, `0x3`                                      | `0`: none; `1`: completed; `2`: started; `3`: stopped
-//! 84     | [`i32`](std::i32) | `IP address`     | The peer IP. Ignored by the tracker. It uses the Sender's IP.| `0x00_00_00_00`                                                 | `0`
+//! 84     | [`i32`](std::i32) | `IP address`     | The peer IP. Ignored by default. It uses the Sender's IP.    | `0x00_00_00_00`                                                 | `0`
 //! 88     | [`i32`](std::i32) | `key`            | A unique key that is randomized by the client.               | `0xEF_34_95_D6`                                                 | `-281766442`
 //! 92     | [`i32`](std::i32) | `num_want`       | The maximum number of peers the peer wants in the response.  | `0x00_00_00_C8`                                                 | `200`
 //! 96     | [`i16`](std::i16) | `port`           | The port the peer is listening on.                           | `0x44_8C`                                                       | `17548`
 //!
+//! **Peer key**
+//!
+//! The tracker keeps the `key` sent by the peer in the announce request. When the
+//! `core.on_peer_identity_mismatch` option is enabled, the following announces
+//! for the same `peer_id` must contain the same `key`. Otherwise, they are
+//! rejected or answered as if they came from a new peer that is not added to
+//! the swarm. This prevents other clients from removing or replacing the peer
+//! with a forged announce.
+//!
 //! **Peer IP address**
 //!
-//! The peer IP address is always ignored by the tracker. It uses the sender's
-//! IP address.
+//! By default, the peer IP address is ignored by the tracker. It uses the
+//! sender's IP address.
 //!
 //! _"Do note that most trackers will only honor the IP address field under
 //! limited circumstances."_ ([BEP 15](https://www.bittorrent.org/beps/bep_0015.html)).
 //!
-//! Although not supported by this tracker a UDP tracker can use the IP address
+//! A UDP tracker can use the IP address
 //! provided by the peer in the announce request under specific circumstances
 //! when it cannot rely on the source IP address of the incoming request. These
 //! circumstances might include:
@@ -267,12 +276,18 @@
 //! risks, as malicious peers might spoof their IP addresses in the announce
 //! request to perform various types of attacks.
 //!
-//! > **NOTICE**: The current tracker behavior is to ignore the IP address
+//! > **NOTICE**: The default tracker behavior is to ignore the IP address
 //! > provided by the peer, and use the source IP address of the incoming request,
 //! > when the tracker is not running behind a proxy, and to use the right-most IP
 //! > address in the `X-Forwarded-For` header when the tracker is running behind a
 //! > proxy.
 //!
+//! > **NOTICE**: The tracker can be configured to use the IP address provided by
+//! > the peer when the request comes from a trusted network, with the
+//! > `core.net.client_supplied_ip` and `core.net.trusted_networks` options. See
+//! > the [`Network`](torrust_tracker_configuration::v2_0_0::network::Network)
+//! > configuration.
+//!
 //! > **NOTICE**: The tracker also changes the peer IP address to the tracker
 //! > external IP when the peer is using a loopback IP address.
 //!
@@ -398,7 +413,9 @@
 //! dynamically generated data with the list of peers in the swarm. The list may
 //! include `IPv4` or `IPv6` peers, depending on the address family of the
 //! underlying UDP packet. I.e. packets from a v4 address use the v4 format,
-//! those from a v6 address use the v6 format.
+//! those from a v6 address use the v6 format. Dual-stack peers, that have
+//! announced an address in each family, are returned with the address in the
+//! same family as the client.
 //!
 //! UDP packet bytes (`IPv4` peer list):
 //!
@@ -619,7 +636,13 @@
 //! ## Extensions
 //!
 //! Extensions described in [BEP 41. UDP Tracker Protocol Extensions](https://www.bittorrent.org/beps/bep_0041.html)
-//! are not supported yet.
+//! are parsed from the end of the `announce` request.
+//!
+//! The `URLData` option is used to pass the authentication key when the
+//! tracker runs in `private` mode. The key is expected in the path, in the same
+//! format the HTTP tracker uses: `udp://tracker.example.com:6969/announce/YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ`.
+//!
+//! Refer to [`extensions`] for more information.
 //!
 //! ## Links
 //!
@@ -640,6 +663,7 @@ use std::net::SocketAddr;
 
 pub mod connection_cookie;
 pub mod error;
+pub mod extensions;
 pub mod handlers;
 pub mod logging;
 pub mod peer_builder;
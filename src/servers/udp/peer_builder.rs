@@ -11,12 +11,16 @@ use crate::CurrentClock;
 ///
 /// # Arguments
 ///
-/// * `peer_ip` - The real IP address of the peer, not the one in the announce request.
+/// * `peer_ip` - The IP address of the peer. It's the IP address of the
+///   client, unless the tracker trusts the one in the announce request.
 #[must_use]
 pub fn from_request(announce_request: &aquatic_udp_protocol::AnnounceRequest, peer_ip: &IpAddr) -> peer::Peer {
     peer::Peer {
         peer_id: announce_request.peer_id,
         peer_addr: SocketAddr::new(*peer_ip, announce_request.port.0.into()),
+        alt_peer_addr: None,
+        key: Some(announce_request.key),
+        crypto: peer::Crypto::NotSupported,
         updated: CurrentClock::now(),
         uploaded: announce_request.bytes_uploaded,
         downloaded: announce_request.bytes_downloaded,
@@ -22,7 +22,18 @@ impl Processor {
     #[instrument(skip(self, request))]
     pub async fn process_request(self, request: RawRequest) {
         let from = request.from;
+
+        // The `connect` budget is enforced before parsing the packet, so that
+        // invalid requests can't be used to flood the tracker. The source
+        // address has not been validated yet, so rejected packets are not
+        // answered.
+        if !handlers::is_within_connect_rate_limit(&request, &self.tracker).await {
+            tracing::debug!(%from, "rate limited packet dropped");
+            return;
+        }
+
         let response = handlers::handle_packet(request, &self.tracker, self.socket.address()).await;
+
         self.send_response(from, response).await;
     }
 
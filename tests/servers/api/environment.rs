@@ -1,9 +1,10 @@
 use std::net::SocketAddr;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
 use futures::executor::block_on;
 use torrust_tracker::bootstrap::app::initialize_with_configuration;
 use torrust_tracker::bootstrap::jobs::make_rust_tls;
+use torrust_tracker::bootstrap::reload::Reloader;
 use torrust_tracker::core::Tracker;
 use torrust_tracker::servers::apis::server::{ApiServer, Launcher, Running, Stopped};
 use torrust_tracker::servers::registar::Registar;
@@ -19,6 +20,10 @@ where
 {
     pub config: Arc<HttpApi>,
     pub tracker: Arc<Tracker>,
+    /// The configuration loaded when the configuration is reloaded. It
+    /// replaces the configuration file in the tests.
+    pub config_source: Arc<RwLock<Configuration>>,
+    pub reloader: Arc<Reloader>,
     pub registar: Registar,
     pub server: ApiServer<S>,
 }
@@ -45,24 +50,35 @@ impl Environment<Stopped> {
 
         let server = ApiServer::new(Launcher::new(bind_to, tls));
 
+        let config_source = Arc::new(RwLock::new(configuration.as_ref().clone()));
+
+        let loader_source = config_source.clone();
+        let reloader = Arc::new(Reloader::new(
+            configuration,
+            tracker.clone(),
+            Box::new(move || Ok(loader_source.read().unwrap().clone())),
+        ));
+
         Self {
             config,
             tracker,
+            config_source,
+            reloader,
             registar: Registar::default(),
             server,
         }
     }
 
     pub async fn start(self) -> Environment<Running> {
-        let access_tokens = Arc::new(self.config.access_tokens.clone());
-
         Environment {
             config: self.config,
             tracker: self.tracker.clone(),
+            config_source: self.config_source,
+            reloader: self.reloader.clone(),
             registar: self.registar.clone(),
             server: self
                 .server
-                .start(self.tracker, self.registar.give_form(), access_tokens)
+                .start(self.tracker, self.registar.give_form(), self.reloader)
                 .await
                 .unwrap(),
         }
@@ -78,6 +94,8 @@ impl Environment<Running> {
         Environment {
             config: self.config,
             tracker: self.tracker,
+            config_source: self.config_source,
+            reloader: self.reloader,
             registar: Registar::default(),
             server: self.server.stop().await.unwrap(),
         }
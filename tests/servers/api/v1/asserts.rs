@@ -1,9 +1,13 @@
 // code-review: should we use macros to return the exact line where the assert fails?
 
 use reqwest::Response;
+use torrust_tracker::servers::apis::v1::context::accounting::resources::KeyTraffic;
 use torrust_tracker::servers::apis::v1::context::auth_key::resources::AuthKey;
-use torrust_tracker::servers::apis::v1::context::stats::resources::Stats;
+use torrust_tracker::servers::apis::v1::context::config::resources::ReloadReport;
+use torrust_tracker::servers::apis::v1::context::ip_access_list::resources::IpAccessRule;
+use torrust_tracker::servers::apis::v1::context::stats::resources::{HistoryPoint, Stats};
 use torrust_tracker::servers::apis::v1::context::torrent::resources::torrent::{ListItem, Torrent};
+use torrust_tracker::servers::apis::v1::context::whitelist::resources::WhitelistedTorrent;
 
 // Resource responses
 
@@ -13,6 +17,48 @@ pub async fn assert_stats(response: Response, stats: Stats) {
     assert_eq!(response.json::<Stats>().await.unwrap(), stats);
 }
 
+pub async fn assert_stats_history(response: Response, expected_points: usize) -> Vec<HistoryPoint> {
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+
+    let points = response.json::<Vec<HistoryPoint>>().await.unwrap();
+
+    assert_eq!(points.len(), expected_points);
+
+    points
+}
+
+pub async fn assert_stats_in_openmetrics_format(response: Response, expected_samples: &[&str]) {
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "application/openmetrics-text; version=1.0.0; charset=utf-8"
+    );
+
+    let text = response.text().await.unwrap();
+
+    for sample in expected_samples {
+        assert!(
+            text.lines().any(|line| line == *sample),
+            "missing sample `{sample}` in:\n{text}"
+        );
+    }
+
+    assert!(text.ends_with("# EOF\n"));
+}
+
+pub async fn assert_reload_report(response: Response, report: ReloadReport) {
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    assert_eq!(response.json::<ReloadReport>().await.unwrap(), report);
+}
+
+pub async fn assert_ip_access_rules(response: Response, rules: Vec<IpAccessRule>) {
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    assert_eq!(response.json::<Vec<IpAccessRule>>().await.unwrap(), rules);
+}
+
 pub async fn assert_torrent_list(response: Response, torrents: Vec<ListItem>) {
     assert_eq!(response.status(), 200);
     assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
@@ -25,6 +71,24 @@ pub async fn assert_torrent_info(response: Response, torrent: Torrent) {
     assert_eq!(response.json::<Torrent>().await.unwrap(), torrent);
 }
 
+pub async fn assert_whitelist(response: Response, torrents: Vec<WhitelistedTorrent>) {
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "application/json; charset=utf-8"
+    );
+    assert_eq!(response.json::<Vec<WhitelistedTorrent>>().await.unwrap(), torrents);
+}
+
+pub async fn assert_whitelisted_torrent(response: Response, torrent: WhitelistedTorrent) {
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "application/json; charset=utf-8"
+    );
+    assert_eq!(response.json::<WhitelistedTorrent>().await.unwrap(), torrent);
+}
+
 pub async fn assert_auth_key_utf8(response: Response) -> AuthKey {
     assert_eq!(response.status(), 200);
     assert_eq!(
@@ -34,6 +98,33 @@ pub async fn assert_auth_key_utf8(response: Response) -> AuthKey {
     response.json::<AuthKey>().await.unwrap()
 }
 
+pub async fn assert_auth_key_list_utf8(response: Response) -> Vec<AuthKey> {
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "application/json; charset=utf-8"
+    );
+    response.json::<Vec<AuthKey>>().await.unwrap()
+}
+
+pub async fn assert_key_traffic(response: Response, key_traffic: KeyTraffic) {
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "application/json; charset=utf-8"
+    );
+    assert_eq!(response.json::<KeyTraffic>().await.unwrap(), key_traffic);
+}
+
+pub async fn assert_keys_traffic(response: Response, keys_traffic: Vec<KeyTraffic>) {
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "application/json; charset=utf-8"
+    );
+    assert_eq!(response.json::<Vec<KeyTraffic>>().await.unwrap(), keys_traffic);
+}
+
 // OK response
 
 pub async fn assert_ok(response: Response) {
@@ -86,6 +177,26 @@ pub async fn assert_torrent_not_known(response: Response) {
     assert_eq!(response.text().await.unwrap(), "\"torrent not known\"");
 }
 
+pub async fn assert_auth_key_not_known(response: Response) {
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    assert_eq!(response.text().await.unwrap(), "\"auth key not known\"");
+}
+
+pub async fn assert_torrent_not_whitelisted(response: Response) {
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    assert_eq!(response.text().await.unwrap(), "\"torrent not whitelisted\"");
+}
+
+pub async fn assert_invalid_infohash(response: Response, invalid_infohash: &str) {
+    assert_bad_request(
+        response,
+        &format!("invalid infohash: string \"{invalid_infohash}\", expected a 40 character long string"),
+    )
+    .await;
+}
+
 pub async fn assert_invalid_infohash_param(response: Response, invalid_infohash: &str) {
     assert_bad_request(
         response,
@@ -130,6 +241,10 @@ pub async fn assert_unauthorized(response: Response) {
     assert_unhandled_rejection(response, "unauthorized").await;
 }
 
+pub async fn assert_scope_not_granted(response: Response, scope: &str) {
+    assert_unhandled_rejection(response, &format!("token does not have the scope {scope}")).await;
+}
+
 pub async fn assert_failed_to_remove_torrent_from_whitelist(response: Response) {
     assert_unhandled_rejection(response, "failed to remove torrent from whitelist").await;
 }
@@ -142,6 +257,18 @@ pub async fn assert_failed_to_reload_whitelist(response: Response) {
     assert_unhandled_rejection(response, "failed to reload whitelist").await;
 }
 
+pub async fn assert_failed_to_add_ip_access_rule(response: Response) {
+    assert_unhandled_rejection(response, "failed to add IP access rule").await;
+}
+
+pub async fn assert_failed_to_remove_ip_access_rule(response: Response) {
+    assert_unhandled_rejection(response, "failed to remove IP access rule").await;
+}
+
+pub async fn assert_failed_to_reload_ip_access_list(response: Response) {
+    assert_unhandled_rejection(response, "failed to reload IP access list").await;
+}
+
 pub async fn assert_failed_to_generate_key(response: Response) {
     assert_unhandled_rejection(response, "failed to generate key").await;
 }
@@ -154,6 +281,14 @@ pub async fn assert_failed_to_reload_keys(response: Response) {
     assert_unhandled_rejection(response, "failed to reload keys").await;
 }
 
+pub async fn assert_failed_to_reload_configuration(response: Response) {
+    assert_unhandled_rejection(response, "failed to reload configuration").await;
+}
+
+pub async fn assert_failed_to_reset_stats(response: Response) {
+    assert_unhandled_rejection(response, "failed to reset statistics").await;
+}
+
 async fn assert_unhandled_rejection(response: Response, reason: &str) {
     assert_eq!(response.status(), 500);
     assert_eq!(response.headers().get("content-type").unwrap(), "text/plain; charset=utf-8");
@@ -26,6 +26,14 @@ impl Client {
         self.post_form("keys", &add_key_form).await
     }
 
+    pub async fn get_auth_key(&self, key: &str) -> Response {
+        self.get(&format!("key/{}", &key), Query::default()).await
+    }
+
+    pub async fn get_auth_keys(&self, params: Query) -> Response {
+        self.get("keys", params).await
+    }
+
     pub async fn delete_auth_key(&self, key: &str) -> Response {
         self.delete(&format!("key/{}", &key)).await
     }
@@ -34,6 +42,30 @@ impl Client {
         self.get("keys/reload", Query::default()).await
     }
 
+    pub async fn get_keys_traffic(&self, params: Query) -> Response {
+        self.get("accounting", params).await
+    }
+
+    pub async fn get_key_traffic(&self, key: &str) -> Response {
+        self.get(&format!("accounting/{}", &key), Query::default()).await
+    }
+
+    pub async fn get_whitelist(&self, params: Query) -> Response {
+        self.get("whitelist", params).await
+    }
+
+    pub async fn get_whitelisted_torrent(&self, info_hash: &str) -> Response {
+        self.get(&format!("whitelist/{}", &info_hash), Query::default()).await
+    }
+
+    pub async fn whitelist_torrents(&self, info_hashes: &[&str]) -> Response {
+        self.post_form("whitelist", info_hashes).await
+    }
+
+    pub async fn remove_torrents_from_whitelist(&self, info_hashes: &[&str]) -> Response {
+        self.delete_json("whitelist", info_hashes).await
+    }
+
     pub async fn whitelist_a_torrent(&self, info_hash: &str) -> Response {
         self.post_empty(&format!("whitelist/{}", &info_hash)).await
     }
@@ -46,6 +78,30 @@ impl Client {
         self.get("whitelist/reload", Query::default()).await
     }
 
+    pub async fn get_ip_access_rules(&self) -> Response {
+        self.get("ip-access-list", Query::default()).await
+    }
+
+    pub async fn add_ip_access_rule(&self, add_rule_form: AddIpAccessRuleForm) -> Response {
+        self.post_form("ip-access-list", &add_rule_form).await
+    }
+
+    pub async fn remove_ip_access_rule(&self, network: &str) -> Response {
+        self.delete(&format!("ip-access-list/{}", network.replace('/', "%2F"))).await
+    }
+
+    pub async fn reload_ip_access_list(&self) -> Response {
+        self.get("ip-access-list/reload", Query::default()).await
+    }
+
+    pub async fn reload_configuration(&self) -> Response {
+        self.post_empty("config/reload").await
+    }
+
+    pub async fn subscribe_to_events(&self, params: Query) -> Response {
+        self.get("events", params).await
+    }
+
     pub async fn get_torrent(&self, info_hash: &str) -> Response {
         self.get(&format!("torrent/{}", &info_hash), Query::default()).await
     }
@@ -58,6 +114,18 @@ impl Client {
         self.get("stats", Query::default()).await
     }
 
+    pub async fn get_tracker_statistics_with_params(&self, params: Query) -> Response {
+        self.get("stats", params).await
+    }
+
+    pub async fn get_tracker_statistics_history(&self, params: Query) -> Response {
+        self.get("stats/history", params).await
+    }
+
+    pub async fn reset_tracker_statistics(&self) -> Response {
+        self.delete("stats").await
+    }
+
     pub async fn get(&self, path: &str, params: Query) -> Response {
         let mut query: Query = params;
 
@@ -96,6 +164,16 @@ impl Client {
             .unwrap()
     }
 
+    async fn delete_json<T: Serialize + ?Sized>(&self, path: &str, body: &T) -> Response {
+        reqwest::Client::new()
+            .delete(self.base_url(path).clone())
+            .query(&ReqwestQuery::from(self.query_with_token()))
+            .json(&body)
+            .send()
+            .await
+            .unwrap()
+    }
+
     pub async fn get_request_with_query(&self, path: &str, params: Query) -> Response {
         get(&self.base_url(path), Some(params)).await
     }
@@ -104,6 +182,24 @@ impl Client {
         get(&self.base_url(path), None).await
     }
 
+    pub async fn get_request_with_bearer_token(&self, path: &str, token: &str) -> Response {
+        reqwest::Client::new()
+            .get(self.base_url(path))
+            .bearer_auth(token)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_request_with_authorization_header(&self, path: &str, value: &str) -> Response {
+        reqwest::Client::new()
+            .get(self.base_url(path))
+            .header(reqwest::header::AUTHORIZATION, value)
+            .send()
+            .await
+            .unwrap()
+    }
+
     fn query_with_token(&self) -> Query {
         match &self.connection_info.api_token {
             Some(token) => Query::params([QueryParam::new("token", token)].to_vec()),
@@ -136,3 +232,9 @@ pub struct AddKeyForm {
     pub opt_key: Option<String>,
     pub seconds_valid: Option<u64>,
 }
+
+#[derive(Serialize, Debug)]
+pub struct AddIpAccessRuleForm {
+    pub network: String,
+    pub policy: String,
+}
@@ -1,9 +1,11 @@
+use torrust_tracker_configuration::Scope;
 use torrust_tracker_test_helpers::configuration;
 use tracing::level_filters::LevelFilter;
 
 use crate::common::http::{Query, QueryParam};
 use crate::common::logging::{tracing_stderr_init, INIT};
-use crate::servers::api::v1::asserts::{assert_token_not_valid, assert_unauthorized};
+use crate::servers::api::connection_info::ConnectionInfo;
+use crate::servers::api::v1::asserts::{assert_scope_not_granted, assert_token_not_valid, assert_unauthorized};
 use crate::servers::api::v1::client::Client;
 use crate::servers::api::Started;
 
@@ -26,6 +28,93 @@ async fn should_authenticate_requests_by_using_a_token_query_param() {
     env.stop().await;
 }
 
+#[tokio::test]
+async fn should_authenticate_requests_by_using_an_authorization_bearer_header() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let token = env.get_connection_info().api_token.unwrap();
+
+    let response = Client::new(env.get_connection_info())
+        .get_request_with_bearer_token("stats", &token)
+        .await;
+
+    assert_eq!(response.status(), 200);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_accept_the_bearer_scheme_in_any_case() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let token = env.get_connection_info().api_token.unwrap();
+
+    let client = Client::new(env.get_connection_info());
+
+    for scheme in ["bearer", "BEARER"] {
+        let response = client
+            .get_request_with_authorization_header("stats", &format!("{scheme} {token}"))
+            .await;
+
+        assert_eq!(response.status(), 200);
+    }
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_authenticate_requests_when_the_bearer_token_is_invalid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .get_request_with_bearer_token("stats", "INVALID TOKEN")
+        .await;
+
+    assert_token_not_valid(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_authenticate_requests_by_using_the_token_query_param_when_it_is_disabled() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let mut configuration = configuration::ephemeral();
+    configuration.http_api.as_mut().unwrap().query_token_enabled = false;
+
+    let env = Started::new(&configuration.into()).await;
+
+    let token = env.get_connection_info().api_token.unwrap();
+
+    let response = Client::new(env.get_connection_info())
+        .get_request_with_query("stats", Query::params([QueryParam::new("token", &token)].to_vec()))
+        .await;
+
+    assert_unauthorized(response).await;
+
+    let response = Client::new(env.get_connection_info())
+        .get_request_with_bearer_token("stats", &token)
+        .await;
+
+    assert_eq!(response.status(), 200);
+
+    env.stop().await;
+}
+
 #[tokio::test]
 async fn should_not_authenticate_requests_when_the_token_is_missing() {
     INIT.call_once(|| {
@@ -103,3 +192,53 @@ async fn should_allow_the_token_query_param_to_be_at_any_position_in_the_url_que
 
     env.stop().await;
 }
+
+#[tokio::test]
+async fn should_allow_requests_when_the_token_has_the_scope_required_by_the_endpoint() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let mut configuration = configuration::ephemeral();
+    let http_api = configuration.http_api.as_mut().unwrap();
+    http_api.add_token("monitoring", "MyMonitoringToken");
+    http_api.limit_token_scopes("monitoring", &[Scope::StatsRead]);
+
+    let env = Started::new(&configuration.into()).await;
+
+    let response = Client::new(ConnectionInfo::authenticated(
+        env.get_connection_info().bind_address.as_str(),
+        "MyMonitoringToken",
+    ))
+    .get_tracker_statistics()
+    .await;
+
+    assert_eq!(response.status(), 200);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_requests_when_the_token_does_not_have_the_scope_required_by_the_endpoint() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let mut configuration = configuration::ephemeral();
+    let http_api = configuration.http_api.as_mut().unwrap();
+    http_api.add_token("monitoring", "MyMonitoringToken");
+    http_api.limit_token_scopes("monitoring", &[Scope::StatsRead]);
+
+    let env = Started::new(&configuration.into()).await;
+
+    let client = Client::new(ConnectionInfo::authenticated(
+        env.get_connection_info().bind_address.as_str(),
+        "MyMonitoringToken",
+    ));
+
+    assert_scope_not_granted(client.reset_tracker_statistics().await, "stats:write").await;
+    assert_scope_not_granted(client.reload_whitelist().await, "whitelist:write").await;
+    assert_scope_not_granted(client.reload_keys().await, "keys:write").await;
+
+    env.stop().await;
+}
@@ -0,0 +1,198 @@
+use aquatic_udp_protocol::NumberOfBytes;
+use torrust_tracker::core::auth::Key;
+use torrust_tracker::servers::apis::v1::context::accounting::resources::KeyTraffic;
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::peer::fixture::PeerBuilder;
+use torrust_tracker_primitives::peer::Peer;
+use torrust_tracker_test_helpers::configuration;
+use tracing::level_filters::LevelFilter;
+
+use crate::common::http::{Query, QueryParam};
+use crate::common::logging::{tracing_stderr_init, INIT};
+use crate::servers::api::connection_info::{connection_with_invalid_token, connection_with_no_token};
+use crate::servers::api::v1::asserts::{
+    assert_auth_key_not_known, assert_invalid_auth_key_get_param, assert_key_traffic, assert_keys_traffic,
+    assert_token_not_valid, assert_unauthorized,
+};
+use crate::servers::api::v1::client::Client;
+use crate::servers::api::Started;
+
+fn started_leecher(uploaded: i64, downloaded: i64) -> Peer {
+    let mut peer = PeerBuilder::leecher().build();
+    peer.uploaded = NumberOfBytes::new(uploaded);
+    peer.downloaded = NumberOfBytes::new(downloaded);
+    peer
+}
+
+#[tokio::test]
+async fn should_allow_getting_the_traffic_of_an_auth_key() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let key = "xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6".parse::<Key>().unwrap();
+    env.tracker.add_permanent_auth_key(key.clone()).await.unwrap();
+
+    env.tracker
+        .record_key_traffic(&key, &InfoHash::from_bytes(&[1; 20]), &started_leecher(30, 20));
+
+    let response = Client::new(env.get_connection_info()).get_key_traffic(&key.to_string()).await;
+
+    assert_key_traffic(
+        response,
+        KeyTraffic {
+            key: key.to_string(),
+            uploaded: 30,
+            downloaded: 20,
+            ratio: Some(1.5),
+        },
+    )
+    .await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_return_no_traffic_for_an_auth_key_that_has_not_announced_yet() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let key = "xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6".parse::<Key>().unwrap();
+    env.tracker.add_permanent_auth_key(key.clone()).await.unwrap();
+
+    let response = Client::new(env.get_connection_info()).get_key_traffic(&key.to_string()).await;
+
+    assert_key_traffic(
+        response,
+        KeyTraffic {
+            key: key.to_string(),
+            uploaded: 0,
+            downloaded: 0,
+            ratio: None,
+        },
+    )
+    .await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_return_auth_key_not_known_when_getting_the_traffic_of_a_key_the_tracker_does_not_have() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .get_key_traffic("Xc1L4PbQJSFGlrgSRZl8wxSFAuMa21z5")
+        .await;
+
+    assert_auth_key_not_known(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_getting_the_traffic_of_an_auth_key_when_the_key_is_invalid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let invalid_auth_key = "INVALID AUTH KEY ID";
+
+    let response = Client::new(env.get_connection_info()).get_key_traffic(invalid_auth_key).await;
+
+    assert_invalid_auth_key_get_param(response, invalid_auth_key).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_listing_and_paginating_the_traffic_of_the_auth_keys() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let key_a = "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA".parse::<Key>().unwrap();
+    let key_b = "BBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBB".parse::<Key>().unwrap();
+    env.tracker.add_permanent_auth_key(key_a.clone()).await.unwrap();
+    env.tracker.add_permanent_auth_key(key_b.clone()).await.unwrap();
+
+    env.tracker
+        .record_key_traffic(&key_b, &InfoHash::from_bytes(&[1; 20]), &started_leecher(10, 0));
+    env.tracker
+        .record_key_traffic(&key_a, &InfoHash::from_bytes(&[2; 20]), &started_leecher(30, 20));
+
+    let response = Client::new(env.get_connection_info()).get_keys_traffic(Query::empty()).await;
+
+    assert_keys_traffic(
+        response,
+        vec![
+            KeyTraffic {
+                key: key_a.to_string(),
+                uploaded: 30,
+                downloaded: 20,
+                ratio: Some(1.5),
+            },
+            KeyTraffic {
+                key: key_b.to_string(),
+                uploaded: 10,
+                downloaded: 0,
+                ratio: None,
+            },
+        ],
+    )
+    .await;
+
+    let response = Client::new(env.get_connection_info())
+        .get_keys_traffic(Query::params(
+            [QueryParam::new("offset", "1"), QueryParam::new("limit", "1")].to_vec(),
+        ))
+        .await;
+
+    assert_keys_traffic(
+        response,
+        vec![KeyTraffic {
+            key: key_b.to_string(),
+            uploaded: 10,
+            downloaded: 0,
+            ratio: None,
+        }],
+    )
+    .await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_getting_the_traffic_of_the_auth_keys_for_unauthenticated_users() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let response = Client::new(connection_with_invalid_token(env.get_connection_info().bind_address.as_str()))
+        .get_keys_traffic(Query::empty())
+        .await;
+
+    assert_token_not_valid(response).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .get_key_traffic("Xc1L4PbQJSFGlrgSRZl8wxSFAuMa21z5")
+        .await;
+
+    assert_unauthorized(response).await;
+
+    env.stop().await;
+}
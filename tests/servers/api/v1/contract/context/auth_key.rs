@@ -5,12 +5,14 @@ use torrust_tracker::core::auth::Key;
 use torrust_tracker_test_helpers::configuration;
 use tracing::level_filters::LevelFilter;
 
+use crate::common::http::{Query, QueryParam};
 use crate::common::logging::{tracing_stderr_init, INIT};
 use crate::servers::api::connection_info::{connection_with_invalid_token, connection_with_no_token};
 use crate::servers::api::v1::asserts::{
-    assert_auth_key_utf8, assert_failed_to_delete_key, assert_failed_to_generate_key, assert_failed_to_reload_keys,
-    assert_invalid_auth_key_get_param, assert_invalid_auth_key_post_param, assert_ok, assert_token_not_valid,
-    assert_unauthorized, assert_unprocessable_auth_key_duration_param,
+    assert_auth_key_list_utf8, assert_auth_key_not_known, assert_auth_key_utf8, assert_failed_to_delete_key,
+    assert_failed_to_generate_key, assert_failed_to_reload_keys, assert_invalid_auth_key_get_param,
+    assert_invalid_auth_key_post_param, assert_ok, assert_token_not_valid, assert_unauthorized,
+    assert_unprocessable_auth_key_duration_param,
 };
 use crate::servers::api::v1::client::{AddKeyForm, Client};
 use crate::servers::api::{force_database_error, Started};
@@ -386,6 +388,148 @@ async fn should_not_allow_reloading_keys_for_unauthenticated_users() {
     env.stop().await;
 }
 
+#[tokio::test]
+async fn should_allow_getting_an_auth_key() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let auth_key = env.tracker.generate_auth_key(Some(Duration::from_secs(60))).await.unwrap();
+
+    let response = Client::new(env.get_connection_info())
+        .get_auth_key(&auth_key.key.to_string())
+        .await;
+
+    let auth_key_resource = assert_auth_key_utf8(response).await;
+
+    assert_eq!(auth_key_resource.key, auth_key.key.to_string());
+    assert_eq!(
+        auth_key_resource.expiry_time,
+        auth_key.expiry_time().map(|expiry_time| expiry_time.to_string())
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_return_auth_key_not_known_when_the_tracker_does_not_have_the_auth_key() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .get_auth_key("Xc1L4PbQJSFGlrgSRZl8wxSFAuMa21z5")
+        .await;
+
+    assert_auth_key_not_known(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_getting_an_auth_key_when_the_key_is_invalid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let invalid_auth_key = "INVALID AUTH KEY ID";
+
+    let response = Client::new(env.get_connection_info()).get_auth_key(invalid_auth_key).await;
+
+    assert_invalid_auth_key_get_param(response, invalid_auth_key).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_listing_the_auth_keys() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let permanent_key = env.tracker.generate_permanent_auth_key().await.unwrap();
+    let expiring_key = env.tracker.generate_auth_key(Some(Duration::from_secs(60))).await.unwrap();
+
+    let response = Client::new(env.get_connection_info()).get_auth_keys(Query::empty()).await;
+
+    let mut keys: Vec<String> = assert_auth_key_list_utf8(response)
+        .await
+        .into_iter()
+        .map(|auth_key| auth_key.key)
+        .collect();
+    keys.sort();
+
+    let mut expected_keys = vec![permanent_key.key.to_string(), expiring_key.key.to_string()];
+    expected_keys.sort();
+
+    assert_eq!(keys, expected_keys);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_filtering_and_paginating_the_listed_auth_keys() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    env.tracker.generate_permanent_auth_key().await.unwrap();
+    env.tracker.generate_permanent_auth_key().await.unwrap();
+    env.tracker.generate_auth_key(Some(Duration::from_secs(60))).await.unwrap();
+
+    let response = Client::new(env.get_connection_info())
+        .get_auth_keys(Query::params(
+            [QueryParam::new("permanent", "true"), QueryParam::new("limit", "1")].to_vec(),
+        ))
+        .await;
+
+    let auth_keys = assert_auth_key_list_utf8(response).await;
+
+    assert_eq!(auth_keys.len(), 1);
+    assert!(auth_keys[0].expiry_time.is_none());
+
+    let response = Client::new(env.get_connection_info())
+        .get_auth_keys(Query::params([QueryParam::new("expired", "true")].to_vec()))
+        .await;
+
+    assert_eq!(assert_auth_key_list_utf8(response).await.len(), 0);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_getting_auth_keys_for_unauthenticated_users() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(connection_with_invalid_token(env.get_connection_info().bind_address.as_str()))
+        .get_auth_keys(Query::empty())
+        .await;
+
+    assert_token_not_valid(response).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .get_auth_key("Xc1L4PbQJSFGlrgSRZl8wxSFAuMa21z5")
+        .await;
+
+    assert_unauthorized(response).await;
+
+    env.stop().await;
+}
+
 mod deprecated_generate_key_endpoint {
 
     use torrust_tracker::core::auth::Key;
@@ -0,0 +1,124 @@
+use torrust_tracker::servers::apis::v1::context::config::resources::ReloadReport;
+use torrust_tracker_configuration::v2_0_0::core::PrivateMode;
+use torrust_tracker_test_helpers::configuration;
+use tracing::level_filters::LevelFilter;
+
+use crate::common::logging::{tracing_stderr_init, INIT};
+use crate::servers::api::connection_info::{connection_with_invalid_token, connection_with_no_token, ConnectionInfo};
+use crate::servers::api::v1::asserts::{
+    assert_failed_to_reload_configuration, assert_reload_report, assert_token_not_valid, assert_unauthorized,
+};
+use crate::servers::api::v1::client::Client;
+use crate::servers::api::Started;
+
+#[tokio::test]
+async fn should_allow_reloading_the_configuration() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    {
+        let mut config = env.config_source.write().unwrap();
+        config.core.announce_policy.interval = 60;
+        config.http_api.as_mut().unwrap().bind_address = "127.0.0.1:1313".parse().unwrap();
+    }
+
+    let response = Client::new(env.get_connection_info()).reload_configuration().await;
+
+    assert_reload_report(
+        response,
+        ReloadReport {
+            applied: vec!["core.announce_policy".to_string()],
+            requires_restart: vec!["http_api.bind_address".to_string()],
+        },
+    )
+    .await;
+
+    assert_eq!(env.tracker.get_announce_policy().interval, 60);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_use_the_new_access_tokens_after_reloading_the_configuration() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    env.config_source
+        .write()
+        .unwrap()
+        .http_api
+        .as_mut()
+        .unwrap()
+        .add_token("admin", "NewAccessToken");
+
+    let response = Client::new(env.get_connection_info()).reload_configuration().await;
+
+    assert_eq!(response.status(), 200);
+
+    let response = Client::new(env.get_connection_info()).get_tracker_statistics().await;
+
+    assert_token_not_valid(response).await;
+
+    let response = Client::new(ConnectionInfo::authenticated(
+        env.get_connection_info().bind_address.as_str(),
+        "NewAccessToken",
+    ))
+    .get_tracker_statistics()
+    .await;
+
+    assert_eq!(response.status(), 200);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_reloading_the_configuration_when_the_new_configuration_is_not_valid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_public().into()).await;
+
+    {
+        let mut config = env.config_source.write().unwrap();
+        config.core.announce_policy.interval = 60;
+        config.core.private_mode = Some(PrivateMode::default());
+    }
+
+    let response = Client::new(env.get_connection_info()).reload_configuration().await;
+
+    assert_failed_to_reload_configuration(response).await;
+
+    assert_ne!(env.tracker.get_announce_policy().interval, 60);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_reloading_the_configuration_for_unauthenticated_users() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(connection_with_invalid_token(env.get_connection_info().bind_address.as_str()))
+        .reload_configuration()
+        .await;
+
+    assert_token_not_valid(response).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .reload_configuration()
+        .await;
+
+    assert_unauthorized(response).await;
+
+    env.stop().await;
+}
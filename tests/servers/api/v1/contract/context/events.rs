@@ -0,0 +1,213 @@
+use std::str::FromStr;
+
+use reqwest::Response;
+use torrust_tracker::servers::apis::v1::context::events::resources::Event;
+use torrust_tracker_configuration::Scope;
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_test_helpers::configuration;
+use tracing::level_filters::LevelFilter;
+
+use crate::common::http::{Query, QueryParam};
+use crate::common::logging::{tracing_stderr_init, INIT};
+use crate::servers::api::connection_info::{connection_with_invalid_token, connection_with_no_token, ConnectionInfo};
+use crate::servers::api::v1::asserts::{assert_bad_request, assert_token_not_valid, assert_unauthorized};
+use crate::servers::api::v1::client::Client;
+use crate::servers::api::Started;
+
+/// It reads the next event from the Server-Sent Events stream.
+async fn next_event(response: &mut Response, buffer: &mut String) -> Event {
+    loop {
+        if let Some(end) = buffer.find("\n\n") {
+            let message = buffer[..end].to_string();
+            buffer.drain(..end + 2);
+
+            if let Some(data) = message.strip_prefix("data: ") {
+                return serde_json::from_str(data).unwrap();
+            }
+
+            continue;
+        }
+
+        let chunk = response.chunk().await.unwrap().expect("the event stream should not end");
+        buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+    }
+}
+
+#[tokio::test]
+async fn should_stream_the_tracker_events() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let mut response = Client::new(env.get_connection_info())
+        .subscribe_to_events(Query::default())
+        .await;
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "text/event-stream");
+
+    let info_hash = InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap();
+
+    env.tracker.add_torrent_to_whitelist(&info_hash).await.unwrap();
+
+    let mut buffer = String::new();
+
+    assert_eq!(
+        next_event(&mut response, &mut buffer).await,
+        Event::TorrentWhitelisted {
+            info_hash: info_hash.to_string()
+        }
+    );
+
+    drop(response);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_filtering_the_events_by_infohash() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let info_hash = InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap();
+    let other_info_hash = InfoHash::from_str("3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0").unwrap();
+
+    let mut response = Client::new(env.get_connection_info())
+        .subscribe_to_events(Query::params([QueryParam::new("info_hash", &info_hash.to_string())].to_vec()))
+        .await;
+
+    env.tracker.add_torrent_to_whitelist(&other_info_hash).await.unwrap();
+    env.tracker.add_torrent_to_whitelist(&info_hash).await.unwrap();
+
+    let mut buffer = String::new();
+
+    assert_eq!(
+        next_event(&mut response, &mut buffer).await,
+        Event::TorrentWhitelisted {
+            info_hash: info_hash.to_string()
+        }
+    );
+
+    drop(response);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_stream_the_authentication_key_events_when_the_token_has_the_keys_read_scope() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let mut response = Client::new(env.get_connection_info())
+        .subscribe_to_events(Query::default())
+        .await;
+
+    let peer_key = env.tracker.generate_permanent_auth_key().await.unwrap();
+
+    let mut buffer = String::new();
+
+    assert_eq!(
+        next_event(&mut response, &mut buffer).await,
+        Event::KeyAdded {
+            key: peer_key.key.to_string(),
+            valid_until: None
+        }
+    );
+
+    drop(response);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_stream_the_authentication_key_events_when_the_token_does_not_have_the_keys_read_scope() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let mut configuration = configuration::ephemeral();
+    let http_api = configuration.http_api.as_mut().unwrap();
+    http_api.add_token("monitoring", "MyMonitoringToken");
+    http_api.limit_token_scopes("monitoring", &[Scope::EventsRead]);
+
+    let env = Started::new(&configuration.into()).await;
+
+    let mut response = Client::new(ConnectionInfo::authenticated(
+        env.get_connection_info().bind_address.as_str(),
+        "MyMonitoringToken",
+    ))
+    .subscribe_to_events(Query::default())
+    .await;
+
+    assert_eq!(response.status(), 200);
+
+    let info_hash = InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap();
+
+    env.tracker.generate_permanent_auth_key().await.unwrap();
+    env.tracker.add_torrent_to_whitelist(&info_hash).await.unwrap();
+
+    let mut buffer = String::new();
+
+    assert_eq!(
+        next_event(&mut response, &mut buffer).await,
+        Event::TorrentWhitelisted {
+            info_hash: info_hash.to_string()
+        }
+    );
+
+    drop(response);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_subscribing_to_the_events_when_the_infohash_is_invalid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .subscribe_to_events(Query::params([QueryParam::new("info_hash", "INVALID INFO_HASH")].to_vec()))
+        .await;
+
+    assert_bad_request(
+        response,
+        "Invalid URL: invalid infohash param: string \"INVALID INFO_HASH\", expected a 40 character long string",
+    )
+    .await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_subscribing_to_the_events_for_unauthenticated_users() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(connection_with_invalid_token(env.get_connection_info().bind_address.as_str()))
+        .subscribe_to_events(Query::default())
+        .await;
+
+    assert_token_not_valid(response).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .subscribe_to_events(Query::default())
+        .await;
+
+    assert_unauthorized(response).await;
+
+    env.stop().await;
+}
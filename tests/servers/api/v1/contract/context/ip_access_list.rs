@@ -0,0 +1,227 @@
+use std::net::IpAddr;
+
+use torrust_tracker::core::ip_access_list::{parse_network, Policy, Rule};
+use torrust_tracker::servers::apis::v1::context::ip_access_list::resources::IpAccessRule;
+use torrust_tracker_test_helpers::configuration;
+use tracing::level_filters::LevelFilter;
+
+use crate::common::logging::{tracing_stderr_init, INIT};
+use crate::servers::api::connection_info::{connection_with_invalid_token, connection_with_no_token};
+use crate::servers::api::v1::asserts::{
+    assert_bad_request_with_text, assert_failed_to_add_ip_access_rule, assert_failed_to_reload_ip_access_list,
+    assert_failed_to_remove_ip_access_rule, assert_ip_access_rules, assert_ok, assert_token_not_valid, assert_unauthorized,
+};
+use crate::servers::api::v1::client::{AddIpAccessRuleForm, Client};
+use crate::servers::api::{force_database_error, Started};
+
+fn deny_rule(network: &str) -> Rule {
+    Rule::new(parse_network(network).unwrap(), Policy::Deny)
+}
+
+fn add_deny_rule_form(network: &str) -> AddIpAccessRuleForm {
+    AddIpAccessRuleForm {
+        network: network.to_string(),
+        policy: "deny".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn should_allow_adding_a_rule_to_the_ip_access_list() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .add_ip_access_rule(add_deny_rule_form("203.0.113.0/24"))
+        .await;
+
+    assert_ok(response).await;
+    assert!(env
+        .tracker
+        .authorize_client_ip(&"203.0.113.1".parse::<IpAddr>().unwrap())
+        .await
+        .is_err());
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_adding_a_rule_when_the_network_is_invalid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .add_ip_access_rule(add_deny_rule_form("203.0.113.0/33"))
+        .await;
+
+    assert_bad_request_with_text(response, "Invalid network").await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_adding_a_rule_when_the_policy_is_invalid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .add_ip_access_rule(AddIpAccessRuleForm {
+            network: "203.0.113.0/24".to_string(),
+            policy: "block".to_string(),
+        })
+        .await;
+
+    assert_bad_request_with_text(response, "Invalid policy").await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_when_the_rule_cannot_be_added() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    force_database_error(&env.tracker);
+
+    let response = Client::new(env.get_connection_info())
+        .add_ip_access_rule(add_deny_rule_form("203.0.113.0/24"))
+        .await;
+
+    assert_failed_to_add_ip_access_rule(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_listing_the_rules_in_the_ip_access_list() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    env.tracker.add_ip_access_rule(&deny_rule("203.0.113.0/24")).await.unwrap();
+
+    let response = Client::new(env.get_connection_info()).get_ip_access_rules().await;
+
+    assert_ip_access_rules(
+        response,
+        vec![IpAccessRule {
+            network: "203.0.113.0/24".to_string(),
+            policy: "deny".to_string(),
+        }],
+    )
+    .await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_removing_a_rule_from_the_ip_access_list() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    env.tracker.add_ip_access_rule(&deny_rule("203.0.113.0/24")).await.unwrap();
+
+    let response = Client::new(env.get_connection_info())
+        .remove_ip_access_rule("203.0.113.0/24")
+        .await;
+
+    assert_ok(response).await;
+    assert!(env.tracker.get_ip_access_rules().await.is_empty());
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_when_the_rule_cannot_be_removed() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    env.tracker.add_ip_access_rule(&deny_rule("203.0.113.0/24")).await.unwrap();
+
+    force_database_error(&env.tracker);
+
+    let response = Client::new(env.get_connection_info())
+        .remove_ip_access_rule("203.0.113.0/24")
+        .await;
+
+    assert_failed_to_remove_ip_access_rule(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_reloading_the_ip_access_list_from_the_database() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    env.tracker.add_ip_access_rule(&deny_rule("203.0.113.0/24")).await.unwrap();
+
+    let response = Client::new(env.get_connection_info()).reload_ip_access_list().await;
+
+    assert_ok(response).await;
+    assert_eq!(env.tracker.get_ip_access_rules().await, vec![deny_rule("203.0.113.0/24")]);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_when_the_ip_access_list_cannot_be_reloaded_from_the_database() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    force_database_error(&env.tracker);
+
+    let response = Client::new(env.get_connection_info()).reload_ip_access_list().await;
+
+    assert_failed_to_reload_ip_access_list(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_managing_the_ip_access_list_for_unauthenticated_users() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(connection_with_invalid_token(env.get_connection_info().bind_address.as_str()))
+        .add_ip_access_rule(add_deny_rule_form("203.0.113.0/24"))
+        .await;
+
+    assert_token_not_valid(response).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .get_ip_access_rules()
+        .await;
+
+    assert_unauthorized(response).await;
+
+    env.stop().await;
+}
@@ -1,5 +1,9 @@
+pub mod accounting;
 pub mod auth_key;
+pub mod config;
+pub mod events;
 pub mod health_check;
+pub mod ip_access_list;
 pub mod stats;
 pub mod torrent;
 pub mod whitelist;
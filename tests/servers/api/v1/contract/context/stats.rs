@@ -1,16 +1,21 @@
 use std::str::FromStr;
 
 use torrust_tracker::servers::apis::v1::context::stats::resources::Stats;
+use torrust_tracker_configuration::{MetricsHistory, StatisticsPersistence};
 use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_primitives::peer::fixture::PeerBuilder;
 use torrust_tracker_test_helpers::configuration;
 use tracing::level_filters::LevelFilter;
 
+use crate::common::http::{Query, QueryParam};
 use crate::common::logging::{tracing_stderr_init, INIT};
 use crate::servers::api::connection_info::{connection_with_invalid_token, connection_with_no_token};
-use crate::servers::api::v1::asserts::{assert_stats, assert_token_not_valid, assert_unauthorized};
+use crate::servers::api::v1::asserts::{
+    assert_bad_request, assert_failed_to_reset_stats, assert_ok, assert_stats, assert_stats_history,
+    assert_stats_in_openmetrics_format, assert_token_not_valid, assert_unauthorized,
+};
 use crate::servers::api::v1::client::Client;
-use crate::servers::api::Started;
+use crate::servers::api::{force_database_error, Started};
 
 #[tokio::test]
 async fn should_allow_getting_tracker_statistics() {
@@ -37,15 +42,29 @@ async fn should_allow_getting_tracker_statistics() {
             tcp4_connections_handled: 0,
             tcp4_announces_handled: 0,
             tcp4_scrapes_handled: 0,
+            tcp4_requests_blocked: 0,
+            tcp4_requests_rate_limited: 0,
+            tcp4_early_announces: 0,
             tcp6_connections_handled: 0,
             tcp6_announces_handled: 0,
             tcp6_scrapes_handled: 0,
+            tcp6_requests_blocked: 0,
+            tcp6_requests_rate_limited: 0,
+            tcp6_early_announces: 0,
             udp4_connections_handled: 0,
             udp4_announces_handled: 0,
             udp4_scrapes_handled: 0,
+            udp4_requests_blocked: 0,
+            udp4_requests_rate_limited: 0,
+            udp4_early_announces: 0,
             udp6_connections_handled: 0,
             udp6_announces_handled: 0,
             udp6_scrapes_handled: 0,
+            udp6_requests_blocked: 0,
+            udp6_requests_rate_limited: 0,
+            udp6_early_announces: 0,
+            scrape_cache_hits: 0,
+            scrape_cache_misses: 0,
         },
     )
     .await;
@@ -53,6 +72,37 @@ async fn should_allow_getting_tracker_statistics() {
     env.stop().await;
 }
 
+#[tokio::test]
+async fn should_allow_getting_tracker_statistics_in_the_prometheus_format() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    env.add_torrent_peer(
+        &InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap(),
+        &PeerBuilder::default().into(),
+    );
+
+    let response = Client::new(env.get_connection_info())
+        .get_tracker_statistics_with_params(Query::params([QueryParam::new("format", "prometheus")].to_vec()))
+        .await;
+
+    assert_stats_in_openmetrics_format(
+        response,
+        &[
+            "torrust_tracker_torrents 1",
+            "torrust_tracker_seeders 1",
+            "torrust_tracker_leechers 0",
+            "torrust_tracker_announces_handled_total{protocol=\"udp\",family=\"ipv6\"} 0",
+        ],
+    )
+    .await;
+
+    env.stop().await;
+}
+
 #[tokio::test]
 async fn should_not_allow_getting_tracker_statistics_for_unauthenticated_users() {
     INIT.call_once(|| {
@@ -75,3 +125,164 @@ async fn should_not_allow_getting_tracker_statistics_for_unauthenticated_users()
 
     env.stop().await;
 }
+
+#[tokio::test]
+async fn should_allow_resetting_the_tracker_statistics() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let mut configuration = configuration::ephemeral();
+    configuration.core.statistics_persistence = Some(StatisticsPersistence::default());
+
+    let env = Started::new(&configuration.into()).await;
+
+    let response = Client::new(env.get_connection_info()).reset_tracker_statistics().await;
+
+    assert_ok(response).await;
+    assert_eq!(env.tracker.get_stats().await.udp4_connections_handled, 0);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_when_the_tracker_statistics_cannot_be_reset_in_the_database() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let mut configuration = configuration::ephemeral();
+    configuration.core.statistics_persistence = Some(StatisticsPersistence::default());
+
+    let env = Started::new(&configuration.into()).await;
+
+    force_database_error(&env.tracker);
+
+    let response = Client::new(env.get_connection_info()).reset_tracker_statistics().await;
+
+    assert_failed_to_reset_stats(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_resetting_the_tracker_statistics_for_unauthenticated_users() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(connection_with_invalid_token(env.get_connection_info().bind_address.as_str()))
+        .reset_tracker_statistics()
+        .await;
+
+    assert_token_not_valid(response).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .reset_tracker_statistics()
+        .await;
+
+    assert_unauthorized(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_getting_the_history_of_the_tracker_metrics() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let mut configuration = configuration::ephemeral();
+    configuration.core.metrics_history = Some(MetricsHistory::default());
+
+    let env = Started::new(&configuration.into()).await;
+
+    env.tracker.sample_metrics().await;
+
+    env.add_torrent_peer(
+        &InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap(),
+        &PeerBuilder::default().into(),
+    );
+
+    env.tracker.sample_metrics().await;
+
+    let response = Client::new(env.get_connection_info())
+        .get_tracker_statistics_history(Query::default())
+        .await;
+
+    let points = assert_stats_history(response, 2).await;
+
+    assert_eq!(points[0].torrents, 0);
+    assert_eq!(points[0].rates, None);
+    assert_eq!(points[1].torrents, 1);
+    assert_eq!(points[1].seeders, 1);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_getting_the_history_of_the_tracker_metrics_within_a_time_range() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let mut configuration = configuration::ephemeral();
+    configuration.core.metrics_history = Some(MetricsHistory::default());
+
+    let env = Started::new(&configuration.into()).await;
+
+    env.tracker.sample_metrics().await;
+
+    let response = Client::new(env.get_connection_info())
+        .get_tracker_statistics_history(Query::params([QueryParam::new("to", "0")].to_vec()))
+        .await;
+
+    assert_stats_history(response, 0).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_getting_the_history_of_the_tracker_metrics_when_it_is_disabled() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .get_tracker_statistics_history(Query::default())
+        .await;
+
+    assert_bad_request(response, "The metrics history is disabled in the tracker configuration").await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_getting_the_history_of_the_tracker_metrics_for_unauthenticated_users() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let mut configuration = configuration::ephemeral();
+    configuration.core.metrics_history = Some(MetricsHistory::default());
+
+    let env = Started::new(&configuration.into()).await;
+
+    let response = Client::new(connection_with_invalid_token(env.get_connection_info().bind_address.as_str()))
+        .get_tracker_statistics_history(Query::default())
+        .await;
+
+    assert_token_not_valid(response).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .get_tracker_statistics_history(Query::default())
+        .await;
+
+    assert_unauthorized(response).await;
+
+    env.stop().await;
+}
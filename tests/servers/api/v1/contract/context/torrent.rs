@@ -202,6 +202,27 @@ async fn should_fail_getting_torrents_when_the_limit_query_parameter_cannot_be_p
     env.stop().await;
 }
 
+#[tokio::test]
+async fn should_fail_getting_torrents_when_the_sort_query_parameter_cannot_be_parsed() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .get_torrents(Query::params([QueryParam::new("sort", "INVALID SORT")].to_vec()))
+        .await;
+
+    assert_bad_request(
+        response,
+        "Failed to deserialize query string: Invalid sort field: INVALID SORT, it should be `announces`, `scrapes`, `last_activity`, `peak_seeders` or `peak_leechers`",
+    )
+    .await;
+
+    env.stop().await;
+}
+
 #[tokio::test]
 async fn should_fail_getting_torrents_when_the_info_hash_parameter_is_invalid() {
     INIT.call_once(|| {
@@ -276,6 +297,7 @@ async fn should_allow_getting_a_torrent_info() {
             completed: 0,
             leechers: 0,
             peers: Some(vec![Peer::from(peer)]),
+            stats: None,
         },
     )
     .await;
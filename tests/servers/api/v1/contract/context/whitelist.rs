@@ -1,14 +1,17 @@
 use std::str::FromStr;
 
+use torrust_tracker::servers::apis::v1::context::whitelist::resources::WhitelistedTorrent;
 use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_test_helpers::configuration;
 use tracing::level_filters::LevelFilter;
 
+use crate::common::http::{Query, QueryParam};
 use crate::common::logging::{tracing_stderr_init, INIT};
 use crate::servers::api::connection_info::{connection_with_invalid_token, connection_with_no_token};
 use crate::servers::api::v1::asserts::{
     assert_failed_to_reload_whitelist, assert_failed_to_remove_torrent_from_whitelist, assert_failed_to_whitelist_torrent,
-    assert_invalid_infohash_param, assert_not_found, assert_ok, assert_token_not_valid, assert_unauthorized,
+    assert_invalid_infohash, assert_invalid_infohash_param, assert_not_found, assert_ok, assert_token_not_valid,
+    assert_torrent_not_whitelisted, assert_unauthorized, assert_whitelist, assert_whitelisted_torrent,
 };
 use crate::servers::api::v1::client::Client;
 use crate::servers::api::v1::contract::fixtures::{
@@ -298,3 +301,229 @@ async fn should_fail_when_the_whitelist_cannot_be_reloaded_from_the_database() {
 
     env.stop().await;
 }
+
+#[tokio::test]
+async fn should_allow_listing_the_whitelisted_torrents() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    for hash in [
+        "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d", // DevSkim: ignore DS173237
+        "0b3aea4adc213ce32295be85d3883a63bca25446", // DevSkim: ignore DS173237
+    ] {
+        env.tracker
+            .add_torrent_to_whitelist(&InfoHash::from_str(hash).unwrap())
+            .await
+            .unwrap();
+    }
+
+    let response = Client::new(env.get_connection_info()).get_whitelist(Query::empty()).await;
+
+    assert_whitelist(
+        response,
+        vec![
+            WhitelistedTorrent {
+                info_hash: "0b3aea4adc213ce32295be85d3883a63bca25446".to_string(), // DevSkim: ignore DS173237
+            },
+            WhitelistedTorrent {
+                info_hash: "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_string(), // DevSkim: ignore DS173237
+            },
+        ],
+    )
+    .await;
+
+    let response = Client::new(env.get_connection_info())
+        .get_whitelist(Query::params(
+            [QueryParam::new("offset", "1"), QueryParam::new("limit", "1")].to_vec(),
+        ))
+        .await;
+
+    assert_whitelist(
+        response,
+        vec![WhitelistedTorrent {
+            info_hash: "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_string(), // DevSkim: ignore DS173237
+        }],
+    )
+    .await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_getting_a_whitelisted_torrent() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let hash = "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_owned();
+    env.tracker
+        .add_torrent_to_whitelist(&InfoHash::from_str(&hash).unwrap())
+        .await
+        .unwrap();
+
+    let response = Client::new(env.get_connection_info()).get_whitelisted_torrent(&hash).await;
+
+    assert_whitelisted_torrent(response, WhitelistedTorrent { info_hash: hash }).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_return_torrent_not_whitelisted_when_the_torrent_is_not_in_the_whitelist() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .get_whitelisted_torrent("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d")
+        .await;
+
+    assert_torrent_not_whitelisted(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_getting_the_whitelist_for_unauthenticated_users() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(connection_with_invalid_token(env.get_connection_info().bind_address.as_str()))
+        .get_whitelist(Query::empty())
+        .await;
+
+    assert_token_not_valid(response).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .get_whitelisted_torrent("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d")
+        .await;
+
+    assert_unauthorized(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_whitelisting_many_torrents_at_once() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let hashes = [
+        "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d", // DevSkim: ignore DS173237
+        "0b3aea4adc213ce32295be85d3883a63bca25446", // DevSkim: ignore DS173237
+    ];
+
+    let response = Client::new(env.get_connection_info()).whitelist_torrents(&hashes).await;
+
+    assert_ok(response).await;
+    for hash in hashes {
+        assert!(env.tracker.is_info_hash_whitelisted(&InfoHash::from_str(hash).unwrap()).await);
+    }
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_whitelist_any_torrent_when_one_of_the_infohashes_is_invalid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .whitelist_torrents(&["9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d", "INVALID INFOHASH"])
+        .await;
+
+    assert_invalid_infohash(response, "INVALID INFOHASH").await;
+    assert!(env.tracker.get_whitelist().await.is_empty());
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_when_the_torrents_cannot_be_whitelisted() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    force_database_error(&env.tracker);
+
+    let response = Client::new(env.get_connection_info())
+        .whitelist_torrents(&["9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d"])
+        .await;
+
+    assert_failed_to_whitelist_torrent(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_removing_many_torrents_from_the_whitelist_at_once() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let hashes = [
+        "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d", // DevSkim: ignore DS173237
+        "0b3aea4adc213ce32295be85d3883a63bca25446", // DevSkim: ignore DS173237
+    ];
+
+    for hash in hashes {
+        env.tracker
+            .add_torrent_to_whitelist(&InfoHash::from_str(hash).unwrap())
+            .await
+            .unwrap();
+    }
+
+    let response = Client::new(env.get_connection_info())
+        .remove_torrents_from_whitelist(&hashes)
+        .await;
+
+    assert_ok(response).await;
+    assert!(env.tracker.get_whitelist().await.is_empty());
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_whitelisting_many_torrents_for_unauthenticated_users() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let hashes = ["9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d"];
+
+    let response = Client::new(connection_with_invalid_token(env.get_connection_info().bind_address.as_str()))
+        .whitelist_torrents(&hashes)
+        .await;
+
+    assert_token_not_valid(response).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .remove_torrents_from_whitelist(&hashes)
+        .await;
+
+    assert_unauthorized(response).await;
+
+    env.stop().await;
+}
@@ -114,6 +114,32 @@ pub async fn assert_torrent_not_in_whitelist_error_response(response: Response)
     assert_bencoded_error(&response.text().await.unwrap(), "is not whitelisted", Location::caller());
 }
 
+pub async fn assert_ip_address_not_allowed_error_response(response: Response) {
+    assert_eq!(response.status(), 200);
+
+    assert_bencoded_error(&response.text().await.unwrap(), "is not allowed", Location::caller());
+}
+
+pub async fn assert_rate_limit_exceeded_error_response(response: Response) {
+    assert_eq!(response.status(), 200);
+
+    assert_bencoded_error(
+        &response.text().await.unwrap(),
+        "has exceeded the rate limit",
+        Location::caller(),
+    );
+}
+
+pub async fn assert_announce_too_early_error_response(response: Response) {
+    assert_eq!(response.status(), 200);
+
+    assert_bencoded_error(
+        &response.text().await.unwrap(),
+        "announced again before the minimum announce interval",
+        Location::caller(),
+    );
+}
+
 pub async fn assert_could_not_find_remote_address_on_x_forwarded_for_header_error_response(response: Response) {
     assert_eq!(response.status(), 200);
 
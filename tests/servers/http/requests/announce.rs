@@ -1,5 +1,5 @@
 use std::fmt;
-use std::net::{IpAddr, Ipv4Addr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::str::FromStr;
 
 use aquatic_udp_protocol::PeerId;
@@ -19,6 +19,7 @@ pub struct Query {
     pub event: Option<Event>,
     pub compact: Option<Compact>,
     pub numwant: Option<u32>,
+    pub ipv6: Option<Ipv6Addr>,
 }
 
 impl fmt::Display for Query {
@@ -100,6 +101,7 @@ impl QueryBuilder {
             event: Some(Event::Completed),
             compact: Some(Compact::NotAccepted),
             numwant: None,
+            ipv6: None,
         };
         Self {
             announce_query: default_announce_query,
@@ -126,6 +128,16 @@ impl QueryBuilder {
         self
     }
 
+    pub fn with_port(mut self, port: PortNumber) -> Self {
+        self.announce_query.port = port;
+        self
+    }
+
+    pub fn with_ipv6(mut self, ipv6: &Ipv6Addr) -> Self {
+        self.announce_query.ipv6 = Some(*ipv6);
+        self
+    }
+
     pub fn without_compact(mut self) -> Self {
         self.announce_query.compact = None;
         self
@@ -152,6 +164,7 @@ impl QueryBuilder {
 ///     event=completed
 ///     compact=0
 ///     numwant=50
+///     ipv6=2001:db8::1
 /// ```
 #[derive(Debug)]
 pub struct QueryParams {
@@ -165,6 +178,7 @@ pub struct QueryParams {
     pub event: Option<String>,
     pub compact: Option<String>,
     pub numwant: Option<String>,
+    pub ipv6: Option<String>,
 }
 
 impl std::fmt::Display for QueryParams {
@@ -201,6 +215,9 @@ impl std::fmt::Display for QueryParams {
         if let Some(numwant) = &self.numwant {
             params.push(("numwant", numwant));
         }
+        if let Some(ipv6) = &self.ipv6 {
+            params.push(("ipv6", ipv6));
+        }
 
         let query = params
             .iter()
@@ -217,6 +234,7 @@ impl QueryParams {
         let event = announce_query.event.as_ref().map(std::string::ToString::to_string);
         let compact = announce_query.compact.as_ref().map(std::string::ToString::to_string);
         let numwant = announce_query.numwant.map(|numwant| numwant.to_string());
+        let ipv6 = announce_query.ipv6.map(|ipv6| ipv6.to_string());
 
         Self {
             info_hash: Some(percent_encode_byte_array(&announce_query.info_hash)),
@@ -229,6 +247,7 @@ impl QueryParams {
             event,
             compact,
             numwant,
+            ipv6,
         }
     }
 
@@ -252,6 +271,7 @@ impl QueryParams {
         self.event = None;
         self.compact = None;
         self.numwant = None;
+        self.ipv6 = None;
     }
 
     pub fn set(&mut self, param_name: &str, param_value: &str) {
@@ -266,6 +286,7 @@ impl QueryParams {
             "event" => self.event = Some(param_value.to_string()),
             "compact" => self.compact = Some(param_value.to_string()),
             "numwant" => self.numwant = Some(param_value.to_string()),
+            "ipv6" => self.ipv6 = Some(param_value.to_string()),
             &_ => panic!("Invalid param name for announce query"),
         }
     }
@@ -42,6 +42,8 @@ pub struct DeserializedCompact {
     pub min_interval: u32,
     #[serde(with = "serde_bytes")]
     pub peers: Vec<u8>,
+    #[serde(default, with = "serde_bytes")]
+    pub peers6: Vec<u8>,
 }
 
 impl DeserializedCompact {
@@ -106,6 +106,7 @@ mod for_all_config_modes {
         use local_ip_address::local_ip;
         use reqwest::{Response, StatusCode};
         use tokio::net::TcpListener;
+        use torrust_tracker_configuration::ClientSuppliedIp;
         use torrust_tracker_primitives::info_hash::InfoHash;
         use torrust_tracker_primitives::peer::fixture::PeerBuilder;
         use torrust_tracker_test_helpers::configuration;
@@ -675,6 +676,102 @@ mod for_all_config_modes {
             env.stop().await;
         }
 
+        #[tokio::test]
+        async fn should_return_the_ipv6_address_of_dual_stack_peers_in_the_compact_response() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            // IPv6 Tracker Extension
+            // https://www.bittorrent.org/beps/bep_0007.html
+
+            // The test client announces from the loopback address
+            let mut configuration = configuration::ephemeral_public();
+            configuration.core.net.client_supplied_ip = ClientSuppliedIp::PrivateNetworks;
+
+            let env = Started::new(&configuration.into()).await;
+
+            let info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+
+            let peer_ipv6: Ipv6Addr = "2001:db8::1".parse().unwrap();
+
+            // Announce the Peer 1 using IPv4 and including its IPv6 address
+            Client::new(*env.bind_address())
+                .announce(
+                    &QueryBuilder::default()
+                        .with_info_hash(&info_hash)
+                        .with_peer_id(&PeerId(*b"-qB00000000000000001"))
+                        .with_port(17549)
+                        .with_ipv6(&peer_ipv6)
+                        .query(),
+                )
+                .await;
+
+            // Announce the new Peer 2 accepting compact responses
+            let response = Client::new(*env.bind_address())
+                .announce(
+                    &QueryBuilder::default()
+                        .with_info_hash(&info_hash)
+                        .with_peer_id(&PeerId(*b"-qB00000000000000002"))
+                        .with_compact(Compact::Accepted)
+                        .query(),
+                )
+                .await;
+
+            let bytes = response.bytes().await.unwrap();
+            let compact_announce = responses::announce::DeserializedCompact::from_bytes(&bytes).unwrap();
+
+            let mut expected_peers6 = peer_ipv6.octets().to_vec();
+            expected_peers6.extend_from_slice(&17549u16.to_be_bytes());
+
+            assert_eq!(compact_announce.peers6, expected_peers6);
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_ignore_the_ipv6_address_of_dual_stack_peers_when_the_tracker_does_not_trust_the_client() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral_public().into()).await;
+
+            let info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+
+            let peer_ipv6: Ipv6Addr = "2001:db8::1".parse().unwrap();
+
+            // Announce the Peer 1 using IPv4 and including an IPv6 address
+            Client::new(*env.bind_address())
+                .announce(
+                    &QueryBuilder::default()
+                        .with_info_hash(&info_hash)
+                        .with_peer_id(&PeerId(*b"-qB00000000000000001"))
+                        .with_port(17549)
+                        .with_ipv6(&peer_ipv6)
+                        .query(),
+                )
+                .await;
+
+            // Announce the new Peer 2 accepting compact responses
+            let response = Client::new(*env.bind_address())
+                .announce(
+                    &QueryBuilder::default()
+                        .with_info_hash(&info_hash)
+                        .with_peer_id(&PeerId(*b"-qB00000000000000002"))
+                        .with_compact(Compact::Accepted)
+                        .query(),
+                )
+                .await;
+
+            let bytes = response.bytes().await.unwrap();
+            let compact_announce = responses::announce::DeserializedCompact::from_bytes(&bytes).unwrap();
+
+            assert!(compact_announce.peers6.is_empty());
+
+            env.stop().await;
+        }
+
         #[tokio::test]
         async fn should_not_return_the_compact_response_by_default() {
             INIT.call_once(|| {
@@ -1043,6 +1140,7 @@ mod for_all_config_modes {
 
         use aquatic_udp_protocol::PeerId;
         use tokio::net::TcpListener;
+        use torrust_tracker_configuration::FullScrape;
         use torrust_tracker_primitives::info_hash::InfoHash;
         use torrust_tracker_primitives::peer::fixture::PeerBuilder;
         use torrust_tracker_test_helpers::configuration;
@@ -1074,6 +1172,48 @@ mod for_all_config_modes {
             env.stop().await;
         }
 
+        #[tokio::test]
+        async fn should_return_all_the_files_when_the_request_is_empty_and_the_full_scrape_is_enabled() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let mut configuration = configuration::ephemeral_public();
+            configuration.core.full_scrape = Some(FullScrape {
+                public: true,
+                ..Default::default()
+            });
+
+            let env = Started::new(&configuration.into()).await;
+
+            let info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+
+            env.add_torrent_peer(
+                &info_hash,
+                &PeerBuilder::default()
+                    .with_peer_id(&PeerId(*b"-qB00000000000000001"))
+                    .with_bytes_pending_to_download(1)
+                    .build(),
+            );
+
+            let response = Client::new(*env.bind_address()).get("scrape").await;
+
+            let expected_scrape_response = ResponseBuilder::default()
+                .add_file(
+                    info_hash.bytes(),
+                    File {
+                        complete: 0,
+                        downloaded: 0,
+                        incomplete: 1,
+                    },
+                )
+                .build();
+
+            assert_scrape_response(response, &expected_scrape_response).await;
+
+            env.stop().await;
+        }
+
         #[tokio::test]
         async fn should_fail_when_the_info_hash_param_is_invalid() {
             INIT.call_once(|| {
@@ -1295,6 +1435,311 @@ mod for_all_config_modes {
     }
 }
 
+mod configured_with_an_ip_access_list {
+
+    mod and_receiving_an_announce_request {
+        use torrust_tracker::core::ip_access_list::{parse_network, Policy, Rule};
+        use torrust_tracker_test_helpers::configuration;
+        use tracing::level_filters::LevelFilter;
+
+        use crate::common::logging::{tracing_stderr_init, INIT};
+        use crate::servers::http::asserts::{assert_ip_address_not_allowed_error_response, assert_is_announce_response};
+        use crate::servers::http::client::Client;
+        use crate::servers::http::requests::announce::QueryBuilder;
+        use crate::servers::http::Started;
+
+        #[tokio::test]
+        async fn should_fail_if_the_client_ip_is_not_allowed() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral_public().into()).await;
+
+            env.tracker
+                .add_ip_access_rule(&Rule::new(parse_network("127.0.0.1").unwrap(), Policy::Deny))
+                .await
+                .expect("should add the rule to the IP access list");
+
+            let response = Client::new(*env.bind_address())
+                .announce(&QueryBuilder::default().query())
+                .await;
+
+            assert_ip_address_not_allowed_error_response(response).await;
+
+            let stats = env.tracker.get_stats().await;
+
+            assert_eq!(stats.tcp4_requests_blocked, 1);
+            assert_eq!(stats.tcp4_announces_handled, 0);
+
+            drop(stats);
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_allow_announcing_from_an_allowed_network() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral_public().into()).await;
+
+            env.tracker
+                .add_ip_access_rule(&Rule::new(parse_network("127.0.0.0/8").unwrap(), Policy::Allow))
+                .await
+                .expect("should add the rule to the IP access list");
+
+            let response = Client::new(*env.bind_address())
+                .announce(&QueryBuilder::default().query())
+                .await;
+
+            assert_is_announce_response(response).await;
+
+            env.stop().await;
+        }
+    }
+
+    mod receiving_an_scrape_request {
+        use std::str::FromStr;
+
+        use torrust_tracker::core::ip_access_list::{parse_network, Policy, Rule};
+        use torrust_tracker_primitives::info_hash::InfoHash;
+        use torrust_tracker_test_helpers::configuration;
+        use tracing::level_filters::LevelFilter;
+
+        use crate::common::logging::{tracing_stderr_init, INIT};
+        use crate::servers::http::asserts::assert_ip_address_not_allowed_error_response;
+        use crate::servers::http::client::Client;
+        use crate::servers::http::{requests, Started};
+
+        #[tokio::test]
+        async fn should_fail_if_the_client_ip_is_not_allowed() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral_public().into()).await;
+
+            env.tracker
+                .add_ip_access_rule(&Rule::new(parse_network("127.0.0.1").unwrap(), Policy::Deny))
+                .await
+                .expect("should add the rule to the IP access list");
+
+            let info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+
+            let response = Client::new(*env.bind_address())
+                .scrape(
+                    &requests::scrape::QueryBuilder::default()
+                        .with_one_info_hash(&info_hash)
+                        .query(),
+                )
+                .await;
+
+            assert_ip_address_not_allowed_error_response(response).await;
+
+            let stats = env.tracker.get_stats().await;
+
+            assert_eq!(stats.tcp4_requests_blocked, 1);
+            assert_eq!(stats.tcp4_scrapes_handled, 0);
+
+            drop(stats);
+
+            env.stop().await;
+        }
+    }
+}
+
+mod configured_with_a_rate_limit {
+
+    mod and_receiving_an_announce_request {
+        use torrust_tracker_configuration::v2_0_0::rate_limit::Budget;
+        use torrust_tracker_configuration::RateLimit;
+        use torrust_tracker_test_helpers::configuration;
+        use tracing::level_filters::LevelFilter;
+
+        use crate::common::logging::{tracing_stderr_init, INIT};
+        use crate::servers::http::asserts::{
+            assert_is_announce_response, assert_missing_query_params_for_announce_request_error_response,
+            assert_rate_limit_exceeded_error_response,
+        };
+        use crate::servers::http::client::Client;
+        use crate::servers::http::requests::announce::QueryBuilder;
+        use crate::servers::http::Started;
+
+        #[tokio::test]
+        async fn should_fail_when_the_client_exceeds_its_announce_budget() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(
+                &configuration::ephemeral_with_rate_limit(RateLimit {
+                    announce: Budget::new(1, 1),
+                    ..Default::default()
+                })
+                .into(),
+            )
+            .await;
+
+            let response = Client::new(*env.bind_address())
+                .announce(&QueryBuilder::default().query())
+                .await;
+
+            assert_is_announce_response(response).await;
+
+            let response = Client::new(*env.bind_address())
+                .announce(&QueryBuilder::default().query())
+                .await;
+
+            assert_rate_limit_exceeded_error_response(response).await;
+
+            let stats = env.tracker.get_stats().await;
+
+            assert_eq!(stats.tcp4_requests_rate_limited, 1);
+            assert_eq!(stats.tcp4_announces_handled, 1);
+
+            drop(stats);
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_count_the_invalid_requests_in_the_client_announce_budget() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(
+                &configuration::ephemeral_with_rate_limit(RateLimit {
+                    announce: Budget::new(1, 1),
+                    ..Default::default()
+                })
+                .into(),
+            )
+            .await;
+
+            let response = Client::new(*env.bind_address()).get("announce").await;
+
+            assert_missing_query_params_for_announce_request_error_response(response).await;
+
+            let response = Client::new(*env.bind_address())
+                .announce(&QueryBuilder::default().query())
+                .await;
+
+            assert_rate_limit_exceeded_error_response(response).await;
+
+            let stats = env.tracker.get_stats().await;
+
+            assert_eq!(stats.tcp4_requests_rate_limited, 1);
+            assert_eq!(stats.tcp4_announces_handled, 0);
+
+            drop(stats);
+
+            env.stop().await;
+        }
+    }
+
+    mod receiving_an_scrape_request {
+        use std::str::FromStr;
+
+        use torrust_tracker_configuration::v2_0_0::rate_limit::Budget;
+        use torrust_tracker_configuration::RateLimit;
+        use torrust_tracker_primitives::info_hash::InfoHash;
+        use torrust_tracker_test_helpers::configuration;
+        use tracing::level_filters::LevelFilter;
+
+        use crate::common::logging::{tracing_stderr_init, INIT};
+        use crate::servers::http::asserts::assert_rate_limit_exceeded_error_response;
+        use crate::servers::http::client::Client;
+        use crate::servers::http::{requests, Started};
+
+        #[tokio::test]
+        async fn should_fail_when_the_client_exceeds_its_scrape_budget() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(
+                &configuration::ephemeral_with_rate_limit(RateLimit {
+                    scrape: Budget::new(1, 1),
+                    ..Default::default()
+                })
+                .into(),
+            )
+            .await;
+
+            let info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+            let query = requests::scrape::QueryBuilder::default()
+                .with_one_info_hash(&info_hash)
+                .query();
+
+            let response = Client::new(*env.bind_address()).scrape(&query).await;
+
+            assert_eq!(response.status(), 200);
+
+            let response = Client::new(*env.bind_address()).scrape(&query).await;
+
+            assert_rate_limit_exceeded_error_response(response).await;
+
+            let stats = env.tracker.get_stats().await;
+
+            assert_eq!(stats.tcp4_requests_rate_limited, 1);
+
+            drop(stats);
+
+            env.stop().await;
+        }
+    }
+}
+
+mod configured_to_reject_early_announces {
+
+    mod and_receiving_an_announce_request {
+        use torrust_tracker_configuration::OnEarlyAnnounce;
+        use torrust_tracker_test_helpers::configuration;
+        use tracing::level_filters::LevelFilter;
+
+        use crate::common::logging::{tracing_stderr_init, INIT};
+        use crate::servers::http::asserts::{assert_announce_too_early_error_response, assert_is_announce_response};
+        use crate::servers::http::client::Client;
+        use crate::servers::http::requests::announce::QueryBuilder;
+        use crate::servers::http::Started;
+
+        #[tokio::test]
+        async fn should_fail_when_the_peer_announces_again_before_the_minimum_announce_interval() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let mut configuration = configuration::ephemeral_public();
+            configuration.core.on_early_announce = OnEarlyAnnounce::Reject;
+
+            let env = Started::new(&configuration.into()).await;
+
+            let mut query = QueryBuilder::default().query();
+            query.event = None;
+
+            let response = Client::new(*env.bind_address()).announce(&query).await;
+
+            assert_is_announce_response(response).await;
+
+            let response = Client::new(*env.bind_address()).announce(&query).await;
+
+            assert_announce_too_early_error_response(response).await;
+
+            let stats = env.tracker.get_stats().await;
+
+            assert_eq!(stats.tcp4_early_announces, 1);
+            assert_eq!(stats.tcp4_announces_handled, 1);
+
+            drop(stats);
+
+            env.stop().await;
+        }
+    }
+}
+
 mod configured_as_whitelisted {
 
     mod and_receiving_an_announce_request {
@@ -212,6 +212,114 @@ mod receiving_an_announce_request {
     }
 }
 
+mod receiving_an_announce_request_in_private_mode {
+    use std::io::Cursor;
+    use std::net::Ipv4Addr;
+    use std::time::Duration;
+
+    use aquatic_udp_protocol::{
+        AnnounceActionPlaceholder, AnnounceEvent, AnnounceRequest, ConnectionId, InfoHash, NumberOfBytes, NumberOfPeers, PeerId,
+        PeerKey, Port, Request, Response, TransactionId,
+    };
+    use torrust_tracker::shared::bit_torrent::tracker::udp::client::UdpTrackerClient;
+    use torrust_tracker_configuration::DEFAULT_TIMEOUT;
+    use torrust_tracker_test_helpers::configuration;
+    use tracing::level_filters::LevelFilter;
+
+    use crate::common::logging::{tracing_stderr_init, INIT};
+    use crate::servers::udp::asserts::{is_error_response, is_ipv4_announce_response};
+    use crate::servers::udp::contract::send_connection_request;
+    use crate::servers::udp::Started;
+
+    /// It sends an announce request with the BEP 41 `URLData` option appended.
+    async fn send_and_get_announce_with_url_data(c_id: ConnectionId, url_data: &str, client: &UdpTrackerClient) -> Response {
+        let announce_request = AnnounceRequest {
+            connection_id: ConnectionId(c_id.0),
+            action_placeholder: AnnounceActionPlaceholder::default(),
+            transaction_id: TransactionId::new(123),
+            info_hash: InfoHash([0u8; 20]),
+            peer_id: PeerId([255u8; 20]),
+            bytes_downloaded: NumberOfBytes(0i64.into()),
+            bytes_uploaded: NumberOfBytes(0i64.into()),
+            bytes_left: NumberOfBytes(0i64.into()),
+            event: AnnounceEvent::Started.into(),
+            ip_address: Ipv4Addr::new(0, 0, 0, 0).into(),
+            key: PeerKey::new(0i32),
+            peers_wanted: NumberOfPeers(1i32.into()),
+            port: Port(client.client.socket.local_addr().unwrap().port().into()),
+        };
+
+        let mut writer = Cursor::new(Vec::new());
+        Request::from(announce_request).write_bytes(&mut writer).unwrap();
+
+        let mut packet = writer.into_inner();
+        packet.push(0x2); // `URLData` option type
+        packet.push(u8::try_from(url_data.len()).unwrap());
+        packet.extend_from_slice(url_data.as_bytes());
+        packet.push(0x0); // `EndOfOptions` option type
+
+        match client.client.send(&packet).await {
+            Ok(_) => (),
+            Err(err) => panic!("{err}"),
+        };
+
+        match client.receive().await {
+            Ok(response) => response,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_return_an_announce_response_when_the_url_data_contains_a_valid_key() {
+        INIT.call_once(|| {
+            tracing_stderr_init(LevelFilter::ERROR);
+        });
+
+        let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+        let peer_key = env
+            .tracker
+            .generate_auth_key(Some(Duration::from_secs(60)))
+            .await
+            .expect("it should generate a key");
+
+        let client = match UdpTrackerClient::new(env.bind_address(), DEFAULT_TIMEOUT).await {
+            Ok(udp_tracker_client) => udp_tracker_client,
+            Err(err) => panic!("{err}"),
+        };
+
+        let c_id = send_connection_request(TransactionId::new(123), &client).await;
+
+        let response = send_and_get_announce_with_url_data(c_id, &format!("/announce/{}", peer_key.key()), &client).await;
+
+        assert!(is_ipv4_announce_response(&response));
+
+        env.stop().await;
+    }
+
+    #[tokio::test]
+    async fn should_return_an_error_response_when_the_url_data_does_not_contain_a_key() {
+        INIT.call_once(|| {
+            tracing_stderr_init(LevelFilter::ERROR);
+        });
+
+        let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+        let client = match UdpTrackerClient::new(env.bind_address(), DEFAULT_TIMEOUT).await {
+            Ok(udp_tracker_client) => udp_tracker_client,
+            Err(err) => panic!("{err}"),
+        };
+
+        let c_id = send_connection_request(TransactionId::new(123), &client).await;
+
+        let response = send_and_get_announce_with_url_data(c_id, "/announce", &client).await;
+
+        assert!(is_error_response(&response, "domain tracker requires authentication"));
+
+        env.stop().await;
+    }
+}
+
 mod receiving_an_scrape_request {
     use aquatic_udp_protocol::{ConnectionId, InfoHash, ScrapeRequest, TransactionId};
     use torrust_tracker::shared::bit_torrent::tracker::udp::client::UdpTrackerClient;